@@ -0,0 +1,148 @@
+// src/app/a11y.rs
+use eframe::egui as eg;
+
+use crate::app::types::{OwnedTier, PosterRow};
+use crate::app::utils::{day_bucket, format_day_label, hhmm_utc, humanize_channel};
+
+/// What a sighted user reads off a card, painted badges included, as one sentence:
+/// "Heat (1995), BBC Two, Tuesday 14th Oct 21:00 UTC, HD, owned in SD, HD upgrade airing, …".
+fn card_label(
+    row: &PosterRow,
+    owned: Option<OwnedTier>,
+    hd_upgrade: bool,
+    score: Option<f32>,
+) -> String {
+    let mut parts = vec![row
+        .year
+        .map_or_else(|| row.title.clone(), |y| format!("{} ({y})", row.title))];
+    if let Some(channel) = row.channel.as_deref() {
+        parts.push(humanize_channel(channel));
+    }
+    if let Some(ts) = row.airing {
+        parts.push(format!(
+            "{} {} UTC",
+            format_day_label(day_bucket(ts)),
+            hhmm_utc(ts)
+        ));
+    }
+    if row.broadcast_hd {
+        parts.push("HD".into());
+    }
+    parts.push(owned.map_or_else(
+        || "not owned".to_string(),
+        |tier| format!("owned in {}", tier.label()),
+    ));
+    if hd_upgrade {
+        parts.push("HD upgrade airing".into());
+    }
+    if row.conflict {
+        parts.push("recording clash".into());
+    } else if row.scheduled {
+        parts.push("scheduled to record".into());
+    }
+    if row.premiere {
+        parts.push("new, premiere".into());
+    }
+    if row.new_in_guide {
+        parts.push("new in the guide".into());
+    }
+    if let Some(score) = score {
+        parts.push(format!("rated {score:.1}"));
+    }
+    if let Some(rating) = row.my_rating {
+        parts.push(format!("you rated it {rating}"));
+    }
+    if row.watched {
+        parts.push("watched".into());
+    }
+    let lists: Vec<&str> = [
+        (row.trakt_watchlist, "Trakt"),
+        (row.letterboxd, "Letterboxd"),
+        (row.imdb_watchlist, "IMDb"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect();
+    if !lists.is_empty() {
+        parts.push(format!("on your {} watchlist", lists.join(", ")));
+    }
+    if row.radarr {
+        parts.push("in Radarr".into());
+    }
+    parts.join(", ")
+}
+
+impl crate::app::PexApp {
+    /// Give a painter-drawn card or timeline block an AccessKit node, so screen readers
+    /// announce the film and its badges rather than an unlabeled button.
+    pub(crate) fn describe_card(&self, resp: &eg::Response, idx: usize) {
+        resp.widget_info(|| {
+            let label = self.rows.get(idx).map_or_else(String::new, |row| {
+                let score = self
+                    .rating_badges
+                    .then(|| self.rating_states.get(&row.key).and_then(|s| s.score()))
+                    .flatten();
+                card_label(
+                    row,
+                    self.row_owned_tier(row),
+                    self.row_hd_upgrade_available(row),
+                    score,
+                )
+            });
+            eg::WidgetInfo::selected(
+                eg::WidgetType::Button,
+                true,
+                self.selected_idx == Some(idx),
+                label,
+            )
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{PexApp, PrepItem};
+
+    #[test]
+    fn card_label_reads_out_the_badges() {
+        let mut row = PexApp::row_from_prep(
+            PrepItem {
+                title: "Heat".into(),
+                thumb_url: String::new(),
+                key: "a11y-test".into(),
+                begins_at: Some(1_700_000_000),
+                ends_at: None,
+                year: Some(1995),
+                tags_genre: None,
+                channel_call_sign: None,
+                channel_title: Some("BBC Two HD".into()),
+                channel_thumb: None,
+                guid: None,
+                summary: None,
+                audience_rating: None,
+                critic_rating: None,
+                premiere: false,
+            },
+            &Default::default(),
+        );
+        assert!(card_label(&row, None, false, None).contains("not owned"));
+
+        row.scheduled = true;
+        row.watched = true;
+        row.trakt_watchlist = true;
+        row.imdb_watchlist = true;
+        let label = card_label(&row, Some(OwnedTier::Sd), true, Some(8.3));
+        assert!(label.starts_with("Heat (1995), "));
+        for part in [
+            "owned in SD",
+            "HD upgrade airing",
+            "scheduled to record",
+            "rated 8.3",
+            "watched",
+            "on your Trakt, IMDb watchlist",
+        ] {
+            assert!(label.contains(part), "{label}");
+        }
+    }
+}
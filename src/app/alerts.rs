@@ -0,0 +1,777 @@
+// src/app/alerts.rs
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, UNIX_EPOCH};
+
+use eframe::egui as eg;
+use tracing::warn;
+
+use crate::app::search::{split_tokens, SearchField};
+use crate::app::utils::{day_bucket, format_day_label, hhmm_utc};
+use crate::app::{PexApp, PosterRow};
+
+const RULES_FILE: &str = "alert_rules.txt";
+const SEEN_FILE: &str = "alerts_seen.txt";
+/// Ending of a seen key once the match has also been sent to the Trakt watchlist.
+const WATCHLISTED: &str = "\twatchlist";
+/// Seen keys are kept this long after the airing starts, so a film still on air isn't
+/// announced again.
+const SEEN_GRACE: Duration = Duration::from_secs(24 * 3600);
+
+fn rules_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(RULES_FILE)
+}
+
+fn seen_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(SEEN_FILE)
+}
+
+/// Row state a rule can test with a bare word (`hd`, `!owned`, …).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AlertFlag {
+    Hd,
+    Premiere,
+    New,
+    Owned,
+    Upgrade,
+    Watched,
+    Watchlist,
+    Scheduled,
+}
+
+impl AlertFlag {
+    const ALL: [Self; 8] = [
+        Self::Hd,
+        Self::Premiere,
+        Self::New,
+        Self::Owned,
+        Self::Upgrade,
+        Self::Watched,
+        Self::Watchlist,
+        Self::Scheduled,
+    ];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hd => "hd",
+            Self::Premiere => "premiere",
+            Self::New => "new",
+            Self::Owned => "owned",
+            Self::Upgrade => "upgrade",
+            Self::Watched => "watched",
+            Self::Watchlist => "watchlist",
+            Self::Scheduled => "scheduled",
+        }
+    }
+}
+
+impl FromStr for AlertFlag {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|flag| flag.as_str().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    Flag(AlertFlag),
+    /// Case-insensitive substring of a row field (lowercased value).
+    Field(SearchField, String),
+    /// Release year within the inclusive range.
+    Year(i32, i32),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Condition {
+    term: Term,
+    negate: bool,
+}
+
+fn parse_year_range(value: &str) -> Option<(i32, i32)> {
+    match value.split_once('-') {
+        Some((lo, hi)) => {
+            let (lo, hi) = (lo.trim().parse().ok()?, hi.trim().parse().ok()?);
+            (lo <= hi).then_some((lo, hi))
+        }
+        None => value.trim().parse().ok().map(|year| (year, year)),
+    }
+}
+
+/// Conditions of one rule, all of which must hold: `genre:western hd !owned`. Field terms use
+/// the search prefixes (quotes keep spaces), `year:` takes a year or `1970-1979`, and `!` or
+/// `not` negates the next term. A literal `AND` between terms is allowed and ignored.
+fn parse_conditions(text: &str) -> Result<Vec<Condition>, String> {
+    let mut conditions = Vec::new();
+    let mut negate_next = false;
+    for token in split_tokens(text) {
+        if token.eq_ignore_ascii_case("and") {
+            continue;
+        }
+        if token.eq_ignore_ascii_case("not") {
+            negate_next = !negate_next;
+            continue;
+        }
+        let (negate, word) = match token.strip_prefix('!') {
+            Some(rest) => (!negate_next, rest),
+            None => (negate_next, token.as_str()),
+        };
+        negate_next = false;
+        let term = if let Some((prefix, value)) = word.split_once(':') {
+            let value = value.trim_matches('"').trim();
+            if value.is_empty() {
+                return Err(format!("“{word}” needs a value"));
+            }
+            if prefix.eq_ignore_ascii_case("year") {
+                let (lo, hi) = parse_year_range(value)
+                    .ok_or_else(|| format!("“{word}”: use year:1995 or year:1970-1979"))?;
+                Term::Year(lo, hi)
+            } else {
+                let field = SearchField::from_prefix(prefix)
+                    .ok_or_else(|| format!("unknown field “{prefix}:”"))?;
+                Term::Field(field, value.to_lowercase())
+            }
+        } else {
+            Term::Flag(
+                word.parse()
+                    .map_err(|()| format!("unknown condition “{word}”"))?,
+            )
+        };
+        conditions.push(Condition { term, negate });
+    }
+    if negate_next {
+        return Err("“not” needs a condition after it".into());
+    }
+    if conditions.is_empty() {
+        return Err("add at least one condition".into());
+    }
+    Ok(conditions)
+}
+
+/// Whether every condition holds for the row. `upgrade` is the app's HD-upgrade verdict,
+/// which needs the owned library details.
+fn conditions_match(conditions: &[Condition], row: &PosterRow, upgrade: bool) -> bool {
+    conditions.iter().all(|condition| {
+        let holds = match &condition.term {
+            Term::Flag(flag) => match flag {
+                AlertFlag::Hd => row.broadcast_hd,
+                AlertFlag::Premiere => row.premiere,
+                AlertFlag::New => row.new_in_guide,
+                AlertFlag::Owned => row.owned,
+                AlertFlag::Upgrade => upgrade,
+                AlertFlag::Watched => row.watched,
+                AlertFlag::Watchlist => row.trakt_watchlist || row.letterboxd || row.imdb_watchlist,
+                AlertFlag::Scheduled => row.scheduled,
+            },
+            Term::Field(field, value) => field
+                .values(row)
+                .iter()
+                .any(|text| text.to_lowercase().contains(value.as_str())),
+            Term::Year(lo, hi) => row.year.is_some_and(|year| (*lo..=*hi).contains(&year)),
+        };
+        holds != condition.negate
+    })
+}
+
+/// One saved rule: conditions plus what to do with new matches.
+#[derive(Clone, Debug)]
+struct AlertRule {
+    /// Stable across renames and edits; seen keys refer to it. 0 until the rule is saved.
+    id: u32,
+    name: String,
+    conditions: String,
+    enabled: bool,
+    /// Count new matches on the Alerts button and in the status bar.
+    notify: bool,
+    /// Add new matches to the Trakt watchlist.
+    trakt_watchlist: bool,
+    parsed: Result<Vec<Condition>, String>,
+}
+
+impl Default for AlertRule {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: String::new(),
+            conditions: String::new(),
+            enabled: true,
+            notify: true,
+            trakt_watchlist: false,
+            parsed: Err("add at least one condition".into()),
+        }
+    }
+}
+
+impl AlertRule {
+    fn reparse(&mut self) {
+        self.parsed = parse_conditions(&self.conditions);
+    }
+
+    /// `<id>\t<enabled>\t<notify>\t<watchlist>\t<name>\t<conditions>`; lines written before
+    /// rules had ids lack the first field and come back with id 0.
+    fn parse_line(line: &str) -> Option<Self> {
+        let line = line.trim_end_matches('\r');
+        let (id, line) = if line.split('\t').count() == 6 {
+            let (id, rest) = line.split_once('\t')?;
+            (id.parse().ok()?, rest)
+        } else {
+            (0, line)
+        };
+        let mut parts = line.splitn(5, '\t');
+        let mut flag = || parts.next().map(|f| f == "1");
+        let (enabled, notify, trakt_watchlist) = (flag()?, flag()?, flag()?);
+        let name = parts.next()?.to_string();
+        let conditions = parts.next()?.to_string();
+        let mut rule = Self {
+            id,
+            name,
+            conditions,
+            enabled,
+            notify,
+            trakt_watchlist,
+            ..Self::default()
+        };
+        rule.reparse();
+        Some(rule)
+    }
+
+    fn to_line(&self) -> String {
+        let flag = |on: bool| if on { "1" } else { "0" };
+        let clean = |text: &str| text.replace(['\t', '\n', '\r'], " ");
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            self.id,
+            flag(self.enabled),
+            flag(self.notify),
+            flag(self.trakt_watchlist),
+            clean(self.name.trim()),
+            clean(self.conditions.trim())
+        )
+    }
+
+    fn label(&self) -> &str {
+        if self.name.trim().is_empty() {
+            &self.conditions
+        } else {
+            &self.name
+        }
+    }
+}
+
+/// Alert rules from `<cache>/alert_rules.txt` (`<id>\t<enabled>\t<notify>\t<watchlist>\t
+/// <name>\t<conditions>` lines) and what they matched. Matches already announced are
+/// remembered in `<cache>/alerts_seen.txt` until their airing has passed, so a restart, or
+/// turning a rule off and on, doesn't repeat them.
+#[derive(Default)]
+pub(crate) struct AlertRules {
+    rules: Vec<AlertRule>,
+    /// `<rule id>\t<title key>\t<airing secs>` of every match announced so far, and the same
+    /// with `\twatchlist` once it was sent to the Trakt watchlist.
+    seen: HashSet<String>,
+    /// Row indices each rule matched in the last evaluation.
+    matches: Vec<Vec<usize>>,
+    /// New notifying matches since the Alerts window was last opened.
+    pub unread: usize,
+    pub window_open: bool,
+    /// Rule being edited: `Some(None)` is a new rule.
+    editing: Option<Option<usize>>,
+    draft: AlertRule,
+}
+
+impl AlertRules {
+    pub fn load() -> Self {
+        let rules = fs::read_to_string(rules_path())
+            .map(|text| text.lines().filter_map(AlertRule::parse_line).collect())
+            .unwrap_or_default();
+        let seen = fs::read_to_string(seen_path())
+            .map(|text| text.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        let mut alerts = Self {
+            rules,
+            seen,
+            ..Self::default()
+        };
+        if alerts.rules.iter().any(|rule| rule.id == 0) {
+            alerts.assign_ids();
+            alerts.save_rules();
+            alerts.save_seen();
+        }
+        alerts
+    }
+
+    fn next_id(&self) -> u32 {
+        self.rules.iter().map(|rule| rule.id).max().unwrap_or(0) + 1
+    }
+
+    /// Give rules from an older rules file ids, moving their seen keys (then keyed by the
+    /// rule's label) over to them.
+    fn assign_ids(&mut self) {
+        let first = self.next_id();
+        let mut by_label = std::collections::HashMap::new();
+        for (rule, id) in self
+            .rules
+            .iter_mut()
+            .filter(|rule| rule.id == 0)
+            .zip(first..)
+        {
+            rule.id = id;
+            by_label
+                .entry(rule.label().replace('\t', " "))
+                .or_insert(rule.id);
+        }
+        self.seen = std::mem::take(&mut self.seen)
+            .into_iter()
+            .map(|key| match key.split_once('\t') {
+                Some((label, rest)) if by_label.contains_key(label) => {
+                    format!("{}\t{rest}", by_label[label])
+                }
+                _ => key,
+            })
+            .collect();
+    }
+
+    fn save_rules(&self) {
+        let text: String = self.rules.iter().map(AlertRule::to_line).collect();
+        if let Err(err) = fs::write(rules_path(), text) {
+            warn!("Failed to write {}: {err}", rules_path().display());
+        }
+    }
+
+    fn save_seen(&self) {
+        let mut lines: Vec<&str> = self.seen.iter().map(String::as_str).collect();
+        lines.sort_unstable();
+        let mut text = lines.join("\n");
+        text.push('\n');
+        if let Err(err) = fs::write(seen_path(), text) {
+            warn!("Failed to write {}: {err}", seen_path().display());
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.iter().map(Vec::len).sum()
+    }
+}
+
+fn seen_key(rule: &AlertRule, row: &PosterRow) -> String {
+    let secs = row
+        .airing
+        .and_then(|ts| ts.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs());
+    format!(
+        "{}\t{}\t{secs}",
+        rule.id,
+        PexApp::make_owned_key(&row.title, row.year)
+    )
+}
+
+/// Drop the seen keys of airings that started before `cutoff_secs`; the rest stay whether or
+/// not their rule matched this time, so a rule that is off or being edited keeps its history.
+fn prune_seen(seen: &mut HashSet<String>, cutoff_secs: u64) {
+    seen.retain(|key| {
+        key.split('\t')
+            .nth(2)
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .is_some_and(|secs| secs >= cutoff_secs)
+    });
+}
+
+impl PexApp {
+    /// Run every enabled rule over the airing rows. Matches not seen before are announced
+    /// (notify), and matches not yet sent go to the Trakt watchlist (when the rule asks and an
+    /// account is linked). Waits for the owned scan so `owned`/`upgrade` rules don't fire on
+    /// unknown rows.
+    pub(crate) fn evaluate_alerts(&mut self) {
+        if self.owned_keys.is_none() || self.alerts.rules.is_empty() {
+            self.alerts.matches.clear();
+            return;
+        }
+        let mut matches = Vec::with_capacity(self.alerts.rules.len());
+        let mut seen = self.alerts.seen.clone();
+        let cutoff = self
+            .now()
+            .checked_sub(SEEN_GRACE)
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs());
+        prune_seen(&mut seen, cutoff);
+        let mut notify: Vec<(usize, usize)> = Vec::new();
+        let mut to_watchlist: Vec<(usize, String)> = Vec::new();
+        for (rule_idx, rule) in self.alerts.rules.iter().enumerate() {
+            let (true, Ok(conditions)) = (rule.enabled, &rule.parsed) else {
+                matches.push(Vec::new());
+                continue;
+            };
+            let idxs: Vec<usize> = self
+                .rows
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row.airing.is_some())
+                .filter(|(_, row)| {
+                    conditions_match(conditions, row, self.row_hd_upgrade_available(row))
+                })
+                .map(|(idx, _)| idx)
+                .collect();
+            for &idx in &idxs {
+                let key = seen_key(rule, &self.rows[idx]);
+                if rule.trakt_watchlist {
+                    let listed = format!("{key}{WATCHLISTED}");
+                    if !seen.contains(&listed) {
+                        to_watchlist.push((idx, listed));
+                    }
+                }
+                if seen.insert(key) && rule.notify {
+                    notify.push((rule_idx, idx));
+                }
+            }
+            matches.push(idxs);
+        }
+        self.alerts.matches = matches;
+        if !to_watchlist.is_empty() && self.trakt_linked() {
+            let idxs: Vec<usize> = to_watchlist.iter().map(|(idx, _)| *idx).collect();
+            if self.add_to_trakt_watchlist(&idxs) {
+                seen.extend(to_watchlist.into_iter().map(|(_, listed)| listed));
+            }
+        }
+        if seen != self.alerts.seen {
+            self.alerts.seen = seen;
+            self.alerts.save_seen();
+        }
+        if let Some(&(rule_idx, row_idx)) = notify.first() {
+            self.alerts.unread += notify.len();
+            let rule = self.alerts.rules[rule_idx].label().to_string();
+            let title = self.rows[row_idx].title.clone();
+            self.set_status(match notify.len() {
+                1 => format!("Alert “{rule}”: {title} is in the guide."),
+                n => format!("Alerts: {n} new matches, e.g. {title} (“{rule}”). See Alerts."),
+            });
+        }
+    }
+
+    /// Top-bar button opening the Alerts window, with the count of unseen matches.
+    pub(crate) fn ui_render_alerts_button(&mut self, ui: &mut eg::Ui) {
+        let label: eg::WidgetText = if self.alerts.unread > 0 {
+            eg::RichText::new(format!("Alerts ({})", self.alerts.unread))
+                .strong()
+                .color(eg::Color32::from_rgb(230, 170, 40))
+                .into()
+        } else {
+            "Alerts".into()
+        };
+        if ui
+            .button(label)
+            .on_hover_text("Rules that flag (and can watchlist) matching airings automatically")
+            .clicked()
+        {
+            self.alerts.window_open = true;
+            self.alerts.unread = 0;
+        }
+    }
+
+    fn apply_alert_draft(&mut self) {
+        let Some(target) = self.alerts.editing.take() else {
+            return;
+        };
+        let mut rule = std::mem::take(&mut self.alerts.draft);
+        rule.reparse();
+        if rule.id == 0 {
+            rule.id = self.alerts.next_id();
+        }
+        match target.filter(|&i| i < self.alerts.rules.len()) {
+            Some(i) => self.alerts.rules[i] = rule,
+            None => self.alerts.rules.push(rule),
+        }
+        self.alerts.save_rules();
+        self.evaluate_alerts();
+    }
+
+    /// Rule editor form (new or existing rule).
+    fn ui_render_alert_draft(&mut self, ui: &mut eg::Ui) {
+        let mut save = false;
+        let mut cancel = false;
+        let draft = &mut self.alerts.draft;
+        eg::Grid::new("alert_draft_grid")
+            .num_columns(2)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Name");
+                ui.add(
+                    eg::TextEdit::singleline(&mut draft.name)
+                        .hint_text("Westerns in HD")
+                        .desired_width(280.0),
+                );
+                ui.end_row();
+                ui.label("Conditions");
+                if ui
+                    .add(
+                        eg::TextEdit::singleline(&mut draft.conditions)
+                            .hint_text("genre:western AND hd AND !owned")
+                            .desired_width(280.0),
+                    )
+                    .on_hover_text(
+                        "All must hold. Fields: title:, genre:, channel:, summary: (quote \
+                         values with spaces), year:1995 or year:1970-1979. Flags: hd, premiere, \
+                         new, owned, upgrade, watched, watchlist, scheduled. Put ! or not in \
+                         front to negate.",
+                    )
+                    .changed()
+                {
+                    draft.reparse();
+                }
+                ui.end_row();
+            });
+        if let Err(err) = &draft.parsed {
+            ui.colored_label(eg::Color32::from_rgb(200, 80, 80), err);
+        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut draft.notify, "Notify")
+                .on_hover_text("Count new matches on the Alerts button and in the status bar");
+            ui.checkbox(&mut draft.trakt_watchlist, "Add to Trakt watchlist")
+                .on_hover_text("Needs a linked account (Advanced ▸ Trakt)");
+        });
+        ui.horizontal(|ui| {
+            save = ui
+                .add_enabled(draft.parsed.is_ok(), eg::Button::new("Save rule"))
+                .clicked();
+            cancel = ui.button("Cancel").clicked();
+        });
+        if save {
+            self.apply_alert_draft();
+        } else if cancel {
+            self.alerts.editing = None;
+        }
+    }
+
+    /// Alerts window: the rules with an editor, and what each currently matches.
+    pub(crate) fn ui_render_alerts_window(&mut self, ctx: &eg::Context) {
+        if !self.alerts.window_open {
+            return;
+        }
+        let mut open = true;
+        let mut edit: Option<Option<usize>> = None;
+        let mut delete: Option<usize> = None;
+        let mut toggled = false;
+        let mut select: Option<usize> = None;
+        eg::Window::new("Alerts")
+            .collapsible(false)
+            .default_width(460.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(eg::RichText::new("Rules").strong());
+                if self.alerts.rules.is_empty() {
+                    ui.label(
+                        eg::RichText::new(
+                            "No rules yet. A rule flags airings that match all its conditions \
+                             whenever the guide or library changes.",
+                        )
+                        .weak(),
+                    );
+                }
+                for (i, rule) in self.alerts.rules.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        toggled |= ui
+                            .checkbox(&mut rule.enabled, "")
+                            .on_hover_text("Enable this rule")
+                            .changed();
+                        ui.label(eg::RichText::new(rule.label()).strong());
+                        ui.label(eg::RichText::new(&rule.conditions).monospace().weak());
+                        if ui.small_button("Edit").clicked() {
+                            edit = Some(Some(i));
+                        }
+                        if ui.small_button("Delete").clicked() {
+                            delete = Some(i);
+                        }
+                    });
+                    if let Err(err) = &rule.parsed {
+                        ui.colored_label(eg::Color32::from_rgb(200, 80, 80), err);
+                    }
+                }
+                if self.alerts.editing.is_some() {
+                    ui.separator();
+                    self.ui_render_alert_draft(ui);
+                } else if ui.button("Add rule").clicked() {
+                    edit = Some(None);
+                }
+
+                ui.separator();
+                ui.label(
+                    eg::RichText::new(format!("Matches ({})", self.alerts.match_count())).strong(),
+                );
+                if self.owned_keys.is_none() {
+                    ui.label(
+                        eg::RichText::new("Rules run once the owned library scan has finished.")
+                            .weak(),
+                    );
+                }
+                eg::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for (rule, idxs) in self.alerts.rules.iter().zip(&self.alerts.matches) {
+                        if idxs.is_empty() {
+                            continue;
+                        }
+                        eg::CollapsingHeader::new(format!("{} — {}", rule.label(), idxs.len()))
+                            .id_source(("alert_rule", rule.id))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for &idx in idxs {
+                                    let Some(row) = self.rows.get(idx) else {
+                                        continue;
+                                    };
+                                    let title = row.year.map_or_else(
+                                        || row.title.clone(),
+                                        |y| format!("{} ({y})", row.title),
+                                    );
+                                    let when = row
+                                        .airing
+                                        .map(|ts| {
+                                            format!(
+                                                "{} {}",
+                                                format_day_label(day_bucket(ts)),
+                                                hhmm_utc(ts)
+                                            )
+                                        })
+                                        .unwrap_or_default();
+                                    let channel = row.channel.clone().unwrap_or_default();
+                                    if ui
+                                        .selectable_label(
+                                            self.selected_idx == Some(idx),
+                                            format!("{title}  •  {channel}  •  {when}"),
+                                        )
+                                        .clicked()
+                                    {
+                                        select = Some(idx);
+                                    }
+                                }
+                            });
+                    }
+                });
+            });
+        self.alerts.window_open = open;
+        if let Some(target) = edit {
+            self.alerts.draft = target
+                .and_then(|i| self.alerts.rules.get(i).cloned())
+                .unwrap_or_default();
+            self.alerts.editing = Some(target);
+        }
+        if let Some(i) = delete {
+            self.alerts.rules.remove(i);
+            self.alerts.editing = None;
+            self.alerts.save_rules();
+            self.evaluate_alerts();
+        } else if toggled {
+            self.alerts.save_rules();
+            self.evaluate_alerts();
+        }
+        if let Some(idx) = select {
+            self.selected_idx = Some(idx);
+            self.scroll_to_idx = Some(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::PrepItem;
+
+    #[test]
+    fn conditions_parse_and_match_rows() {
+        let conditions =
+            parse_conditions(r#"genre:western AND hd AND not owned !year:1990-1999"#).unwrap();
+        assert_eq!(conditions.len(), 4);
+        assert_eq!(
+            conditions[2],
+            Condition {
+                term: Term::Flag(AlertFlag::Owned),
+                negate: true
+            }
+        );
+        assert!(parse_conditions("AND").is_err());
+        assert!(parse_conditions("genre:").is_err());
+        assert!(parse_conditions("colour").is_err());
+        assert!(parse_conditions("year:1999-1990").is_err());
+        assert!(parse_conditions("hd not").is_err());
+
+        let row = PexApp::row_from_prep(
+            PrepItem {
+                title: "The Searchers".into(),
+                thumb_url: String::new(),
+                key: "alerts-test".into(),
+                begins_at: Some(1_700_000_000),
+                ends_at: None,
+                year: Some(1956),
+                tags_genre: Some("Western|Drama".into()),
+                channel_call_sign: None,
+                channel_title: Some("Film4 HD".into()),
+                channel_thumb: None,
+                guid: None,
+                summary: None,
+                audience_rating: None,
+                critic_rating: None,
+                premiere: false,
+            },
+            &Default::default(),
+        );
+        assert!(conditions_match(&conditions, &row, false));
+        let owned = parse_conditions(r#"channel:"film4" owned"#).unwrap();
+        assert!(!conditions_match(&owned, &row, false));
+        assert!(conditions_match(
+            &parse_conditions("upgrade").unwrap(),
+            &row,
+            true
+        ));
+
+        let mut rule = AlertRule {
+            id: 3,
+            name: "Westerns".into(),
+            conditions: "genre:western hd".into(),
+            trakt_watchlist: true,
+            ..AlertRule::default()
+        };
+        rule.reparse();
+        let back = AlertRule::parse_line(&rule.to_line()).unwrap();
+        assert_eq!(
+            (back.id, back.name.as_str(), back.trakt_watchlist),
+            (3, "Westerns", true)
+        );
+        assert_eq!(back.parsed, rule.parsed);
+        let legacy = AlertRule::parse_line("1\t1\t0\tWesterns\tgenre:western hd").unwrap();
+        assert_eq!(
+            (legacy.id, legacy.conditions.as_str()),
+            (0, "genre:western hd")
+        );
+    }
+
+    #[test]
+    fn seen_keys_outlive_their_rule_until_the_airing_passes() {
+        let mut alerts = AlertRules {
+            rules: vec![
+                AlertRule::parse_line("2\t1\t1\t0\tOld\thd").unwrap(),
+                AlertRule::parse_line("1\t1\t0\tWesterns\tgenre:western").unwrap(),
+            ],
+            seen: ["Westerns\tsearchers|1956\t500", "2\tran|1985\t50"]
+                .map(String::from)
+                .into(),
+            ..AlertRules::default()
+        };
+        alerts.assign_ids();
+        assert_eq!(alerts.rules[1].id, 3);
+        assert!(alerts.seen.contains("3\tsearchers|1956\t500"));
+
+        alerts
+            .seen
+            .insert(format!("3\tsearchers|1956\t500{WATCHLISTED}"));
+        prune_seen(&mut alerts.seen, 100);
+        let mut left: Vec<&str> = alerts.seen.iter().map(String::as_str).collect();
+        left.sort_unstable();
+        assert_eq!(
+            left,
+            [
+                "3\tsearchers|1956\t500",
+                "3\tsearchers|1956\t500\twatchlist"
+            ]
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// src/app/background.rs
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use eframe::egui as eg;
+use tracing::{info, warn};
+
+use crate::app::clock::Clock;
+use crate::app::types::OwnedMsg;
+use crate::app::{DayRange, PrepMsg};
+
+// Coming back after this long reloads the guide and owned library before showing them.
+const STALE_AFTER: Duration = Duration::from_secs(60 * 60);
+/// How often the minimized-window ticker checks its timers (and whether to stop).
+const TICK: Duration = Duration::from_secs(1);
+
+/// "Keep running when closed": the close button minimizes Pex instead of quitting, so it stays
+/// up in the background and comes back with fresh data.
+#[derive(Default)]
+pub(crate) struct Background {
+    pub enabled: bool,
+    /// Set by Advanced ▸ Quit so the next close request really exits.
+    quitting: bool,
+    /// When the close button last sent the window to the background.
+    hidden_since: Option<Instant>,
+    ticker: Option<HiddenTicker>,
+}
+
+/// Refreshes started while the window is minimized; the normal polling takes them over when
+/// it comes back.
+#[derive(Default)]
+struct HiddenWork {
+    guide: Option<Receiver<PrepMsg>>,
+    owned: Option<Receiver<OwnedMsg>>,
+}
+
+/// eframe stops calling `update` for a minimized window, so this thread keeps the guide
+/// refresh and timed owned rescan going instead.
+struct HiddenTicker {
+    stop: Arc<AtomicBool>,
+    work: Arc<Mutex<HiddenWork>>,
+}
+
+/// `guide` / `owned` are the time left until each refresh is due and its interval.
+fn spawn_hidden_ticker(
+    guide: Option<(Duration, Duration)>,
+    owned: Option<(Duration, Duration)>,
+    range: DayRange,
+    clock: Arc<dyn Clock>,
+) -> HiddenTicker {
+    let stop = Arc::new(AtomicBool::new(false));
+    let work = Arc::new(Mutex::new(HiddenWork::default()));
+    let ticker = HiddenTicker {
+        stop: Arc::clone(&stop),
+        work: Arc::clone(&work),
+    };
+    let start = Instant::now();
+    let mut guide_due = guide.map(|(left, _)| start + left);
+    let mut owned_due = owned.map(|(left, _)| start + left);
+    std::thread::spawn(move || {
+        while !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(TICK);
+            let now = Instant::now();
+            if let (Some(due), Some((_, every))) = (guide_due, guide) {
+                if now >= due {
+                    guide_due = Some(now + every);
+                    info!("guide refresh: re-reading the EPG while minimized");
+                    crate::app::prep::forget_epg_sync();
+                    let window = crate::app::prep::airing_window(range, clock.now());
+                    let (tx, rx) = mpsc::channel();
+                    crate::app::prep::spawn_poster_prep(tx, window);
+                    work.lock().unwrap().guide = Some(rx);
+                }
+            }
+            if let (Some(due), Some((_, every))) = (owned_due, owned) {
+                if now >= due {
+                    owned_due = Some(now + every);
+                    info!("owned rescan while minimized");
+                    if let Err(err) = crate::app::prep::sync_library_db_from_source(true) {
+                        warn!("Plex library DB refresh skipped: {err}");
+                    }
+                    let (tx, rx) = mpsc::channel();
+                    crate::app::owned::spawn_owned_scan(tx);
+                    work.lock().unwrap().owned = Some(rx);
+                }
+            }
+        }
+    });
+    ticker
+}
+
+impl crate::app::PexApp {
+    /// Turn the window's close button into "minimize" while keep-running is on, and refresh
+    /// the guide and owned scan when the window comes back after a long time away.
+    pub(crate) fn handle_close_request(&mut self, ctx: &eg::Context) {
+        let (close_requested, minimized) =
+            ctx.input(|i| (i.viewport().close_requested(), i.viewport().minimized));
+        if close_requested && self.background.enabled && !self.background.quitting {
+            ctx.send_viewport_cmd(eg::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(eg::ViewportCommand::Minimized(true));
+            self.background.hidden_since = Some(Instant::now());
+            self.start_hidden_ticker();
+            info!("close requested; keeping Pex running minimized");
+            return;
+        }
+        let Some(since) = self.background.hidden_since else {
+            return;
+        };
+        if minimized != Some(false) {
+            return;
+        }
+        self.background.hidden_since = None;
+        if self.adopt_hidden_work() {
+            self.set_status("Welcome back – showing what was refreshed while minimized.");
+        } else if since.elapsed() >= STALE_AFTER && !self.demo_mode {
+            self.set_status("Welcome back – refreshing the guide and owned library…");
+            self.restart_poster_pipeline(ctx);
+            self.refresh_owned_scan();
+        }
+    }
+
+    /// Keep the refresh timers going while minimized (not in demo or safe mode, or while the
+    /// guide is still loading).
+    fn start_hidden_ticker(&mut self) {
+        if self.demo_mode || self.in_safe_mode() || self.boot_phase != crate::app::BootPhase::Ready
+        {
+            return;
+        }
+        let guide = self.guide_refresh.next_due();
+        let owned = self.owned_rescan.next_due();
+        if guide.is_none() && owned.is_none() {
+            return;
+        }
+        self.background.ticker = Some(spawn_hidden_ticker(
+            guide,
+            owned,
+            self.current_range,
+            Arc::clone(&self.clock),
+        ));
+    }
+
+    /// Stop the ticker and hand whatever it started to the normal polling. True when it
+    /// refreshed anything.
+    fn adopt_hidden_work(&mut self) -> bool {
+        let Some(ticker) = self.background.ticker.take() else {
+            return false;
+        };
+        ticker.stop.store(true, Ordering::Relaxed);
+        let work = std::mem::take(&mut *ticker.work.lock().unwrap());
+        let mut adopted = false;
+        if let Some(rx) = work.guide {
+            if self.prep_rx.is_none() {
+                self.guide_refresh.adopt(rx, self.current_range);
+                adopted = true;
+            }
+        }
+        if let Some(rx) = work.owned {
+            if self.owned_rx.is_none() {
+                self.owned_rx = Some(rx);
+                self.owned_rescan.scan_started();
+                self.owned_scan_in_progress = true;
+                self.health.owned = crate::app::health::Health::Loading;
+                self.refresh_scheduled_index();
+                self.record_owned_message("Owned rescan started (while minimized).");
+                adopted = true;
+            }
+        }
+        adopted
+    }
+
+    /// Exit even when keep-running is on.
+    pub(crate) fn quit(&mut self, ctx: &eg::Context) {
+        self.background.quitting = true;
+        ctx.send_viewport_cmd(eg::ViewportCommand::Close);
+    }
+}
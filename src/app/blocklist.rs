@@ -0,0 +1,177 @@
+// src/app/blocklist.rs
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui as eg;
+use tracing::warn;
+
+use crate::app::{PexApp, PrepItem};
+
+const BLOCKLIST_FILE: &str = "hidden_titles.txt";
+
+fn blocklist_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(BLOCKLIST_FILE)
+}
+
+/// Titles the user never wants in the grid, kept in `<cache>/hidden_titles.txt` as
+/// `<title key>\t<title as shown>` lines. The title key is `make_owned_key` of the guide title
+/// and year; matching listings are dropped before they become rows.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct TitleBlocklist {
+    titles: BTreeMap<String, String>,
+}
+
+impl TitleBlocklist {
+    fn parse(text: &str) -> Self {
+        let titles = text
+            .lines()
+            .filter_map(|line| {
+                let (key, title) = line.trim_end_matches('\r').split_once('\t')?;
+                (!key.is_empty()).then(|| (key.to_string(), title.to_string()))
+            })
+            .collect();
+        Self { titles }
+    }
+
+    fn to_text(&self) -> String {
+        self.titles
+            .iter()
+            .map(|(key, title)| format!("{key}\t{title}\n"))
+            .collect()
+    }
+
+    pub fn load() -> Self {
+        fs::read_to_string(blocklist_path())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Err(err) = fs::write(blocklist_path(), self.to_text()) {
+            warn!("Failed to write {}: {err}", blocklist_path().display());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.titles.is_empty()
+    }
+
+    fn is_hidden(&self, title: &str, year: Option<i32>) -> bool {
+        self.titles
+            .contains_key(&PexApp::make_owned_key(title, year))
+    }
+
+    /// Drop listings of hidden titles from a prepared guide.
+    pub fn strip(&self, items: &mut Vec<PrepItem>) {
+        if !self.is_empty() {
+            items.retain(|item| !self.is_hidden(&item.title, item.year));
+        }
+    }
+}
+
+impl PexApp {
+    /// Hide the row's title from now on. The row loses its airing, the way a title that left
+    /// the guide does, so every row index stays valid.
+    fn hide_title(&mut self, idx: usize) {
+        let Some(row) = self.rows.get_mut(idx) else {
+            return;
+        };
+        let label = match row.year {
+            Some(year) => format!("{} ({year})", row.title),
+            None => row.title.clone(),
+        };
+        self.title_blocklist
+            .titles
+            .insert(Self::make_owned_key(&row.title, row.year), label.clone());
+        self.title_blocklist.save();
+        row.airing = None;
+        row.ends = None;
+        if self.selected_idx == Some(idx) {
+            self.selected_idx = None;
+        }
+        if self.compare_idx == Some(idx) {
+            self.compare_idx = None;
+        }
+        self.set_status(format!(
+            "Hid {label}. Advanced ▸ Hidden titles brings it back."
+        ));
+    }
+
+    /// Take a title off the blocklist; it returns with the next guide read.
+    fn unhide_title(&mut self, key: &str) {
+        let Some(label) = self.title_blocklist.titles.remove(key) else {
+            return;
+        };
+        self.title_blocklist.save();
+        if self.can_refresh_guide() {
+            self.start_guide_refresh(false);
+            self.set_status(format!("Unhid {label}; re-reading the guide."));
+        } else {
+            self.set_status(format!(
+                "Unhid {label}; it returns with the next guide refresh."
+            ));
+        }
+    }
+
+    /// Detail-panel action that adds the title to the blocklist.
+    pub(crate) fn ui_render_hide_title_action(&mut self, ui: &mut eg::Ui, idx: usize) {
+        if ui
+            .small_button("Hide this title")
+            .on_hover_text(
+                "Never show this film in the grid again (undo in Advanced ▸ Hidden titles)",
+            )
+            .clicked()
+        {
+            self.hide_title(idx);
+        }
+    }
+
+    /// Advanced ▸ Hidden titles: the blocklist, with a button to bring each title back.
+    pub(crate) fn advanced_hidden_titles(&mut self, ui: &mut eg::Ui) {
+        ui.label(eg::RichText::new("Hidden titles").strong());
+        if self.title_blocklist.is_empty() {
+            ui.label(
+                eg::RichText::new("None. Use Hide this title in the detail panel to add one.")
+                    .weak(),
+            );
+            return;
+        }
+        let mut unhide: Option<String> = None;
+        eg::ScrollArea::vertical()
+            .id_source("hidden_titles_scroll")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                for (key, label) in &self.title_blocklist.titles {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Unhide").clicked() {
+                            unhide = Some(key.clone());
+                        }
+                        ui.label(label);
+                    });
+                }
+            });
+        if let Some(key) = unhide {
+            self.unhide_title(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocklist_round_trips_and_matches_title_and_year() {
+        let text = format!(
+            "{}\tBargain Hunt\nheat:1995\tHeat (1995)\nbad line\n",
+            PexApp::make_owned_key("Bargain Hunt", None)
+        );
+        let list = TitleBlocklist::parse(&text);
+        assert_eq!(list.titles.len(), 2);
+        assert_eq!(TitleBlocklist::parse(&list.to_text()), list);
+        assert!(list.is_hidden("Heat", Some(1995)));
+        assert!(!list.is_hidden("Heat", Some(1986)));
+        assert!(list.is_hidden("Bargain Hunt", None));
+    }
+}
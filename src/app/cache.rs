@@ -4,13 +4,16 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use image::{GenericImageView, ImageFormat};
-use reqwest::blocking::Client;
 use tracing::warn;
 
+use crate::app::clock::{Clock, SystemClock};
+use crate::app::net;
 use crate::config::{load_config, resolve_relative_path};
 
 // Chosen once on first call
-use std::sync::{Once, OnceLock};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, Once, OnceLock};
 static CACHE_DIR_ONCE: OnceLock<PathBuf> = OnceLock::new();
 static POSTER_DIR_ONCE: OnceLock<PathBuf> = OnceLock::new();
 static CHANNEL_ICON_DIR_ONCE: OnceLock<PathBuf> = OnceLock::new();
@@ -18,6 +21,137 @@ static POSTER_PRUNE_ONCE: Once = Once::new();
 
 const POSTER_RETENTION_DAYS: u64 = 14;
 const POSTER_RETENTION_SECS: u64 = POSTER_RETENTION_DAYS * 24 * 60 * 60;
+/// Cached posters older than this are revalidated against the server during prefetch.
+pub const POSTER_REVALIDATE_AFTER: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+// Sidecar holding the HTTP validators (ETag / Last-Modified) for `<key>.<ext>`.
+const VALIDATORS_EXT: &str = "validators";
+
+/// Poster variants as (cache-key suffix, max width in px), smallest first.
+/// Prefetch always fetches the first tier; larger ones are fetched on demand.
+pub const POSTER_VARIANTS: [(&str, u32); 2] = [("__s", 320), ("__m", 640)];
+// Upgrade only once the card is drawn this much wider than the cached pixels.
+const VARIANT_UPGRADE_SLACK: f32 = 0.15;
+
+/// Smallest variant that covers `display_px`, or the largest one if none does.
+pub(crate) fn variant_for_width(display_px: f32) -> (&'static str, u32) {
+    POSTER_VARIANTS
+        .iter()
+        .copied()
+        .find(|&(_, w)| w as f32 >= display_px)
+        .unwrap_or(POSTER_VARIANTS[POSTER_VARIANTS.len() - 1])
+}
+
+/// The larger variant to fetch when a `cached_w` poster is displayed at `display_px`.
+pub(crate) fn variant_upgrade_for(cached_w: u32, display_px: f32) -> Option<(&'static str, u32)> {
+    if display_px <= cached_w as f32 * (1.0 + VARIANT_UPGRADE_SLACK) {
+        return None;
+    }
+    let target = variant_for_width(display_px);
+    (target.1 > cached_w).then_some(target)
+}
+
+/// Swap the variant suffix on a poster cache key (`<md5>__s` -> `<md5>__m`).
+pub(crate) fn variant_key(key: &str, suffix: &str) -> String {
+    format!("{}{suffix}", base_poster_key(key))
+}
+
+/// Poster cache key without its variant suffix (`<md5>__s` -> `<md5>`); the full-size
+/// poster is stored under it.
+pub(crate) fn base_poster_key(key: &str) -> &str {
+    POSTER_VARIANTS
+        .iter()
+        .find_map(|(sfx, _)| key.strip_suffix(sfx))
+        .unwrap_or(key)
+}
+
+// Dominant colour per poster (base cache key, no variant suffix), painted as the card
+// placeholder until the texture is uploaded. Persisted as `key=rrggbb` lines.
+const SWATCH_FILE: &str = "poster_swatches.txt";
+static SWATCHES: OnceLock<Mutex<HashMap<String, [u8; 3]>>> = OnceLock::new();
+static SWATCHES_DIRTY: AtomicBool = AtomicBool::new(false);
+
+fn swatches() -> &'static Mutex<HashMap<String, [u8; 3]>> {
+    SWATCHES.get_or_init(|| {
+        let text = fs::read_to_string(cache_dir().join(SWATCH_FILE)).unwrap_or_default();
+        Mutex::new(text.lines().filter_map(parse_swatch_line).collect())
+    })
+}
+
+fn parse_swatch_line(line: &str) -> Option<(String, [u8; 3])> {
+    let (key, hex) = line.trim().split_once('=')?;
+    let rgb = u32::from_str_radix(hex, 16)
+        .ok()
+        .filter(|_| hex.len() == 6)?;
+    Some((
+        key.to_string(),
+        [(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8],
+    ))
+}
+
+/// Average colour of a 16x16 sample grid (cheap enough for full-size posters).
+fn sample_swatch(img: &impl GenericImageView<Pixel = image::Rgba<u8>>) -> [u8; 3] {
+    const STEPS: u32 = 16;
+    let (w, h) = img.dimensions();
+    if w == 0 || h == 0 {
+        return [40, 40, 40];
+    }
+    let mut sum = [0u64; 3];
+    let mut n = 0u64;
+    for sy in 0..STEPS {
+        for sx in 0..STEPS {
+            let px = img.get_pixel((sx * w / STEPS).min(w - 1), (sy * h / STEPS).min(h - 1));
+            for (acc, c) in sum.iter_mut().zip(px.0) {
+                *acc += u64::from(c);
+            }
+            n += 1;
+        }
+    }
+    sum.map(|c| (c / n) as u8)
+}
+
+/// Remember the swatch for `key` (the first sample wins unless `replace`).
+fn record_swatch(key: &str, img: &impl GenericImageView<Pixel = image::Rgba<u8>>, replace: bool) {
+    let key = base_poster_key(key);
+    let mut map = swatches().lock().unwrap();
+    if !replace && map.contains_key(key) {
+        return;
+    }
+    map.insert(key.to_string(), sample_swatch(img));
+    SWATCHES_DIRTY.store(true, Ordering::Relaxed);
+}
+
+/// Placeholder colour for a poster that has no texture yet.
+pub fn poster_swatch(key: &str) -> Option<[u8; 3]> {
+    swatches()
+        .lock()
+        .unwrap()
+        .get(base_poster_key(key))
+        .copied()
+}
+
+/// Backfill a swatch from pixels already loaded for a texture (posters cached before swatches).
+pub fn note_swatch_from_rgba(key: &str, w: u32, h: u32, rgba: &[u8]) {
+    if poster_swatch(key).is_some() {
+        return;
+    }
+    if let Some(img) = image::RgbaImage::from_raw(w, h, rgba.to_vec()) {
+        record_swatch(key, &img, false);
+    }
+}
+
+/// Write the swatch index if anything changed since the last save.
+pub fn save_poster_swatches() -> std::io::Result<()> {
+    if !SWATCHES_DIRTY.swap(false, Ordering::Relaxed) {
+        return Ok(());
+    }
+    let map = swatches().lock().unwrap();
+    let mut lines: Vec<String> = map
+        .iter()
+        .map(|(key, [r, g, b])| format!("{key}={r:02x}{g:02x}{b:02x}"))
+        .collect();
+    lines.sort_unstable();
+    fs::write(cache_dir().join(SWATCH_FILE), lines.join("\n"))
+}
 
 pub fn cache_dir() -> PathBuf {
     CACHE_DIR_ONCE
@@ -53,7 +187,7 @@ pub fn poster_cache_dir() -> PathBuf {
     POSTER_PRUNE_ONCE.call_once({
         let path = dir.clone();
         move || {
-            if let Err(err) = prune_poster_cache_in_dir(&path) {
+            if let Err(err) = prune_poster_cache_in_dir(&path, &SystemClock) {
                 warn!("poster cache prune failed: {err}");
             }
         }
@@ -64,11 +198,13 @@ pub fn poster_cache_dir() -> PathBuf {
 
 fn prune_poster_cache_if_needed() -> std::io::Result<usize> {
     let dir = poster_cache_dir();
-    prune_poster_cache_in_dir(&dir)
+    prune_poster_cache_in_dir(&dir, &SystemClock)
 }
 
-fn prune_poster_cache_in_dir(dir: &Path) -> std::io::Result<usize> {
-    let cutoff = SystemTime::now()
+/// Remove poster images in `dir` whose mtime is older than the retention window relative to `clock`.
+fn prune_poster_cache_in_dir(dir: &Path, clock: &dyn Clock) -> std::io::Result<usize> {
+    let cutoff = clock
+        .now()
         .checked_sub(Duration::from_secs(POSTER_RETENTION_SECS))
         .unwrap_or(SystemTime::UNIX_EPOCH);
     let mut removed = 0usize;
@@ -80,7 +216,10 @@ fn prune_poster_cache_in_dir(dir: &Path) -> std::io::Result<usize> {
         let path = entry.path();
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
             let ext = ext.to_ascii_lowercase();
-            if !matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "webp" | "rgba") {
+            if !matches!(
+                ext.as_str(),
+                "png" | "jpg" | "jpeg" | "webp" | "rgba" | VALIDATORS_EXT
+            ) {
                 continue;
             }
         } else {
@@ -158,15 +297,13 @@ pub fn find_any_by_key(key: &str) -> Option<PathBuf> {
 
 /// Download, normalize to PNG or RGBA and store in cache. Returns the stored path.
 pub fn download_and_store(url: &str, key: &str) -> Result<PathBuf, String> {
-    let client = Client::builder()
+    net::ensure_online()?;
+    let client = net::client_builder()
         .timeout(Duration::from_secs(15))
         .build()
         .map_err(|e| format!("http client: {e}"))?;
 
-    let resp = client
-        .get(url)
-        .send()
-        .map_err(|e| format!("GET {url}: {e}"))?;
+    let resp = net::track(client.get(url).send()).map_err(|e| format!("GET {url}: {e}"))?;
     if !resp.status().is_success() {
         return Err(format!("HTTP {} for {url}", resp.status()));
     }
@@ -175,20 +312,24 @@ pub fn download_and_store(url: &str, key: &str) -> Result<PathBuf, String> {
         .map_err(|e| format!("read body: {e}"))?
         .to_vec();
 
-    // Try decode with image crate
-    match image::load_from_memory(&body) {
+    // Try decode with image crate (same caps as the resize path)
+    let _turn = large_decode_turn(&body);
+    match decode_poster_bounded(&body) {
         Ok(img) => {
+            let _mem = DecodeMemory::track(img.as_bytes().len());
+            record_swatch(key, &img, true);
             let out = poster_cache_dir().join(format!("{key}.png"));
-            let mut f =
-                fs::File::create(&out).map_err(|e| format!("create {}: {e}", out.display()))?;
-            let mut png_bytes: Vec<u8> = Vec::new();
-            img.write_to(&mut std::io::Cursor::new(&mut png_bytes), ImageFormat::Png)
+            let f = fs::File::create(&out).map_err(|e| format!("create {}: {e}", out.display()))?;
+            let mut writer = std::io::BufWriter::new(f);
+            img.write_to(&mut writer, ImageFormat::Png)
                 .map_err(|e| format!("encode png: {e}"))?;
-            f.write_all(&png_bytes)
+            writer
+                .flush()
                 .map_err(|e| format!("write {}: {e}", out.display()))?;
             let _ = prune_poster_cache_if_needed();
             Ok(out)
         }
+        Err(image::ImageError::Limits(e)) => Err(format!("poster too large: {e}")),
         Err(e) => {
             warn!("image decode failed for {url}: {e}; storing raw");
             // Store as rgba with w/h header if we really fail (rare)
@@ -207,52 +348,121 @@ pub fn download_and_store(url: &str, key: &str) -> Result<PathBuf, String> {
         }
     }
 }
-/// Download an image, resize to `max_width` (keeping aspect), and store as JPEG with `quality`.
-///
-/// Returns the on-disk path. Falls back to `download_and_store` if decode/resize fails.
-/// This writes `<poster_cache_dir>/<key>.jpg`.
-pub fn download_and_store_resized(
-    url: &str,
-    key: &str,
-    max_width: u32,
-    quality: u8,
-) -> Result<std::path::PathBuf, String> {
-    use image::{imageops::FilterType, DynamicImage};
-    use reqwest::blocking::Client;
-    use std::{fs, io::Write};
+// Decode caps for downloaded artwork. Oversized art is still decoded and shrunk straight
+// away, but anything over SERIAL_DECODE_DIM takes turns, so the WORKER_COUNT decodes in flight
+// can't all hold a full-size buffer at once. Only art past MAX_DECODE_DIM (or the allocation
+// cap) is rejected by the reader.
+const SERIAL_DECODE_DIM: u32 = 4096;
+const MAX_DECODE_DIM: u32 = 16_384;
+const MAX_DECODE_ALLOC: u64 = 320 * 1024 * 1024;
+
+static LARGE_DECODE: Mutex<()> = Mutex::new(());
+
+/// Estimated pixel-buffer bytes held by decodes right now, and the peak.
+#[derive(Default)]
+struct DecodeCounter {
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+}
 
-    let dest = poster_cache_dir().join(format!("{key}.jpg"));
+impl DecodeCounter {
+    const fn new() -> Self {
+        Self {
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
 
-    // If already present, return immediately.
-    if dest.exists() {
-        return Ok(dest);
+    fn get(&self) -> (usize, usize) {
+        (
+            self.in_flight.load(Ordering::Relaxed),
+            self.peak.load(Ordering::Relaxed),
+        )
     }
+}
 
-    // Download bytes
-    let client = Client::builder()
-        .user_agent("pex_new/resize-prefetch")
-        .build()
-        .map_err(|e| format!("reqwest client build: {e}"))?;
+static POSTER_DECODES: DecodeCounter = DecodeCounter::new();
 
-    let bytes = client
-        .get(url)
-        .send()
-        .and_then(|r| r.error_for_status())
-        .and_then(|r| r.bytes())
-        .map_err(|e| format!("download bytes: {e}"))?;
+/// Estimated pixel-buffer bytes held by poster decodes right now and the peak this run.
+pub fn poster_decode_memory() -> (usize, usize) {
+    POSTER_DECODES.get()
+}
+
+/// Counts a decoded buffer towards the in-flight total until dropped.
+struct DecodeMemory<'a> {
+    counter: &'a DecodeCounter,
+    bytes: usize,
+}
+
+impl<'a> DecodeMemory<'a> {
+    fn track(bytes: usize) -> DecodeMemory<'static> {
+        DecodeMemory::track_in(&POSTER_DECODES, bytes)
+    }
 
-    // Try to decode the image
-    let img = match image::load_from_memory(&bytes) {
+    fn track_in(counter: &'a DecodeCounter, bytes: usize) -> Self {
+        let now = counter.in_flight.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        counter.peak.fetch_max(now, Ordering::Relaxed);
+        Self { counter, bytes }
+    }
+}
+
+impl Drop for DecodeMemory<'_> {
+    fn drop(&mut self) {
+        self.counter
+            .in_flight
+            .fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// The large-art turn, for art over SERIAL_DECODE_DIM on a side; hold it until the full-size
+/// buffer is gone.
+fn large_decode_turn(bytes: &[u8]) -> Option<std::sync::MutexGuard<'static, ()>> {
+    let (w, h) = image::ImageReader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    (w > SERIAL_DECODE_DIM || h > SERIAL_DECODE_DIM).then(|| {
+        LARGE_DECODE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    })
+}
+
+fn decode_poster_bounded(bytes: &[u8]) -> image::ImageResult<image::DynamicImage> {
+    let mut reader = image::ImageReader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(image::ImageError::IoError)?;
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_DECODE_DIM);
+    limits.max_image_height = Some(MAX_DECODE_DIM);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC);
+    reader.limits(limits);
+    reader.decode()
+}
+
+/// Decode `bytes`, shrink to `max_width` and stream the JPEG straight into `dest` (via a
+/// `.part` file). Ok(false) means the bytes aren't a decodable image; callers may fall back.
+fn resize_to_jpeg_file(
+    bytes: &[u8],
+    dest: &Path,
+    max_width: u32,
+    quality: u8,
+) -> Result<bool, String> {
+    use image::imageops::FilterType;
+    use std::io::BufWriter;
+
+    let turn = large_decode_turn(bytes);
+    let img = match decode_poster_bounded(bytes) {
         Ok(img) => img,
-        Err(_) => {
-            // Fallback to original path via existing helper
-            return download_and_store(url, key);
-        }
+        Err(image::ImageError::Limits(e)) => return Err(format!("poster too large: {e}")),
+        Err(_) => return Ok(false),
     };
+    let decoded_mem = DecodeMemory::track(img.as_bytes().len());
 
-    // Resize if needed, keep aspect
+    // Resize if needed, keep aspect; the full-size buffer is released right after.
     let (w, h) = img.dimensions();
-    let out: DynamicImage = if w > max_width {
+    let out = if w > max_width {
         let new_h = ((h as f32) * (max_width as f32 / w as f32))
             .round()
             .max(1.0) as u32;
@@ -260,31 +470,57 @@ pub fn download_and_store_resized(
     } else {
         img
     };
-
-    // Encode JPEG with requested quality
-    let mut jpeg_bytes: Vec<u8> = Vec::new();
-    {
-        let mut encoder =
-            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg_bytes, quality);
-        encoder
-            .encode_image(&out)
-            .map_err(|e| format!("jpeg encode: {e}"))?;
+    let _out_mem = DecodeMemory::track(out.as_bytes().len());
+    drop(decoded_mem);
+    drop(turn);
+    if let Some(key) = dest.file_stem().and_then(|s| s.to_str()) {
+        record_swatch(key, &out, true);
     }
 
-    // Ensure cache dir exists and write atomically-ish
     if let Some(parent) = dest.parent() {
         let _ = fs::create_dir_all(parent);
     }
     let tmp = dest.with_extension("jpg.part");
-    {
-        let mut f = fs::File::create(&tmp).map_err(|e| format!("create tmp: {e}"))?;
-        f.write_all(&jpeg_bytes)
-            .map_err(|e| format!("write: {e}"))?;
+    let written = (|| {
+        let file = fs::File::create(&tmp).map_err(|e| format!("create tmp: {e}"))?;
+        let mut writer = BufWriter::new(file);
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut writer, quality)
+            .encode_image(&out)
+            .map_err(|e| format!("jpeg encode: {e}"))?;
+        writer.flush().map_err(|e| format!("write: {e}"))
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
     }
-    fs::rename(&tmp, &dest).map_err(|e| format!("rename: {e}"))?;
+    fs::rename(&tmp, dest).map_err(|e| format!("rename: {e}"))?;
+    Ok(true)
+}
 
-    let _ = prune_poster_cache_if_needed();
-    Ok(dest)
+/// Download an image, resize to `max_width` (keeping aspect), and store as JPEG with `quality`.
+///
+/// Returns the on-disk path. Falls back to `download_and_store` if decode/resize fails.
+/// This writes `<poster_cache_dir>/<key>.jpg`.
+pub fn download_and_store_resized(
+    url: &str,
+    key: &str,
+    max_width: u32,
+    quality: u8,
+) -> Result<std::path::PathBuf, String> {
+    let dest = poster_cache_dir().join(format!("{key}.jpg"));
+
+    // If already present, return immediately.
+    if dest.exists() {
+        return Ok(dest);
+    }
+
+    // Download bytes
+    let client = net::client_builder()
+        .user_agent("pex_new/resize-prefetch")
+        .build()
+        .map_err(|e| format!("reqwest client build: {e}"))?;
+
+    store_fetched_poster(client.get(url), url, key, &dest, max_width, quality)
 }
 
 pub fn prune_poster_cache_now() -> std::io::Result<usize> {
@@ -318,6 +554,8 @@ pub fn refresh_poster_cache_light() -> std::io::Result<usize> {
                 metadata.len() == 0
             } else if ext == "rgba" {
                 metadata.len() <= 8
+            } else if ext == VALIDATORS_EXT {
+                metadata.len() == 0
             } else {
                 true
             }
@@ -359,8 +597,9 @@ pub fn ensure_channel_icon(url: &str) -> Result<PathBuf, String> {
     if dest.exists() {
         return Ok(dest);
     }
+    net::ensure_online()?;
 
-    let client = Client::builder()
+    let client = net::client_builder()
         .timeout(Duration::from_secs(15))
         .build()
         .map_err(|e| format!("http client: {e}"))?;
@@ -407,9 +646,6 @@ pub fn download_and_store_resized_with_client(
     max_width: u32,
     quality: u8,
 ) -> Result<std::path::PathBuf, String> {
-    use image::{imageops::FilterType, DynamicImage};
-    use std::{fs, io::Write};
-
     let dest = poster_cache_dir().join(format!("{key}.jpg"));
 
     // If already present, return immediately.
@@ -418,57 +654,182 @@ pub fn download_and_store_resized_with_client(
     }
 
     // Download bytes using shared client
-    let bytes = client
-        .get(url)
-        .send()
-        .and_then(|r| r.error_for_status())
-        .and_then(|r| r.bytes())
-        .map_err(|e| format!("download bytes: {e}"))?;
+    store_fetched_poster(client.get(url), url, key, &dest, max_width, quality)
+}
 
-    // Try to decode the image
-    let img = match image::load_from_memory(&bytes) {
-        Ok(img) => img,
-        Err(_) => {
-            // Fallback to original path via existing helper
-            return download_and_store(url, key);
+/// HTTP cache validators remembered for a stored poster.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            etag: get(reqwest::header::ETAG),
+            last_modified: get(reqwest::header::LAST_MODIFIED),
         }
-    };
+    }
 
-    // Resize if needed, keep aspect
-    let (w, h) = img.dimensions();
-    let out: DynamicImage = if w > max_width {
-        let new_h = ((h as f32) * (max_width as f32 / w as f32))
-            .round()
-            .max(1.0) as u32;
-        img.resize_exact(max_width, new_h, FilterType::CatmullRom)
-    } else {
-        img
-    };
+    const fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
 
-    // Encode JPEG with requested quality
-    let mut jpeg_bytes: Vec<u8> = Vec::new();
-    {
-        let mut encoder =
-            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg_bytes, quality);
-        encoder
-            .encode_image(&out)
-            .map_err(|e| format!("jpeg encode: {e}"))?;
+    fn sidecar(poster: &Path) -> PathBuf {
+        poster.with_extension(VALIDATORS_EXT)
     }
 
-    // Ensure cache dir exists and write atomically-ish
-    if let Some(parent) = dest.parent() {
-        let _ = fs::create_dir_all(parent);
+    /// Read the sidecar next to `poster` (`etag=` / `last_modified=` lines).
+    pub fn load(poster: &Path) -> Self {
+        let mut out = Self::default();
+        let Ok(text) = fs::read_to_string(Self::sidecar(poster)) else {
+            return out;
+        };
+        for line in text.lines() {
+            match line.split_once('=') {
+                Some(("etag", v)) if !v.is_empty() => out.etag = Some(v.to_string()),
+                Some(("last_modified", v)) if !v.is_empty() => {
+                    out.last_modified = Some(v.to_string())
+                }
+                _ => {}
+            }
+        }
+        out
     }
-    let tmp = dest.with_extension("jpg.part");
-    {
-        let mut f = fs::File::create(&tmp).map_err(|e| format!("create tmp: {e}"))?;
-        f.write_all(&jpeg_bytes)
-            .map_err(|e| format!("write: {e}"))?;
+
+    /// Write (or clear) the sidecar for `poster`.
+    pub fn store(&self, poster: &Path) {
+        let sidecar = Self::sidecar(poster);
+        if self.is_empty() {
+            let _ = fs::remove_file(sidecar);
+            return;
+        }
+        let mut text = String::new();
+        if let Some(etag) = &self.etag {
+            text.push_str(&format!("etag={etag}\n"));
+        }
+        if let Some(lm) = &self.last_modified {
+            text.push_str(&format!("last_modified={lm}\n"));
+        }
+        if let Err(e) = fs::write(&sidecar, text) {
+            warn!("failed to write {}: {e}", sidecar.display());
+        }
+    }
+}
+
+/// Send `request`, resize the body into `dest` and remember the response validators.
+fn store_fetched_poster(
+    request: reqwest::blocking::RequestBuilder,
+    url: &str,
+    key: &str,
+    dest: &Path,
+    max_width: u32,
+    quality: u8,
+) -> Result<PathBuf, String> {
+    net::ensure_online()?;
+    let resp = net::track(request.send())
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("download bytes: {e}"))?;
+    let validators = Validators::from_headers(resp.headers());
+    let bytes = resp.bytes().map_err(|e| format!("download bytes: {e}"))?;
+
+    if !resize_to_jpeg_file(&bytes, dest, max_width, quality)? {
+        // Not an image the decoder understands; keep the original bytes instead.
+        return download_and_store(url, key);
     }
-    fs::rename(&tmp, &dest).map_err(|e| format!("rename: {e}"))?;
+    validators.store(dest);
 
     let _ = prune_poster_cache_if_needed();
-    Ok(dest)
+    Ok(dest.to_path_buf())
+}
+
+/// True once a cached poster's mtime is at least `stale_after` old.
+pub fn poster_is_stale(path: &Path, stale_after: Duration) -> bool {
+    let modified = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    SystemTime::now()
+        .duration_since(modified)
+        .is_ok_and(|age| age >= stale_after)
+}
+
+/// Outcome of a conditional poster request.
+#[derive(Debug)]
+pub enum Revalidated {
+    /// 304: the stored file is still current (its age has been reset).
+    Unchanged(PathBuf),
+    /// The server sent new artwork, now stored in place of the old file.
+    Updated(PathBuf),
+}
+
+/// Re-check a cached poster with `If-None-Match` / `If-Modified-Since`. Posters without stored
+/// validators are fetched again in full so they gain some for next time.
+pub fn revalidate_poster(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    path: &Path,
+    quality: u8,
+) -> Result<Revalidated, String> {
+    net::ensure_online()?;
+    let validators = Validators::load(path);
+    let mut request = client.get(url);
+    if let Some(etag) = &validators.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(lm) = &validators.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, lm);
+    }
+    let resp = net::track(request.send()).map_err(|e| format!("revalidate: {e}"))?;
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        let refreshed = Validators::from_headers(resp.headers());
+        if !refreshed.is_empty() && refreshed != validators {
+            refreshed.store(path);
+        }
+        touch(path);
+        touch(&Validators::sidecar(path));
+        return Ok(Revalidated::Unchanged(path.to_path_buf()));
+    }
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {} for {url}", resp.status()));
+    }
+
+    let validators = Validators::from_headers(resp.headers());
+    let bytes = resp.bytes().map_err(|e| format!("read body: {e}"))?;
+    let key = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| format!("bad cache path {}", path.display()))?;
+    let max_width = POSTER_VARIANTS
+        .iter()
+        .find(|(sfx, _)| key.ends_with(sfx))
+        .map_or(POSTER_VARIANTS[0].1, |&(_, w)| w);
+    let dest = path.with_extension("jpg");
+    if !resize_to_jpeg_file(&bytes, &dest, max_width, quality)? {
+        return Err(format!(
+            "revalidated poster for {url} is not a decodable image"
+        ));
+    }
+    if dest != path {
+        // Older non-JPEG fallback; the fresh JPEG replaces it.
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(Validators::sidecar(path));
+    }
+    validators.store(&dest);
+    Ok(Revalidated::Updated(dest))
+}
+
+fn touch(path: &Path) {
+    if let Ok(file) = fs::File::options().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
 }
 
 fn normalize_dir(p: PathBuf) -> PathBuf {
@@ -484,3 +845,125 @@ fn normalize_dir(p: PathBuf) -> PathBuf {
         p
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::clock::FixedClock;
+
+    #[test]
+    fn prune_keeps_fresh_posters_and_removes_expired_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("poster__s.jpg"), b"jpg").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"keep").unwrap();
+
+        let clock = FixedClock::new(SystemTime::now());
+        assert_eq!(prune_poster_cache_in_dir(dir.path(), &clock).unwrap(), 0);
+        assert!(dir.path().join("poster__s.jpg").exists());
+
+        // Just inside the window: still kept.
+        clock.advance(Duration::from_secs(POSTER_RETENTION_SECS - 60));
+        assert_eq!(prune_poster_cache_in_dir(dir.path(), &clock).unwrap(), 0);
+
+        // Past the window: the image goes, unrelated files stay.
+        clock.advance(Duration::from_secs(120));
+        assert_eq!(prune_poster_cache_in_dir(dir.path(), &clock).unwrap(), 1);
+        assert!(!dir.path().join("poster__s.jpg").exists());
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    fn png_bytes(w: u32, h: u32) -> Vec<u8> {
+        let img = image::RgbImage::from_pixel(w, h, image::Rgb([200, 40, 40]));
+        let mut out = Vec::new();
+        img.write_to(&mut std::io::Cursor::new(&mut out), ImageFormat::Png)
+            .unwrap();
+        out
+    }
+
+    #[test]
+    fn resize_streams_a_capped_jpeg_and_shrinks_oversized_art() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("poster__s.jpg");
+        assert!(resize_to_jpeg_file(&png_bytes(640, 960), &dest, 320, 75).unwrap());
+        let stored = image::open(&dest).unwrap();
+        assert_eq!(stored.dimensions(), (320, 480));
+        assert!(!dir.path().join("poster__s.jpg.part").exists());
+
+        // Past the serial-decode size: still stored, just shrunk like any other poster.
+        let wide = dir.path().join("wide__s.jpg");
+        let wide_png = png_bytes(SERIAL_DECODE_DIM * 2, 4);
+        assert!(large_decode_turn(&wide_png).is_some());
+        assert!(resize_to_jpeg_file(&wide_png, &wide, 320, 75).unwrap());
+        assert_eq!(image::open(&wide).unwrap().dimensions(), (320, 1));
+        assert!(large_decode_turn(&png_bytes(640, 960)).is_none());
+
+        let huge = dir.path().join("huge__s.jpg");
+        assert!(resize_to_jpeg_file(&png_bytes(MAX_DECODE_DIM + 1, 2), &huge, 320, 75).is_err());
+        assert!(!huge.exists());
+
+        assert!(!resize_to_jpeg_file(b"not an image", &huge, 320, 75).unwrap());
+    }
+
+    #[test]
+    fn decode_memory_counts_buffers_until_dropped() {
+        let counter = DecodeCounter::default();
+        let first = DecodeMemory::track_in(&counter, 100);
+        let second = DecodeMemory::track_in(&counter, 50);
+        assert_eq!(counter.get(), (150, 150));
+        drop(first);
+        assert_eq!(counter.get(), (50, 150));
+        drop(second);
+        let _third = DecodeMemory::track_in(&counter, 20);
+        assert_eq!(counter.get(), (20, 150));
+    }
+
+    #[test]
+    fn validators_round_trip_through_the_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let poster = dir.path().join("abc__s.jpg");
+        fs::write(&poster, b"jpg").unwrap();
+        assert!(Validators::load(&poster).is_empty());
+
+        let validators = Validators {
+            etag: Some("W/\"5f2-abc=\"".into()),
+            last_modified: Some("Tue, 13 Oct 2026 08:00:00 GMT".into()),
+        };
+        validators.store(&poster);
+        assert!(dir.path().join("abc__s.validators").exists());
+        assert_eq!(Validators::load(&poster), validators);
+
+        Validators::default().store(&poster);
+        assert!(!dir.path().join("abc__s.validators").exists());
+        assert!(!poster_is_stale(&poster, POSTER_REVALIDATE_AFTER));
+        assert!(poster_is_stale(&poster, Duration::ZERO));
+    }
+
+    #[test]
+    fn swatches_average_the_poster_and_round_trip() {
+        let img = image::RgbaImage::from_fn(40, 60, |x, _| {
+            if x < 20 {
+                image::Rgba([200, 0, 0, 255])
+            } else {
+                image::Rgba([0, 0, 100, 255])
+            }
+        });
+        assert_eq!(sample_swatch(&img), [100, 0, 50]);
+        assert_eq!(
+            parse_swatch_line("abc123=c80a32"),
+            Some(("abc123".to_string(), [200, 10, 50]))
+        );
+        assert_eq!(parse_swatch_line("abc123=c80a3"), None);
+        assert_eq!(base_poster_key("abc__m"), "abc");
+    }
+
+    #[test]
+    fn variant_upgrade_needs_to_clear_the_slack() {
+        // 220pt cards at 1x fit within 320px + slack; at 2x they need the 640px tier.
+        assert_eq!(variant_upgrade_for(320, 220.0), None);
+        assert_eq!(variant_upgrade_for(320, 360.0), None);
+        assert_eq!(variant_upgrade_for(320, 440.0), Some(("__m", 640)));
+        // Nothing bigger than the largest tier.
+        assert_eq!(variant_upgrade_for(640, 1200.0), None);
+        assert_eq!(variant_key("abc__s", "__m"), "abc__m");
+    }
+}
@@ -0,0 +1,151 @@
+// src/app/channel_groups.rs
+use std::collections::{BTreeMap, BTreeSet};
+
+use eframe::egui as eg;
+
+/// Named channel sets saved in the UI prefs, one `channel_group=<name>:<raw>,<raw>` line each.
+#[derive(Default)]
+pub(crate) struct ChannelGroups {
+    pub groups: BTreeMap<String, BTreeSet<String>>,
+    /// Name typed in the channel popup for "Save as group".
+    pub new_name: String,
+}
+
+impl ChannelGroups {
+    pub(crate) fn load_pref(&mut self, value: &str) {
+        if let Some((name, channels)) = parse_group_pref(value) {
+            self.groups.insert(name, channels);
+        }
+    }
+
+    /// Pref lines for every group, each ending in a newline.
+    pub(crate) fn pref_lines(&self) -> String {
+        self.groups
+            .iter()
+            .map(|(name, channels)| {
+                format!("channel_group={}\n", format_group_pref(name, channels))
+            })
+            .collect()
+    }
+}
+
+fn parse_group_pref(value: &str) -> Option<(String, BTreeSet<String>)> {
+    let (name, channels) = value.split_once(':')?;
+    let name = name.trim();
+    let channels: BTreeSet<String> = channels
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(String::from)
+        .collect();
+    (!name.is_empty() && !channels.is_empty()).then(|| (name.to_string(), channels))
+}
+
+fn format_group_pref(name: &str, channels: &BTreeSet<String>) -> String {
+    let channels: Vec<String> = channels.iter().map(|c| c.replace(',', " ")).collect();
+    format!("{}:{}", name.replace(':', " "), channels.join(","))
+}
+
+impl crate::app::PexApp {
+    /// Filter to exactly `channels`; clicking the group that is already applied clears it.
+    fn apply_channel_group(&mut self, channels: &BTreeSet<String>) {
+        if self.selected_channels == *channels {
+            self.selected_channels.clear();
+        } else {
+            self.selected_channels = channels.clone();
+        }
+        self.mark_dirty();
+    }
+
+    /// One-click group chips in the top bar: pinned favourites first, then named groups.
+    pub(crate) fn ui_render_channel_group_chips(&mut self, ui: &mut eg::Ui) {
+        let favourites = self.favorite_channels.clone();
+        if !favourites.is_empty() {
+            let active = self.selected_channels == favourites;
+            if ui
+                .selectable_label(active, "★ Favourites")
+                .on_hover_text("Show only pinned channels (click again for all channels)")
+                .clicked()
+            {
+                self.apply_channel_group(&favourites);
+            }
+        }
+        let groups: Vec<(String, BTreeSet<String>)> = self
+            .channel_groups
+            .groups
+            .iter()
+            .map(|(name, channels)| (name.clone(), channels.clone()))
+            .collect();
+        for (name, channels) in groups {
+            let active = self.selected_channels == channels;
+            if ui
+                .selectable_label(active, &name)
+                .on_hover_text(format!(
+                    "Show only the {} channels in this group (click again for all channels)",
+                    channels.len()
+                ))
+                .clicked()
+            {
+                self.apply_channel_group(&channels);
+            }
+        }
+    }
+
+    /// "Groups" section of the channel popup: save the ticked channels under a name, or
+    /// delete a group.
+    pub(crate) fn ui_render_channel_group_editor(&mut self, ui: &mut eg::Ui) {
+        ui.label(eg::RichText::new("Channel groups").strong());
+        ui.horizontal(|ui| {
+            ui.add(
+                eg::TextEdit::singleline(&mut self.channel_groups.new_name)
+                    .hint_text("Group name")
+                    .desired_width(140.0),
+            );
+            let name = self.channel_groups.new_name.trim().replace(':', " ");
+            let can_save = !name.is_empty() && !self.selected_channels.is_empty();
+            if ui
+                .add_enabled(can_save, eg::Button::new("Save ticked as group"))
+                .on_hover_text(
+                    "Store the ticked channels under this name \
+                     (replaces a group with the same name)",
+                )
+                .clicked()
+            {
+                self.channel_groups
+                    .groups
+                    .insert(name, self.selected_channels.clone());
+                self.channel_groups.new_name.clear();
+                self.mark_dirty();
+            }
+        });
+        let mut remove: Option<String> = None;
+        for (name, channels) in &self.channel_groups.groups {
+            ui.horizontal(|ui| {
+                if ui.small_button("🗑").on_hover_text("Delete group").clicked() {
+                    remove = Some(name.clone());
+                }
+                ui.label(format!("{name} ({})", channels.len()));
+            });
+        }
+        if let Some(name) = remove {
+            self.channel_groups.groups.remove(&name);
+            self.mark_dirty();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_pref_round_trip() {
+        let channels: BTreeSet<String> = ["bbc1hd", "itv,hd"].map(String::from).into();
+        let line = format_group_pref("Freeview: HD", &channels);
+        let (name, parsed) = parse_group_pref(&line).unwrap();
+        assert_eq!(name, "Freeview  HD");
+        assert_eq!(parsed, ["bbc1hd", "itv hd"].map(String::from).into());
+        assert!(parse_group_pref("Empty:").is_none());
+        assert!(parse_group_pref("no colon").is_none());
+    }
+}
@@ -0,0 +1,596 @@
+// src/app/collections.rs
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::{Duration, SystemTime};
+
+use eframe::egui as eg;
+use serde::Deserialize;
+use urlencoding::encode;
+
+use crate::app::utils::{day_bucket, format_day_label, hhmm_utc};
+use crate::config::load_config;
+
+/// One film in a TMDb collection.
+#[derive(Clone, Debug)]
+pub(crate) struct CollectionPart {
+    pub title: String,
+    pub year: Option<i32>,
+    pub release_date: Option<String>,
+}
+
+/// A TMDb collection (franchise) with every film in it, owned or not.
+#[derive(Clone, Debug)]
+pub(crate) struct Collection {
+    pub name: String,
+    pub parts: Vec<CollectionPart>,
+}
+
+impl Collection {
+    /// Films released on or before `today` (YYYY-MM-DD); announced entries can't be owned yet.
+    pub fn released_parts<'a>(
+        &'a self,
+        today: &'a str,
+    ) -> impl Iterator<Item = &'a CollectionPart> {
+        self.parts.iter().filter(move |part| {
+            part.release_date
+                .as_deref()
+                .is_some_and(|date| !date.is_empty() && date <= today)
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum CollectionLookup {
+    Pending,
+    Member(u64),
+    NotInCollection,
+    MissingApiKey,
+    Error(String),
+}
+
+struct CollectionMsg {
+    key: String,
+    result: Result<Option<(u64, Collection)>, String>,
+}
+
+/// TMDb collection lookups keyed by poster key, plus the collections resolved so far.
+#[derive(Default)]
+pub(crate) struct CollectionsState {
+    lookups: HashMap<String, CollectionLookup>,
+    collections: HashMap<u64, Collection>,
+    tx: Option<Sender<CollectionMsg>>,
+    rx: Option<Receiver<CollectionMsg>>,
+    pub show_popup: bool,
+}
+
+impl CollectionsState {
+    fn pending(&self) -> usize {
+        self.lookups
+            .values()
+            .filter(|l| matches!(l, CollectionLookup::Pending))
+            .count()
+    }
+}
+
+struct LookupJob {
+    key: String,
+    imdb_id: Option<String>,
+    title: String,
+    year: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct TmdbMovieRef {
+    id: u64,
+    #[serde(default)]
+    release_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TmdbFind {
+    #[serde(default)]
+    movie_results: Vec<TmdbMovieRef>,
+}
+
+#[derive(Deserialize)]
+struct TmdbSearch {
+    #[serde(default)]
+    results: Vec<TmdbMovieRef>,
+}
+
+#[derive(Deserialize)]
+struct TmdbCollectionRef {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct TmdbMovieDetails {
+    #[serde(default)]
+    belongs_to_collection: Option<TmdbCollectionRef>,
+}
+
+#[derive(Deserialize)]
+struct TmdbCollectionPart {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    release_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TmdbCollection {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    parts: Vec<TmdbCollectionPart>,
+}
+
+struct Tmdb {
+    client: reqwest::blocking::Client,
+    api_key: String,
+}
+
+impl Tmdb {
+    fn new(api_key: String) -> Result<Self, String> {
+        let client = crate::app::net::client_builder()
+            .user_agent("pex/collections")
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|err| format!("client: {err}"))?;
+        Ok(Self { client, api_key })
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, path_and_query: &str) -> Result<T, String> {
+        let sep = if path_and_query.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let url = format!(
+            "https://api.themoviedb.org/3/{path_and_query}{sep}api_key={}&language=en-US",
+            self.api_key
+        );
+        let resp = self
+            .client
+            .get(url)
+            .send()
+            .map_err(|err| format!("network: {err}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("HTTP {}", resp.status()));
+        }
+        let body = resp.text().map_err(|err| format!("read: {err}"))?;
+        serde_json::from_str(&body).map_err(|err| format!("parse: {err}"))
+    }
+
+    fn movie_id(&self, job: &LookupJob) -> Result<Option<u64>, String> {
+        if let Some(imdb_id) = job.imdb_id.as_deref() {
+            let found: TmdbFind = self.get(&format!("find/{imdb_id}?external_source=imdb_id"))?;
+            if let Some(movie) = found.movie_results.first() {
+                return Ok(Some(movie.id));
+            }
+        }
+        let title = job.title.trim();
+        if title.is_empty() {
+            return Ok(None);
+        }
+        let mut query = format!("search/movie?include_adult=false&query={}", encode(title));
+        if let Some(year) = job.year {
+            query.push_str(&format!("&year={year}"));
+        }
+        let found: TmdbSearch = self.get(&query)?;
+        let by_year = job.year.and_then(|year| {
+            found.results.iter().find(|movie| {
+                movie
+                    .release_date
+                    .as_deref()
+                    .and_then(|d| d.get(..4))
+                    .and_then(|y| y.parse::<i32>().ok())
+                    == Some(year)
+            })
+        });
+        Ok(by_year.or(found.results.first()).map(|movie| movie.id))
+    }
+
+    /// Resolve the collection a film belongs to, reusing collections fetched earlier in the run.
+    fn lookup(
+        &self,
+        job: &LookupJob,
+        known: &mut HashMap<u64, Collection>,
+    ) -> Result<Option<(u64, Collection)>, String> {
+        let Some(movie_id) = self.movie_id(job)? else {
+            return Ok(None);
+        };
+        let details: TmdbMovieDetails = self.get(&format!("movie/{movie_id}"))?;
+        let Some(collection_id) = details.belongs_to_collection.map(|c| c.id) else {
+            return Ok(None);
+        };
+        if let Some(collection) = known.get(&collection_id) {
+            return Ok(Some((collection_id, collection.clone())));
+        }
+        let raw: TmdbCollection = self.get(&format!("collection/{collection_id}"))?;
+        let collection = collection_from_tmdb(raw);
+        known.insert(collection_id, collection.clone());
+        Ok(Some((collection_id, collection)))
+    }
+}
+
+fn collection_from_tmdb(raw: TmdbCollection) -> Collection {
+    let mut parts: Vec<CollectionPart> = raw
+        .parts
+        .into_iter()
+        .filter(|part| !part.title.trim().is_empty())
+        .map(|part| CollectionPart {
+            year: part
+                .release_date
+                .as_deref()
+                .and_then(|d| d.get(..4))
+                .and_then(|y| y.parse().ok()),
+            title: part.title,
+            release_date: part.release_date.filter(|d| !d.is_empty()),
+        })
+        .collect();
+    // Unreleased entries (no date) sort last.
+    parts.sort_by(|a, b| match (&a.release_date, &b.release_date) {
+        (Some(x), Some(y)) => x.cmp(y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.title.cmp(&b.title),
+    });
+    Collection {
+        name: raw.name,
+        parts,
+    }
+}
+
+fn today_iso(now: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(now)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+impl crate::app::PexApp {
+    /// Queue TMDb collection lookups for `idxs` (rows already resolved or pending are skipped).
+    /// Lookups run sequentially on one background thread to stay well inside TMDb rate limits.
+    pub(crate) fn request_collection_lookups(&mut self, idxs: &[usize]) {
+        if self.is_offline() {
+            return;
+        }
+        let mut jobs: Vec<LookupJob> = Vec::new();
+        for &idx in idxs {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            if matches!(
+                self.collections.lookups.get(&row.key),
+                Some(
+                    CollectionLookup::Pending
+                        | CollectionLookup::Member(_)
+                        | CollectionLookup::NotInCollection
+                )
+            ) || jobs.iter().any(|job| job.key == row.key)
+            {
+                continue;
+            }
+            jobs.push(LookupJob {
+                key: row.key.clone(),
+                imdb_id: row.guid.as_deref().and_then(super::imdb_id_from_guid),
+                title: row.title.clone(),
+                year: row.year,
+            });
+        }
+        if jobs.is_empty() {
+            return;
+        }
+
+        let Some(api_key) = load_config()
+            .tmdb_api_key
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+        else {
+            for job in jobs {
+                self.collections
+                    .lookups
+                    .insert(job.key, CollectionLookup::MissingApiKey);
+            }
+            return;
+        };
+
+        if self.collections.tx.is_none() {
+            let (tx, rx) = mpsc::channel();
+            self.collections.tx = Some(tx);
+            self.collections.rx = Some(rx);
+        }
+        let tx = self.collections.tx.clone().expect("collection channel");
+        for job in &jobs {
+            self.collections
+                .lookups
+                .insert(job.key.clone(), CollectionLookup::Pending);
+        }
+        let mut known = self.collections.collections.clone();
+
+        std::thread::spawn(move || {
+            let tmdb = match Tmdb::new(api_key) {
+                Ok(tmdb) => tmdb,
+                Err(err) => {
+                    for job in jobs {
+                        let _ = tx.send(CollectionMsg {
+                            key: job.key,
+                            result: Err(err.clone()),
+                        });
+                    }
+                    return;
+                }
+            };
+            for job in jobs {
+                let result = tmdb.lookup(&job, &mut known);
+                if tx
+                    .send(CollectionMsg {
+                        key: job.key,
+                        result,
+                    })
+                    .is_err()
+                {
+                    return; // app reset the collection state
+                }
+            }
+        });
+    }
+
+    pub(crate) fn poll_collection_updates(&mut self) {
+        while let Some(rx) = self.collections.rx.as_ref() {
+            match rx.try_recv() {
+                Ok(msg) => {
+                    let lookup = match msg.result {
+                        Ok(Some((id, collection))) => {
+                            self.collections.collections.insert(id, collection);
+                            CollectionLookup::Member(id)
+                        }
+                        Ok(None) => CollectionLookup::NotInCollection,
+                        Err(err) => CollectionLookup::Error(err),
+                    };
+                    self.collections.lookups.insert(msg.key, lookup);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.collections.tx = None;
+                    self.collections.rx = None;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn part_is_owned(&self, part: &CollectionPart) -> bool {
+        let Some(keys) = self.owned_keys.as_ref() else {
+            return false;
+        };
+        Self::owned_key_variants(&part.title, part.year)
+            .iter()
+            .any(|key| keys.contains(key))
+    }
+
+    /// (owned, released) film counts for a collection.
+    fn collection_ownership(&self, collection: &Collection, today: &str) -> (usize, usize) {
+        collection
+            .released_parts(today)
+            .fold((0, 0), |(owned, total), part| {
+                (owned + usize::from(self.part_is_owned(part)), total + 1)
+            })
+    }
+
+    /// Collection line for the detail panel; looks the film up on first view.
+    pub(crate) fn ui_render_collection_summary(&mut self, ui: &mut eg::Ui, idx: usize) {
+        let Some(key) = self.rows.get(idx).map(|row| row.key.clone()) else {
+            return;
+        };
+        if !self.collections.lookups.contains_key(&key) {
+            self.request_collection_lookups(&[idx]);
+        }
+        match self.collections.lookups.get(&key).cloned() {
+            Some(CollectionLookup::Member(id)) => {
+                let Some(collection) = self.collections.collections.get(&id) else {
+                    return;
+                };
+                let today = today_iso(self.now());
+                let (owned, total) = self.collection_ownership(collection, &today);
+                ui.add_space(6.0);
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(eg::RichText::new(&collection.name).strong());
+                    ui.label(format!(
+                        "You own {owned} of {total} film{} in this collection.",
+                        if total == 1 { "" } else { "s" }
+                    ));
+                    if ui.small_button("Collections…").clicked() {
+                        self.collections.show_popup = true;
+                    }
+                });
+            }
+            Some(CollectionLookup::Pending) => {
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.add(eg::Spinner::new().size(12.0));
+                    ui.label(eg::RichText::new("Checking TMDb collection…").weak());
+                });
+            }
+            Some(CollectionLookup::Error(err)) => {
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label(
+                        eg::RichText::new(format!("Collection lookup failed: {err}"))
+                            .color(eg::Color32::LIGHT_RED),
+                    );
+                    if ui.small_button("Retry").clicked() {
+                        self.collections.lookups.remove(&key);
+                    }
+                });
+            }
+            Some(CollectionLookup::NotInCollection | CollectionLookup::MissingApiKey) | None => {}
+        }
+    }
+
+    // ---------- COLLECTIONS BROWSER ----------
+    pub(crate) fn ui_render_collections_popup(&mut self, ctx: &eg::Context) {
+        if !self.collections.show_popup {
+            return;
+        }
+        self.poll_collection_updates();
+
+        let now = self.now();
+        let today = today_iso(self.now());
+        // Upcoming airings of films you don't own, grouped by collection.
+        let mut missing: BTreeMap<(String, u64), Vec<usize>> = BTreeMap::new();
+        let mut looked_up = 0usize;
+        for (idx, row) in self.rows.iter().enumerate() {
+            let Some(lookup) = self.collections.lookups.get(&row.key) else {
+                continue;
+            };
+            looked_up += 1;
+            let CollectionLookup::Member(id) = lookup else {
+                continue;
+            };
+            if row.owned || row.airing.is_some_and(|ts| ts < now) {
+                continue;
+            }
+            if let Some(collection) = self.collections.collections.get(id) {
+                missing
+                    .entry((collection.name.clone(), *id))
+                    .or_default()
+                    .push(idx);
+            }
+        }
+        let pending = self.collections.pending();
+        let api_key_missing = self
+            .collections
+            .lookups
+            .values()
+            .any(|l| matches!(l, CollectionLookup::MissingApiKey));
+
+        let mut open = self.collections.show_popup;
+        let mut lookup_all = false;
+        let mut select: Option<usize> = None;
+        eg::Window::new("Collections")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(
+                    eg::RichText::new(
+                        "Collections with upcoming airings of films you don't own yet (TMDb).",
+                    )
+                    .weak(),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "Looked up {looked_up} of {} airings.",
+                        self.rows.len()
+                    ));
+                    if pending > 0 {
+                        ui.add(eg::Spinner::new().size(12.0));
+                        ui.label(format!("{pending} pending"));
+                    } else if ui
+                        .button("Look up all airings")
+                        .on_hover_text("Query TMDb for every airing's collection (one at a time)")
+                        .clicked()
+                    {
+                        lookup_all = true;
+                    }
+                });
+                if api_key_missing {
+                    ui.label(
+                        eg::RichText::new(
+                            "Set tmdb_api_key in config.json to look up collections.",
+                        )
+                        .weak(),
+                    );
+                }
+                ui.separator();
+
+                if missing.is_empty() {
+                    ui.label(
+                        eg::RichText::new("No collection gaps found yet.")
+                            .italics()
+                            .weak(),
+                    );
+                    return;
+                }
+                eg::ScrollArea::vertical().max_height(420.0).show(ui, |ui| {
+                    for ((name, id), idxs) in &missing {
+                        let Some(collection) = self.collections.collections.get(id) else {
+                            continue;
+                        };
+                        let (owned, total) = self.collection_ownership(collection, &today);
+                        eg::CollapsingHeader::new(format!("{name} — own {owned} of {total}"))
+                            .id_source(("collection", *id))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for &idx in idxs {
+                                    let Some(row) = self.rows.get(idx) else {
+                                        continue;
+                                    };
+                                    let title = row.year.map_or_else(
+                                        || row.title.clone(),
+                                        |y| format!("{} ({y})", row.title),
+                                    );
+                                    let when = row
+                                        .airing
+                                        .map(|ts| {
+                                            format!(
+                                                "{} {}",
+                                                format_day_label(day_bucket(ts)),
+                                                hhmm_utc(ts)
+                                            )
+                                        })
+                                        .unwrap_or_default();
+                                    let channel = row.channel.clone().unwrap_or_default();
+                                    let selected = self.selected_idx == Some(idx);
+                                    if ui
+                                        .selectable_label(
+                                            selected,
+                                            format!("{title}  •  {channel}  •  {when}"),
+                                        )
+                                        .clicked()
+                                    {
+                                        select = Some(idx);
+                                    }
+                                }
+                            });
+                    }
+                });
+            });
+
+        self.collections.show_popup = open;
+        if lookup_all {
+            let all: Vec<usize> = (0..self.rows.len()).collect();
+            self.request_collection_lookups(&all);
+        }
+        if let Some(idx) = select {
+            self.selected_idx = Some(idx);
+            self.scroll_to_idx = Some(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tmdb_parts_sorted_and_unreleased_excluded_from_counts() {
+        let raw: TmdbCollection = serde_json::from_str(
+            r#"{"name":"Trilogy","parts":[
+                {"title":"Part Three","release_date":""},
+                {"title":"Part Two","release_date":"1989-05-24"},
+                {"title":"Part One","release_date":"1985-07-03"},
+                {"title":"","release_date":"1990-01-01"}
+            ]}"#,
+        )
+        .unwrap();
+        let collection = collection_from_tmdb(raw);
+        let titles: Vec<&str> = collection.parts.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["Part One", "Part Two", "Part Three"]);
+        assert_eq!(collection.parts[0].year, Some(1985));
+        assert_eq!(collection.released_parts("2026-01-01").count(), 2);
+        assert_eq!(collection.released_parts("1986-01-01").count(), 1);
+    }
+}
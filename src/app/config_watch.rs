@@ -0,0 +1,98 @@
+// src/app/config_watch.rs
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use eframe::egui as eg;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+
+// A stat() every couple of seconds is plenty for a file people edit by hand.
+const CHECK_EVERY: Duration = Duration::from_secs(2);
+
+/// Notices edits to config.json while Pex runs by polling its modification time.
+#[derive(Default)]
+pub(crate) struct ConfigWatch {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    last_check: Option<Instant>,
+    /// The config the app is currently running with; `None` until the first poll.
+    applied: Option<AppConfig>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+impl ConfigWatch {
+    /// The app now runs with `cfg` (e.g. after saving from Settings); don't apply it twice.
+    pub(crate) fn remember(&mut self, cfg: AppConfig) {
+        self.path = crate::config::config_path();
+        self.modified = modified(&self.path);
+        self.applied = Some(cfg);
+    }
+
+    /// The config the app runs with, once the first poll has loaded it.
+    pub(crate) const fn applied(&self) -> Option<&AppConfig> {
+        self.applied.as_ref()
+    }
+
+    /// `Some((old, new))` when the file changed since the last look and parses to a different
+    /// config.
+    fn poll(&mut self) -> Option<(AppConfig, AppConfig)> {
+        if self.last_check.is_some_and(|t| t.elapsed() < CHECK_EVERY) {
+            return None;
+        }
+        self.last_check = Some(Instant::now());
+        let Some(applied) = self.applied.as_ref() else {
+            self.remember(crate::config::load_config());
+            return None;
+        };
+        // PEX_CONFIG or a new file next to the exe can change which file is read.
+        let path = crate::config::config_path();
+        let stamp = modified(&path);
+        if path == self.path && stamp == self.modified {
+            return None;
+        }
+        self.path = path;
+        self.modified = stamp;
+        let fresh = crate::config::load_config();
+        if fresh == *applied {
+            return None;
+        }
+        let old = applied.clone();
+        // A broken edit is reported but not applied; the last good config stays in force.
+        if fresh.errors.is_empty() {
+            self.applied = Some(fresh.clone());
+        }
+        Some((old, fresh))
+    }
+}
+
+impl crate::app::PexApp {
+    /// Re-apply config.json when it is edited outside the app.
+    pub(crate) fn poll_config_changes(&mut self, ctx: &eg::Context) {
+        let Some((old, new)) = self.config_watch.poll() else {
+            return;
+        };
+        if !new.errors.is_empty() {
+            let msg = format!(
+                "Config not reloaded: {} Keeping the current settings.",
+                new.errors.join("; ")
+            );
+            warn!("{msg}");
+            self.set_status(msg.clone());
+            self.advanced_feedback = Some(msg);
+            return;
+        }
+        let notes = self.apply_config_change(ctx, &old, &new);
+        let msg = std::iter::once("config.json changed; settings reloaded.".to_string())
+            .chain(notes)
+            .collect::<Vec<_>>()
+            .join(" ");
+        info!("{msg}");
+        self.set_status(msg.clone());
+        self.advanced_feedback = Some(msg);
+    }
+}
@@ -0,0 +1,280 @@
+// src/app/conflicts.rs
+use std::collections::BTreeSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use eframe::egui as eg;
+
+use crate::app::utils::{day_bucket, format_day_label, hhmm_utc};
+use crate::app::Airing;
+use crate::config::AppConfig;
+
+pub(crate) const DEFAULT_TUNER_COUNT: u32 = 2;
+/// Assumed length of an airing the guide gives no end time for.
+const DEFAULT_RUNTIME_SECS: i64 = 2 * 3600;
+/// Clash badge on cards and the warning in the detail panel.
+pub(crate) const CLASH_COLOR: eg::Color32 = eg::Color32::from_rgb(235, 150, 40);
+
+/// One recording Plex is set to make: a row's own airing or a later airing of its title.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Recording {
+    row: usize,
+    begins: i64,
+    ends: i64,
+}
+
+impl Recording {
+    const fn overlaps(&self, begins: i64, ends: i64) -> bool {
+        self.begins < ends && begins < self.ends
+    }
+}
+
+/// Which recordings overlap more others than there are tuners to record them on.
+fn clashing(recordings: &[Recording], tuners: usize) -> Vec<bool> {
+    let mut clash = vec![false; recordings.len()];
+    // The busiest moments all start with some recording starting.
+    for start in recordings {
+        let active: Vec<usize> = (0..recordings.len())
+            .filter(|&i| recordings[i].begins <= start.begins && start.begins < recordings[i].ends)
+            .collect();
+        if active.len() > tuners {
+            for i in active {
+                clash[i] = true;
+            }
+        }
+    }
+    clash
+}
+
+/// Whether one more recording over `begins..ends` still leaves a free tuner throughout.
+fn fits(recordings: &[Recording], begins: i64, ends: i64, tuners: usize) -> bool {
+    let during: Vec<&Recording> = recordings
+        .iter()
+        .filter(|r| r.overlaps(begins, ends))
+        .collect();
+    std::iter::once(begins)
+        .chain(during.iter().map(|r| r.begins).filter(|&t| t > begins))
+        .all(|t| {
+            during
+                .iter()
+                .filter(|r| r.begins <= t && t < r.ends)
+                .count()
+                < tuners
+        })
+}
+
+fn unix(ts: SystemTime) -> Option<i64> {
+    ts.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|dur| dur.as_secs() as i64)
+}
+
+fn slot(begins: i64, ends: Option<i64>) -> (i64, i64) {
+    let ends = ends.filter(|&e| e > begins);
+    (begins, ends.unwrap_or(begins + DEFAULT_RUNTIME_SECS))
+}
+
+/// Scheduled recordings checked against the number of tuners (`tuner_count`).
+pub(crate) struct RecordingConflicts {
+    tuners: usize,
+    recordings: Vec<Recording>,
+    clash: Vec<bool>,
+}
+
+impl Default for RecordingConflicts {
+    fn default() -> Self {
+        Self {
+            tuners: DEFAULT_TUNER_COUNT as usize,
+            recordings: Vec::new(),
+            clash: Vec::new(),
+        }
+    }
+}
+
+impl RecordingConflicts {
+    pub fn configure(&mut self, cfg: &AppConfig) {
+        self.tuners = cfg.tuner_count.unwrap_or(DEFAULT_TUNER_COUNT).max(1) as usize;
+    }
+}
+
+impl crate::app::PexApp {
+    /// Every recording: rows whose airing is scheduled, plus later airings of their titles
+    /// that Plex has a slot for.
+    fn scheduled_recordings(&self) -> Vec<Recording> {
+        let Some(index) = self.scheduled_index.as_ref() else {
+            return Vec::new();
+        };
+        let mut recordings = Vec::new();
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            if row.scheduled {
+                if let Some(begins) = row.airing.and_then(unix) {
+                    let (begins, ends) = slot(begins, row.ends.and_then(unix));
+                    recordings.push(Recording {
+                        row: row_idx,
+                        begins,
+                        ends,
+                    });
+                }
+            }
+            for airing in self.repeats_of(row) {
+                let when = UNIX_EPOCH + Duration::from_secs(airing.begins_at.max(0) as u64);
+                if index.is_scheduled(None, &row.title, row.year, Some(when)) {
+                    let (begins, ends) = slot(airing.begins_at, airing.ends_at);
+                    recordings.push(Recording {
+                        row: row_idx,
+                        begins,
+                        ends,
+                    });
+                }
+            }
+        }
+        recordings
+    }
+
+    /// Later airings of the row's title and year (the row itself holds the soonest one).
+    pub(crate) fn repeats_of(&self, row: &crate::app::PosterRow) -> &[Airing] {
+        self.other_airings
+            .get(&(row.title.to_ascii_lowercase(), row.year))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Every airing of the row's title, soonest (the row's own) first.
+    pub(crate) fn airing_slots(&self, row: &crate::app::PosterRow) -> Vec<Airing> {
+        let first = row.airing.and_then(unix).map(|begins_at| Airing {
+            begins_at,
+            ends_at: row.ends.and_then(unix),
+            channel: row.channel.clone(),
+            broadcast_hd: row.broadcast_hd,
+        });
+        first
+            .into_iter()
+            .chain(self.repeats_of(row).iter().cloned())
+            .collect()
+    }
+
+    /// Re-check scheduled recordings against the tuner count and flag rows that clash.
+    pub(crate) fn apply_conflict_flags(&mut self) {
+        let recordings = self.scheduled_recordings();
+        let clash = clashing(&recordings, self.recording_conflicts.tuners);
+        for row in &mut self.rows {
+            row.conflict = false;
+        }
+        for (recording, _) in recordings.iter().zip(&clash).filter(|(_, &c)| c) {
+            if let Some(row) = self.rows.get_mut(recording.row) {
+                row.conflict = true;
+            }
+        }
+        self.recording_conflicts.recordings = recordings;
+        self.recording_conflicts.clash = clash;
+    }
+
+    /// Airings of the row's title that are not being recorded and would have a free tuner.
+    fn clash_free_airings(&self, idx: usize) -> Vec<Airing> {
+        let Some(row) = self.rows.get(idx) else {
+            return Vec::new();
+        };
+        let conflicts = &self.recording_conflicts;
+        let others: Vec<Recording> = conflicts
+            .recordings
+            .iter()
+            .filter(|r| r.row != idx)
+            .copied()
+            .collect();
+        let own: Vec<(i64, i64)> = conflicts
+            .recordings
+            .iter()
+            .filter(|r| r.row == idx)
+            .map(|r| (r.begins, r.ends))
+            .collect();
+        self.airing_slots(row)
+            .into_iter()
+            .filter(|airing| {
+                let (begins, ends) = slot(airing.begins_at, airing.ends_at);
+                !own.contains(&(begins, ends)) && fits(&others, begins, ends, conflicts.tuners)
+            })
+            .collect()
+    }
+
+    /// Detail-panel warning for a clashing recording, with airings of the title that avoid it.
+    pub(crate) fn ui_render_recording_conflict(&self, ui: &mut eg::Ui, idx: usize) {
+        if !self.rows.get(idx).is_some_and(|row| row.conflict) {
+            return;
+        }
+        let conflicts = &self.recording_conflicts;
+        let clashes_with: BTreeSet<String> = conflicts
+            .recordings
+            .iter()
+            .zip(&conflicts.clash)
+            .filter(|(r, &c)| c && r.row != idx)
+            .filter(|(r, _)| {
+                conflicts
+                    .recordings
+                    .iter()
+                    .zip(&conflicts.clash)
+                    .any(|(own, &c)| c && own.row == idx && own.overlaps(r.begins, r.ends))
+            })
+            .filter_map(|(r, _)| self.rows.get(r.row).map(|row| row.title.clone()))
+            .collect();
+        ui.label(
+            eg::RichText::new(format!(
+                "Recording clash: more than {} recordings at once",
+                conflicts.tuners
+            ))
+            .color(CLASH_COLOR)
+            .strong(),
+        )
+        .on_hover_text("tuner_count in config.json sets how many recordings can run at once");
+        if !clashes_with.is_empty() {
+            let titles: Vec<String> = clashes_with.into_iter().collect();
+            ui.label(eg::RichText::new(format!("Overlaps {}", titles.join(", "))).weak());
+        }
+        let alternatives = self.clash_free_airings(idx);
+        if alternatives.is_empty() {
+            ui.label(eg::RichText::new("No other airing of this title avoids the clash.").weak());
+            return;
+        }
+        ui.label("Airings with a free tuner:");
+        for airing in alternatives {
+            let ts = UNIX_EPOCH + Duration::from_secs(airing.begins_at.max(0) as u64);
+            let channel = airing.channel.as_deref().unwrap_or("—");
+            let hd = if airing.broadcast_hd { "  HD" } else { "" };
+            ui.label(format!(
+                "• {} {}  {channel}{hd}",
+                format_day_label(day_bucket(ts)),
+                hhmm_utc(ts)
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(row: usize, begins: i64, ends: i64) -> Recording {
+        Recording { row, begins, ends }
+    }
+
+    #[test]
+    fn clashes_need_more_overlapping_recordings_than_tuners() {
+        let recordings = [
+            rec(0, 0, 100),
+            rec(1, 50, 150),
+            rec(2, 120, 200),
+            rec(3, 300, 400),
+        ];
+        assert_eq!(clashing(&recordings, 2), vec![false; 4]);
+        assert_eq!(clashing(&recordings, 1), vec![true, true, true, false]);
+
+        let three = [rec(0, 0, 100), rec(1, 10, 90), rec(2, 20, 80)];
+        assert_eq!(clashing(&three, 2), vec![true; 3]);
+        // Back-to-back recordings share a tuner.
+        assert_eq!(
+            clashing(&[rec(0, 0, 100), rec(1, 100, 200)], 1),
+            vec![false; 2]
+        );
+
+        assert!(fits(&three[..2], 100, 200, 2));
+        assert!(!fits(&three[..2], 50, 150, 2));
+        assert!(fits(&[rec(0, 0, 100), rec(1, 150, 250)], 50, 200, 2));
+    }
+}
@@ -0,0 +1,50 @@
+// src/app/day_strip.rs
+use eframe::egui as eg;
+
+use crate::app::utils::{format_day_chip, format_day_label};
+
+/// Row of day chips above the day-grouped grid: a click scrolls to that day's heading, and the
+/// chip of the day at the top of the viewport stays highlighted.
+#[derive(Default)]
+pub(crate) struct DayStrip {
+    /// Day heading the grid should bring to the top next frame.
+    pub scroll_to: Option<i64>,
+    /// Topmost day section in the viewport last frame.
+    pub current: Option<i64>,
+    /// Chip last scrolled into view, so the strip follows the grid without fighting the user.
+    revealed: Option<i64>,
+}
+
+impl crate::app::PexApp {
+    /// `days` are the grid's day buckets in order, each with its number of cards.
+    pub(crate) fn ui_render_day_strip(&mut self, ui: &mut eg::Ui, days: &[(i64, usize)]) {
+        if days.len() < 2 {
+            return;
+        }
+        eg::ScrollArea::horizontal()
+            .id_source("day_strip")
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for &(bucket, count) in days {
+                        let current = self.day_strip.current == Some(bucket);
+                        let resp = ui
+                            .selectable_label(current, format_day_chip(bucket))
+                            .on_hover_text(format!(
+                                "{} · {count} airing{}",
+                                format_day_label(bucket),
+                                if count == 1 { "" } else { "s" }
+                            ));
+                        if current && self.day_strip.revealed != Some(bucket) {
+                            self.day_strip.revealed = Some(bucket);
+                            resp.scroll_to_me(None);
+                        }
+                        if resp.clicked() {
+                            self.day_strip.scroll_to = Some(bucket);
+                        }
+                    }
+                });
+            });
+        ui.add_space(2.0);
+    }
+}
@@ -0,0 +1,196 @@
+// src/app/demo.rs
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::Sender;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::app::cache::{poster_cache_dir, url_to_cache_key};
+use crate::app::types::{OwnedMsg, PrepItem, PrepMsg};
+
+const DEMO_JSON: &str = include_str!("../assets/demo_airings.json");
+const POSTER_W: u32 = 320;
+const POSTER_H: u32 = 480;
+
+#[derive(Deserialize)]
+struct DemoFile {
+    airings: Vec<DemoAiring>,
+}
+
+#[derive(Deserialize)]
+struct DemoAiring {
+    title: String,
+    year: i32,
+    genres: String,
+    channel: String,
+    starts_in_min: i64,
+    runtime_min: i64,
+    imdb: String,
+    summary: String,
+    audience_rating: Option<f32>,
+    critic_rating: Option<f32>,
+    #[serde(default)]
+    owned: bool,
+    #[serde(default)]
+    owned_hd: bool,
+    recorded_days_ago: Option<u64>,
+    #[serde(default)]
+    scheduled: bool,
+    #[serde(default)]
+    premiere: bool,
+}
+
+impl DemoAiring {
+    fn guid(&self) -> String {
+        format!("com.plexapp.agents.imdb://{}", self.imdb)
+    }
+
+    fn thumb_url(&self) -> String {
+        format!("demo://poster/{}", self.imdb)
+    }
+}
+
+fn load() -> Result<Vec<DemoAiring>, String> {
+    serde_json::from_str::<DemoFile>(DEMO_JSON)
+        .map(|file| file.airings)
+        .map_err(|err| format!("demo dataset: {err}"))
+}
+
+/// Demo airings as prep items, scheduled relative to the hour containing `now`.
+fn demo_items(now: SystemTime) -> Result<Vec<PrepItem>, String> {
+    let now_unix = now
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    let hour = now_unix - now_unix.rem_euclid(3600);
+    Ok(load()?
+        .into_iter()
+        .map(|airing| {
+            let begins = hour + airing.starts_in_min * 60;
+            let thumb_url = airing.thumb_url();
+            PrepItem {
+                key: url_to_cache_key(&thumb_url),
+                thumb_url,
+                begins_at: Some(begins),
+                ends_at: Some(begins + airing.runtime_min * 60),
+                year: Some(airing.year),
+                tags_genre: Some(airing.genres.clone()),
+                channel_call_sign: None,
+                channel_title: Some(airing.channel.clone()),
+                channel_thumb: None,
+                guid: Some(airing.guid()),
+                summary: Some(airing.summary.clone()),
+                audience_rating: airing.audience_rating,
+                critic_rating: airing.critic_rating,
+                premiere: airing.premiere,
+                title: airing.title,
+            }
+        })
+        .collect())
+}
+
+/// A flat two-tone poster for `seed` (no network, no bundled images needed).
+fn placeholder_poster(seed: &str) -> image::RgbImage {
+    let hash = md5::compute(seed.as_bytes());
+    let top = [hash[0] / 2 + 40, hash[1] / 2 + 40, hash[2] / 2 + 40];
+    let bottom = top.map(|c| c / 3);
+    image::RgbImage::from_fn(POSTER_W, POSTER_H, |x, y| {
+        // Light frame so the placeholder reads as a poster, not a missing image.
+        if x < 6 || y < 6 || x >= POSTER_W - 6 || y >= POSTER_H - 6 {
+            return image::Rgb([220, 220, 220]);
+        }
+        let t = y as f32 / POSTER_H as f32;
+        image::Rgb(std::array::from_fn(|i| {
+            (top[i] as f32 * (1.0 - t) + bottom[i] as f32 * t) as u8
+        }))
+    })
+}
+
+/// Write placeholder art into the poster cache so prefetch finds every demo poster on disk.
+fn write_placeholder_posters(items: &[PrepItem]) -> Result<(), String> {
+    let dir = poster_cache_dir();
+    for item in items {
+        let path = dir.join(format!("{}__s.jpg", item.key));
+        placeholder_poster(&item.title)
+            .save_with_format(&path, image::ImageFormat::Jpeg)
+            .map_err(|err| format!("write {}: {err}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Demo replacement for `prep::spawn_poster_prep`.
+pub(crate) fn spawn_demo_prep(tx: Sender<PrepMsg>, now: SystemTime) {
+    std::thread::spawn(move || {
+        let _ = tx.send(PrepMsg::Info(
+            "Stage 2/4 - Loading the built-in demo guide (no Plex database needed).".into(),
+        ));
+        let msg = demo_items(now).and_then(|items| {
+            write_placeholder_posters(&items)?;
+            Ok(items)
+        });
+        let _ = tx.send(msg.map_or_else(PrepMsg::Error, PrepMsg::Done));
+    });
+}
+
+/// Demo replacement for the owned scan: owned keys plus their "recorded" timestamps.
+pub(crate) fn spawn_demo_owned(tx: Sender<OwnedMsg>, now: SystemTime) {
+    let now_unix = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let msg = load().map_or_else(OwnedMsg::Error, |airings| {
+        let mut keys = HashSet::new();
+        let mut modified = HashMap::new();
+        for airing in airings.iter().filter(|a| a.owned) {
+            let key = crate::app::PexApp::make_owned_key(&airing.title, Some(airing.year));
+            let recorded = airing
+                .recorded_days_ago
+                .map(|days| now_unix.saturating_sub(days * 24 * 60 * 60));
+            modified.insert(key.clone(), recorded);
+            keys.insert(key);
+        }
+        OwnedMsg::Done { keys, modified }
+    });
+    let _ = tx.send(msg);
+}
+
+/// Owned keys of demo titles that count as HD copies.
+pub(crate) fn demo_owned_hd_keys() -> HashSet<String> {
+    load()
+        .unwrap_or_default()
+        .iter()
+        .filter(|a| a.owned && a.owned_hd)
+        .map(|a| crate::app::PexApp::make_owned_key(&a.title, Some(a.year)))
+        .collect()
+}
+
+/// GUIDs of demo airings shown as scheduled recordings.
+pub(crate) fn demo_scheduled_guids() -> HashSet<String> {
+    load()
+        .unwrap_or_default()
+        .iter()
+        .filter(|a| a.scheduled)
+        .map(DemoAiring::guid)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_dataset_parses_and_is_relative_to_now() {
+        // 1_800_000_000 falls exactly on the hour.
+        let hour = 1_800_000_000;
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(hour as u64 + 1_234);
+        let items = demo_items(now).unwrap();
+        assert!(items.len() >= 20);
+        // Something is on right now; nothing started more than an hour before it.
+        assert!(items.iter().any(|item| {
+            item.begins_at.unwrap() <= hour + 1_234 && item.ends_at.unwrap() > hour + 1_234
+        }));
+        assert!(items
+            .iter()
+            .all(|item| item.begins_at.unwrap() >= hour - 3600));
+        let keys: HashSet<&str> = items.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(keys.len(), items.len());
+        assert!(!demo_scheduled_guids().is_empty());
+        assert!(items.iter().any(|item| item.premiere));
+    }
+}
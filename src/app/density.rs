@@ -0,0 +1,197 @@
+// src/app/density.rs
+use std::str::FromStr;
+
+use eframe::egui as eg;
+
+/// Card size range of the Poster slider.
+pub(crate) const CARD_WIDTH_RANGE: std::ops::RangeInclusive<f32> = 100.0..=260.0;
+
+/// Grid density: card width, the text block under the poster and badge size move together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Density {
+    Compact,
+    #[default]
+    Comfortable,
+    /// Posters only, no text block.
+    PosterWall,
+}
+
+impl Density {
+    pub const ALL: [Self; 3] = [Self::Compact, Self::Comfortable, Self::PosterWall];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Compact => "compact",
+            Self::Comfortable => "comfortable",
+            Self::PosterWall => "poster_wall",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Compact => "Compact",
+            Self::Comfortable => "Comfortable",
+            Self::PosterWall => "Poster wall",
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::Compact => 0,
+            Self::Comfortable => 1,
+            Self::PosterWall => 2,
+        }
+    }
+
+    const fn default_width(self) -> f32 {
+        match self {
+            Self::Compact => 112.0,
+            Self::Comfortable => 143.0,
+            Self::PosterWall => 170.0,
+        }
+    }
+
+    /// Height of the title / channel / time block under the poster.
+    pub const fn text_h(self) -> f32 {
+        match self {
+            Self::Compact => 46.0,
+            Self::Comfortable => 56.0,
+            Self::PosterWall => 0.0,
+        }
+    }
+
+    pub const fn text_size(self) -> f32 {
+        match self {
+            Self::Compact => 12.0,
+            Self::Comfortable | Self::PosterWall => 14.0,
+        }
+    }
+
+    /// Multiplier for badge, chip and dot sizes on the poster.
+    pub const fn badge_scale(self) -> f32 {
+        match self {
+            Self::Compact => 0.8,
+            Self::Comfortable => 1.0,
+            Self::PosterWall => 1.2,
+        }
+    }
+}
+
+impl FromStr for Density {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|density| density.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// The active preset plus the card width remembered for each one.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DensityPrefs {
+    pub preset: Density,
+    widths: [f32; 3],
+}
+
+impl Default for DensityPrefs {
+    fn default() -> Self {
+        Self {
+            preset: Density::default(),
+            widths: Density::ALL.map(Density::default_width),
+        }
+    }
+}
+
+impl DensityPrefs {
+    pub fn width(&self, preset: Density) -> f32 {
+        self.widths[preset.index()]
+    }
+
+    pub fn set_width(&mut self, preset: Density, width: f32) {
+        self.widths[preset.index()] =
+            width.clamp(*CARD_WIDTH_RANGE.start(), *CARD_WIDTH_RANGE.end());
+    }
+
+    /// `compact:112,comfortable:143,poster_wall:170` for the prefs file.
+    pub fn widths_pref(&self) -> String {
+        Density::ALL
+            .into_iter()
+            .map(|preset| format!("{}:{:.0}", preset.as_str(), self.width(preset)))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Read `widths_pref` output; unknown presets and bad numbers are skipped.
+    pub fn load_widths(&mut self, value: &str) {
+        for pair in value.split(',') {
+            let Some((name, width)) = pair.split_once(':') else {
+                continue;
+            };
+            if let (Ok(preset), Ok(width)) = (name.trim().parse(), width.trim().parse()) {
+                self.set_width(preset, width);
+            }
+        }
+    }
+}
+
+impl crate::app::PexApp {
+    /// Switch presets, keeping the width the old one was left at.
+    pub(crate) fn set_density(&mut self, preset: Density) {
+        if preset == self.density.preset {
+            return;
+        }
+        let old = self.density.preset;
+        self.density.set_width(old, self.poster_width_ui);
+        self.density.preset = preset;
+        self.poster_width_ui = self.density.width(preset);
+        self.mark_dirty();
+    }
+
+    /// Top bar: density preset combo and the card width slider for that preset.
+    pub(crate) fn ui_render_density_controls(&mut self, ui: &mut eg::Ui) {
+        let mut chosen = self.density.preset;
+        eg::ComboBox::from_id_source("density_preset")
+            .selected_text(chosen.label())
+            .width(110.0)
+            .show_ui(ui, |ui| {
+                for preset in Density::ALL {
+                    ui.selectable_value(&mut chosen, preset, preset.label());
+                }
+            })
+            .response
+            .on_hover_text("Card size, text and badges together; each preset keeps its own width");
+        self.set_density(chosen);
+
+        if ui
+            .add(eg::Slider::new(&mut self.poster_width_ui, CARD_WIDTH_RANGE).suffix(" px"))
+            .changed()
+        {
+            let preset = self.density.preset;
+            self.density.set_width(preset, self.poster_width_ui);
+            self.mark_dirty();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_widths_round_trip() {
+        let mut prefs = DensityPrefs::default();
+        prefs.set_width(Density::PosterWall, 200.0);
+        prefs.set_width(Density::Compact, 10.0);
+        let mut loaded = DensityPrefs::default();
+        loaded.load_widths(&prefs.widths_pref());
+        assert_eq!(loaded, prefs);
+        assert_eq!(loaded.width(Density::Compact), *CARD_WIDTH_RANGE.start());
+
+        loaded.load_widths("bogus:150,comfortable:abc,comfortable:160");
+        assert_eq!(loaded.width(Density::Comfortable), 160.0);
+        assert_eq!("poster_wall".parse(), Ok(Density::PosterWall));
+        assert_eq!(Density::PosterWall.text_h(), 0.0);
+    }
+}
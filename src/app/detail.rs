@@ -1,7 +1,25 @@
 // src/app/detail.rs
-use crate::app::types::RatingState;
+use crate::app::keymap::Action;
+use crate::app::types::{OwnedTier, RatingState};
 use eframe::egui as eg;
 
+/// Description scroll step for arrow keys / Page Up-Down while it has keyboard focus.
+const SUMMARY_LINE_STEP: f32 = 40.0;
+const SUMMARY_PAGE_STEP: f32 = 200.0;
+
+/// One side of the compare panel, snapshotted from a row.
+struct CompareFacts {
+    title: String,
+    channel: String,
+    when: String,
+    broadcast_hd: bool,
+    scheduled: bool,
+    owned: Option<(OwnedTier, Option<String>)>,
+    critic: Option<f32>,
+    audience: Option<f32>,
+    fetched: Option<String>,
+}
+
 impl crate::app::PexApp {
     pub(crate) fn ui_render_detail_panel(&mut self, ctx: &eg::Context) {
         // Use poster/card sizing to keep the panel width within a sane range
@@ -12,6 +30,19 @@ impl crate::app::PexApp {
         let default_width = self.detail_panel_width.clamp(min_w, max_w);
 
         let mut trigger_rating_request: Option<usize> = None;
+        let palette = self.palette();
+
+        // Checked before any widget is laid out: once one is, egui hands Tab to it.
+        let focus_into_panel = self.selected_idx.is_some()
+            && self.keymap_capture.is_none()
+            && ctx.memory(|mem| mem.focused().is_none())
+            && (std::mem::take(&mut self.detail_focus_pending)
+                || ctx.input(|i| {
+                    self.keymap.pressed(Action::FocusDetail, i)
+                        || self.keymap.pressed(Action::OpenDetail, i)
+                }));
+        // Keyboard-focusable controls, in Tab order.
+        let mut focus_ring: Vec<eg::Response> = Vec::new();
 
         let panel = eg::SidePanel::right("detail_panel")
             .resizable(true)
@@ -40,7 +71,18 @@ impl crate::app::PexApp {
                 };
 
                 let broadcast_hd = Self::row_broadcast_hd(row);
-                let owned_is_hd = self.row_owned_is_hd(row);
+                let owned_tier = self.row_owned_tier(row);
+                let owned_low_bitrate = self.row_owned_low_bitrate(row);
+                let premiere = row.premiere;
+                let new_in_guide = row.new_in_guide;
+                let letterboxd = row.letterboxd;
+                let owned_media = self
+                    .row_owned_media(row)
+                    .and_then(crate::app::OwnedMedia::describe);
+                let owned_file = self
+                    .row_owned_media(row)
+                    .map(crate::app::OwnedMedia::file_details)
+                    .unwrap_or_default();
 
                 // Snapshot values so we can release the immutable borrow on self.rows
                 let poster_tex = row.tex.clone();
@@ -52,7 +94,6 @@ impl crate::app::PexApp {
                 let airing = row.airing;
                 let critic_rating = row.critic_rating;
                 let audience_rating = row.audience_rating;
-                let owned = row.owned;
                 let owned_modified = row.owned_modified;
                 let genres = row.genres.clone();
                 let summary = row.summary.clone();
@@ -67,16 +108,19 @@ impl crate::app::PexApp {
                 if let Some(tex) = poster_tex {
                     ui.image((tex.id(), poster_size));
                 } else {
-                    // Placeholder if texture not ready
+                    // Placeholder (poster's dominant colour if known) if texture not ready
                     let (rect, _resp) = ui.allocate_exact_size(poster_size, eg::Sense::hover());
-                    ui.painter()
-                        .rect_filled(rect, 8.0, eg::Color32::from_gray(40));
+                    let fill = crate::app::cache::poster_swatch(&poster_key)
+                        .map_or(palette.placeholder, |[r, g, b]| {
+                            eg::Color32::from_rgb(r, g, b)
+                        });
+                    ui.painter().rect_filled(rect, 8.0, fill);
                     ui.painter().text(
                         rect.center(),
                         eg::Align2::CENTER_CENTER,
                         "Poster loading…",
                         eg::FontId::proportional(14.0),
-                        eg::Color32::WHITE,
+                        palette.on_badge,
                     );
                 }
 
@@ -104,16 +148,33 @@ impl crate::app::PexApp {
                         .id_source(("detail_title_scroll", poster_key.clone()))
                         .max_width(scroll_width)
                         .show(ui, |ui| {
-                            ui.heading(&title_display);
+                            let hits = self.title_highlights(&title_text);
+                            if hits.is_empty() {
+                                ui.heading(&title_display);
+                            } else {
+                                let font = eg::TextStyle::Heading.resolve(ui.style());
+                                let color = ui.visuals().text_color();
+                                let job = crate::app::theme::highlight_job(
+                                    &title_display,
+                                    &hits,
+                                    font,
+                                    color,
+                                    &palette,
+                                );
+                                ui.label(job);
+                            }
                         });
 
-                    if ui
+                    let copy = ui
                         .small_button("📋")
-                        .on_hover_text("Copy title to clipboard")
-                        .clicked()
-                    {
+                        .on_hover_text("Copy title to clipboard");
+                    copy.widget_info(|| {
+                        eg::WidgetInfo::labeled(eg::WidgetType::Button, true, "Copy title")
+                    });
+                    if copy.clicked() {
                         ctx.output_mut(|o| o.copied_text = title_text.clone());
                     }
+                    focus_ring.push(copy);
                 });
 
                 // Channel + time line (humanized)
@@ -143,10 +204,13 @@ impl crate::app::PexApp {
                 if scheduled {
                     ui.label(
                         eg::RichText::new("Scheduled to record")
-                            .color(eg::Color32::from_rgb(220, 80, 80))
+                            .color(palette.rec)
                             .strong(),
                     );
                 }
+                self.ui_render_recording_conflict(ui, sel);
+                self.ui_render_other_airings(ui, sel);
+                self.ui_render_title_tags(ui, sel);
 
                 if critic_rating.is_some() || audience_rating.is_some() {
                     ui.add_space(6.0);
@@ -154,13 +218,13 @@ impl crate::app::PexApp {
                         if let Some(r) = critic_rating {
                             ui.label(
                                 eg::RichText::new(format!("Critics: {r:.1}/10"))
-                                    .color(eg::Color32::from_rgb(255, 208, 121)),
+                                    .color(palette.warning),
                             );
                         }
                         if let Some(r) = audience_rating {
                             ui.label(
                                 eg::RichText::new(format!("Audience: {r:.1}/10"))
-                                    .color(eg::Color32::from_rgb(160, 220, 160)),
+                                    .color(palette.positive),
                             );
                         }
                     });
@@ -170,35 +234,38 @@ impl crate::app::PexApp {
                 let rating_state = self.rating_state_for_key(&poster_key);
                 ui.horizontal(|ui| {
                     let fetch_enabled = !matches!(rating_state, RatingState::Pending);
-                    if ui
+                    let fetch = ui
                         .add_enabled(fetch_enabled, eg::Button::new("⭐ Rating"))
-                        .on_hover_text("Fetch TMDb rating on demand")
-                        .clicked()
-                    {
+                        .on_hover_text("Fetch a rating on demand (rating_providers in config.json)");
+                    if fetch.clicked() {
                         trigger_rating_request = Some(sel);
                     }
+                    focus_ring.push(fetch);
                     ui.add_space(6.0);
                     match rating_state {
                         RatingState::Pending => {
                             ui.add(eg::Spinner::new().size(14.0));
-                            ui.label("Fetching TMDb rating…");
+                            ui.label("Fetching rating…");
                         }
-                        RatingState::Success(ref txt) => {
-                            ui.label(eg::RichText::new(txt).strong());
+                        RatingState::Success {
+                            source, ref text, ..
+                        } => {
+                            ui.label(eg::RichText::new(text).strong());
+                            ui.label(eg::RichText::new(format!("via {source}")).weak());
                         }
                         RatingState::NotFound => {
-                            ui.label(eg::RichText::new("TMDb rating not found.").weak());
+                            ui.label(eg::RichText::new("No rating found.").weak());
                         }
                         RatingState::Error(ref err) => {
                             ui.label(
                                 eg::RichText::new(format!("Rating error: {err}"))
-                                    .color(eg::Color32::LIGHT_RED),
+                                    .color(palette.error),
                             );
                         }
                         RatingState::MissingApiKey => {
                             ui.label(
                                 eg::RichText::new(
-                                    "Set tmdb_api_key in config.json to enable ratings.",
+                                    "Set tmdb_api_key or omdb_api_key in config.json to enable ratings.",
                                 )
                                 .weak(),
                             );
@@ -222,22 +289,55 @@ impl crate::app::PexApp {
                                     "Airing SD"
                                 })
                                 .color(if broadcast_hd {
-                                    eg::Color32::from_rgb(120, 180, 255)
+                                    palette.info
                                 } else {
-                                    eg::Color32::GRAY
+                                    ui.visuals().weak_text_color()
                                 }),
                             )
                             .wrap(),
                         );
 
-                        // Owned chip (Owned HD / Owned SD)
-                        if owned {
-                            let (txt, col) = if owned_is_hd {
-                                ("Owned HD", eg::Color32::from_rgb(130, 200, 130))
-                            } else {
-                                ("Owned SD", eg::Color32::from_gray(200))
+                        if premiere {
+                            ui.label(
+                                eg::RichText::new("Premiere")
+                                    .color(palette.new_badge),
+                            )
+                            .on_hover_text(
+                                "First showing: marked as a premiere by the guide, or not in \
+                                 any earlier guide Pex has seen",
+                            );
+                        }
+                        if new_in_guide {
+                            ui.label(
+                                eg::RichText::new("New in guide")
+                                    .color(palette.new_in_guide),
+                            )
+                            .on_hover_text("Added to the guide since Pex last ran");
+                        }
+                        if letterboxd {
+                            ui.label(
+                                eg::RichText::new("Letterboxd watchlist")
+                                    .color(crate::app::letterboxd::LETTERBOXD_DOTS[0]),
+                            )
+                            .on_hover_text("On the watchlist imported in Advanced");
+                        }
+                        self.ui_render_imdb_chips(ui, sel);
+
+                        // Owned chip (Owned 4K / Owned HD / Owned SD)
+                        if let Some(tier) = owned_tier {
+                            let (txt, col) = match tier {
+                                _ if owned_low_bitrate => (
+                                    "Owned HD (low bitrate)",
+                                    palette.warning,
+                                ),
+                                OwnedTier::Uhd => ("Owned 4K", palette.info),
+                                OwnedTier::Hd => ("Owned HD", palette.positive),
+                                OwnedTier::Sd => ("Owned SD", ui.visuals().text_color()),
                             };
                             ui.add(eg::Label::new(eg::RichText::new(txt).color(col)));
+                            if let Some(media) = &owned_media {
+                                ui.label(eg::RichText::new(media).weak());
+                            }
 
                             if let Some(ts) = owned_modified {
                                 if let Some(date_str) =
@@ -257,21 +357,105 @@ impl crate::app::PexApp {
                     });
                 }
 
+                // Owned file details (path, container, streams) as the library recorded them
+                if !owned_file.is_empty() {
+                    eg::CollapsingHeader::new("File")
+                        .id_source("detail_owned_file")
+                        .show(ui, |ui| {
+                            eg::Grid::new("detail_owned_file_grid")
+                                .num_columns(2)
+                                .spacing([10.0, 2.0])
+                                .show(ui, |ui| {
+                                    for (label, value) in &owned_file {
+                                        ui.label(eg::RichText::new(*label).weak());
+                                        ui.add(eg::Label::new(value.as_str()).wrap());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+
+                // Collection rollup (TMDb)
+                self.ui_render_collection_summary(ui, sel);
+                self.ui_render_external_links(ui, sel);
+                self.ui_render_radarr_actions(ui, sel);
+                self.ui_render_trakt_actions(ui, sel);
+                self.ui_render_owned_link_action(ui, sel);
+                self.ui_render_hide_title_action(ui, sel);
+
+                ui.add_space(6.0);
+                let pinned = self.compare_idx == Some(sel);
+                let compare = ui
+                    .selectable_label(
+                        pinned,
+                        if pinned {
+                            "⇆ Pinned for compare"
+                        } else {
+                            "⇆ Compare…"
+                        },
+                    )
+                    .on_hover_text(
+                        "Pin this airing, then select another (or Ctrl+click a card) to compare them side by side",
+                    );
+                if compare.clicked() {
+                    self.compare_idx = (!pinned).then_some(sel);
+                }
+                focus_ring.push(compare);
+
                 ui.add_space(8.0);
                 ui.separator();
                 ui.add_space(8.0);
 
-                // Description
+                // Description (focusable so the keyboard can scroll long summaries)
                 ui.label(eg::RichText::new("Description").strong());
-                if let Some(summary) = summary.as_deref() {
-                    ui.add(eg::Label::new(eg::RichText::new(summary)).wrap());
+                let summary_id = eg::Id::new(("detail_summary", &poster_key));
+                let scroll_by = if ctx.memory(|mem| mem.has_focus(summary_id)) {
+                    ctx.input_mut(|i| {
+                        let mut dy = 0.0;
+                        for (key, step) in [
+                            (eg::Key::ArrowUp, SUMMARY_LINE_STEP),
+                            (eg::Key::ArrowDown, -SUMMARY_LINE_STEP),
+                            (eg::Key::PageUp, SUMMARY_PAGE_STEP),
+                            (eg::Key::PageDown, -SUMMARY_PAGE_STEP),
+                        ] {
+                            dy += step * i.count_and_consume_key(eg::Modifiers::NONE, key) as f32;
+                        }
+                        dy
+                    })
                 } else {
-                    ui.label(
-                        eg::RichText::new("No description available.")
-                            .italics()
-                            .weak(),
+                    0.0
+                };
+                let summary_area = eg::ScrollArea::vertical()
+                    .id_source(summary_id)
+                    .max_height(240.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        if let Some(summary) = summary.as_deref() {
+                            ui.add(eg::Label::new(eg::RichText::new(summary)).wrap());
+                        } else {
+                            ui.label(
+                                eg::RichText::new("No description available.")
+                                    .italics()
+                                    .weak(),
+                            );
+                        }
+                        if scroll_by != 0.0 {
+                            ui.scroll_with_delta(eg::vec2(0.0, scroll_by));
+                        }
+                    });
+                let summary_focus = ui.interact(
+                    summary_area.inner_rect,
+                    summary_id,
+                    eg::Sense::focusable_noninteractive(),
+                );
+                if summary_focus.has_focus() {
+                    ui.painter().rect_stroke(
+                        summary_area.inner_rect.expand(2.0),
+                        4.0,
+                        ui.visuals().selection.stroke,
                     );
                 }
+                focus_ring.push(summary_focus);
 
                 ui.add_space(12.0);
                 ui.separator();
@@ -294,8 +478,349 @@ impl crate::app::PexApp {
             self.mark_dirty(); // let your prefs autosave pick this up
         }
 
+        self.handle_detail_focus(ctx, &focus_ring, focus_into_panel);
+
         if let Some(idx) = trigger_rating_request {
             self.request_rating_for(idx);
         }
     }
+
+    /// Keyboard focus inside the detail panel: Tab / Shift+Tab cycle its controls (instead of
+    /// wandering into the top bar or grid) and Esc hands focus back to the selected card.
+    fn handle_detail_focus(&mut self, ctx: &eg::Context, ring: &[eg::Response], enter: bool) {
+        let Some(first) = ring.first() else {
+            return;
+        };
+        if enter {
+            // Keep the key that moved focus here from also activating the focused control.
+            for action in [Action::FocusDetail, Action::OpenDetail] {
+                if let Some(chord) = self.keymap.chord(action) {
+                    ctx.input_mut(|i| i.consume_key(eg::Modifiers::NONE, chord.key));
+                }
+            }
+            first.request_focus();
+            ctx.request_repaint();
+            return;
+        }
+        let focused = ctx.memory(|mem| mem.focused());
+        let Some(pos) = ring.iter().position(|resp| Some(resp.id) == focused) else {
+            return;
+        };
+        let id = ring[pos].id;
+        ctx.memory_mut(|mem| {
+            mem.set_focus_lock_filter(
+                id,
+                eg::EventFilter {
+                    tab: true,
+                    horizontal_arrows: true,
+                    vertical_arrows: true,
+                    escape: true,
+                },
+            );
+        });
+        if ring[pos].gained_focus() {
+            // The lock filter only applies from the next frame; make sure there is one.
+            ctx.request_repaint();
+        }
+        let (back, forward, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(eg::Modifiers::SHIFT, eg::Key::Tab),
+                i.consume_key(eg::Modifiers::NONE, eg::Key::Tab),
+                i.consume_key(eg::Modifiers::NONE, eg::Key::Escape),
+            )
+        });
+        if escape {
+            ctx.memory_mut(|mem| mem.surrender_focus(id));
+            self.scroll_to_idx = self.selected_idx;
+        } else if forward {
+            ring[(pos + 1) % ring.len()].request_focus();
+        } else if back {
+            ring[(pos + ring.len() - 1) % ring.len()].request_focus();
+        }
+    }
+
+    /// Select a row, or with Ctrl (Cmd) held pin/unpin it for compare.
+    pub(crate) fn click_row(&mut self, idx: usize, resp: &eg::Response) {
+        if resp.ctx.input(|i| i.modifiers.command) {
+            self.compare_idx = (self.compare_idx != Some(idx)).then_some(idx);
+        } else {
+            self.selected_idx = Some(idx);
+        }
+    }
+
+    /// "All airings" section: every upcoming slot of the title, and a way back to its card.
+    fn ui_render_other_airings(&mut self, ui: &mut eg::Ui, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        if self.repeats_of(row).is_empty() {
+            return;
+        }
+        let slots = self.airing_slots(row);
+        let rec = self.palette().rec;
+        let scheduled: Vec<bool> = slots
+            .iter()
+            .map(|slot| {
+                let when = std::time::UNIX_EPOCH
+                    + std::time::Duration::from_secs(slot.begins_at.max(0) as u64);
+                Some(when) == row.airing && row.scheduled
+                    || self.scheduled_index.as_ref().is_some_and(|index| {
+                        index.is_scheduled(None, &row.title, row.year, Some(when))
+                    })
+            })
+            .collect();
+        let mut jump = false;
+        eg::CollapsingHeader::new(format!("All airings ({})", slots.len()))
+            .id_source("detail_other_airings")
+            .default_open(true)
+            .show(ui, |ui| {
+                eg::Grid::new("detail_other_airings_grid")
+                    .num_columns(4)
+                    .spacing([10.0, 2.0])
+                    .show(ui, |ui| {
+                        for (i, slot) in slots.iter().enumerate() {
+                            let when = std::time::UNIX_EPOCH
+                                + std::time::Duration::from_secs(slot.begins_at.max(0) as u64);
+                            let label = format!(
+                                "{} {}",
+                                crate::app::utils::format_day_label(crate::app::utils::day_bucket(
+                                    when
+                                )),
+                                crate::app::utils::hhmm_utc(when)
+                            );
+                            if i == 0 {
+                                ui.label(eg::RichText::new(label).strong())
+                                    .on_hover_text("The airing this card shows");
+                            } else {
+                                ui.label(label);
+                            }
+                            ui.label(slot.channel.as_deref().unwrap_or("—"));
+                            ui.label(if slot.broadcast_hd { "HD" } else { "SD" });
+                            if scheduled[i] {
+                                ui.label(eg::RichText::new("⏺ REC").color(rec));
+                            } else {
+                                ui.label("");
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if ui
+                    .small_button("Jump to card")
+                    .on_hover_text(
+                        "Scroll the grid back to this film's card (one card per film, at its \
+                         soonest airing)",
+                    )
+                    .clicked()
+                {
+                    jump = true;
+                }
+            });
+        if jump {
+            self.scroll_to_idx = Some(idx);
+        }
+    }
+
+    fn compare_facts(&self, idx: usize) -> Option<CompareFacts> {
+        let row = self.rows.get(idx)?;
+        let when = row
+            .airing
+            .map(|ts| {
+                let bucket = crate::app::utils::day_bucket(ts);
+                format!(
+                    "{} {} UTC",
+                    crate::app::utils::format_day_label(bucket),
+                    crate::app::utils::hhmm_utc(ts)
+                )
+            })
+            .unwrap_or_else(|| "—".into());
+        let channel = row
+            .channel
+            .clone()
+            .or_else(|| {
+                row.channel_raw
+                    .as_deref()
+                    .map(crate::app::utils::humanize_channel)
+            })
+            .unwrap_or_else(|| "—".into());
+        let fetched = self.rating_state_for_key(&row.key).summary();
+        Some(CompareFacts {
+            title: row
+                .year
+                .map_or_else(|| row.title.clone(), |y| format!("{} ({y})", row.title)),
+            channel,
+            when,
+            broadcast_hd: Self::row_broadcast_hd(row),
+            scheduled: row.scheduled,
+            owned: self.row_owned_tier(row).map(|tier| {
+                (
+                    tier,
+                    row.owned_modified
+                        .and_then(crate::app::utils::format_owned_timestamp),
+                )
+            }),
+            critic: row.critic_rating,
+            audience: row.audience_rating,
+            fetched,
+        })
+    }
+
+    /// Side-by-side compare of the pinned airing and the current selection.
+    pub(crate) fn ui_render_compare_panel(&mut self, ctx: &eg::Context) {
+        let (Some(pinned), Some(selected)) = (self.compare_idx, self.selected_idx) else {
+            return;
+        };
+        if pinned == selected {
+            return;
+        }
+        let (Some(a), Some(b)) = (self.compare_facts(pinned), self.compare_facts(selected)) else {
+            self.compare_idx = None;
+            return;
+        };
+
+        let palette = self.palette();
+        let better = palette.positive;
+        let mut close = false;
+        let mut swap = false;
+        eg::TopBottomPanel::bottom("compare_panel")
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.heading("Compare airings");
+                    ui.with_layout(eg::Layout::right_to_left(eg::Align::Center), |ui| {
+                        if ui.button("Close").clicked() {
+                            close = true;
+                        }
+                        if ui
+                            .button("Swap")
+                            .on_hover_text("Pin the selected airing instead")
+                            .clicked()
+                        {
+                            swap = true;
+                        }
+                    });
+                });
+                ui.separator();
+
+                let quality = |f: &CompareFacts| if f.broadcast_hd { "HD" } else { "SD" };
+                let owned = |f: &CompareFacts| match &f.owned {
+                    Some((tier, recorded)) => {
+                        let q = format!("Owned {}", tier.label());
+                        recorded
+                            .as_ref()
+                            .map_or_else(|| q.to_string(), |d| format!("{q} (recorded {d})"))
+                    }
+                    None => "Not owned".into(),
+                };
+                let score = |r: Option<f32>| r.map_or_else(|| "—".into(), |r| format!("{r:.1}/10"));
+                let higher = |x: Option<f32>, y: Option<f32>| match (x, y) {
+                    (Some(x), Some(y)) => (x > y, y > x),
+                    _ => (false, false),
+                };
+
+                eg::Grid::new("compare_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([24.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.label(eg::RichText::new("Pinned").color(palette.compare).strong());
+                        ui.label(
+                            eg::RichText::new("Selected")
+                                .color(palette.selection)
+                                .strong(),
+                        );
+                        ui.end_row();
+
+                        compare_row(ui, better, "Title", &a.title, &b.title, (false, false));
+                        compare_row(
+                            ui,
+                            better,
+                            "Channel",
+                            &a.channel,
+                            &b.channel,
+                            (false, false),
+                        );
+                        compare_row(ui, better, "Airs", &a.when, &b.when, (false, false));
+                        compare_row(
+                            ui,
+                            better,
+                            "Broadcast",
+                            quality(&a),
+                            quality(&b),
+                            (
+                                a.broadcast_hd && !b.broadcast_hd,
+                                b.broadcast_hd && !a.broadcast_hd,
+                            ),
+                        );
+                        compare_row(
+                            ui,
+                            better,
+                            "Recording",
+                            if a.scheduled { "Scheduled" } else { "—" },
+                            if b.scheduled { "Scheduled" } else { "—" },
+                            (false, false),
+                        );
+                        compare_row(
+                            ui,
+                            better,
+                            "My copy",
+                            &owned(&a),
+                            &owned(&b),
+                            (false, false),
+                        );
+                        compare_row(
+                            ui,
+                            better,
+                            "Critics",
+                            &score(a.critic),
+                            &score(b.critic),
+                            higher(a.critic, b.critic),
+                        );
+                        compare_row(
+                            ui,
+                            better,
+                            "Audience",
+                            &score(a.audience),
+                            &score(b.audience),
+                            higher(a.audience, b.audience),
+                        );
+                        compare_row(
+                            ui,
+                            better,
+                            "Rating",
+                            a.fetched.as_deref().unwrap_or("—"),
+                            b.fetched.as_deref().unwrap_or("—"),
+                            (false, false),
+                        );
+                    });
+                ui.add_space(4.0);
+            });
+
+        if close {
+            self.compare_idx = None;
+        } else if swap {
+            self.compare_idx = Some(selected);
+            self.selected_idx = Some(pinned);
+        }
+    }
+}
+
+fn compare_row(
+    ui: &mut eg::Ui,
+    better_color: eg::Color32,
+    label: &str,
+    a: &str,
+    b: &str,
+    better: (bool, bool),
+) {
+    ui.label(eg::RichText::new(label).weak());
+    for (text, is_better) in [(a, better.0), (b, better.1)] {
+        let mut rich = eg::RichText::new(text);
+        if is_better {
+            rich = rich.color(better_color).strong();
+        }
+        ui.label(rich);
+    }
+    ui.end_row();
 }
@@ -0,0 +1,359 @@
+// src/app/digest.rs
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Local;
+use eframe::egui as eg;
+use image::imageops::FilterType;
+
+use crate::app::file_picker::{PendingPick, PickKind};
+use crate::app::utils::{day_bucket, format_day_label, hhmm_utc};
+use crate::app::web::html_escape;
+use crate::app::{PexApp, PosterRow};
+use crate::config::resolve_relative_path;
+
+const DIGEST_FILE: &str = "pex_digest.html";
+const DIGEST_SPAN: Duration = Duration::from_secs(7 * 24 * 3600);
+// Embedded thumbnails: small enough that a week of them still fits in an email.
+const THUMB_W: u32 = 92;
+const THUMB_QUALITY: u8 = 78;
+
+/// Default destination shown in Advanced (relative to the base dir).
+pub fn default_digest_path() -> String {
+    format!("{}/{DIGEST_FILE}", crate::app::export::EXPORT_DIR)
+}
+
+/// Headings of the digest, in page order; a film is listed under the first that fits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DigestSection {
+    Watchlist,
+    Upgrades,
+    Premieres,
+}
+
+impl DigestSection {
+    const ALL: [Self; 3] = [Self::Watchlist, Self::Upgrades, Self::Premieres];
+
+    const fn heading(self) -> &'static str {
+        match self {
+            Self::Watchlist => "On your watchlists",
+            Self::Upgrades => "HD upgrades",
+            Self::Premieres => "Premieres",
+        }
+    }
+
+    const fn blurb(self) -> &'static str {
+        match self {
+            Self::Watchlist => "Films on your Trakt, Letterboxd or IMDb watchlist.",
+            Self::Upgrades => "Airing in HD while your copy is SD or below the bitrate threshold.",
+            Self::Premieres => "First showings.",
+        }
+    }
+}
+
+/// Standard base64 with padding, for `data:` URIs.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 63]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// A cached poster shrunk to a thumbnail JPEG `data:` URI.
+fn poster_thumb(path: &Path) -> Option<String> {
+    let (w, h, rgba) = crate::app::cache::load_rgba_raw_or_image(&path.to_string_lossy()).ok()?;
+    let mut img = image::RgbaImage::from_raw(w, h, rgba)?;
+    if w > THUMB_W {
+        let thumb_h = ((h as f32) * (THUMB_W as f32 / w as f32)).round().max(1.0) as u32;
+        img = image::imageops::resize(&img, THUMB_W, thumb_h, FilterType::Triangle);
+    }
+    let rgb = image::DynamicImage::ImageRgba8(img).to_rgb8();
+    let mut jpeg = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, THUMB_QUALITY)
+        .encode_image(&rgb)
+        .ok()?;
+    Some(format!("data:image/jpeg;base64,{}", base64(&jpeg)))
+}
+
+const DIGEST_STYLE: &str = "\
+body{margin:0 auto;padding:16px;max-width:760px;background:#fff;color:#222;\
+font:14px system-ui,sans-serif}\
+h1{font-size:20px;margin:0 0 4px}h2{font-size:17px;margin:22px 0 2px}\
+.note{color:#777;font-size:12px}\
+.film{display:flex;gap:12px;padding:8px 0;border-bottom:1px solid #eee}\
+.film img,.film .noposter{width:92px;min-width:92px;aspect-ratio:2/3;object-fit:cover;\
+border-radius:4px;background:#ddd}\
+.title{font-weight:600}.sub{color:#666;font-size:12px;margin-top:2px}\
+.summary{font-size:13px;margin-top:4px}\
+.badge{display:inline-block;font-size:11px;font-weight:700;color:#fff;padding:1px 5px;\
+border-radius:3px;margin:4px 4px 0 0;background:#555}\
+.hd{background:#2a6fd6}.up{background:#c77d00}.new{background:#2a6fd6}\
+.list{background:#3a7d44}.rec{background:#b33}";
+
+impl PexApp {
+    fn digest_section(&self, row: &PosterRow) -> Option<DigestSection> {
+        DigestSection::ALL
+            .into_iter()
+            .find(|section| match section {
+                DigestSection::Watchlist => {
+                    row.trakt_watchlist || row.letterboxd || row.imdb_watchlist
+                }
+                DigestSection::Upgrades => self.row_hd_upgrade_available(row),
+                DigestSection::Premieres => row.premiere,
+            })
+    }
+
+    /// Coloured labels for a digest card.
+    fn digest_badges(&self, row: &PosterRow) -> Vec<(&'static str, String)> {
+        let mut badges = Vec::new();
+        if Self::row_broadcast_hd(row) {
+            badges.push(("hd", "HD".to_string()));
+        }
+        if self.row_hd_upgrade_available(row) {
+            let owned = self
+                .row_owned_tier(row)
+                .map_or("owned", |tier| tier.label());
+            badges.push(("up", format!("Upgrade from {owned}")));
+        }
+        if row.premiere {
+            badges.push(("new", "Premiere".to_string()));
+        }
+        for (on, label) in [
+            (row.trakt_watchlist, "Trakt watchlist"),
+            (row.letterboxd, "Letterboxd watchlist"),
+            (row.imdb_watchlist, "IMDb watchlist"),
+        ] {
+            if on {
+                badges.push(("list", label.to_string()));
+            }
+        }
+        if row.scheduled {
+            badges.push(("rec", "REC".to_string()));
+        }
+        badges
+    }
+
+    /// Write a standalone HTML page of the coming week's watchlist matches, HD upgrades and
+    /// premieres to `dest` (resolved against the base dir), posters embedded as thumbnails.
+    /// Filters are ignored, but only the loaded day range is covered. Returns the written path
+    /// and the number of films listed.
+    pub(crate) fn export_digest(&self, dest: &str) -> Result<(PathBuf, usize), String> {
+        let trimmed = dest.trim();
+        if trimmed.is_empty() {
+            return Err("destination path is empty".into());
+        }
+        let mut path = resolve_relative_path(trimmed);
+        if path.extension().is_none() {
+            path.set_extension("html");
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+        }
+
+        let now = self.now();
+        let until = now + DIGEST_SPAN;
+        let mut upcoming: Vec<&PosterRow> = self
+            .rows
+            .iter()
+            .filter(|row| {
+                row.airing
+                    .is_some_and(|start| start < until && row.ends.unwrap_or(start) >= now)
+            })
+            .collect();
+        upcoming.sort_by_key(|row| row.airing);
+
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+             <title>Pex weekly digest</title><style>{DIGEST_STYLE}</style></head><body>\
+             <h1>This week on TV</h1><div class=\"note\">{} to {} (times are UTC). \
+             Made by Pex on {}.</div>",
+            html_escape(&format_day_label(day_bucket(now))),
+            html_escape(&format_day_label(day_bucket(
+                until - Duration::from_secs(1)
+            ))),
+            html_escape(&Local::now().format("%a %-d %b %Y %H:%M").to_string()),
+        );
+        let mut thumbs: HashMap<&str, Option<String>> = HashMap::new();
+        let mut listed = 0usize;
+        for section in DigestSection::ALL {
+            let rows: Vec<&PosterRow> = upcoming
+                .iter()
+                .copied()
+                .filter(|row| self.digest_section(row) == Some(section))
+                .collect();
+            let _ = write!(
+                html,
+                "<h2>{} ({})</h2><div class=\"note\">{}</div>",
+                section.heading(),
+                rows.len(),
+                section.blurb()
+            );
+            if rows.is_empty() {
+                html.push_str("<p class=\"note\">Nothing this week.</p>");
+            }
+            for row in rows {
+                let thumb = thumbs.entry(row.key.as_str()).or_insert_with(|| {
+                    row.path
+                        .clone()
+                        .filter(|p| p.exists())
+                        .or_else(|| crate::app::cache::find_any_by_key(&row.key))
+                        .and_then(|p| poster_thumb(&p))
+                });
+                match thumb {
+                    Some(uri) => {
+                        let _ = write!(html, "<div class=\"film\"><img alt=\"\" src=\"{uri}\">");
+                    }
+                    None => html.push_str("<div class=\"film\"><div class=\"noposter\"></div>"),
+                }
+                let title = match row.year {
+                    Some(year) => format!("{} ({year})", row.title),
+                    None => row.title.clone(),
+                };
+                let when = row
+                    .airing
+                    .map(|ts| format!("{} {}", format_day_label(day_bucket(ts)), hhmm_utc(ts)))
+                    .unwrap_or_default();
+                let sub = [Some(when), row.channel.clone()]
+                    .into_iter()
+                    .flatten()
+                    .filter(|s| !s.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                let _ = write!(
+                    html,
+                    "<div><div class=\"title\">{}</div><div class=\"sub\">{}</div>\
+                     <div class=\"sub\">{}</div>",
+                    html_escape(&title),
+                    html_escape(&sub),
+                    html_escape(&row.genres.join(", ")),
+                );
+                if let Some(summary) = row.summary.as_deref() {
+                    let _ = write!(
+                        html,
+                        "<div class=\"summary\">{}</div>",
+                        html_escape(summary)
+                    );
+                }
+                for (class, label) in self.digest_badges(row) {
+                    let _ = write!(
+                        html,
+                        "<span class=\"badge {class}\">{}</span>",
+                        html_escape(&label)
+                    );
+                }
+                html.push_str("</div></div>");
+                listed += 1;
+            }
+        }
+        html.push_str("</body></html>");
+
+        fs::write(&path, html)
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+        Ok((path, listed))
+    }
+
+    /// Advanced ▸ Weekly digest: where to write the HTML page, and the button that writes it.
+    pub(crate) fn advanced_digest_controls(&mut self, ui: &mut eg::Ui) {
+        if let Some((_, result)) = self.digest_pick.as_ref().and_then(PendingPick::poll) {
+            self.digest_pick = None;
+            match result {
+                Ok(Some(path)) => {
+                    self.digest_path_input = path.display().to_string();
+                    self.mark_dirty();
+                }
+                Ok(None) => {}
+                Err(err) => self.advanced_feedback = Some(err),
+            }
+        }
+        ui.label(eg::RichText::new("Weekly digest").strong());
+        ui.horizontal(|ui| {
+            ui.label("Save to:");
+            if ui
+                .add(
+                    eg::TextEdit::singleline(&mut self.digest_path_input)
+                        .hint_text(default_digest_path())
+                        .desired_width(220.0),
+                )
+                .on_hover_text(
+                    "HTML file to (over)write, e.g. on a shared drive; relative paths resolve \
+                     next to the executable",
+                )
+                .changed()
+            {
+                self.mark_dirty();
+            }
+            if ui
+                .add_enabled(self.digest_pick.is_none(), eg::Button::new("Browse…"))
+                .on_hover_text("Choose where to save the digest page")
+                .clicked()
+            {
+                let current = if self.digest_path_input.trim().is_empty() {
+                    default_digest_path()
+                } else {
+                    self.digest_path_input.clone()
+                };
+                self.digest_pick = Some(PendingPick::spawn(
+                    (),
+                    PickKind::Save,
+                    "Save the weekly digest as",
+                    &current,
+                ));
+            }
+        });
+        if ui
+            .button("Export digest")
+            .on_hover_text(
+                "The next seven days' watchlist matches, HD upgrades and premieres as one \
+                 self-contained page with poster thumbnails, ready to email",
+            )
+            .clicked()
+        {
+            let dest = if self.digest_path_input.trim().is_empty() {
+                default_digest_path()
+            } else {
+                self.digest_path_input.clone()
+            };
+            self.advanced_feedback = Some(match self.export_digest(&dest) {
+                Ok((path, count)) => format!(
+                    "Wrote a digest of {count} film{} to {}",
+                    if count == 1 { "" } else { "s" },
+                    path.display()
+                ),
+                Err(err) => format!("Digest export failed: {err}"),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"Man"), "TWFu");
+        assert_eq!(base64(b"Ma"), "TWE=");
+        assert_eq!(base64(b"M"), "TQ==");
+        assert_eq!(base64(&[0xff, 0xee, 0x00, 0x01]), "/+4AAQ==");
+    }
+}
@@ -0,0 +1,340 @@
+// src/app/export.rs
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::app::guide::Guide;
+use crate::app::owned::OwnedDetails;
+use crate::app::{OwnedTier, PosterRow, RatingState};
+use crate::config::resolve_relative_path;
+
+pub(crate) const EXPORT_DIR: &str = "exports";
+// RFC 5545 lines are limited to 75 octets (excluding CRLF); longer ones get folded.
+const ICS_LINE_LIMIT: usize = 75;
+
+pub fn exports_dir() -> PathBuf {
+    resolve_relative_path(EXPORT_DIR)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Default destination shown in the Advanced popup (relative to the base dir).
+pub fn default_guide_export_path(format: ExportFormat) -> String {
+    format!("{EXPORT_DIR}/pex_guide.{}", format.extension())
+}
+
+/// One exported guide row; field order doubles as the CSV column order.
+#[derive(Serialize)]
+struct GuideExportRow {
+    title: String,
+    year: Option<i32>,
+    channel: Option<String>,
+    airing_utc: Option<String>,
+    genres: Vec<String>,
+    broadcast_hd: bool,
+    owned: bool,
+    owned_hd: bool,
+    owned_uhd: bool,
+    owned_bitrate_kbps: Option<u32>,
+    owned_video_codec: Option<String>,
+    hd_upgrade: bool,
+    scheduled: bool,
+    critic_rating: Option<f32>,
+    audience_rating: Option<f32>,
+    fetched_rating: Option<String>,
+}
+
+impl GuideExportRow {
+    fn new(
+        row: &PosterRow,
+        owned: &OwnedDetails,
+        hd_upgrade: bool,
+        fetched_rating: Option<String>,
+    ) -> Self {
+        Self {
+            title: row.title.clone(),
+            year: row.year,
+            channel: row.channel.clone(),
+            airing_utc: row
+                .airing
+                .map(|ts| DateTime::<Utc>::from(ts).to_rfc3339_opts(SecondsFormat::Secs, true)),
+            genres: row.genres.clone(),
+            broadcast_hd: row.broadcast_hd,
+            owned: row.owned,
+            owned_hd: row.owned && owned.is_hd(row),
+            owned_uhd: owned.tier(row) == Some(OwnedTier::Uhd),
+            owned_bitrate_kbps: owned.media(row).and_then(|m| m.bitrate_kbps),
+            owned_video_codec: owned.media(row).and_then(|m| m.video_codec.clone()),
+            hd_upgrade,
+            scheduled: row.scheduled,
+            critic_rating: row.critic_rating,
+            audience_rating: row.audience_rating,
+            fetched_rating,
+        }
+    }
+}
+
+/// Write `rows` to `dest` (resolved against the base dir) as CSV or JSON. A missing extension
+/// is filled in from the format. Returns the written path and row count.
+fn write_guide_export(
+    rows: &[GuideExportRow],
+    dest: &str,
+    format: ExportFormat,
+) -> Result<(PathBuf, usize), String> {
+    let trimmed = dest.trim();
+    if trimmed.is_empty() {
+        return Err("destination path is empty".into());
+    }
+    let mut path = resolve_relative_path(trimmed);
+    if path.extension().is_none() {
+        path.set_extension(format.extension());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let body = match format {
+        ExportFormat::Csv => guide_rows_to_csv(rows),
+        ExportFormat::Json => serde_json::to_string_pretty(rows)
+            .map_err(|err| format!("JSON encode failed: {err}"))?,
+    };
+    fs::write(&path, body).map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    Ok((path, rows.len()))
+}
+
+impl Guide {
+    /// Dump every airing in the day range like Advanced ▸ Export with no filters on.
+    pub(crate) fn export(
+        &self,
+        dest: &str,
+        format: ExportFormat,
+    ) -> Result<(PathBuf, usize), String> {
+        let rows: Vec<GuideExportRow> = self
+            .days()
+            .into_iter()
+            .flat_map(|(_, rows)| rows)
+            .map(|row| {
+                GuideExportRow::new(row, &self.owned, self.owned.hd_upgrade(row, None), None)
+            })
+            .collect();
+        write_guide_export(&rows, dest, format)
+    }
+}
+
+const CSV_HEADER: [&str; 16] = [
+    "title",
+    "year",
+    "channel",
+    "airing_utc",
+    "genres",
+    "broadcast_hd",
+    "owned",
+    "owned_hd",
+    "owned_uhd",
+    "owned_bitrate_kbps",
+    "owned_video_codec",
+    "hd_upgrade",
+    "scheduled",
+    "critic_rating",
+    "audience_rating",
+    "fetched_rating",
+];
+
+impl crate::app::PexApp {
+    /// Row indices currently shown in the grid (filters + day range applied), in display order.
+    pub(crate) fn visible_row_indices(&self) -> Vec<usize> {
+        self.build_grouped_indices()
+            .into_iter()
+            .flat_map(|(_, idxs)| idxs)
+            .collect()
+    }
+
+    /// Dump the visible rows to `dest` (resolved against the base dir) as CSV or JSON.
+    /// A missing extension is filled in from the format. Returns the written path and row count.
+    pub(crate) fn export_visible_guide(
+        &self,
+        dest: &str,
+        format: ExportFormat,
+    ) -> Result<(PathBuf, usize), String> {
+        let rows: Vec<GuideExportRow> = self
+            .visible_row_indices()
+            .into_iter()
+            .filter_map(|idx| self.rows.get(idx))
+            .map(|row| {
+                GuideExportRow::new(
+                    row,
+                    &self.owned_details,
+                    self.row_hd_upgrade_available(row),
+                    self.rating_states
+                        .get(&row.key)
+                        .and_then(RatingState::summary),
+                )
+            })
+            .collect();
+        write_guide_export(&rows, dest, format)
+    }
+
+    /// Write the visible airings as VEVENTs into `exports/pex_airings_<stamp>.ics`.
+    /// Returns the written path and the number of events.
+    pub(crate) fn export_visible_ics(&self) -> Result<(PathBuf, usize), String> {
+        let dir = exports_dir();
+        fs::create_dir_all(&dir)
+            .map_err(|err| format!("Failed to create {}: {err}", dir.display()))?;
+
+        let stamp = ics_datetime(self.now());
+        let mut out = String::new();
+        push_ics_line(&mut out, "BEGIN:VCALENDAR");
+        push_ics_line(&mut out, "VERSION:2.0");
+        push_ics_line(&mut out, "PRODID:-//Pex//Plex EPG Explorer//EN");
+        push_ics_line(&mut out, "CALSCALE:GREGORIAN");
+        push_ics_line(&mut out, "X-WR-CALNAME:Pex upcoming airings");
+
+        let mut count = 0usize;
+        for idx in self.visible_row_indices() {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            let Some(start) = row.airing else {
+                continue;
+            };
+            let start_str = ics_datetime(start);
+            let summary = row
+                .year
+                .map_or_else(|| row.title.clone(), |y| format!("{} ({y})", row.title));
+
+            push_ics_line(&mut out, "BEGIN:VEVENT");
+            push_ics_line(&mut out, &format!("UID:{}-{start_str}@pex", row.key));
+            push_ics_line(&mut out, &format!("DTSTAMP:{stamp}"));
+            push_ics_line(&mut out, &format!("DTSTART:{start_str}"));
+            if let Some(end) = row.ends.filter(|end| *end > start) {
+                push_ics_line(&mut out, &format!("DTEND:{}", ics_datetime(end)));
+            }
+            push_ics_line(&mut out, &format!("SUMMARY:{}", ics_escape(&summary)));
+            if let Some(channel) = row.channel.as_deref() {
+                push_ics_line(&mut out, &format!("LOCATION:{}", ics_escape(channel)));
+            }
+            if let Some(text) = row.summary.as_deref() {
+                push_ics_line(&mut out, &format!("DESCRIPTION:{}", ics_escape(text)));
+            }
+            if !row.genres.is_empty() {
+                let categories: Vec<String> = row.genres.iter().map(|g| ics_escape(g)).collect();
+                push_ics_line(&mut out, &format!("CATEGORIES:{}", categories.join(",")));
+            }
+            push_ics_line(&mut out, "END:VEVENT");
+            count += 1;
+        }
+        push_ics_line(&mut out, "END:VCALENDAR");
+
+        let file_name = format!("pex_airings_{}.ics", Local::now().format("%Y%m%d_%H%M%S"));
+        let path = dir.join(file_name);
+        fs::write(&path, out)
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+        Ok((path, count))
+    }
+}
+
+fn guide_rows_to_csv(rows: &[GuideExportRow]) -> String {
+    fn opt<T: ToString>(v: Option<T>) -> String {
+        v.map(|v| v.to_string()).unwrap_or_default()
+    }
+
+    let mut out = CSV_HEADER.join(",");
+    out.push_str("\r\n");
+    for row in rows {
+        let fields = [
+            row.title.clone(),
+            opt(row.year),
+            opt(row.channel.as_deref()),
+            opt(row.airing_utc.as_deref()),
+            row.genres.join("|"),
+            row.broadcast_hd.to_string(),
+            row.owned.to_string(),
+            row.owned_hd.to_string(),
+            row.owned_uhd.to_string(),
+            opt(row.owned_bitrate_kbps),
+            opt(row.owned_video_codec.as_deref()),
+            row.hd_upgrade.to_string(),
+            row.scheduled.to_string(),
+            opt(row.critic_rating.map(|r| format!("{r:.1}"))),
+            opt(row.audience_rating.map(|r| format!("{r:.1}"))),
+            opt(row.fetched_rating.as_deref()),
+        ];
+        let escaped: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+        out.push_str(&escaped.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn ics_datetime(ts: SystemTime) -> String {
+    DateTime::<Utc>::from(ts)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+fn ics_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Append a content line, folding it at the octet limit without splitting UTF-8 characters.
+fn push_ics_line(out: &mut String, line: &str) {
+    let mut used = 0usize;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if used + len > ICS_LINE_LIMIT {
+            out.push_str("\r\n ");
+            used = 1;
+        }
+        out.push(ch);
+        used += len;
+    }
+    out.push_str("\r\n");
+}
@@ -0,0 +1,343 @@
+// src/app/facets.rs
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use super::PosterRow;
+use crate::app::filters::{day_window_bucket, year_in_range};
+use crate::app::search::{SearchField, SearchQuery};
+
+/// One of the user's filters, so each can be tested, counted or relaxed on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Facet {
+    Search,
+    Channels,
+    Genres,
+    Tags,
+    HdOnly,
+    PremieresOnly,
+    NewOnly,
+    LetterboxdOnly,
+    HideOwned,
+    HideWatched,
+    Decades,
+    YearRange,
+    OwnedCutoff,
+}
+
+impl Facet {
+    pub const ALL: [Self; 13] = [
+        Self::Search,
+        Self::Channels,
+        Self::Genres,
+        Self::Tags,
+        Self::HdOnly,
+        Self::PremieresOnly,
+        Self::NewOnly,
+        Self::LetterboxdOnly,
+        Self::HideOwned,
+        Self::HideWatched,
+        Self::Decades,
+        Self::YearRange,
+        Self::OwnedCutoff,
+    ];
+
+    /// Checkbox filters: the Filters menu counts how many airings each would leave.
+    pub const TOGGLES: [Self; 7] = [
+        Self::HdOnly,
+        Self::PremieresOnly,
+        Self::NewOnly,
+        Self::LetterboxdOnly,
+        Self::HideOwned,
+        Self::HideWatched,
+        Self::OwnedCutoff,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Search => "Search",
+            Self::Channels => "Channel filter",
+            Self::Genres => "Genre filter",
+            Self::Tags => "Tag filter",
+            Self::HdOnly => "HD only",
+            Self::PremieresOnly => "Premieres only",
+            Self::NewOnly => "New in guide only",
+            Self::LetterboxdOnly => "Letterboxd watchlist only",
+            Self::HideOwned => "Hide owned",
+            Self::HideWatched => "Hide watched",
+            Self::Decades => "Decades",
+            Self::YearRange => "Year range",
+            Self::OwnedCutoff => "Owned recorded before cutoff",
+        }
+    }
+
+    /// Button text for switching the filter off.
+    pub const fn relax_label(self) -> &'static str {
+        match self {
+            Self::Search => "Clear search",
+            Self::Channels => "All channels",
+            Self::Genres => "All genres",
+            Self::Tags => "Clear tags",
+            Self::Decades => "All decades",
+            Self::YearRange => "All years",
+            _ => "Turn off",
+        }
+    }
+
+    const fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// The filters a row fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FacetSet(u16);
+
+impl FacetSet {
+    pub fn insert(&mut self, facet: Facet) {
+        self.0 |= facet.bit();
+    }
+
+    pub const fn contains(self, facet: Facet) -> bool {
+        self.0 & facet.bit() != 0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Nothing but `facet` (if that) is in the set.
+    pub const fn only(self, facet: Facet) -> bool {
+        self.0 & !facet.bit() == 0
+    }
+}
+
+/// The search box, parsed once per pass over the rows.
+pub(crate) struct RowSearch<'a> {
+    query: SearchQuery,
+    /// An invalid regex filters nothing; the top bar shows the error instead.
+    regex: Option<&'a Regex>,
+    use_query: bool,
+}
+
+/// Per-option counts for the Filters menu and the channel / genre pickers: how many airings in
+/// the day window would show with that option picked, the other filters staying as they are.
+/// The empty state reads what each active filter hides.
+#[derive(Debug, Default)]
+pub(crate) struct FacetCounts {
+    pub channels: HashMap<String, usize>,
+    pub genres: HashMap<String, usize>,
+    pub decades: HashMap<i32, usize>,
+    toggles: HashMap<Facet, usize>,
+    /// Airings in the day window, whatever the filters.
+    pub in_window: usize,
+    /// Per active filter: airings it hides, and airings that would show with it off.
+    hidden: HashMap<Facet, usize>,
+    relaxed: HashMap<Facet, usize>,
+}
+
+impl FacetCounts {
+    /// Airings shown with the checkbox filter `facet` ticked.
+    pub fn toggle(&self, facet: Facet) -> usize {
+        self.toggles.get(&facet).copied().unwrap_or(0)
+    }
+
+    pub fn hidden_by(&self, facet: Facet) -> usize {
+        self.hidden.get(&facet).copied().unwrap_or(0)
+    }
+
+    pub fn shown_without(&self, facet: Facet) -> usize {
+        self.relaxed.get(&facet).copied().unwrap_or(0)
+    }
+}
+
+impl crate::app::PexApp {
+    pub(crate) fn row_search(&self) -> RowSearch<'_> {
+        let query = SearchQuery::parse(&self.search_query);
+        let regex = self.regex_search.active().and_then(|r| r.as_ref().ok());
+        let use_query = self.regex_search.active().is_none() && !query.is_empty();
+        RowSearch {
+            query,
+            regex,
+            use_query,
+        }
+    }
+
+    /// Whether the filter is narrowing the guide at all.
+    pub(crate) fn facet_active(&self, facet: Facet, search: &RowSearch) -> bool {
+        match facet {
+            Facet::Search => search.use_query || search.regex.is_some(),
+            // EMPTY = no filter (show all)
+            Facet::Channels => !self.selected_channels.is_empty(),
+            Facet::Genres => !self.selected_genres.is_empty(),
+            Facet::Tags => !self.selected_tags.is_empty(),
+            Facet::HdOnly => self.filter_hd_only,
+            Facet::PremieresOnly => self.filter_premieres_only,
+            Facet::NewOnly => self.filter_new_only,
+            Facet::LetterboxdOnly => self.filter_letterboxd_only,
+            Facet::HideOwned => self.hide_owned,
+            Facet::HideWatched => self.hide_watched,
+            Facet::Decades => !self.selected_decades.is_empty(),
+            Facet::YearRange => self.year_min.is_some() || self.year_max.is_some(),
+            Facet::OwnedCutoff => self.filter_owned_before_cutoff,
+        }
+    }
+
+    /// Whether `row` gets through the filter if it is on.
+    fn facet_passes(&self, facet: Facet, row: &PosterRow, search: &RowSearch) -> bool {
+        match facet {
+            // search box (title, field prefixes, optionally every field)
+            Facet::Search => {
+                if search.use_query && !self.row_matches_search(&search.query, row) {
+                    return false;
+                }
+                search.regex.is_none_or(|re| {
+                    if self.search_all_fields {
+                        SearchField::ALL
+                            .into_iter()
+                            .flat_map(|f| f.values(row))
+                            .any(|v| re.is_match(v))
+                    } else {
+                        re.is_match(&row.title)
+                    }
+                })
+            }
+            // include-only channel filter
+            Facet::Channels => self
+                .selected_channels
+                .contains(row.channel_raw.as_deref().unwrap_or("")),
+            Facet::Genres => row.genres.iter().any(|g| self.selected_genres.contains(g)),
+            Facet::Tags => self.row_has_any_tag(row, &self.selected_tags),
+            Facet::HdOnly => Self::row_broadcast_hd(row),
+            Facet::PremieresOnly => row.premiere,
+            Facet::NewOnly => row.new_in_guide,
+            Facet::LetterboxdOnly => row.letterboxd,
+            // hide-owned, but KEEP rows that are HD upgrades (airing HD while owned is SD
+            // or a low-bitrate HD copy)
+            Facet::HideOwned => !row.owned || self.row_hd_upgrade_available(row),
+            Facet::HideWatched => !row.watched,
+            Facet::Decades => row
+                .year
+                .is_some_and(|y| self.selected_decades.contains(&((y / 10) * 10))),
+            Facet::YearRange => year_in_range(row.year, self.year_min, self.year_max),
+            Facet::OwnedCutoff => matches!(
+                (row.owned, row.owned_modified),
+                (true, Some(ts)) if ts < self.owned_before_cutoff_ts
+            ),
+        }
+    }
+
+    /// The active filters `row` fails; with `first_only` it stops at the first one, which is
+    /// all the grid needs to know.
+    pub(crate) fn facet_misses(
+        &self,
+        row: &PosterRow,
+        search: &RowSearch,
+        first_only: bool,
+    ) -> FacetSet {
+        let mut misses = FacetSet::default();
+        for facet in Facet::ALL {
+            if self.facet_active(facet, search) && !self.facet_passes(facet, row, search) {
+                misses.insert(facet);
+                if first_only {
+                    break;
+                }
+            }
+        }
+        misses
+    }
+
+    /// Switch one filter off, e.g. from the empty-state buttons.
+    pub(crate) fn relax_facet(&mut self, facet: Facet) {
+        match facet {
+            Facet::Search => self.search_query.clear(),
+            Facet::Channels => self.selected_channels.clear(),
+            Facet::Genres => self.selected_genres.clear(),
+            Facet::Tags => self.selected_tags.clear(),
+            Facet::HdOnly => self.filter_hd_only = false,
+            Facet::PremieresOnly => self.filter_premieres_only = false,
+            Facet::NewOnly => self.filter_new_only = false,
+            Facet::LetterboxdOnly => self.filter_letterboxd_only = false,
+            Facet::HideOwned => self.hide_owned = false,
+            Facet::HideWatched => self.hide_watched = false,
+            Facet::Decades => self.selected_decades.clear(),
+            Facet::YearRange => {
+                self.year_min = None;
+                self.year_max = None;
+            }
+            Facet::OwnedCutoff => self.filter_owned_before_cutoff = false,
+        }
+        self.mark_dirty();
+    }
+
+    /// Counts for every channel, genre, decade and checkbox filter over the day window.
+    pub(crate) fn facet_counts(&self) -> FacetCounts {
+        let now = self.now();
+        let search = self.row_search();
+        let mut counts = FacetCounts::default();
+        for row in &self.rows {
+            if row
+                .airing
+                .and_then(|ts| day_window_bucket(ts, now, self.current_range))
+                .is_none()
+            {
+                continue;
+            }
+            counts.in_window += 1;
+            let misses = self.facet_misses(row, &search, false);
+            for facet in Facet::ALL {
+                if misses.contains(facet) {
+                    *counts.hidden.entry(facet).or_default() += 1;
+                    if misses.only(facet) {
+                        *counts.relaxed.entry(facet).or_default() += 1;
+                    }
+                }
+            }
+            if misses.only(Facet::Channels) {
+                let raw = row.channel_raw.clone().unwrap_or_default();
+                *counts.channels.entry(raw).or_default() += 1;
+            }
+            if misses.only(Facet::Genres) {
+                let genres: HashSet<&String> = row.genres.iter().collect();
+                for genre in genres {
+                    *counts.genres.entry(genre.clone()).or_default() += 1;
+                }
+            }
+            if misses.only(Facet::Decades) {
+                if let Some(year) = row.year {
+                    *counts.decades.entry((year / 10) * 10).or_default() += 1;
+                }
+            }
+            for facet in Facet::TOGGLES {
+                if misses.only(facet) && self.facet_passes(facet, row, &search) {
+                    *counts.toggles.entry(facet).or_default() += 1;
+                }
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn facet_set_only_allows_the_one_facet() {
+        let mut misses = FacetSet::default();
+        assert!(misses.is_empty());
+        assert!(misses.only(Facet::Channels));
+        misses.insert(Facet::Channels);
+        assert!(misses.only(Facet::Channels));
+        assert!(!misses.only(Facet::Genres));
+        misses.insert(Facet::OwnedCutoff);
+        assert!(!misses.is_empty());
+        assert!(!misses.only(Facet::Channels));
+        assert!(misses.contains(Facet::OwnedCutoff) && !misses.contains(Facet::Genres));
+        assert!(Facet::ALL.iter().all(|f| Facet::ALL
+            .iter()
+            .filter(|g| g.bit() == f.bit())
+            .count()
+            == 1));
+    }
+}
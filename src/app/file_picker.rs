@@ -0,0 +1,253 @@
+// src/app/file_picker.rs
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// What a Browse… button asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PickKind {
+    File,
+    Folder,
+    /// Save-as dialog: pick a folder and type a file name (which may not exist yet).
+    Save,
+}
+
+/// Outcome of one dialog: `Ok(None)` when the user cancelled.
+pub(crate) type PickResult = Result<Option<PathBuf>, String>;
+
+/// A native open-file / choose-folder / save-as dialog running on a helper thread, so the UI keeps
+/// painting while it is open. Uses the dialog the OS already ships (PowerShell WinForms on
+/// Windows, AppleScript on macOS, zenity or kdialog elsewhere) rather than a GUI toolkit.
+pub(crate) struct PendingPick<T> {
+    target: T,
+    rx: Receiver<PickResult>,
+}
+
+impl<T: Copy> PendingPick<T> {
+    /// Open the dialog for `target`, starting near `current` when it names an existing place.
+    /// A save dialog also suggests `current`'s file name.
+    pub(crate) fn spawn(target: T, kind: PickKind, title: &str, current: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let title = title.to_string();
+        let start = start_dir(current);
+        let name = (kind == PickKind::Save)
+            .then(|| save_name(current))
+            .flatten();
+        std::thread::spawn(move || {
+            let _ = tx.send(run_dialog(kind, &title, start.as_deref(), name.as_deref()));
+        });
+        Self { target, rx }
+    }
+
+    /// `Some((target, result))` once the dialog has closed.
+    pub(crate) fn poll(&self) -> Option<(T, PickResult)> {
+        match self.rx.try_recv() {
+            Ok(result) => Some((self.target, result)),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some((self.target, Err("File dialog closed unexpectedly.".into())))
+            }
+        }
+    }
+}
+
+/// The folder to open the dialog in: the current value itself when it is a folder,
+/// otherwise its parent.
+fn start_dir(current: &str) -> Option<PathBuf> {
+    let current = current.trim();
+    if current.is_empty() {
+        return None;
+    }
+    let path = crate::config::resolve_relative_path(current);
+    if path.is_dir() {
+        Some(path)
+    } else {
+        path.parent().filter(|p| p.is_dir()).map(Path::to_path_buf)
+    }
+}
+
+/// File name part of `current`, suggested in a save dialog.
+fn save_name(current: &str) -> Option<String> {
+    let current = current.trim().trim_end_matches(['/', '\\']);
+    let name = current.rsplit(['/', '\\']).next()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// First line of a dialog's stdout as a path; empty output means cancelled.
+fn picked_path(stdout: &[u8]) -> Option<PathBuf> {
+    let text = String::from_utf8_lossy(stdout);
+    let line = text.lines().next().unwrap_or("").trim();
+    (!line.is_empty()).then(|| PathBuf::from(line))
+}
+
+fn run(mut cmd: Command) -> Result<Option<PathBuf>, std::io::Error> {
+    let output = cmd.output()?;
+    // Every dialog exits non-zero (or prints nothing) on cancel.
+    Ok(output
+        .status
+        .success()
+        .then(|| picked_path(&output.stdout))
+        .flatten())
+}
+
+#[cfg(target_os = "windows")]
+fn run_dialog(kind: PickKind, title: &str, start: Option<&Path>, name: Option<&str>) -> PickResult {
+    use std::os::windows::process::CommandExt;
+    // Keep PowerShell from flashing a console window.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let start = start.map(|p| quote(&p.display().to_string()));
+    let script = match kind {
+        PickKind::File => format!(
+            "$d = New-Object System.Windows.Forms.OpenFileDialog; $d.Title = {}; \
+             $d.Filter = 'Plex databases (*.db)|*.db|All files (*.*)|*.*'; {} \
+             if ($d.ShowDialog() -eq 'OK') {{ $d.FileName }}",
+            quote(title),
+            start
+                .map(|s| format!("$d.InitialDirectory = {s};"))
+                .unwrap_or_default(),
+        ),
+        PickKind::Folder => format!(
+            "$d = New-Object System.Windows.Forms.FolderBrowserDialog; $d.Description = {}; {} \
+             if ($d.ShowDialog() -eq 'OK') {{ $d.SelectedPath }}",
+            quote(title),
+            start
+                .map(|s| format!("$d.SelectedPath = {s};"))
+                .unwrap_or_default(),
+        ),
+        PickKind::Save => format!(
+            "$d = New-Object System.Windows.Forms.SaveFileDialog; $d.Title = {}; {} {} \
+             if ($d.ShowDialog() -eq 'OK') {{ $d.FileName }}",
+            quote(title),
+            start
+                .map(|s| format!("$d.InitialDirectory = {s};"))
+                .unwrap_or_default(),
+            name.map(|n| format!("$d.FileName = {};", quote(n)))
+                .unwrap_or_default(),
+        ),
+    };
+    let mut cmd = Command::new("powershell");
+    // The console code page would mangle non-ASCII paths on the way out; force UTF-8, which
+    // `picked_path` decodes.
+    cmd.args(["-NoProfile", "-STA", "-Command"])
+        .arg(format!(
+            "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+             Add-Type -AssemblyName System.Windows.Forms; {script}"
+        ))
+        .creation_flags(CREATE_NO_WINDOW);
+    run(cmd).map_err(|err| format!("Could not open the file dialog (PowerShell): {err}"))
+}
+
+#[cfg(target_os = "macos")]
+fn run_dialog(kind: PickKind, title: &str, start: Option<&Path>, name: Option<&str>) -> PickResult {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let what = match kind {
+        PickKind::File => "file",
+        PickKind::Folder => "folder",
+        PickKind::Save => "file name",
+    };
+    let default_name = name
+        .map(|n| format!(" default name {}", quote(n)))
+        .unwrap_or_default();
+    let location = start
+        .map(|p| {
+            format!(
+                " default location (POSIX file {})",
+                quote(&p.display().to_string())
+            )
+        })
+        .unwrap_or_default();
+    let script = format!(
+        "POSIX path of (choose {what} with prompt {}{default_name}{location})",
+        quote(title)
+    );
+    let mut cmd = Command::new("osascript");
+    cmd.args(["-e", &script]);
+    run(cmd).map_err(|err| format!("Could not open the file dialog (osascript): {err}"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn run_dialog(kind: PickKind, title: &str, start: Option<&Path>, name: Option<&str>) -> PickResult {
+    let start = match (start, name) {
+        (Some(p), name) => {
+            let mut s = p.display().to_string();
+            if !s.ends_with('/') {
+                s.push('/');
+            }
+            s.push_str(name.unwrap_or(""));
+            Some(s)
+        }
+        (None, name) => name.map(str::to_string),
+    };
+
+    let mut zenity = Command::new("zenity");
+    zenity.args(["--file-selection", "--title", title]);
+    match kind {
+        PickKind::Folder => {
+            zenity.arg("--directory");
+        }
+        PickKind::Save => {
+            zenity.args(["--save", "--confirm-overwrite"]);
+        }
+        PickKind::File => {}
+    }
+    if let Some(start) = &start {
+        zenity.args(["--filename", start]);
+    }
+    match run(zenity) {
+        Ok(picked) => return Ok(picked),
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(format!("Could not open the file dialog (zenity): {err}"));
+        }
+        Err(_) => {}
+    }
+
+    let mut kdialog = Command::new("kdialog");
+    kdialog.arg(match kind {
+        PickKind::File => "--getopenfilename",
+        PickKind::Folder => "--getexistingdirectory",
+        PickKind::Save => "--getsavefilename",
+    });
+    kdialog.arg(start.as_deref().unwrap_or("."));
+    kdialog.args(["--title", title]);
+    run(kdialog).map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            "No file dialog available; install zenity or kdialog, or type the path.".into()
+        } else {
+            format!("Could not open the file dialog (kdialog): {err}")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dialog_output_is_trimmed_and_empty_means_cancel() {
+        assert_eq!(
+            picked_path(b"/srv/plex/library.db\n"),
+            Some(PathBuf::from("/srv/plex/library.db"))
+        );
+        assert_eq!(
+            picked_path(b"C:\\Plex\\epg.db\r\n"),
+            Some(PathBuf::from("C:\\Plex\\epg.db"))
+        );
+        assert_eq!(picked_path(b"\n"), None);
+        assert_eq!(picked_path(b""), None);
+    }
+
+    #[test]
+    fn save_dialogs_suggest_the_current_file_name() {
+        assert_eq!(
+            save_name("exports/pex_guide.json").as_deref(),
+            Some("pex_guide.json")
+        );
+        assert_eq!(
+            save_name("D:\\Share\\pex_digest.html").as_deref(),
+            Some("pex_digest.html")
+        );
+        assert_eq!(save_name("  "), None);
+    }
+}
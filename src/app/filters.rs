@@ -1,9 +1,12 @@
 // src/app/filters.rs
 use chrono::{NaiveDate, TimeZone, Utc};
+use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::time::SystemTime;
 
-use super::SortKey;
+use super::{DayRange, GroupBy, GroupKey, PosterRow, SortKey};
+use crate::app::search::{self, SearchField, SearchQuery};
+use crate::app::utils::day_bucket;
 
 pub(crate) const OWNED_BEFORE_CUTOFF_DEFAULT_STR: &str = "2022-12-25";
 pub(crate) const OWNED_BEFORE_CUTOFF_DEFAULT_TS: u64 = 1_671_926_400; // 2022-12-25 00:00:00 UTC
@@ -14,130 +17,108 @@ pub(crate) fn parse_owned_cutoff(input: &str) -> Option<u64> {
     Some(Utc.from_utc_datetime(&dt).timestamp().max(0) as u64)
 }
 
-impl crate::app::PexApp {
-    /// Build grouped indices for the grid: per-day buckets with intra-day sorting applied.
-    /// Returns Vec of (day_bucket, indices_for_that_day)
-    pub(crate) fn build_grouped_indices(&self) -> Vec<(i64, Vec<usize>)> {
-        use std::time::SystemTime;
-
-        let now_bucket = crate::app::utils::day_bucket(SystemTime::now());
-        // The helper is in this module — call it directly.
-        let max_bucket_opt = self.current_range.max_bucket(now_bucket);
-
-        // Precompute filters
-        let query = self.search_query.to_ascii_lowercase();
-        let use_query = !query.is_empty();
-        let have_channel_filter = !self.selected_channels.is_empty(); // EMPTY = no filter (show all)
-        let have_genre_filter = !self.selected_genres.is_empty();
-        let have_decade_filter = !self.selected_decades.is_empty();
-        let owned_cutoff_active = self.filter_owned_before_cutoff;
-        let owned_cutoff_ts = self.owned_before_cutoff_ts;
-
-        // 1) Filter + attach day bucket
-        let mut filtered: Vec<(usize, i64)> = self
-            .rows
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, row)| {
-                // time window
-                let ts = row.airing?;
-                let b = crate::app::utils::day_bucket(ts);
-                if b < now_bucket {
-                    return None;
-                }
-                if let Some(max_b) = max_bucket_opt {
-                    if b >= max_b {
-                        return None;
-                    }
-                }
-
-                // title search
-                if use_query && !row.title.to_ascii_lowercase().contains(&query) {
-                    return None;
-                }
-
-                // include-only channel filter
-                if have_channel_filter {
-                    let raw = row.channel_raw.as_deref().unwrap_or("");
-                    if !self.selected_channels.contains(raw) {
-                        return None;
-                    }
-                }
-
-                if have_genre_filter {
-                    let mut match_genre = false;
-                    for g in &row.genres {
-                        if self.selected_genres.contains(g) {
-                            match_genre = true;
-                            break;
-                        }
-                    }
-                    if !match_genre {
-                        return None;
-                    }
-                }
-                let broadcast_hd = Self::row_broadcast_hd(row);
+/// `cmp` on two present values, reversed for "Desc"; a missing value sorts after every
+/// present one either way.
+fn cmp_present_first<T>(
+    a: Option<T>,
+    b: Option<T>,
+    desc: bool,
+    cmp: impl Fn(T, T) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if desc => cmp(a, b).reverse(),
+        (Some(a), Some(b)) => cmp(a, b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
 
-                if self.filter_hd_only && !broadcast_hd {
-                    return None;
-                }
+/// Day bucket of `airing` when it falls inside the window that starts at today's bucket
+/// and spans `range` days; `None` when it is in the past or beyond the window.
+/// Inclusive year-range check; rows without a year only pass when no bound is set.
+pub(crate) fn year_in_range(year: Option<i32>, min: Option<i32>, max: Option<i32>) -> bool {
+    if min.is_none() && max.is_none() {
+        return true;
+    }
+    year.is_some_and(|y| min.is_none_or(|lo| y >= lo) && max.is_none_or(|hi| y <= hi))
+}
 
-                // hide-owned, but KEEP rows that are HD upgrades (airing HD while owned is SD)
-                if self.hide_owned && row.owned {
-                    let owned_is_hd = self.row_owned_is_hd(row);
+pub(crate) fn day_window_bucket(
+    airing: SystemTime,
+    now: SystemTime,
+    range: DayRange,
+) -> Option<i64> {
+    let now_bucket = day_bucket(now);
+    let bucket = day_bucket(airing);
+    if bucket < now_bucket {
+        return None;
+    }
+    if let Some(max_b) = range.max_bucket(now_bucket) {
+        if bucket >= max_b {
+            return None;
+        }
+    }
+    Some(bucket)
+}
 
-                    let is_upgrade = broadcast_hd && !owned_is_hd;
-                    if !is_upgrade {
-                        return None;
-                    }
-                }
+impl crate::app::PexApp {
+    /// Section a row falls in under the current "Group by" (`bucket` is its airing day).
+    pub(crate) fn group_key(&self, row: &PosterRow, bucket: i64) -> GroupKey {
+        match self.group_by {
+            GroupBy::Day => GroupKey::Day(bucket),
+            GroupBy::Channel => GroupKey::Channel(
+                row.channel
+                    .as_deref()
+                    .map(crate::app::utils::humanize_channel)
+                    .unwrap_or_default(),
+            ),
+            GroupBy::Genre => GroupKey::Genre(row.genres.first().cloned().unwrap_or_default()),
+            GroupBy::None => GroupKey::All,
+        }
+    }
 
-                if have_decade_filter {
-                    let decade = row.year.map(|y| (y / 10) * 10);
-                    match decade {
-                        Some(d) if self.selected_decades.contains(&d) => {}
-                        _ => return None,
-                    }
-                }
+    /// Build grouped indices for the grid: sections per the "Group by" setting (day buckets
+    /// by default) with intra-section sorting applied.
+    /// Returns Vec of (section key, indices_in_that_section)
+    pub(crate) fn build_grouped_indices(&self) -> Vec<(GroupKey, Vec<usize>)> {
+        let now = self.now();
+        let range = self.current_range;
 
-                if owned_cutoff_active {
-                    match (row.owned, row.owned_modified) {
-                        (true, Some(ts)) if ts < owned_cutoff_ts => {}
-                        _ => return None,
-                    }
-                }
+        let search = self.row_search();
 
-                Some((idx, b))
+        // 1) Filter + attach section key
+        let mut filtered: Vec<(usize, GroupKey)> = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, row)| {
+                // time window
+                let b = day_window_bucket(row.airing?, now, range)?;
+                self.facet_misses(row, &search, true)
+                    .is_empty()
+                    .then(|| (idx, self.group_key(row, b)))
             })
             .collect();
 
-        // 2) Sort by (day bucket, then title) for stable grouping
-        filtered.sort_by(|a, b| {
-            let (ai, ab) = a;
-            let (bi, bb) = b;
-            ab.cmp(bb)
-                .then_with(|| self.rows[*ai].title.cmp(&self.rows[*bi].title))
-        });
+        // 2) Sort by (section, then title) for stable grouping
+        filtered.sort_by_cached_key(|(idx, key)| (key.order_key(), self.rows[*idx].title.as_str()));
 
-        // 3) Group contiguous buckets
-        let mut groups: Vec<(i64, Vec<usize>)> = Vec::new();
-        let mut cur_key: Option<i64> = None;
-        for (idx, bucket) in filtered {
-            if cur_key != Some(bucket) {
-                groups.push((bucket, Vec::new()));
-                cur_key = Some(bucket);
+        // 3) Group contiguous sections
+        let mut groups: Vec<(GroupKey, Vec<usize>)> = Vec::new();
+        for (idx, key) in filtered {
+            if groups.last().is_none_or(|(cur, _)| *cur != key) {
+                groups.push((key, Vec::new()));
             }
             if let Some((_, v)) = groups.last_mut() {
                 v.push(idx);
             }
         }
 
-        // 4) Intra-day sorting based on current SortKey (+ optional desc)
-        for (_bucket, idxs) in groups.iter_mut() {
+        // 4) Intra-section sorting based on current SortKey (+ optional desc)
+        for (_key, idxs) in groups.iter_mut() {
             self.sort_intra_day(idxs);
-            if self.sort_desc {
-                idxs.reverse();
-            }
+            self.rank_by_search_score(idxs);
         }
 
         groups
@@ -153,46 +134,228 @@ impl crate::app::PexApp {
         decades.into_iter().collect()
     }
 
-    /// Sort a day's indices according to the current SortKey.
-    fn sort_intra_day(&self, idxs: &mut [usize]) {
-        match self.sort_key {
+    /// Earliest and latest release year in the guide, for the year range control.
+    pub(crate) fn available_year_bounds(&self) -> Option<(i32, i32)> {
+        let mut years = self.rows.iter().filter_map(|row| row.year);
+        let first = years.next()?;
+        Some(years.fold((first, first), |(lo, hi), y| (lo.min(y), hi.max(y))))
+    }
+
+    /// 0–10 score used for rating sort: the fetched rating, else the mean of Plex's
+    /// critic/audience ratings.
+    pub(crate) fn row_rating_score(&self, row: &PosterRow) -> Option<f32> {
+        if let Some(score) = self.rating_states.get(&row.key).and_then(|s| s.score()) {
+            return Some(score);
+        }
+        match (row.critic_rating, row.audience_rating) {
+            (Some(c), Some(a)) => Some((c + a) / 2.0),
+            (c, a) => c.or(a),
+        }
+    }
+
+    /// When the owned copy of an airing's film was recorded / added (file mtime); `None` if
+    /// it isn't owned or the date is unknown.
+    fn row_owned_date(row: &PosterRow) -> Option<u64> {
+        row.owned.then_some(row.owned_modified).flatten()
+    }
+
+    /// Free text must hit the title (any field with "All fields"); each `field:value` term must
+    /// hit its field. Fuzzy mode applies to titles, other fields match substrings.
+    pub(crate) fn row_matches_search(&self, query: &SearchQuery, row: &PosterRow) -> bool {
+        let title_hit = |text: &str, title: &str| {
+            if self.fuzzy_search {
+                search::fuzzy_score(text, title).is_some()
+            } else {
+                search::plain_match(text, title)
+            }
+        };
+        let field_hit = |field: SearchField, text: &str| match field {
+            SearchField::Title => title_hit(text, &row.title),
+            _ => field
+                .values(row)
+                .into_iter()
+                .any(|v| search::plain_match(text, v)),
+        };
+        let free_hit = query.free.is_empty()
+            || title_hit(&query.free, &row.title)
+            || (self.search_all_fields
+                && SearchField::ALL
+                    .into_iter()
+                    .filter(|&f| f != SearchField::Title)
+                    .any(|f| field_hit(f, &query.free)));
+        free_hit
+            && query
+                .fields
+                .iter()
+                .all(|(field, text)| field_hit(*field, text))
+    }
+
+    /// In fuzzy mode, order by how well the free text matches the title; the sort key only
+    /// breaks ties.
+    pub(crate) fn rank_by_search_score(&self, idxs: &mut [usize]) {
+        let query = SearchQuery::parse(&self.search_query);
+        if !self.fuzzy_search || query.free.is_empty() || self.regex_search.active().is_some() {
+            return;
+        }
+        idxs.sort_by_cached_key(|&i| {
+            std::cmp::Reverse(search::fuzzy_score(&query.free, &self.rows[i].title).unwrap_or(0))
+        });
+    }
+
+    /// Sort a section's indices by the current SortKey ("Desc" flips it), then the secondary
+    /// key (always ascending), then title, so equal airings keep a stable order.
+    pub(crate) fn sort_intra_day(&self, idxs: &mut [usize]) {
+        idxs.sort_by(|&a, &b| {
+            self.cmp_by_key(self.sort_key, a, b, self.sort_desc)
+                .then_with(|| {
+                    self.sort_key2
+                        .map_or(Ordering::Equal, |key| self.cmp_by_key(key, a, b, false))
+                })
+                .then_with(|| self.rows[a].title.cmp(&self.rows[b].title))
+        });
+    }
+
+    /// Order of two rows under one sort key. Airings without a rating / owned date sort last
+    /// whichever the direction.
+    fn cmp_by_key(&self, key: SortKey, a: usize, b: usize, desc: bool) -> Ordering {
+        let (ra, rb) = (&self.rows[a], &self.rows[b]);
+        let flip = |ord: Ordering| if desc { ord.reverse() } else { ord };
+        match key {
             SortKey::Time => {
-                idxs.sort_by_key(|&i| {
-                    self.rows[i]
-                        .airing
-                        .map(|ts| {
-                            ts.duration_since(SystemTime::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_secs()
-                        })
-                        .unwrap_or(u64::MAX)
-                });
+                let secs = |row: &PosterRow| {
+                    row.airing.map_or(u64::MAX, |ts| {
+                        ts.duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs()
+                    })
+                };
+                flip(secs(ra).cmp(&secs(rb)))
             }
-            SortKey::Title => idxs.sort_by(|&a, &b| self.rows[a].title.cmp(&self.rows[b].title)),
+            SortKey::Title => flip(ra.title.cmp(&rb.title)),
             SortKey::Channel => {
-                idxs.sort_by(|&a, &b| {
-                    let ca = self.rows[a].channel.as_deref().unwrap_or("");
-                    let cb = self.rows[b].channel.as_deref().unwrap_or("");
-                    ca.cmp(cb)
-                        .then_with(|| self.rows[a].title.cmp(&self.rows[b].title))
-                });
+                fn channel(row: &PosterRow) -> &str {
+                    row.channel.as_deref().unwrap_or("")
+                }
+                flip(channel(ra).cmp(channel(rb)))
             }
             SortKey::Genre => {
-                idxs.sort_by(|&a, &b| {
-                    let ga = self.rows[a]
-                        .genres
-                        .first()
-                        .map(|s| s.as_str())
-                        .unwrap_or("");
-                    let gb = self.rows[b]
-                        .genres
-                        .first()
-                        .map(|s| s.as_str())
-                        .unwrap_or("");
-                    ga.cmp(gb)
-                        .then_with(|| self.rows[a].title.cmp(&self.rows[b].title))
-                });
+                fn genre(row: &PosterRow) -> &str {
+                    row.genres.first().map_or("", String::as_str)
+                }
+                flip(genre(ra).cmp(genre(rb)))
             }
+            SortKey::Rating => cmp_present_first(
+                self.row_rating_score(ra),
+                self.row_rating_score(rb),
+                desc,
+                |x, y| y.total_cmp(&x),
+            ),
+            SortKey::OwnedDate => cmp_present_first(
+                Self::row_owned_date(ra),
+                Self::row_owned_date(rb),
+                desc,
+                |x, y| x.cmp(&y),
+            ),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::clock::{Clock, FixedClock};
+    use std::time::Duration;
+
+    const DAY: u64 = 86_400;
+    // 2024-03-10 13:30:00 UTC
+    const NOW: u64 = 1_710_077_400;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn day_window_includes_start_of_today_and_excludes_yesterday() {
+        let clock = FixedClock::at_unix(NOW);
+        let today_start = NOW - NOW % DAY;
+        let now = clock.now();
+
+        assert_eq!(
+            day_window_bucket(at(today_start), now, DayRange::Two),
+            Some((today_start / DAY) as i64)
+        );
+        assert_eq!(
+            day_window_bucket(at(today_start - 1), now, DayRange::Two),
+            None
+        );
+    }
+
+    #[test]
+    fn day_window_end_is_exclusive() {
+        let clock = FixedClock::at_unix(NOW);
+        let today_start = NOW - NOW % DAY;
+        let now = clock.now();
+
+        let last_second_of_window = today_start + 2 * DAY - 1;
+        assert!(day_window_bucket(at(last_second_of_window), now, DayRange::Two).is_some());
+        assert_eq!(
+            day_window_bucket(at(today_start + 2 * DAY), now, DayRange::Two),
+            None
+        );
+        assert!(day_window_bucket(at(today_start + 13 * DAY), now, DayRange::Fourteen).is_some());
+        assert_eq!(
+            day_window_bucket(at(today_start + 14 * DAY), now, DayRange::Fourteen),
+            None
+        );
+    }
+
+    #[test]
+    fn day_window_follows_the_clock_across_midnight() {
+        let clock = FixedClock::at_unix(NOW);
+        let today_start = NOW - NOW % DAY;
+        let airing = at(today_start + 3600);
+
+        assert!(day_window_bucket(airing, clock.now(), DayRange::Two).is_some());
+        clock.advance(Duration::from_secs(DAY));
+        assert_eq!(day_window_bucket(airing, clock.now(), DayRange::Two), None);
+    }
+
+    #[test]
+    fn rating_and_owned_date_sorts_put_missing_values_last() {
+        let best_first = |x: f32, y: f32| y.total_cmp(&x);
+        let mut scores = vec![None, Some(6.1), Some(8.4), None, Some(7.0)];
+        scores.sort_by(|a, b| cmp_present_first(*a, *b, false, best_first));
+        assert_eq!(scores, vec![Some(8.4), Some(7.0), Some(6.1), None, None]);
+        scores.sort_by(|a, b| cmp_present_first(*a, *b, true, best_first));
+        assert_eq!(scores, vec![Some(6.1), Some(7.0), Some(8.4), None, None]);
+
+        let mut dates = vec![None, Some(1_700_000_000), Some(1_500_000_000)];
+        dates.sort_by(|a, b| cmp_present_first(*a, *b, false, |x: u64, y| x.cmp(&y)));
+        assert_eq!(dates, vec![Some(1_500_000_000), Some(1_700_000_000), None]);
+    }
+
+    #[test]
+    fn year_range_is_inclusive_and_drops_unknown_years() {
+        assert!(year_in_range(None, None, None));
+        assert!(!year_in_range(None, Some(1965), Some(1979)));
+        assert!(year_in_range(Some(1965), Some(1965), Some(1979)));
+        assert!(year_in_range(Some(1979), Some(1965), Some(1979)));
+        assert!(!year_in_range(Some(1980), Some(1965), Some(1979)));
+        assert!(year_in_range(Some(2024), Some(2000), None));
+        assert!(!year_in_range(Some(1999), Some(2000), None));
+    }
+
+    #[test]
+    fn channel_sections_sort_by_name_with_unknown_last() {
+        let mut keys = [
+            GroupKey::Channel(String::new()),
+            GroupKey::Channel("film4".into()),
+            GroupKey::Channel("BBC Two".into()),
+        ];
+        keys.sort_by_key(GroupKey::order_key);
+        let labels: Vec<_> = keys.iter().filter_map(GroupKey::label).collect();
+        assert_eq!(labels, ["BBC Two", "film4", "Unknown channel"]);
+        assert_eq!(GroupKey::All.label(), None);
+        assert!(GroupKey::Day(2).order_key() > GroupKey::Day(1).order_key());
+    }
+}
@@ -0,0 +1,391 @@
+// src/app/gamepad.rs
+// Each platform's reader only uses its own decoder; the others are exercised by the tests.
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+use eframe::egui as eg;
+
+use crate::app::keymap::Action;
+use crate::app::NavDirection;
+
+/// Stick or trigger travel (of ±32767) that counts as a press.
+const AXIS_THRESHOLD: i16 = 16_000;
+/// How often to look for a pad again after none was found or one was unplugged.
+const RESCAN_INTERVAL: Duration = Duration::from_secs(3);
+/// XInput has no events, so the pad state is polled this often.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+const JS_EVENT_BUTTON: u8 = 0x01;
+const JS_EVENT_AXIS: u8 = 0x02;
+/// Set on the synthetic events describing the pad's state when the device is opened.
+const JS_EVENT_INIT: u8 = 0x80;
+
+/// One `struct js_event` from the Linux joystick API (`/dev/input/js*`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PadInput {
+    Button { number: u8, pressed: bool },
+    Axis { number: u8, value: i16 },
+}
+
+fn parse_js_event(buf: [u8; 8]) -> Option<PadInput> {
+    let value = i16::from_le_bytes([buf[4], buf[5]]);
+    let (kind, number) = (buf[6], buf[7]);
+    // Only real presses should move anything, not the state reported on open.
+    if kind & JS_EVENT_INIT != 0 {
+        return None;
+    }
+    match kind {
+        JS_EVENT_BUTTON => Some(PadInput::Button {
+            number,
+            pressed: value != 0,
+        }),
+        JS_EVENT_AXIS => Some(PadInput::Axis { number, value }),
+        _ => None,
+    }
+}
+
+/// Windows XInput pad states, turned into the inputs `PadMapper` reads.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+mod xinput {
+    use super::PadInput;
+
+    /// `XINPUT_GAMEPAD` (Windows): button bits, triggers 0-255, sticks ±32767 with Y pointing up.
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub(super) struct XInputGamepad {
+        pub buttons: u16,
+        pub left_trigger: u8,
+        pub right_trigger: u8,
+        pub thumb_lx: i16,
+        pub thumb_ly: i16,
+        pub thumb_rx: i16,
+        pub thumb_ry: i16,
+    }
+
+    pub(super) const XINPUT_DPAD_UP: u16 = 0x0001;
+    pub(super) const XINPUT_DPAD_DOWN: u16 = 0x0002;
+    pub(super) const XINPUT_DPAD_LEFT: u16 = 0x0004;
+    pub(super) const XINPUT_DPAD_RIGHT: u16 = 0x0008;
+    /// XInput button bits and the xpad button numbers `PadMapper` knows them by.
+    const XINPUT_BUTTONS: [(u16, u8); 7] = [
+        (0x1000, 0), // A
+        (0x2000, 1), // B
+        (0x4000, 2), // X
+        (0x8000, 3), // Y
+        (0x0100, 4), // left bumper
+        (0x0200, 5), // right bumper
+        (0x0020, 6), // Back
+    ];
+
+    fn dpad_axis(buttons: u16, negative: u16, positive: u16) -> i16 {
+        match (buttons & negative != 0, buttons & positive != 0) {
+            (true, false) => -32_767,
+            (false, true) => 32_767,
+            _ => 0,
+        }
+    }
+
+    /// 0-255 onto the joystick API's -32767 (released) to 32767 (pulled).
+    pub(super) fn trigger_axis(value: u8) -> i16 {
+        (i32::from(value) * 65_534 / 255 - 32_767) as i16
+    }
+
+    /// The axes of an XInput state, numbered and oriented like the xpad driver's.
+    fn xinput_axes(pad: &XInputGamepad) -> [(u8, i16); 6] {
+        [
+            (0, pad.thumb_lx),
+            // XInput's stick Y points up, the joystick API's down.
+            (1, pad.thumb_ly.saturating_neg()),
+            (2, trigger_axis(pad.left_trigger)),
+            (5, trigger_axis(pad.right_trigger)),
+            (
+                6,
+                dpad_axis(pad.buttons, XINPUT_DPAD_LEFT, XINPUT_DPAD_RIGHT),
+            ),
+            (7, dpad_axis(pad.buttons, XINPUT_DPAD_UP, XINPUT_DPAD_DOWN)),
+        ]
+    }
+
+    /// What changed between two polled XInput states, as the inputs the Linux joystick API would
+    /// have reported, so both platforms share `PadMapper`.
+    pub(super) fn xinput_changes(old: &XInputGamepad, new: &XInputGamepad) -> Vec<PadInput> {
+        let mut inputs = Vec::new();
+        for (mask, number) in XINPUT_BUTTONS {
+            if (old.buttons ^ new.buttons) & mask != 0 {
+                inputs.push(PadInput::Button {
+                    number,
+                    pressed: new.buttons & mask != 0,
+                });
+            }
+        }
+        for ((number, before), (_, value)) in xinput_axes(old).into_iter().zip(xinput_axes(new)) {
+            if before != value {
+                inputs.push(PadInput::Axis { number, value });
+            }
+        }
+        inputs
+    }
+}
+
+/// Xbox-style layout as the Linux xpad driver numbers it: D-pad or left stick move the
+/// selection, A opens the detail panel, B (or Back) backs out, bumpers and triggers change day.
+#[derive(Default)]
+struct PadMapper {
+    /// Last direction (-1, 0, 1) of each axis, so a held stick fires once.
+    zones: [i8; 8],
+}
+
+impl PadMapper {
+    fn feed(&mut self, input: PadInput) -> Option<Action> {
+        match input {
+            PadInput::Button { pressed: false, .. } => None,
+            PadInput::Button { number, .. } => match number {
+                0 => Some(Action::OpenDetail),
+                1 | 6 => Some(Action::ClearSelection),
+                4 => Some(Action::PrevDay),
+                5 => Some(Action::NextDay),
+                _ => None,
+            },
+            PadInput::Axis { number, value } => {
+                let zone = if value > AXIS_THRESHOLD {
+                    1
+                } else if value < -AXIS_THRESHOLD {
+                    -1
+                } else {
+                    0
+                };
+                let last = self.zones.get_mut(usize::from(number))?;
+                if *last == zone {
+                    return None;
+                }
+                *last = zone;
+                match (number, zone) {
+                    (0 | 6, -1) => Some(Action::NavLeft),
+                    (0 | 6, 1) => Some(Action::NavRight),
+                    (1 | 7, -1) => Some(Action::NavUp),
+                    (1 | 7, 1) => Some(Action::NavDown),
+                    // Triggers rest at -32767 and count once pulled past half way.
+                    (2, 1) => Some(Action::PrevDay),
+                    (5, 1) => Some(Action::NextDay),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Read the first `/dev/input/js*` pad on a background thread, reopening it after an unplug.
+#[cfg(target_os = "linux")]
+fn spawn_reader(tx: Sender<Action>) {
+    use std::io::Read;
+
+    let spawned = std::thread::Builder::new()
+        .name("gamepad".into())
+        .spawn(move || loop {
+            let device = (0..4).find_map(|n| std::fs::File::open(format!("/dev/input/js{n}")).ok());
+            if let Some(mut device) = device {
+                tracing::info!("gamepad connected");
+                let mut mapper = PadMapper::default();
+                let mut buf = [0u8; 8];
+                while device.read_exact(&mut buf).is_ok() {
+                    let Some(action) = parse_js_event(buf).and_then(|input| mapper.feed(input))
+                    else {
+                        continue;
+                    };
+                    if tx.send(action).is_err() {
+                        return;
+                    }
+                }
+            }
+            std::thread::sleep(RESCAN_INTERVAL);
+        });
+    if let Err(err) = spawned {
+        tracing::warn!("gamepad reader failed to start: {err}");
+    }
+}
+
+/// Poll the first connected XInput pad on a background thread, picking one up again after an
+/// unplug.
+#[cfg(target_os = "windows")]
+fn spawn_reader(tx: Sender<Action>) {
+    use self::xinput::{xinput_changes, XInputGamepad};
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct XInputState {
+        _packet_number: u32,
+        gamepad: XInputGamepad,
+    }
+
+    #[link(name = "xinput")]
+    extern "system" {
+        fn XInputGetState(user_index: u32, state: *mut XInputState) -> u32;
+    }
+
+    fn read_pad(user_index: u32) -> Option<XInputGamepad> {
+        let mut state = XInputState::default();
+        // SAFETY: `state` is a live, correctly laid out XINPUT_STATE for the call to fill in.
+        let status = unsafe { XInputGetState(user_index, &mut state) };
+        (status == 0).then_some(state.gamepad)
+    }
+
+    let spawned = std::thread::Builder::new()
+        .name("gamepad".into())
+        .spawn(move || loop {
+            // Whatever is held when the pad is found is its starting state, not a press.
+            if let Some((user_index, mut last)) = (0..4).find_map(|n| Some((n, read_pad(n)?))) {
+                tracing::info!("gamepad connected");
+                let mut mapper = PadMapper::default();
+                while let Some(pad) = read_pad(user_index) {
+                    for input in xinput_changes(&last, &pad) {
+                        if let Some(action) = mapper.feed(input) {
+                            if tx.send(action).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    last = pad;
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+            std::thread::sleep(RESCAN_INTERVAL);
+        });
+    if let Err(err) = spawned {
+        tracing::warn!("gamepad reader failed to start: {err}");
+    }
+}
+
+/// Only the Linux joystick API and XInput are read; other platforms get no pad input.
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn spawn_reader(_tx: Sender<Action>) {}
+
+/// Gamepad / remote navigation for 10-foot use, on top of the keyboard navigation.
+pub(crate) struct Gamepad {
+    pub enabled: bool,
+    rx: Option<Receiver<Action>>,
+}
+
+impl Default for Gamepad {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rx: None,
+        }
+    }
+}
+
+impl crate::app::PexApp {
+    /// Turn pad presses into grid navigation. Runs before the detail panel so A can move
+    /// focus into it in the same frame.
+    pub(crate) fn poll_gamepad(&mut self, ctx: &eg::Context) {
+        if !self.gamepad.enabled {
+            return;
+        }
+        let rx = self.gamepad.rx.get_or_insert_with(|| {
+            let (tx, rx) = mpsc::channel();
+            spawn_reader(tx);
+            rx
+        });
+        let actions: Vec<Action> = rx.try_iter().collect();
+        for action in actions {
+            let direction = match action {
+                Action::NavUp => NavDirection::Up,
+                Action::NavDown => NavDirection::Down,
+                Action::NavLeft => NavDirection::Left,
+                Action::NavRight => NavDirection::Right,
+                Action::PrevDay => NavDirection::PrevDay,
+                Action::NextDay => NavDirection::NextDay,
+                Action::OpenDetail => {
+                    if self.selected_idx.is_none() {
+                        self.selected_idx = self.grid_rows.first().and_then(|r| r.first()).copied();
+                        self.scroll_to_idx = self.selected_idx;
+                    }
+                    self.detail_focus_pending = self.selected_idx.is_some();
+                    continue;
+                }
+                Action::ClearSelection => {
+                    // Out of the detail panel first, then popups, then the selection.
+                    if let Some(id) = ctx.memory(|mem| mem.focused()) {
+                        ctx.memory_mut(|mem| mem.surrender_focus(id));
+                    } else if !self.close_popups() {
+                        self.selected_idx = None;
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            self.pad_nav = Some(direction);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xinput::{trigger_axis, xinput_changes, XInputGamepad, XINPUT_DPAD_DOWN};
+    use super::*;
+
+    fn event(kind: u8, number: u8, value: i16) -> [u8; 8] {
+        let [lo, hi] = value.to_le_bytes();
+        [0, 0, 0, 0, lo, hi, kind, number]
+    }
+
+    #[test]
+    fn pad_events_map_to_navigation_once_per_press() {
+        assert_eq!(
+            parse_js_event(event(JS_EVENT_BUTTON | JS_EVENT_INIT, 0, 0)),
+            None
+        );
+        let mut mapper = PadMapper::default();
+        let mut feed = |kind, number, value| {
+            parse_js_event(event(kind, number, value)).and_then(|input| mapper.feed(input))
+        };
+        assert_eq!(feed(JS_EVENT_BUTTON, 0, 1), Some(Action::OpenDetail));
+        assert_eq!(feed(JS_EVENT_BUTTON, 0, 0), None);
+        assert_eq!(feed(JS_EVENT_AXIS, 7, 32_767), Some(Action::NavDown));
+        assert_eq!(feed(JS_EVENT_AXIS, 7, 32_000), None);
+        assert_eq!(feed(JS_EVENT_AXIS, 7, 0), None);
+        assert_eq!(feed(JS_EVENT_AXIS, 0, -20_000), Some(Action::NavLeft));
+        assert_eq!(feed(JS_EVENT_AXIS, 5, -32_767), None);
+        assert_eq!(feed(JS_EVENT_AXIS, 5, 30_000), Some(Action::NextDay));
+        assert_eq!(feed(JS_EVENT_AXIS, 9, 30_000), None);
+    }
+
+    #[test]
+    fn xinput_states_map_like_the_linux_driver() {
+        let mut mapper = PadMapper::default();
+        let mut last = XInputGamepad::default();
+        let mut step = |pad: XInputGamepad| {
+            let actions: Vec<Action> = xinput_changes(&last, &pad)
+                .into_iter()
+                .filter_map(|input| mapper.feed(input))
+                .collect();
+            last = pad;
+            actions
+        };
+        let a = XInputGamepad {
+            buttons: 0x1000,
+            ..Default::default()
+        };
+        assert_eq!(step(a), vec![Action::OpenDetail]);
+        assert_eq!(step(a), vec![]);
+        let down = XInputGamepad {
+            buttons: XINPUT_DPAD_DOWN,
+            ..Default::default()
+        };
+        assert_eq!(step(down), vec![Action::NavDown]);
+        let stick_up = XInputGamepad {
+            thumb_ly: 30_000,
+            ..Default::default()
+        };
+        assert_eq!(step(stick_up), vec![Action::NavUp]);
+        let right_trigger = XInputGamepad {
+            right_trigger: 255,
+            ..Default::default()
+        };
+        assert_eq!(step(right_trigger), vec![Action::NextDay]);
+        assert_eq!(trigger_axis(0), -32_767);
+        assert_eq!(trigger_axis(255), 32_767);
+    }
+}
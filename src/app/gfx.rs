@@ -20,5 +20,6 @@ pub fn load_texture_from_path(
     if !(0.55..=0.80).contains(&ar) {
         return Err(format!("non-poster aspect {w}x{h} ar={ar:.2}"));
     }
+    crate::app::cache::note_swatch_from_rgba(cache_name, w, h, &bytes);
     Ok(upload_rgba(ctx, w, h, &bytes, cache_name))
 }
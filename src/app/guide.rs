@@ -0,0 +1,121 @@
+// src/app/guide.rs
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+use std::time::SystemTime;
+
+use crate::app::blocklist::TitleBlocklist;
+use crate::app::clock::SystemClock;
+use crate::app::filters::day_window_bucket;
+use crate::app::owned::overrides::OwnedOverrides;
+use crate::app::owned::{flag_owned_rows, OwnedDetails};
+use crate::app::types::{DayRange, GroupKey, OwnedMsg, PrepMsg};
+use crate::app::{PexApp, PosterRow};
+
+/// Owned keys and their library timestamps, as a finished scan reports them.
+pub(crate) type OwnedLibrary = (HashSet<String>, HashMap<String, Option<u64>>);
+
+/// The guide for a day range as plain data, for `--headless` and `--serve`: rows with the
+/// owned, REC and first-showing flags set, and the owned copies' details. No window, prefs or
+/// filters are involved.
+pub(crate) struct Guide {
+    pub rows: Vec<PosterRow>,
+    pub owned: OwnedDetails,
+    range: DayRange,
+    now: SystemTime,
+}
+
+impl Guide {
+    /// Run the guide prep (including the daily DB copies) on this thread, then the owned scan
+    /// when the library DB copy is available.
+    pub fn load(range: DayRange) -> Result<Self, String> {
+        let now = SystemTime::now();
+        let mut rows = load_rows(range, now)?;
+        match crate::app::scheduled::load_scheduled_index(&SystemClock) {
+            Ok(index) => index.flag_rows(&mut rows),
+            Err(err) => eprintln!("Failed to load scheduled recordings: {err}"),
+        }
+        crate::app::premieres::flag_first_showings(&mut rows, now);
+        let mut owned = OwnedDetails::default();
+        if crate::config::local_library_db_path().exists() {
+            match scan_owned() {
+                Ok((keys, modified)) => {
+                    flag_owned_rows(&mut rows, &keys, Some(&modified), &OwnedOverrides::load());
+                    owned = OwnedDetails::load();
+                }
+                Err(err) => eprintln!("{err}; continuing without owned flags."),
+            }
+        } else {
+            eprintln!("No Plex library DB copy; continuing without owned flags.");
+        }
+        Ok(Self {
+            rows,
+            owned,
+            range,
+            now,
+        })
+    }
+
+    /// Rows in the day range by airing day, in airing order, as the grid lists them with the
+    /// default filters and sort.
+    pub fn days(&self) -> Vec<(GroupKey, Vec<&PosterRow>)> {
+        let mut dated: Vec<(i64, &PosterRow)> = self
+            .rows
+            .iter()
+            .filter_map(|row| Some((day_window_bucket(row.airing?, self.now, self.range)?, row)))
+            .collect();
+        dated.sort_by(|(a, ra), (b, rb)| {
+            a.cmp(b)
+                .then(ra.airing.cmp(&rb.airing))
+                .then_with(|| ra.title.cmp(&rb.title))
+        });
+        let mut days: Vec<(GroupKey, Vec<&PosterRow>)> = Vec::new();
+        for (bucket, row) in dated {
+            let key = GroupKey::Day(bucket);
+            match days.last_mut() {
+                Some((last, rows)) if *last == key => rows.push(row),
+                _ => days.push((key, vec![row])),
+            }
+        }
+        days
+    }
+}
+
+/// Prepared guide items as rows, with blocklisted titles left out.
+fn load_rows(range: DayRange, now: SystemTime) -> Result<Vec<PosterRow>, String> {
+    let (tx, rx) = mpsc::channel();
+    let window = crate::app::prep::airing_window(range, now);
+    crate::app::prep::spawn_poster_prep(tx, window);
+    let genre_aliases = crate::config::load_config().genre_aliases;
+    let mut items = Vec::new();
+    for msg in rx {
+        match msg {
+            PrepMsg::Info(s) => eprintln!("{s}"),
+            PrepMsg::Batch(batch) => items.extend(batch),
+            PrepMsg::Repeats(_) => {}
+            PrepMsg::Done(rest) => {
+                items.extend(rest);
+                TitleBlocklist::load().strip(&mut items);
+                return Ok(items
+                    .into_iter()
+                    .map(|item| PexApp::row_from_prep(item, &genre_aliases))
+                    .collect());
+            }
+            PrepMsg::Error(e) => return Err(format!("guide prep failed: {e}")),
+        }
+    }
+    Err("guide prep stopped before finishing".into())
+}
+
+/// Run the owned scan to completion, refreshing the sidecars in the cache folder.
+pub(crate) fn scan_owned() -> Result<OwnedLibrary, String> {
+    let (tx, rx) = mpsc::channel();
+    crate::app::owned::spawn_owned_scan(tx);
+    for msg in rx {
+        match msg {
+            OwnedMsg::Info(s) => eprintln!("{s}"),
+            OwnedMsg::Done { keys, modified } => return Ok((keys, modified)),
+            OwnedMsg::Error(e) => return Err(format!("owned scan failed: {e}")),
+        }
+    }
+    Err("owned scan stopped before finishing".into())
+}
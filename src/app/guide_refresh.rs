@@ -0,0 +1,364 @@
+// src/app/guide_refresh.rs
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use eframe::egui as eg;
+use tracing::{info, warn};
+
+use crate::app::{DayRange, PosterRow, PrepItem, PrepMsg};
+use crate::config::AppConfig;
+
+pub(crate) const DEFAULT_GUIDE_REFRESH_MINUTES: u32 = 60;
+
+/// Re-reads the EPG every `guide_refresh_minutes` while Pex runs and merges the result into the
+/// grid in place, so a window left open for days keeps showing what is actually airing.
+pub(crate) struct GuideRefresh {
+    /// None when the refresh is turned off (`guide_refresh_minutes = 0`).
+    every: Option<Duration>,
+    last: Instant,
+    rx: Option<Receiver<PrepMsg>>,
+    /// Rows streamed in so far by the running re-read.
+    partial: Vec<PrepItem>,
+    /// Started from the top-bar button: report "no changes" too.
+    manual: bool,
+    /// Day range the loaded guide was queried for; a wider range needs a re-query.
+    pub covers: Option<DayRange>,
+}
+
+impl Default for GuideRefresh {
+    fn default() -> Self {
+        Self {
+            every: Some(minutes(DEFAULT_GUIDE_REFRESH_MINUTES)),
+            last: Instant::now(),
+            rx: None,
+            partial: Vec::new(),
+            manual: false,
+            covers: None,
+        }
+    }
+}
+
+const fn days_shown(range: DayRange) -> i64 {
+    match range.max_bucket(0) {
+        Some(days) => days,
+        None => i64::MAX,
+    }
+}
+
+const fn minutes(n: u32) -> Duration {
+    Duration::from_secs(n as u64 * 60)
+}
+
+impl GuideRefresh {
+    pub fn configure(&mut self, cfg: &AppConfig) {
+        let every = cfg
+            .guide_refresh_minutes
+            .unwrap_or(DEFAULT_GUIDE_REFRESH_MINUTES);
+        self.every = (every > 0).then(|| minutes(every));
+    }
+
+    /// Time left until the next timed refresh and the interval, if timed refreshes are on.
+    pub fn next_due(&self) -> Option<(Duration, Duration)> {
+        self.every
+            .map(|every| (every.saturating_sub(self.last.elapsed()), every))
+    }
+
+    /// Take over a re-read started elsewhere (the minimized-window ticker).
+    pub fn adopt(&mut self, rx: Receiver<PrepMsg>, covers: DayRange) {
+        self.last = Instant::now();
+        self.manual = false;
+        self.partial.clear();
+        self.covers = Some(covers);
+        self.rx = Some(rx);
+    }
+
+    pub fn in_flight(&self) -> bool {
+        self.rx.is_some()
+    }
+}
+
+/// For each fresh title, the index of the existing row it replaces (None for a new title).
+/// Titles are matched case-insensitively, the same way prep de-duplicates them.
+fn match_existing<'a>(
+    old: impl IntoIterator<Item = &'a str>,
+    fresh: impl IntoIterator<Item = &'a str>,
+) -> Vec<Option<usize>> {
+    let index: HashMap<String, usize> = old
+        .into_iter()
+        .enumerate()
+        .map(|(idx, title)| (title.to_ascii_lowercase(), idx))
+        .collect();
+    fresh
+        .into_iter()
+        .map(|title| index.get(&title.to_ascii_lowercase()).copied())
+        .collect()
+}
+
+impl PosterRow {
+    /// Take the listing from a freshly prepared row, keeping the loaded poster when the artwork
+    /// is the same. Returns true when anything the grid shows changed.
+    fn absorb_listing(&mut self, fresh: Self) -> bool {
+        let changed = self.airing != fresh.airing
+            || self.ends != fresh.ends
+            || self.channel_raw != fresh.channel_raw
+            || self.key != fresh.key;
+        if self.key != fresh.key {
+            self.key = fresh.key;
+            self.url = fresh.url;
+            self.path = fresh.path;
+            self.variant_w = fresh.variant_w;
+            self.tex = None;
+            self.state = fresh.state;
+        }
+        self.airing = fresh.airing;
+        self.ends = fresh.ends;
+        self.year = fresh.year;
+        self.channel = fresh.channel;
+        self.channel_raw = fresh.channel_raw;
+        self.channel_title = fresh.channel_title;
+        self.channel_thumb = fresh.channel_thumb;
+        self.genres = fresh.genres;
+        self.guid = fresh.guid;
+        self.summary = fresh.summary;
+        self.audience_rating = fresh.audience_rating;
+        self.critic_rating = fresh.critic_rating;
+        self.broadcast_hd = fresh.broadcast_hd;
+        self.premiere = fresh.premiere;
+        changed
+    }
+}
+
+/// Rows added, changed and dropped by one merge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct GuideDiff {
+    pub added: usize,
+    pub updated: usize,
+    pub gone: usize,
+}
+
+impl crate::app::PexApp {
+    /// Start a background re-read of the EPG once the refresh interval has passed.
+    pub(crate) fn poll_guide_refresh(&mut self, ctx: &eg::Context) {
+        if self.prep_rx.is_some() {
+            // A full reload is running; its result replaces whatever this would merge.
+            self.guide_refresh.rx = None;
+            return;
+        }
+        if self.guide_refresh.in_flight() {
+            self.drain_guide_refresh(ctx);
+            return;
+        }
+        let widened = self
+            .guide_refresh
+            .covers
+            .is_some_and(|covers| days_shown(self.current_range) > days_shown(covers));
+        if widened && self.can_refresh_guide() {
+            self.start_guide_refresh(false);
+            self.set_status(format!(
+                "Loading airings for the next {} days…",
+                self.current_range.as_str()
+            ));
+            return;
+        }
+        let Some(every) = self.guide_refresh.every else {
+            return;
+        };
+        if self.in_safe_mode() || self.guide_refresh.last.elapsed() < every {
+            return;
+        }
+        self.start_guide_refresh(false);
+    }
+
+    pub(crate) fn can_refresh_guide(&self) -> bool {
+        !self.demo_mode
+            && self.boot_phase == crate::app::BootPhase::Ready
+            && self.prep_rx.is_none()
+            && !self.guide_refresh.in_flight()
+    }
+
+    /// Re-read the EPG in the background and merge it into the grid when it arrives.
+    pub(crate) fn start_guide_refresh(&mut self, manual: bool) {
+        if self.can_refresh_guide() {
+            self.spawn_guide_refresh(manual);
+        }
+    }
+
+    /// Start the re-read unconditionally (the snapshot start-up uses this before `Ready`).
+    pub(crate) fn spawn_guide_refresh(&mut self, manual: bool) {
+        self.guide_refresh.last = Instant::now();
+        self.guide_refresh.manual = manual;
+        self.guide_refresh.partial.clear();
+        self.guide_refresh.covers = Some(self.current_range);
+        if manual {
+            self.set_status("Refreshing the guide…");
+        }
+        info!("guide refresh: re-reading the EPG");
+        // Copy the source DB again if it changed since today's copy.
+        crate::app::prep::forget_epg_sync();
+        let window = crate::app::prep::airing_window(self.current_range, self.now());
+        let (tx, rx) = mpsc::channel();
+        self.guide_refresh.rx = Some(rx);
+        crate::app::prep::spawn_poster_prep(tx, window);
+    }
+
+    fn drain_guide_refresh(&mut self, ctx: &eg::Context) {
+        let Some(rx) = self.guide_refresh.rx.as_ref() else {
+            return;
+        };
+        let list = loop {
+            match rx.try_recv() {
+                Ok(PrepMsg::Info(_)) => continue,
+                Ok(PrepMsg::Batch(batch)) => self.guide_refresh.partial.extend(batch),
+                Ok(PrepMsg::Repeats(repeats)) => self.other_airings = repeats,
+                Ok(PrepMsg::Done(rest)) => {
+                    let mut list = std::mem::take(&mut self.guide_refresh.partial);
+                    list.extend(rest);
+                    break list;
+                }
+                Ok(PrepMsg::Error(err)) => {
+                    warn!("guide refresh failed: {err}");
+                    self.health.guide = crate::app::health::Health::Failed(err.clone());
+                    self.set_status(format!(
+                        "Guide refresh failed (keeping the current guide): {err}"
+                    ));
+                    self.guide_refresh.rx = None;
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.guide_refresh.rx = None;
+                    if self.guide_refresh.manual {
+                        self.set_status("Guide refresh stopped before finishing.");
+                    }
+                    return;
+                }
+            }
+        };
+        self.guide_refresh.rx = None;
+        let diff = self.merge_guide(list);
+        info!("guide refresh: {diff:?}");
+        if diff != GuideDiff::default() {
+            self.set_status(format!(
+                "Guide refreshed: {} new, {} updated, {} gone.",
+                diff.added, diff.updated, diff.gone
+            ));
+        } else if self.guide_refresh.manual {
+            self.set_status("Guide refreshed: no changes.");
+        }
+        ctx.request_repaint();
+    }
+
+    /// Top-bar "⟳ Guide" button: refresh now instead of waiting for the timer.
+    pub(crate) fn ui_render_guide_refresh_button(&mut self, ui: &mut eg::Ui) {
+        let label = if self.guide_refresh.in_flight() {
+            "⟳ Refreshing…"
+        } else {
+            "⟳ Guide"
+        };
+        let hint = if self.demo_mode {
+            "The sample guide does not change"
+        } else {
+            "Re-read the EPG now and merge new, moved and finished airings into the grid \
+             (keeps the selection, scroll position and loaded posters)"
+        };
+        if ui
+            .add_enabled(self.can_refresh_guide(), eg::Button::new(label).small())
+            .on_hover_text(hint)
+            .on_disabled_hover_text(hint)
+            .clicked()
+        {
+            self.start_guide_refresh(true);
+        }
+    }
+
+    /// Merge a freshly prepared guide into `rows` without moving any existing row, so the
+    /// selection, scroll position and loaded posters stay put. New titles are appended and
+    /// titles that left the guide lose their airing, which hides them from the grid.
+    pub(crate) fn merge_guide(&mut self, mut list: Vec<PrepItem>) -> GuideDiff {
+        self.title_blocklist.strip(&mut list);
+        let genre_aliases = crate::config::load_config().genre_aliases;
+        let fresh: Vec<PosterRow> = list
+            .into_iter()
+            .map(|item| Self::row_from_prep(item, &genre_aliases))
+            .collect();
+        let matches = match_existing(
+            self.rows.iter().map(|row| row.title.as_str()),
+            fresh.iter().map(|row| row.title.as_str()),
+        );
+
+        let mut diff = GuideDiff::default();
+        let mut kept = vec![false; self.rows.len()];
+        let mut downloads = Vec::new();
+        for (row, existing) in fresh.into_iter().zip(matches) {
+            match existing {
+                Some(idx) => {
+                    kept[idx] = true;
+                    let old = &mut self.rows[idx];
+                    let was_gone = old.airing.is_none();
+                    let new_poster = old.key != row.key;
+                    if old.absorb_listing(row) {
+                        if was_gone {
+                            diff.added += 1;
+                        } else {
+                            diff.updated += 1;
+                        }
+                    }
+                    if new_poster && old.path.is_none() {
+                        downloads.push(idx);
+                    }
+                }
+                None => {
+                    if row.path.is_none() {
+                        downloads.push(self.rows.len());
+                    }
+                    self.rows.push(row);
+                    diff.added += 1;
+                }
+            }
+        }
+        for (row, kept) in self.rows.iter_mut().zip(kept) {
+            if !kept && row.airing.is_some() {
+                row.airing = None;
+                row.ends = None;
+                diff.gone += 1;
+            }
+        }
+        let hidden = |idx: Option<usize>, rows: &[PosterRow]| {
+            idx.is_some_and(|idx| rows.get(idx).is_none_or(|row| row.airing.is_none()))
+        };
+        if hidden(self.selected_idx, &self.rows) {
+            self.selected_idx = None;
+        }
+        if hidden(self.compare_idx, &self.rows) {
+            self.compare_idx = None;
+        }
+
+        self.health.guide =
+            crate::app::health::Health::ok_or_stale(&crate::config::local_db_path(), self.now());
+        self.refresh_scheduled_index();
+        self.apply_premiere_flags();
+        self.apply_new_listing_flags();
+        self.apply_owned_flags();
+        self.apply_radarr_flags_from(0);
+        self.apply_trakt_flags_from(0);
+        self.apply_letterboxd_flags_from(0);
+        self.apply_imdb_flags_from(0);
+        self.evaluate_alerts();
+        self.queue_new_posters(&downloads);
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_titles_match_existing_rows_case_insensitively() {
+        let old = ["Alien", "Heat", "Arrival"];
+        let fresh = ["ALIEN", "Blade Runner", "arrival"];
+        assert_eq!(match_existing(old, fresh), vec![Some(0), None, Some(2)]);
+        assert_eq!(match_existing([], ["Heat"]), vec![None]);
+    }
+}
@@ -0,0 +1,158 @@
+// src/app/headless.rs
+use std::str::FromStr;
+
+use crate::app::export::{default_guide_export_path, ExportFormat};
+use crate::app::guide::{scan_owned, Guide};
+use crate::app::types::DayRange;
+
+const USAGE: &str = "\
+Usage:
+  pex --headless export [--format csv|json] [--days 2|4|5|7|14] [--out PATH]
+  pex --headless prune-cache
+  pex --headless owned-scan";
+
+/// A maintenance job run from the command line without opening a window.
+#[derive(Debug, PartialEq, Eq)]
+enum Job {
+    /// Write every airing in the day range (no UI filters) like Advanced ▸ Export.
+    Export {
+        format: ExportFormat,
+        days: DayRange,
+        out: Option<String>,
+    },
+    PruneCache,
+    OwnedScan,
+}
+
+/// Parse the arguments after `--headless` (a `--profile` flag may be mixed in).
+fn parse_job(args: &[String]) -> Result<Job, String> {
+    let args = crate::app::profiles::args_without_profile(args.iter().cloned());
+    let mut args = args.iter().map(String::as_str);
+    let job = match args.next() {
+        Some("export") => {
+            let mut format = ExportFormat::Json;
+            let mut days = DayRange::Seven;
+            let mut out = None;
+            while let Some(flag) = args.next() {
+                let mut value = || args.next().ok_or_else(|| format!("{flag} needs a value"));
+                match flag {
+                    "--format" => {
+                        let v = value()?;
+                        format = ExportFormat::from_str(v)
+                            .map_err(|()| format!("unknown format `{v}` (csv or json)"))?;
+                    }
+                    "--days" => {
+                        let v = value()?;
+                        days = DayRange::from_str(v)
+                            .map_err(|()| format!("--days must be 2, 4, 5, 7 or 14, not `{v}`"))?;
+                    }
+                    "--out" => out = Some(value()?.to_string()),
+                    other => return Err(format!("unknown export option `{other}`")),
+                }
+            }
+            return Ok(Job::Export { format, days, out });
+        }
+        Some("prune-cache") => Job::PruneCache,
+        Some("owned-scan") => Job::OwnedScan,
+        Some(other) => return Err(format!("unknown command `{other}`")),
+        None => return Err("no command given".into()),
+    };
+    match args.next() {
+        Some(extra) => Err(format!("unexpected argument `{extra}`")),
+        None => Ok(job),
+    }
+}
+
+/// Run `pex --headless …` and return the process exit code. Progress goes to stderr, the
+/// result line to stdout.
+pub fn run(args: &[String]) -> i32 {
+    let job = match parse_job(args) {
+        Ok(job) => job,
+        Err(err) => {
+            eprintln!("pex: {err}\n{USAGE}");
+            return 2;
+        }
+    };
+    let cfg = crate::config::load_config();
+    if !cfg.errors.is_empty() {
+        for err in &cfg.errors {
+            eprintln!("pex: {err}");
+        }
+        return 1;
+    }
+    let result = match job {
+        Job::Export { format, days, out } => export(format, days, out),
+        Job::PruneCache => prune_cache(),
+        Job::OwnedScan => owned_scan(),
+    };
+    match result {
+        Ok(summary) => {
+            println!("{summary}");
+            0
+        }
+        Err(err) => {
+            eprintln!("pex: {err}");
+            1
+        }
+    }
+}
+
+fn export(format: ExportFormat, days: DayRange, out: Option<String>) -> Result<String, String> {
+    let guide = Guide::load(days)?;
+    let dest = out.unwrap_or_else(|| default_guide_export_path(format));
+    let (path, count) = guide.export(&dest, format)?;
+    Ok(format!("Exported {count} airings to {}", path.display()))
+}
+
+fn prune_cache() -> Result<String, String> {
+    let old = crate::app::cache::prune_poster_cache_now()
+        .map_err(|err| format!("poster prune failed: {err}"))?;
+    let partial = crate::app::cache::refresh_poster_cache_light()
+        .map_err(|err| format!("poster cleanup failed: {err}"))?;
+    Ok(format!(
+        "Removed {old} expired posters and {partial} partial or invalid files from {}",
+        crate::app::cache::poster_cache_dir().display()
+    ))
+}
+
+fn owned_scan() -> Result<String, String> {
+    let copied = crate::app::prep::sync_library_db_from_source(true)?;
+    if !copied && !crate::config::local_library_db_path().exists() {
+        return Err("plex_library_db_source is not set and there is no library DB copy".into());
+    }
+    let (keys, _) = scan_owned()?;
+    Ok(format!(
+        "Owned scan complete: {} titles in {}",
+        keys.len(),
+        crate::app::cache::cache_dir().display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_jobs_and_rejects_bad_options() {
+        assert_eq!(
+            parse_job(&args(&["export", "--format", "csv", "--days", "14"])),
+            Ok(Job::Export {
+                format: ExportFormat::Csv,
+                days: DayRange::Fourteen,
+                out: None,
+            })
+        );
+        assert_eq!(
+            parse_job(&args(&["--profile", "Parents", "owned-scan"])),
+            Ok(Job::OwnedScan)
+        );
+        assert!(parse_job(&args(&["export", "--days", "3"])).is_err());
+        assert!(parse_job(&args(&["export", "--out"])).is_err());
+        assert!(parse_job(&args(&["prune-cache", "now"])).is_err());
+        assert!(parse_job(&[]).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+// src/app/health.rs
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+// Local DB copies older than this are flagged as stale.
+const STALE_AFTER: Duration = Duration::from_secs(48 * 60 * 60);
+
+/// Load state of one data source feeding the grid.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Health {
+    #[default]
+    Pending,
+    Loading,
+    Ok,
+    Stale(String),
+    Failed(String),
+}
+
+impl Health {
+    /// OK unless the DB copy at `db` is older than the stale window.
+    pub(crate) fn ok_or_stale(db: &Path, now: SystemTime) -> Self {
+        db_age_note(db, now).map_or(Self::Ok, Self::Stale)
+    }
+
+    pub const fn needs_attention(&self) -> bool {
+        matches!(self, Self::Stale(_) | Self::Failed(_))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subsystem {
+    Guide,
+    Owned,
+    Scheduled,
+}
+
+impl Subsystem {
+    pub const ALL: [Self; 3] = [Self::Guide, Self::Owned, Self::Scheduled];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Guide => "Guide",
+            Self::Owned => "Owned",
+            Self::Scheduled => "REC",
+        }
+    }
+
+    /// What the user loses while this subsystem is not healthy.
+    pub const fn impact(self) -> &'static str {
+        match self {
+            Self::Guide => "Airings come from the Plex EPG database; without it the grid is empty.",
+            Self::Owned => {
+                "Owned dimming, Hide owned and HD ↑ upgrade badges depend on the library scan."
+            }
+            Self::Scheduled => "REC badges come from the Plex library's DVR schedule.",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SubsystemHealth {
+    pub guide: Health,
+    pub owned: Health,
+    pub scheduled: Health,
+}
+
+impl SubsystemHealth {
+    pub fn get(&self, subsystem: Subsystem) -> &Health {
+        match subsystem {
+            Subsystem::Guide => &self.guide,
+            Subsystem::Owned => &self.owned,
+            Subsystem::Scheduled => &self.scheduled,
+        }
+    }
+}
+
+fn db_age_note(db: &Path, now: SystemTime) -> Option<String> {
+    let modified = fs::metadata(db).and_then(|m| m.modified()).ok()?;
+    let age = now.duration_since(modified).ok()?;
+    (age > STALE_AFTER).then(|| {
+        format!(
+            "{} was last refreshed {} hours ago.",
+            db.display(),
+            age.as_secs() / 3600
+        )
+    })
+}
+
+impl crate::app::PexApp {
+    /// Re-run whichever loader backs `subsystem`.
+    pub(crate) fn retry_subsystem(&mut self, subsystem: Subsystem, ctx: &eframe::egui::Context) {
+        match subsystem {
+            Subsystem::Guide => self.restart_poster_pipeline(ctx),
+            Subsystem::Owned => self.refresh_owned_scan(),
+            Subsystem::Scheduled => self.refresh_scheduled_index(),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// src/app/hover_preview.rs
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use eframe::egui as eg;
+
+use crate::app::cache;
+
+const PREVIEW_W: f32 = 300.0;
+const SUMMARY_CHARS: usize = 360;
+
+type Decoded = Result<(u32, u32, Vec<u8>), String>;
+
+/// Enlarged poster shown when a grid card is hovered (after egui's tooltip delay). The
+/// full-size image is cached under the poster's base key, without the `__s` / `__m` suffix of
+/// the card variants, and fetched only once a preview asks for it. One is kept at a time.
+pub(crate) struct HoverPreview {
+    pub enabled: bool,
+    /// Base cache key of the poster loaded (or loading).
+    key: Option<String>,
+    tex: Option<eg::TextureHandle>,
+    rx: Option<Receiver<Decoded>>,
+    /// Base keys whose full-size poster couldn't be loaded; they show the card texture.
+    failed: HashSet<String>,
+}
+
+impl Default for HoverPreview {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            key: None,
+            tex: None,
+            rx: None,
+            failed: HashSet::new(),
+        }
+    }
+}
+
+impl HoverPreview {
+    /// Start loading `base`'s full-size poster unless it's the one already shown.
+    fn request(&mut self, base: &str, url: &str) {
+        if self.key.as_deref() == Some(base) {
+            return;
+        }
+        self.key = Some(base.to_string());
+        self.tex = None;
+        self.rx = None;
+        if self.failed.contains(base) {
+            return;
+        }
+        let cached = cache::find_any_by_key(base);
+        if cached.is_none() && (url.is_empty() || crate::app::net::is_offline()) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let (base, url) = (base.to_string(), url.to_string());
+        std::thread::spawn(move || {
+            let decoded = cached
+                .map_or_else(|| cache::download_and_store(&url, &base), Ok)
+                .and_then(|path| cache::load_rgba_raw_or_image(&path.to_string_lossy()));
+            let _ = tx.send(decoded);
+        });
+        self.rx = Some(rx);
+    }
+
+    fn poll(&mut self, ctx: &eg::Context) {
+        let Some(rx) = &self.rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("preview loader stopped".into()),
+        };
+        self.rx = None;
+        let Some(key) = self.key.clone() else {
+            return;
+        };
+        match result {
+            Ok((w, h, rgba)) if w > 0 && h > 0 && !rgba.is_empty() => {
+                let name = format!("preview_{key}");
+                self.tex = Some(crate::app::gfx::upload_rgba(ctx, w, h, &rgba, &name));
+            }
+            Ok(_) => {
+                self.failed.insert(key);
+            }
+            Err(err) => {
+                tracing::debug!("full-size poster for {key} unavailable: {err}");
+                self.failed.insert(key);
+            }
+        }
+    }
+}
+
+impl crate::app::PexApp {
+    /// Tooltip body for a hovered card: the full-size poster (the card's own texture until it
+    /// loads), title, channel and time, and the start of the summary.
+    pub(crate) fn ui_render_hover_preview(&mut self, ui: &mut eg::Ui, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let base = cache::base_poster_key(&row.key).to_string();
+        self.hover_preview.request(&base, &row.url);
+        self.hover_preview.poll(ui.ctx());
+
+        ui.set_max_width(PREVIEW_W);
+        let tex = self.hover_preview.tex.as_ref().or(row.tex.as_ref());
+        if let Some(tex) = tex {
+            let [w, h] = tex.size_vec2().into();
+            let size = eg::vec2(PREVIEW_W, PREVIEW_W * h / w.max(1.0));
+            ui.add(eg::Image::new((tex.id(), size)).rounding(6.0));
+        }
+        if self.hover_preview.rx.is_some() {
+            ui.horizontal(|ui| {
+                ui.add(eg::Spinner::new().size(12.0));
+                ui.label(eg::RichText::new("Loading full-size poster…").weak());
+            });
+        }
+
+        let title = row
+            .year
+            .map_or_else(|| row.title.clone(), |y| format!("{} ({y})", row.title));
+        ui.label(eg::RichText::new(title).strong());
+        let channel = row
+            .channel
+            .as_deref()
+            .map(crate::app::utils::humanize_channel)
+            .unwrap_or_default();
+        let when = row
+            .airing
+            .map(|ts| {
+                format!(
+                    "{} {} UTC",
+                    crate::app::utils::format_day_label(crate::app::utils::day_bucket(ts)),
+                    crate::app::utils::hhmm_utc(ts)
+                )
+            })
+            .unwrap_or_default();
+        ui.label(eg::RichText::new(format!("{channel} · {when}")).weak());
+        if let Some(summary) = row.summary.as_deref() {
+            let mut text: String = summary.chars().take(SUMMARY_CHARS).collect();
+            if summary.chars().count() > SUMMARY_CHARS {
+                text.push('…');
+            }
+            ui.add(eg::Label::new(text).wrap());
+        }
+    }
+}
@@ -0,0 +1,369 @@
+// src/app/imdb_lists.rs
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui as eg;
+use tracing::warn;
+
+use crate::app::file_picker::{PendingPick, PickKind};
+use crate::app::{PexApp, PosterRow};
+
+const RATINGS_FILE: &str = "imdb_ratings.txt";
+const WATCHLIST_FILE: &str = "imdb_watchlist.txt";
+/// IMDb's yellow, for the personal-rating pill, the watchlist ribbon and the chips.
+pub(crate) const IMDB_COLOR: eg::Color32 = eg::Color32::from_rgb(245, 197, 24);
+
+/// One title of an IMDb export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ImdbEntry {
+    imdb_id: String,
+    title: String,
+    year: Option<i32>,
+    /// "Your Rating", 1-10.
+    rating: Option<u8>,
+}
+
+/// Which IMDb export a file is: `watchlist.csv` has a Position column, `ratings.csv` does not.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ImdbExport {
+    Ratings,
+    Watchlist,
+}
+
+impl ImdbExport {
+    const fn file(self) -> &'static str {
+        match self {
+            Self::Ratings => RATINGS_FILE,
+            Self::Watchlist => WATCHLIST_FILE,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Ratings => "ratings",
+            Self::Watchlist => "watchlist",
+        }
+    }
+}
+
+/// Titles of an IMDb `ratings.csv` or `watchlist.csv` export and which export it was.
+fn parse_export(text: &str) -> Result<(ImdbExport, Vec<ImdbEntry>), String> {
+    let mut records = crate::app::utils::parse_csv(text).into_iter();
+    let header = records.next().ok_or("the file is empty")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let id_col =
+        column("Const").ok_or("no Const column; is this an IMDb ratings or watchlist CSV?")?;
+    let title_col = column("Title");
+    let year_col = column("Year");
+    let rating_col = column("Your Rating");
+    let kind = if column("Position").is_some() {
+        ImdbExport::Watchlist
+    } else if rating_col.is_some() {
+        ImdbExport::Ratings
+    } else {
+        return Err("neither a ratings (Your Rating) nor a watchlist (Position) export".into());
+    };
+    let field = |record: &[String], col: Option<usize>| {
+        col.and_then(|col| record.get(col))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let entries = records
+        .filter_map(|record| {
+            let imdb_id = field(&record, Some(id_col)).filter(|id| id.starts_with("tt"))?;
+            let rating = field(&record, rating_col)
+                .and_then(|r| r.parse().ok())
+                .filter(|r| (1..=10).contains(r));
+            if kind == ImdbExport::Ratings && rating.is_none() {
+                return None;
+            }
+            Some(ImdbEntry {
+                imdb_id,
+                title: field(&record, title_col).unwrap_or_default(),
+                year: field(&record, year_col).and_then(|y| y.parse().ok()),
+                rating,
+            })
+        })
+        .collect();
+    Ok((kind, entries))
+}
+
+/// One imported export, matched by `imdb:` id key and by `make_owned_key` of title and year.
+#[derive(Default)]
+struct ImdbList {
+    entries: Vec<ImdbEntry>,
+    /// Match key -> index into `entries`.
+    keys: HashMap<String, usize>,
+}
+
+impl ImdbList {
+    fn new(entries: Vec<ImdbEntry>) -> Self {
+        let mut keys = HashMap::new();
+        for (idx, entry) in entries.iter().enumerate() {
+            keys.extend(
+                crate::app::owned::nfo::id_keys(Some(&entry.imdb_id), None)
+                    .into_iter()
+                    .map(|key| (key, idx)),
+            );
+            if !entry.title.is_empty() {
+                keys.entry(PexApp::make_owned_key(&entry.title, entry.year))
+                    .or_insert(idx);
+            }
+        }
+        Self { entries, keys }
+    }
+
+    fn path(kind: ImdbExport) -> PathBuf {
+        crate::app::cache::cache_dir().join(kind.file())
+    }
+
+    /// Cached copy: `<tt id>\t<year>\t<rating>\t<title>` lines.
+    fn load(kind: ImdbExport) -> Self {
+        let entries = fs::read_to_string(Self::path(kind))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(4, '\t');
+                let imdb_id = parts.next().filter(|id| !id.is_empty())?.to_string();
+                let year = parts.next()?.parse().ok();
+                let rating = parts.next()?.parse().ok();
+                let title = parts.next().unwrap_or_default().to_string();
+                Some(ImdbEntry {
+                    imdb_id,
+                    title,
+                    year,
+                    rating,
+                })
+            })
+            .collect();
+        Self::new(entries)
+    }
+
+    fn save(&self, kind: ImdbExport) {
+        let text: String = self
+            .entries
+            .iter()
+            .map(|e| {
+                let year = e.year.map(|y| y.to_string()).unwrap_or_default();
+                let rating = e.rating.map(|r| r.to_string()).unwrap_or_default();
+                format!("{}\t{year}\t{rating}\t{}\n", e.imdb_id, e.title)
+            })
+            .collect();
+        if let Err(err) = fs::write(Self::path(kind), text) {
+            warn!("Failed to write {}: {err}", Self::path(kind).display());
+        }
+    }
+
+    /// The row's entry: by the tt id in its guid, else by title and year.
+    fn find(&self, row: &PosterRow) -> Option<&ImdbEntry> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        let imdb_id = row.guid.as_deref().and_then(crate::app::imdb_id_from_guid);
+        crate::app::owned::nfo::id_keys(imdb_id.as_deref(), None)
+            .into_iter()
+            .chain(PexApp::owned_key_variants(&row.title, row.year))
+            .find_map(|key| self.keys.get(&key))
+            .and_then(|&idx| self.entries.get(idx))
+    }
+}
+
+/// Personal IMDb ratings and watchlist imported from IMDb's CSV exports (no API key needed).
+pub(crate) struct ImdbLists {
+    ratings: ImdbList,
+    watchlist: ImdbList,
+    /// Path typed or picked in Advanced.
+    path_input: String,
+    pick: Option<PendingPick<()>>,
+}
+
+impl ImdbLists {
+    pub fn load() -> Self {
+        Self {
+            ratings: ImdbList::load(ImdbExport::Ratings),
+            watchlist: ImdbList::load(ImdbExport::Watchlist),
+            path_input: String::new(),
+            pick: None,
+        }
+    }
+
+    fn list_mut(&mut self, kind: ImdbExport) -> &mut ImdbList {
+        match kind {
+            ImdbExport::Ratings => &mut self.ratings,
+            ImdbExport::Watchlist => &mut self.watchlist,
+        }
+    }
+}
+
+impl PexApp {
+    /// Copy personal IMDb ratings and watchlist membership onto rows from `start` on.
+    pub(crate) fn apply_imdb_flags_from(&mut self, start: usize) {
+        let lists = &self.imdb_lists;
+        for row in self.rows.iter_mut().skip(start) {
+            row.my_rating = lists.ratings.find(row).and_then(|entry| entry.rating);
+            row.imdb_watchlist = lists.watchlist.find(row).is_some();
+        }
+    }
+
+    fn import_imdb(&mut self, path: &str) {
+        let path = crate::config::resolve_relative_path(path.trim());
+        let result = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| parse_export(&text));
+        match result {
+            Ok((kind, entries)) => {
+                let count = entries.len();
+                let list = ImdbList::new(entries);
+                list.save(kind);
+                *self.imdb_lists.list_mut(kind) = list;
+                self.apply_imdb_flags_from(0);
+                let in_guide = self
+                    .rows
+                    .iter()
+                    .filter(|row| row.airing.is_some())
+                    .filter(|row| match kind {
+                        ImdbExport::Ratings => row.my_rating.is_some(),
+                        ImdbExport::Watchlist => row.imdb_watchlist,
+                    })
+                    .count();
+                self.advanced_feedback = Some(format!(
+                    "Imported {count} titles from your IMDb {}; {in_guide} are in the guide.",
+                    kind.label()
+                ));
+            }
+            Err(err) => {
+                self.advanced_feedback =
+                    Some(format!("IMDb import from {} failed: {err}", path.display()));
+            }
+        }
+    }
+
+    fn clear_imdb(&mut self, kind: ImdbExport) {
+        let list = self.imdb_lists.list_mut(kind);
+        *list = ImdbList::default();
+        list.save(kind);
+        self.apply_imdb_flags_from(0);
+    }
+
+    /// Detail-panel chips for your IMDb rating and watchlist.
+    pub(crate) fn ui_render_imdb_chips(&self, ui: &mut eg::Ui, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        if let Some(rating) = row.my_rating {
+            ui.label(
+                eg::RichText::new(format!("Your IMDb rating ★ {rating}/10")).color(IMDB_COLOR),
+            )
+            .on_hover_text("From the ratings.csv imported in Advanced");
+        }
+        if row.imdb_watchlist {
+            ui.label(eg::RichText::new("IMDb watchlist").color(IMDB_COLOR))
+                .on_hover_text("From the watchlist.csv imported in Advanced");
+        }
+    }
+
+    /// Advanced ▸ IMDb lists: import `ratings.csv` / `watchlist.csv` exports (detected by header).
+    pub(crate) fn advanced_imdb_controls(&mut self, ui: &mut eg::Ui) {
+        if let Some((_, result)) = self.imdb_lists.pick.as_ref().and_then(PendingPick::poll) {
+            self.imdb_lists.pick = None;
+            match result {
+                Ok(Some(path)) => {
+                    self.imdb_lists.path_input = path.display().to_string();
+                    let path = self.imdb_lists.path_input.clone();
+                    self.import_imdb(&path);
+                }
+                Ok(None) => {}
+                Err(err) => self.advanced_feedback = Some(err),
+            }
+        }
+        ui.label(eg::RichText::new("IMDb ratings & watchlist").strong());
+        ui.label(
+            eg::RichText::new(format!(
+                "{} rated titles, {} on the watchlist. Export them from imdb.com (Your ratings / \
+                 Your watchlist ▸ Export) and import each CSV here.",
+                self.imdb_lists.ratings.entries.len(),
+                self.imdb_lists.watchlist.entries.len()
+            ))
+            .weak(),
+        );
+        ui.horizontal(|ui| {
+            ui.add(
+                eg::TextEdit::singleline(&mut self.imdb_lists.path_input)
+                    .hint_text("ratings.csv or watchlist.csv")
+                    .desired_width(180.0),
+            );
+            if ui
+                .add_enabled(self.imdb_lists.pick.is_none(), eg::Button::new("Browse…"))
+                .clicked()
+            {
+                self.imdb_lists.pick = Some(PendingPick::spawn(
+                    (),
+                    PickKind::File,
+                    "IMDb ratings or watchlist CSV",
+                    &self.imdb_lists.path_input,
+                ));
+            }
+            if ui
+                .add_enabled(
+                    !self.imdb_lists.path_input.trim().is_empty(),
+                    eg::Button::new("Import"),
+                )
+                .on_hover_text("Replace the imported ratings or watchlist with this file")
+                .clicked()
+            {
+                let path = self.imdb_lists.path_input.clone();
+                self.import_imdb(&path);
+            }
+        });
+        ui.horizontal(|ui| {
+            for kind in [ImdbExport::Ratings, ImdbExport::Watchlist] {
+                let list = match kind {
+                    ImdbExport::Ratings => &self.imdb_lists.ratings,
+                    ImdbExport::Watchlist => &self.imdb_lists.watchlist,
+                };
+                if !list.entries.is_empty()
+                    && ui.small_button(format!("Clear {}", kind.label())).clicked()
+                {
+                    self.clear_imdb(kind);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_are_told_apart_and_keep_personal_ratings() {
+        let (kind, ratings) = parse_export(
+            "Const,Your Rating,Date Rated,Title,URL,Title Type,IMDb Rating,Runtime (mins),Year\n\
+             tt0113277,9,2023-05-01,Heat,https://www.imdb.com/title/tt0113277/,Movie,8.3,170,1995\n\
+             tt0078748,,2023-05-02,Alien,https://www.imdb.com/title/tt0078748/,Movie,8.5,117,1979\n",
+        )
+        .unwrap();
+        assert_eq!(kind, ImdbExport::Ratings);
+        assert_eq!(ratings.len(), 1);
+        assert_eq!(ratings[0].rating, Some(9));
+        assert_eq!(ratings[0].year, Some(1995));
+
+        let (kind, watchlist) = parse_export(
+            "Position,Const,Created,Modified,Description,Title,URL,Title Type,Year,Your Rating\n\
+             1,tt0078748,2023-05-02,,,\"Alien\",https://www.imdb.com/title/tt0078748/,Movie,1979,\n",
+        )
+        .unwrap();
+        assert_eq!(kind, ImdbExport::Watchlist);
+        assert_eq!(watchlist[0].title, "Alien");
+        assert!(parse_export("Name,Year\nHeat,1995\n").is_err());
+
+        let list = ImdbList::new(ratings);
+        assert!(list.keys.contains_key("imdb:tt0113277"));
+        assert!(list.keys.contains_key("heat:1995"));
+    }
+}
@@ -0,0 +1,664 @@
+// src/app/keymap.rs
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use eframe::egui as eg;
+
+use crate::app::theme::UI_SCALE_STEP;
+use crate::app::types::ViewMode;
+
+/// Commands that can be bound to a key chord.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    NavUp,
+    NavDown,
+    NavLeft,
+    NavRight,
+    PageUp,
+    PageDown,
+    PrevDay,
+    NextDay,
+    DayBack,
+    DayForward,
+    ClearSelection,
+    OpenDetail,
+    FocusDetail,
+    FocusSearch,
+    QuickSearch,
+    ToggleWatchlist,
+    FetchRating,
+    CycleView,
+    ToggleHideOwned,
+    ChannelFilter,
+    GenreFilter,
+    Collections,
+    Advanced,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+}
+
+impl Action {
+    pub const ALL: [Self; 26] = [
+        Self::NavUp,
+        Self::NavDown,
+        Self::NavLeft,
+        Self::NavRight,
+        Self::PageUp,
+        Self::PageDown,
+        Self::PrevDay,
+        Self::NextDay,
+        Self::DayBack,
+        Self::DayForward,
+        Self::ClearSelection,
+        Self::OpenDetail,
+        Self::FocusDetail,
+        Self::FocusSearch,
+        Self::QuickSearch,
+        Self::ToggleWatchlist,
+        Self::FetchRating,
+        Self::CycleView,
+        Self::ToggleHideOwned,
+        Self::ChannelFilter,
+        Self::GenreFilter,
+        Self::Collections,
+        Self::Advanced,
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::ZoomReset,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NavUp => "nav_up",
+            Self::NavDown => "nav_down",
+            Self::NavLeft => "nav_left",
+            Self::NavRight => "nav_right",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::PrevDay => "prev_day",
+            Self::NextDay => "next_day",
+            Self::DayBack => "day_back",
+            Self::DayForward => "day_forward",
+            Self::ClearSelection => "clear_selection",
+            Self::OpenDetail => "open_detail",
+            Self::FocusDetail => "focus_detail",
+            Self::FocusSearch => "focus_search",
+            Self::QuickSearch => "quick_search",
+            Self::ToggleWatchlist => "toggle_watchlist",
+            Self::FetchRating => "fetch_rating",
+            Self::CycleView => "cycle_view",
+            Self::ToggleHideOwned => "toggle_hide_owned",
+            Self::ChannelFilter => "channel_filter",
+            Self::GenreFilter => "genre_filter",
+            Self::Collections => "collections",
+            Self::Advanced => "advanced",
+            Self::ZoomIn => "zoom_in",
+            Self::ZoomOut => "zoom_out",
+            Self::ZoomReset => "zoom_reset",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::NavUp => "Select card above",
+            Self::NavDown => "Select card below",
+            Self::NavLeft => "Select previous card",
+            Self::NavRight => "Select next card",
+            Self::PageUp => "Select a screen up",
+            Self::PageDown => "Select a screen down",
+            Self::PrevDay => "Select start of day / previous day",
+            Self::NextDay => "Select start of next day",
+            Self::DayBack => "Jump to the previous day",
+            Self::DayForward => "Jump to the next day",
+            Self::ClearSelection => "Close popups / clear selection",
+            Self::OpenDetail => "Open the selected card's details",
+            Self::FocusDetail => "Move focus into the detail panel",
+            Self::FocusSearch => "Focus title search",
+            Self::QuickSearch => "Focus title search (quick key)",
+            Self::ToggleWatchlist => "Add to / remove from Trakt watchlist",
+            Self::FetchRating => "Fetch rating",
+            Self::CycleView => "Cycle Grid / Timeline / List / On now",
+            Self::ToggleHideOwned => "Toggle Hide owned",
+            Self::ChannelFilter => "Open channel filter",
+            Self::GenreFilter => "Open genre filter",
+            Self::Collections => "Open collections",
+            Self::Advanced => "Open Advanced controls",
+            Self::ZoomIn => "Zoom in",
+            Self::ZoomOut => "Zoom out",
+            Self::ZoomReset => "Reset zoom",
+        }
+    }
+
+    const fn default_chord(self) -> KeyChord {
+        match self {
+            Self::NavUp => KeyChord::plain(eg::Key::ArrowUp),
+            Self::NavDown => KeyChord::plain(eg::Key::ArrowDown),
+            Self::NavLeft => KeyChord::plain(eg::Key::ArrowLeft),
+            Self::NavRight => KeyChord::plain(eg::Key::ArrowRight),
+            Self::PageUp => KeyChord::plain(eg::Key::PageUp),
+            Self::PageDown => KeyChord::plain(eg::Key::PageDown),
+            Self::PrevDay => KeyChord::plain(eg::Key::Home),
+            Self::NextDay => KeyChord::plain(eg::Key::End),
+            Self::DayBack => KeyChord::ctrl(eg::Key::ArrowLeft),
+            Self::DayForward => KeyChord::ctrl(eg::Key::ArrowRight),
+            Self::ClearSelection => KeyChord::plain(eg::Key::Escape),
+            Self::OpenDetail => KeyChord::plain(eg::Key::Enter),
+            Self::FocusDetail => KeyChord::plain(eg::Key::Tab),
+            Self::FocusSearch => KeyChord::ctrl(eg::Key::F),
+            Self::QuickSearch => KeyChord::plain(eg::Key::Slash),
+            Self::ToggleWatchlist => KeyChord::plain(eg::Key::W),
+            Self::FetchRating => KeyChord::plain(eg::Key::R),
+            Self::CycleView => KeyChord::plain(eg::Key::V),
+            Self::ToggleHideOwned => KeyChord::plain(eg::Key::H),
+            Self::ChannelFilter => KeyChord::plain(eg::Key::C),
+            Self::GenreFilter => KeyChord::plain(eg::Key::G),
+            Self::Collections => KeyChord::plain(eg::Key::L),
+            Self::Advanced => KeyChord::ctrl(eg::Key::Comma),
+            Self::ZoomIn => KeyChord::ctrl(eg::Key::Equals),
+            Self::ZoomOut => KeyChord::ctrl(eg::Key::Minus),
+            Self::ZoomReset => KeyChord::ctrl(eg::Key::Num0),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// A key plus modifiers. `ctrl` is the platform command key (Cmd on macOS).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: eg::Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyChord {
+    const fn plain(key: eg::Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    const fn ctrl(key: eg::Key) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::plain(key)
+        }
+    }
+
+    pub const fn from_event(key: eg::Key, modifiers: eg::Modifiers) -> Self {
+        Self {
+            key,
+            ctrl: modifiers.command,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+        }
+    }
+
+    /// Pressed this frame with exactly these modifiers (so `Shift+V` doesn't also fire `V`).
+    pub fn pressed(&self, input: &eg::InputState) -> bool {
+        input.key_pressed(self.key)
+            && input.modifiers.command == self.ctrl
+            && input.modifiers.shift == self.shift
+            && input.modifiers.alt == self.alt
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Key names are words ("Plus", "Comma"), so '+' only ever separates parts.
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key_name = parts.pop().filter(|k| !k.is_empty()).ok_or(())?;
+        let mut chord = Self::plain(eg::Key::from_name(key_name).ok_or(())?);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" => chord.alt = true,
+                _ => return Err(()),
+            }
+        }
+        Ok(chord)
+    }
+}
+
+/// Action → chord table. Unbound actions are simply absent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, KeyChord>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .into_iter()
+                .map(|action| (action, action.default_chord()))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    pub fn chord(&self, action: Action) -> Option<KeyChord> {
+        self.bindings.get(&action).copied()
+    }
+
+    pub fn action_for(&self, chord: KeyChord) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find_map(|(action, bound)| (*bound == chord).then_some(*action))
+    }
+
+    pub fn pressed(&self, action: Action, input: &eg::InputState) -> bool {
+        self.chord(action).is_some_and(|chord| chord.pressed(input))
+    }
+
+    /// Bind `chord` to `action`. A chord maps to one action only, so whichever action held it
+    /// before is unbound and returned.
+    pub fn assign(&mut self, action: Action, chord: KeyChord) -> Option<Action> {
+        let displaced = self.action_for(chord).filter(|other| *other != action);
+        if let Some(other) = displaced {
+            self.bindings.remove(&other);
+        }
+        self.bindings.insert(action, chord);
+        displaced
+    }
+
+    pub fn unbind(&mut self, action: Action) {
+        self.bindings.remove(&action);
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Defaults with h/j/k/l moving the selection; Hide owned and Collections, which held H and
+    /// L, move to Shift+H and Shift+L.
+    pub fn vim() -> Self {
+        let mut keymap = Self::default();
+        let shifted = |key| KeyChord {
+            shift: true,
+            ..KeyChord::plain(key)
+        };
+        keymap.assign(Action::ToggleHideOwned, shifted(eg::Key::H));
+        keymap.assign(Action::Collections, shifted(eg::Key::L));
+        keymap.assign(Action::NavLeft, KeyChord::plain(eg::Key::H));
+        keymap.assign(Action::NavDown, KeyChord::plain(eg::Key::J));
+        keymap.assign(Action::NavUp, KeyChord::plain(eg::Key::K));
+        keymap.assign(Action::NavRight, KeyChord::plain(eg::Key::L));
+        keymap
+    }
+
+    /// `action:chord` pairs for the prefs file; unbound actions keep an empty chord.
+    pub fn to_pref_string(&self) -> String {
+        Action::ALL
+            .into_iter()
+            .map(|action| {
+                let chord = self
+                    .chord(action)
+                    .map(|c| c.to_string())
+                    .unwrap_or_default();
+                format!("{}:{chord}", action.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Saved pairs first, then defaults for actions the prefs don't mention (unless their
+    /// default chord is already taken); unknown actions or keys are ignored.
+    pub fn from_pref_string(value: &str) -> Self {
+        let mut keymap = Self {
+            bindings: BTreeMap::new(),
+        };
+        let mut mentioned: Vec<Action> = Vec::new();
+        for pair in value.split(',') {
+            let Some((name, chord)) = pair.split_once(':') else {
+                continue;
+            };
+            let Ok(action) = name.trim().parse::<Action>() else {
+                continue;
+            };
+            let chord = chord.trim();
+            if chord.is_empty() {
+                keymap.unbind(action);
+            } else if let Ok(chord) = chord.parse::<KeyChord>() {
+                keymap.assign(action, chord);
+            } else {
+                continue;
+            }
+            mentioned.push(action);
+        }
+        for action in Action::ALL {
+            let chord = action.default_chord();
+            if !mentioned.contains(&action) && keymap.action_for(chord).is_none() {
+                keymap.bindings.insert(action, chord);
+            }
+        }
+        keymap
+    }
+}
+
+impl crate::app::PexApp {
+    /// Run bound commands for this frame's key presses. Text fields keep their keys.
+    pub(crate) fn handle_shortcuts(&mut self, ctx: &eg::Context) {
+        if self.keymap_capture.is_some() || ctx.memory(|mem| mem.focused().is_some()) {
+            return;
+        }
+        let fired: Vec<Action> = ctx.input(|input| {
+            Action::ALL
+                .into_iter()
+                .filter(|action| self.keymap.pressed(*action, input))
+                .collect()
+        });
+        for action in fired {
+            match action {
+                // Card navigation runs inside the active view (it needs the fresh layout);
+                // the detail panel takes focus itself, before its widgets are laid out.
+                Action::NavUp
+                | Action::NavDown
+                | Action::NavLeft
+                | Action::NavRight
+                | Action::PageUp
+                | Action::PageDown
+                | Action::PrevDay
+                | Action::NextDay
+                | Action::DayBack
+                | Action::DayForward
+                | Action::FocusDetail => {}
+                Action::ClearSelection => {
+                    if !self.close_popups() {
+                        self.selected_idx = None;
+                    }
+                }
+                Action::OpenDetail => {
+                    // With a card selected the detail panel already took focus this frame.
+                    if self.selected_idx.is_none() {
+                        self.selected_idx = self.grid_rows.first().and_then(|r| r.first()).copied();
+                        self.scroll_to_idx = self.selected_idx;
+                        self.detail_focus_pending = self.selected_idx.is_some();
+                    }
+                }
+                Action::FocusSearch | Action::QuickSearch => self.search_focus_pending = true,
+                Action::ToggleWatchlist => {
+                    if let Some(idx) = self.selected_idx {
+                        self.toggle_trakt_watchlist(idx);
+                    }
+                }
+                Action::FetchRating => {
+                    if let Some(idx) = self.selected_idx {
+                        self.request_rating_for(idx);
+                    }
+                }
+                Action::CycleView => {
+                    self.view_mode = match self.view_mode {
+                        ViewMode::Grid => ViewMode::Timeline,
+                        ViewMode::Timeline => ViewMode::List,
+                        ViewMode::List => ViewMode::NowNext,
+                        ViewMode::NowNext => ViewMode::Grid,
+                    };
+                    self.mark_dirty();
+                }
+                Action::ToggleHideOwned => {
+                    self.hide_owned = !self.hide_owned;
+                    self.mark_dirty();
+                }
+                Action::ChannelFilter => {
+                    self.show_channel_filter_popup = true;
+                    self.channel_filter_focus_pending = true;
+                }
+                Action::GenreFilter => self.show_genre_filter_popup = true,
+                Action::Collections => self.collections.show_popup = true,
+                Action::Advanced => self.show_advanced_popup = true,
+                Action::ZoomIn => self.set_ui_scale(self.ui_scale + UI_SCALE_STEP),
+                Action::ZoomOut => self.set_ui_scale(self.ui_scale - UI_SCALE_STEP),
+                Action::ZoomReset => self.set_ui_scale(1.0),
+            }
+        }
+    }
+
+    /// Close every open popup window; false when none was open.
+    pub(crate) fn close_popups(&mut self) -> bool {
+        let open = [
+            &mut self.show_channel_filter_popup,
+            &mut self.show_genre_filter_popup,
+            &mut self.show_advanced_popup,
+            &mut self.show_keymap_popup,
+            &mut self.collections.show_popup,
+            &mut self.title_tags.manager_open,
+            &mut self.alerts.window_open,
+            &mut self.owned_browser.open,
+            &mut self.settings.open,
+        ];
+        let mut closed = false;
+        for flag in open {
+            closed |= std::mem::take(flag);
+        }
+        closed
+    }
+
+    // ---------- KEYBOARD SHORTCUTS EDITOR ----------
+    pub(crate) fn ui_render_keymap_popup(&mut self, ctx: &eg::Context) {
+        if !self.show_keymap_popup {
+            return;
+        }
+
+        // Capture the next key press for the action being rebound (Escape cancels).
+        if let Some(action) = self.keymap_capture {
+            let captured = ctx.input_mut(|input| {
+                let chord = input.events.iter().find_map(|event| match event {
+                    eg::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some(KeyChord::from_event(*key, *modifiers)),
+                    _ => None,
+                });
+                if chord.is_some() {
+                    input.events.retain(|e| !matches!(e, eg::Event::Key { .. }));
+                }
+                chord
+            });
+            if let Some(chord) = captured {
+                self.keymap_capture = None;
+                if chord.key == eg::Key::Escape && !chord.ctrl && !chord.shift && !chord.alt {
+                    self.keymap_feedback = None;
+                } else {
+                    let displaced = self.keymap.assign(action, chord);
+                    self.keymap_feedback = Some(displaced.map_or_else(
+                        || format!("{} → {chord}", action.label()),
+                        |other| {
+                            format!(
+                                "{chord} moved from \"{}\" (now unbound) to \"{}\".",
+                                other.label(),
+                                action.label()
+                            )
+                        },
+                    ));
+                    self.mark_dirty();
+                }
+            }
+        }
+
+        let mut open = self.show_keymap_popup;
+        eg::Window::new("Keyboard shortcuts")
+            .collapsible(false)
+            .resizable(false)
+            .default_width(380.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(
+                    eg::RichText::new(
+                        "Click a shortcut, then press the new key combination (Esc cancels).",
+                    )
+                    .weak(),
+                );
+                ui.separator();
+                eg::Grid::new("keymap_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for action in Action::ALL {
+                            ui.label(action.label());
+                            let capturing = self.keymap_capture == Some(action);
+                            let text = if capturing {
+                                "Press keys…".to_string()
+                            } else {
+                                self.keymap
+                                    .chord(action)
+                                    .map_or_else(|| "Unbound".to_string(), |c| c.to_string())
+                            };
+                            let unbound = self.keymap.chord(action).is_none();
+                            let mut rich = eg::RichText::new(text).monospace();
+                            if unbound && !capturing {
+                                rich = rich.color(eg::Color32::from_rgb(230, 170, 40));
+                            }
+                            if ui.selectable_label(capturing, rich).clicked() {
+                                self.keymap_capture = (!capturing).then_some(action);
+                            }
+                            if ui
+                                .add_enabled(!unbound, eg::Button::new("Clear").small())
+                                .clicked()
+                            {
+                                self.keymap.unbind(action);
+                                self.mark_dirty();
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !self.keymap.is_default(),
+                            eg::Button::new("Reset to defaults"),
+                        )
+                        .clicked()
+                    {
+                        self.keymap = Keymap::default();
+                        self.keymap_capture = None;
+                        self.keymap_feedback = Some("Shortcuts reset to defaults.".into());
+                        self.mark_dirty();
+                    }
+                    if ui
+                        .add_enabled(self.keymap != Keymap::vim(), eg::Button::new("Vim keys"))
+                        .on_hover_text(
+                            "h/j/k/l move the selection; Hide owned and Collections move to \
+                             Shift+H and Shift+L",
+                        )
+                        .clicked()
+                    {
+                        self.keymap = Keymap::vim();
+                        self.keymap_capture = None;
+                        self.keymap_feedback = Some("Vim-style navigation keys applied.".into());
+                        self.mark_dirty();
+                    }
+                    if let Some(msg) = &self.keymap_feedback {
+                        ui.label(eg::RichText::new(msg).weak());
+                    }
+                });
+            });
+
+        if !open {
+            self.keymap_capture = None;
+        }
+        self.show_keymap_popup = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chords_round_trip_through_text() {
+        for text in [
+            "Ctrl+F",
+            "Shift+Alt+Up",
+            "Escape",
+            "Ctrl+Comma",
+            "Ctrl+Minus",
+        ] {
+            let chord: KeyChord = text.parse().unwrap();
+            assert_eq!(chord.to_string(), text);
+        }
+        assert_eq!(
+            "cmd+shift+v".parse::<KeyChord>(),
+            Ok(KeyChord {
+                key: eg::Key::V,
+                ctrl: true,
+                shift: true,
+                alt: false
+            })
+        );
+        assert!("Hyper+F".parse::<KeyChord>().is_err());
+        assert!("Ctrl+".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn assigning_a_taken_chord_unbinds_the_other_action() {
+        let mut keymap = Keymap::default();
+        let chord: KeyChord = "Ctrl+F".parse().unwrap();
+        assert_eq!(
+            keymap.assign(Action::CycleView, chord),
+            Some(Action::FocusSearch)
+        );
+        assert_eq!(keymap.chord(Action::FocusSearch), None);
+        assert_eq!(keymap.action_for(chord), Some(Action::CycleView));
+
+        let restored = Keymap::from_pref_string(&keymap.to_pref_string());
+        assert_eq!(restored, keymap);
+
+        // Swapped defaults survive a reload regardless of save order.
+        let mut swapped = Keymap::default();
+        swapped.assign(Action::NavUp, "ArrowDown".parse().unwrap());
+        swapped.assign(Action::NavDown, "ArrowUp".parse().unwrap());
+        assert_eq!(Keymap::from_pref_string(&swapped.to_pref_string()), swapped);
+        assert_eq!(
+            Keymap::from_pref_string("bogus:Ctrl+Q,nav_up:NotAKey"),
+            Keymap::default()
+        );
+
+        // The Vim preset keeps every action bound and survives a save.
+        let vim = Keymap::vim();
+        assert!(Action::ALL.iter().all(|a| vim.chord(*a).is_some()));
+        assert_eq!(vim.action_for("J".parse().unwrap()), Some(Action::NavDown));
+        assert_eq!(
+            vim.action_for("Shift+H".parse().unwrap()),
+            Some(Action::ToggleHideOwned)
+        );
+        assert_eq!(Keymap::from_pref_string(&vim.to_pref_string()), vim);
+    }
+}
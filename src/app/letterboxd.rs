@@ -0,0 +1,225 @@
+// src/app/letterboxd.rs
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui as eg;
+use tracing::warn;
+
+use crate::app::file_picker::{PendingPick, PickKind};
+use crate::app::PexApp;
+
+const WATCHLIST_FILE: &str = "letterboxd_watchlist.txt";
+/// Letterboxd's orange, green and blue, drawn as the three-dot badge.
+pub(crate) const LETTERBOXD_DOTS: [eg::Color32; 3] = [
+    eg::Color32::from_rgb(255, 128, 0),
+    eg::Color32::from_rgb(0, 224, 84),
+    eg::Color32::from_rgb(64, 188, 244),
+];
+
+fn watchlist_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(WATCHLIST_FILE)
+}
+
+/// Films of a Letterboxd `watchlist.csv` export (`Date,Name,Year,Letterboxd URI`).
+fn parse_export(text: &str) -> Result<Vec<(String, Option<i32>)>, String> {
+    let mut records = crate::app::utils::parse_csv(text).into_iter();
+    let header = records.next().ok_or("the file is empty")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let name_col = column("Name").ok_or("no Name column; is this a Letterboxd watchlist.csv?")?;
+    let year_col = column("Year");
+    Ok(records
+        .filter_map(|record| {
+            let title = record.get(name_col)?.trim();
+            let year = year_col
+                .and_then(|col| record.get(col))
+                .and_then(|y| y.trim().parse().ok());
+            (!title.is_empty()).then(|| (title.to_string(), year))
+        })
+        .collect())
+}
+
+/// The imported Letterboxd watchlist, copied to `<cache>/letterboxd_watchlist.txt` as
+/// `<title>\t<year>` lines so it survives the export file being moved.
+#[derive(Default)]
+pub(crate) struct LetterboxdWatchlist {
+    films: Vec<(String, Option<i32>)>,
+    /// `make_owned_key` of every film; rows match through their owned key variants.
+    keys: HashSet<String>,
+    /// Path typed or picked in Advanced.
+    pub path_input: String,
+    pick: Option<PendingPick<()>>,
+}
+
+impl LetterboxdWatchlist {
+    pub fn load() -> Self {
+        let mut list = Self::default();
+        if let Ok(text) = fs::read_to_string(watchlist_path()) {
+            let films = text
+                .lines()
+                .filter_map(|line| {
+                    let (title, year) = line.split_once('\t')?;
+                    Some((title.to_string(), year.trim().parse().ok()))
+                })
+                .collect();
+            list.set_films(films);
+        }
+        list
+    }
+
+    fn set_films(&mut self, films: Vec<(String, Option<i32>)>) {
+        self.keys = films
+            .iter()
+            .map(|(title, year)| PexApp::make_owned_key(title, *year))
+            .collect();
+        self.films = films;
+    }
+
+    fn save(&self) {
+        let text: String = self
+            .films
+            .iter()
+            .map(|(title, year)| {
+                let year = year.map(|y| y.to_string()).unwrap_or_default();
+                format!("{title}\t{year}\n")
+            })
+            .collect();
+        if let Err(err) = fs::write(watchlist_path(), text) {
+            warn!("Failed to write {}: {err}", watchlist_path().display());
+        }
+    }
+
+    fn contains(&self, title: &str, year: Option<i32>) -> bool {
+        !self.keys.is_empty()
+            && PexApp::owned_key_variants(title, year)
+                .iter()
+                .any(|key| self.keys.contains(key))
+    }
+}
+
+impl PexApp {
+    /// Mark rows (from `start` on) whose film is on the imported Letterboxd watchlist.
+    pub(crate) fn apply_letterboxd_flags_from(&mut self, start: usize) {
+        for row in self.rows.iter_mut().skip(start) {
+            row.letterboxd = self.letterboxd.contains(&row.title, row.year);
+        }
+    }
+
+    fn import_letterboxd(&mut self, path: &str) {
+        let path = crate::config::resolve_relative_path(path.trim());
+        let result = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| parse_export(&text));
+        match result {
+            Ok(films) => {
+                let count = films.len();
+                self.letterboxd.set_films(films);
+                self.letterboxd.save();
+                self.apply_letterboxd_flags_from(0);
+                let airing = self
+                    .rows
+                    .iter()
+                    .filter(|row| row.letterboxd && row.airing.is_some())
+                    .count();
+                self.advanced_feedback = Some(format!(
+                    "Imported {count} Letterboxd watchlist films; {airing} are in the guide."
+                ));
+            }
+            Err(err) => {
+                self.advanced_feedback = Some(format!(
+                    "Letterboxd import from {} failed: {err}",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    /// Advanced ▸ Letterboxd watchlist: import (or clear) a `watchlist.csv` export.
+    pub(crate) fn advanced_letterboxd_controls(&mut self, ui: &mut eg::Ui) {
+        if let Some((_, result)) = self.letterboxd.pick.as_ref().and_then(PendingPick::poll) {
+            self.letterboxd.pick = None;
+            match result {
+                Ok(Some(path)) => {
+                    self.letterboxd.path_input = path.display().to_string();
+                    let path = self.letterboxd.path_input.clone();
+                    self.import_letterboxd(&path);
+                }
+                Ok(None) => {}
+                Err(err) => self.advanced_feedback = Some(err),
+            }
+        }
+        ui.label(eg::RichText::new("Letterboxd watchlist").strong());
+        ui.label(
+            eg::RichText::new(if self.letterboxd.films.is_empty() {
+                "Export it from letterboxd.com ▸ Settings ▸ Data and pick watchlist.csv."
+                    .to_string()
+            } else {
+                format!("{} films imported.", self.letterboxd.films.len())
+            })
+            .weak(),
+        );
+        ui.horizontal(|ui| {
+            ui.add(
+                eg::TextEdit::singleline(&mut self.letterboxd.path_input)
+                    .hint_text("watchlist.csv")
+                    .desired_width(180.0),
+            );
+            if ui
+                .add_enabled(self.letterboxd.pick.is_none(), eg::Button::new("Browse…"))
+                .clicked()
+            {
+                self.letterboxd.pick = Some(PendingPick::spawn(
+                    (),
+                    PickKind::File,
+                    "Letterboxd watchlist.csv",
+                    &self.letterboxd.path_input,
+                ));
+            }
+            if ui
+                .add_enabled(
+                    !self.letterboxd.path_input.trim().is_empty(),
+                    eg::Button::new("Import"),
+                )
+                .on_hover_text("Replace the imported watchlist with this file")
+                .clicked()
+            {
+                let path = self.letterboxd.path_input.clone();
+                self.import_letterboxd(&path);
+            }
+            if !self.letterboxd.films.is_empty() && ui.small_button("Clear").clicked() {
+                self.letterboxd.set_films(Vec::new());
+                self.letterboxd.save();
+                self.apply_letterboxd_flags_from(0);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watchlist_export_is_read_and_matched_by_title_and_year() {
+        let films = parse_export(
+            "Date,Name,Year,Letterboxd URI\n\
+             2024-01-02,\"Crouching Tiger, Hidden Dragon\",2000,https://boxd.it/1\n\
+             2024-01-03,Heat,1995,https://boxd.it/2\n\
+             2024-01-04,Untitled Project,,https://boxd.it/3\n",
+        )
+        .unwrap();
+        assert_eq!(films.len(), 3);
+        assert_eq!(films[2], ("Untitled Project".to_string(), None));
+        assert!(parse_export("Title,Year\nHeat,1995\n").is_err());
+
+        let mut list = LetterboxdWatchlist::default();
+        list.set_films(films);
+        assert!(list.contains("Crouching Tiger, Hidden Dragon", Some(2000)));
+        assert!(list.contains("Heat", Some(1996)));
+        assert!(!list.contains("Heat", Some(1986)));
+    }
+}
@@ -0,0 +1,107 @@
+// src/app/links.rs
+use eframe::egui as eg;
+
+use crate::config::{local_library_db_path, AppConfig};
+
+fn imdb_url(imdb_id: &str) -> String {
+    format!("https://www.imdb.com/title/{imdb_id}/")
+}
+
+/// The TMDb page from the guid's TMDb id, else via TMDb's IMDb-id redirect.
+fn tmdb_url(tmdb_id: Option<&str>, imdb_id: Option<&str>) -> Option<String> {
+    match (tmdb_id, imdb_id) {
+        (Some(id), _) => Some(format!("https://www.themoviedb.org/movie/{id}")),
+        (None, Some(id)) => Some(format!(
+            "https://www.themoviedb.org/redirect?external_source=imdb_id&external_id={id}"
+        )),
+        (None, None) => None,
+    }
+}
+
+/// Plex Web (as served by the server itself) opened on a library item.
+fn plex_web_url(server_url: &str, machine_id: &str, rating_key: u64) -> String {
+    format!(
+        "{server_url}/web/index.html#!/server/{machine_id}/details?key=%2Flibrary%2Fmetadata%2F{rating_key}"
+    )
+}
+
+/// Where the detail panel's Plex Web link points: `plex_server_url` and the server's machine
+/// identifier from the library database.
+#[derive(Default)]
+pub(crate) struct ExternalLinks {
+    plex_web: Option<(String, String)>,
+}
+
+impl ExternalLinks {
+    pub fn configure(&mut self, cfg: &AppConfig) {
+        self.plex_web = cfg.plex_server_url.clone().and_then(|url| {
+            crate::app::server_identity::read_server_identifier(&local_library_db_path())
+                .map(|machine_id| (url, machine_id))
+        });
+    }
+}
+
+impl crate::app::PexApp {
+    /// Detail-panel links to the title's IMDb and TMDb pages and, for owned Plex items, Plex Web.
+    pub(crate) fn ui_render_external_links(&self, ui: &mut eg::Ui, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let guid = row.guid.as_deref();
+        let imdb_id = guid.and_then(crate::app::imdb_id_from_guid);
+        let tmdb_id = guid.and_then(crate::app::owned::nfo::tmdb_id_from_guid);
+        let plex_web = self
+            .external_links
+            .plex_web
+            .as_ref()
+            .zip(self.row_owned_media(row).and_then(|m| m.plex_rating_key))
+            .map(|((url, machine_id), key)| plex_web_url(url, machine_id, key));
+        let tmdb = tmdb_url(tmdb_id.as_deref(), imdb_id.as_deref());
+        if imdb_id.is_none() && tmdb.is_none() && plex_web.is_none() {
+            return;
+        }
+        ui.add_space(6.0);
+        ui.horizontal_wrapped(|ui| {
+            if let Some(id) = &imdb_id {
+                ui.hyperlink_to("IMDb", imdb_url(id))
+                    .on_hover_text(format!("Open {id} on IMDb"));
+            }
+            if let Some(url) = tmdb {
+                ui.hyperlink_to("TMDb", url)
+                    .on_hover_text("Open the film on The Movie Database");
+            }
+            if let Some(url) = plex_web {
+                ui.hyperlink_to("Plex Web", url)
+                    .on_hover_text("Open your copy in Plex Web on plex_server_url");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_prefer_the_tmdb_id_and_point_plex_web_at_the_item() {
+        assert_eq!(
+            imdb_url("tt0113277"),
+            "https://www.imdb.com/title/tt0113277/"
+        );
+        assert_eq!(
+            tmdb_url(Some("949"), Some("tt0113277")).as_deref(),
+            Some("https://www.themoviedb.org/movie/949")
+        );
+        assert_eq!(
+            tmdb_url(None, Some("tt0113277")).as_deref(),
+            Some(
+                "https://www.themoviedb.org/redirect?external_source=imdb_id&external_id=tt0113277"
+            )
+        );
+        assert_eq!(tmdb_url(None, None), None);
+        assert_eq!(
+            plex_web_url("http://nas:32400", "abc123", 4242),
+            "http://nas:32400/web/index.html#!/server/abc123/details?key=%2Flibrary%2Fmetadata%2F4242"
+        );
+    }
+}
@@ -4,26 +4,29 @@
 use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io::ErrorKind;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime};
 
 // ---- Crates ----
 use eframe::egui as eg;
-use serde::{de::DeserializeOwned, Deserialize};
-use tracing::warn;
-use urlencoding::encode;
+use tracing::{info, warn};
 
 // ---- Local modules ----
 pub mod cache;
+pub mod clock;
 use crate::app::cache::find_any_by_key;
+use crate::app::clock::{Clock, SystemClock};
 use crate::app::filters::{
     parse_owned_cutoff, OWNED_BEFORE_CUTOFF_DEFAULT_STR, OWNED_BEFORE_CUTOFF_DEFAULT_TS,
 };
+use crate::app::prefetch_queue::PrefetchQueue;
 use crate::app::scheduled::ScheduledIndex;
-use crate::config::{load_config, local_db_path};
+use crate::config::{load_config, local_db_path, local_library_db_path};
 
-type WorkItem = (usize, String, String, Option<PathBuf>);
+// (row idx, cache key, url, cached path, upgrade width — None for the initial small variant)
+pub(crate) type WorkItem = (usize, String, String, Option<PathBuf>, Option<u32>);
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum NavDirection {
@@ -31,34 +34,93 @@ enum NavDirection {
     Right,
     Up,
     Down,
+    /// A screenful of rows.
+    PageUp,
+    PageDown,
+    /// First card of the selected card's day, or of the day before when already there.
+    PrevDay,
+    /// First card of the next day.
+    NextDay,
+    /// First card of the day before the selected card's day.
+    DayBack,
 }
 
 pub mod prep;
 pub mod scheduled;
+pub mod server_identity;
 pub mod types;
 pub mod utils;
 pub use types::{
-    BootPhase, DayRange, OwnedMsg, Phase, PosterRow, PosterState, PrefetchDone, PrepItem, PrepMsg,
-    RatingMsg, RatingState, SortKey,
+    Airing, BootPhase, DayRange, GroupBy, GroupKey, OwnedMedia, OwnedMsg, OwnedTier, Phase,
+    PosterRow, PosterState, PrefetchDone, PrepItem, PrepMsg, RatingMsg, RatingState, RepeatKey,
+    SortKey, ViewMode,
 };
+pub mod a11y;
+pub mod alerts;
+pub mod background;
+pub mod blocklist;
+pub mod channel_groups;
+pub mod collections;
+pub mod config_watch;
+pub mod conflicts;
+pub mod day_strip;
+pub mod demo;
+pub mod density;
 pub mod detail;
+pub mod digest;
+pub mod export;
+pub mod facets;
+pub mod file_picker;
 pub mod filters;
+pub mod gamepad;
 pub mod gfx;
+pub mod guide;
+pub mod guide_refresh;
+pub mod headless;
+pub mod health;
+pub mod hover_preview;
+pub mod imdb_lists;
+pub mod keymap;
+pub mod letterboxd;
+pub mod links;
+pub mod net;
+pub mod new_listings;
 pub mod owned;
+pub mod plex_api;
 pub mod prefetch;
+pub mod prefetch_queue;
 pub mod prefs;
+pub mod premieres;
+pub mod profiles;
+pub mod radarr;
+pub mod ratings;
+pub mod resume;
+pub mod safe_mode;
+pub mod search;
+pub mod settings;
+pub mod snapshot;
+pub mod tags;
+pub mod theme;
+pub mod trakt;
 #[path = "ui/uimod.rs"] // this is we don't have duplicate file names in within the workspace.
 pub mod ui;
+pub mod web;
 
 // ---- Tunables ----
 const WORKER_COUNT: usize = 16; // up from 8 — tune freely (8–32 typical)
-const RESIZE_MAX_W: u32 = 320;
+const RESIZE_MAX_W: u32 = cache::POSTER_VARIANTS[0].1;
 const RESIZE_QUALITY: u8 = 75;
 const SHOW_GRID_EARLY: bool = true;
 const MIN_READY_BEFORE_GRID: usize = 24;
 const STATUS_EMIT_EVERY_MS: u64 = 120;
 const MAX_DONE_PER_FRAME: usize = 12;
 const MAX_UPLOADS_PER_FRAME: usize = 4;
+const MAX_FRESH_UPLOADS_PER_FRAME: usize = 6;
+// GPU poster texture budget (config `texture_budget_mb`); eviction trims to 90% of it.
+const DEFAULT_TEXTURE_BUDGET_MB: u32 = 512;
+const MIN_TEXTURE_BUDGET_MB: u32 = 64;
+// Textures seen on screen within this many frames are never evicted.
+const TEXTURE_MIN_IDLE_FRAMES: u64 = 120;
 const PREWARM_UPLOADS: usize = 24;
 const OWNED_AUTO_RETRY_MAX: u8 = 2;
 pub(crate) const OWNED_SCAN_COMPLETE_STATUS: &str =
@@ -68,6 +130,9 @@ pub struct PexApp {
     // data
     rows: Vec<PosterRow>,
 
+    // time source for day windows / pruning (swappable in tests)
+    clock: Arc<dyn Clock>,
+
     // range
     current_range: DayRange,
 
@@ -98,22 +163,52 @@ pub struct PexApp {
     total_targets: usize,
     completed: usize,
     failed: usize,
-
-    work_tx: Option<Sender<WorkItem>>,
+    // queued posters dropped by a cancel (already excluded from total_targets)
+    prefetch_skipped: usize,
+    // next prefetch pass revalidates every cached poster, not just stale ones
+    revalidate_all_artwork: bool,
+    retry_sweep: prefetch::RetrySweep,
+
+    work_queue: Option<Arc<PrefetchQueue>>,
+    prefetch_spawner: Option<prefetch::WorkerSpawner>,
     done_rx: Option<Receiver<PrefetchDone>>,
 
+    // freshly downloaded posters waiting for a texture upload (drained visible-first)
+    fresh_uploads: VecDeque<usize>,
+    // cards/blocks that intersected the viewport last frame
+    visible_idxs: HashSet<usize>,
+    // rows with a larger poster variant download in flight, and rows whose upgrade failed
+    // (not retried until the next prefetch restart)
+    variant_upgrades: HashSet<usize>,
+    variant_upgrade_failed: HashSet<usize>,
+    // texture LRU: frame counter + last frame each row's card/block was on screen
+    frame_no: u64,
+    tex_last_used: HashMap<usize, u64>,
+    texture_budget_bytes: usize,
+
     // --- control flags (UI only; not wired yet) ---
     hide_owned: bool,
     dim_owned: bool,
+    hide_watched: bool,
+    dim_watched: bool,
 
     // darken strength for dimming (0.10–0.90)
     dim_strength_ui: f32,
 
+    // treat owned HD copies below this bitrate as upgradable
+    low_bitrate_rule: bool,
+    low_bitrate_mbps: f32,
+
     // background owned scan
     owned_rx: Option<Receiver<OwnedMsg>>,
     owned_keys: Option<HashSet<String>>,
-    owned_hd_keys: Option<HashSet<String>>,
+    owned_details: owned::OwnedDetails,
     owned_modified: Option<HashMap<String, Option<u64>>>,
+    owned_overrides: owned::overrides::OwnedOverrides,
+    owned_link_picker: owned::overrides::OwnedLinkPicker,
+    owned_duplicates: Option<Vec<owned::manifest::DuplicateGroup>>,
+    owned_browser: owned::browser::OwnedBrowser,
+    owned_rescan: owned::rescan::OwnedRescan,
     owned_scan_in_progress: bool,
     owned_scan_messages: VecDeque<String>,
     owned_retry_attempts: u8,
@@ -121,12 +216,33 @@ pub struct PexApp {
     rating_tx: Option<Sender<RatingMsg>>,
     rating_rx: Option<Receiver<RatingMsg>>,
     rating_states: HashMap<String, RatingState>,
+    collections: collections::CollectionsState,
+    bulk_ratings: ratings::BulkRatings,
+    rating_badges: bool,
+    channel_logos: bool,
+    library_refresh: plex_api::LibraryRefreshState,
+    radarr: radarr::RadarrState,
+    trakt: trakt::TraktState,
+    letterboxd: letterboxd::LetterboxdWatchlist,
+    imdb_lists: imdb_lists::ImdbLists,
+    alerts: alerts::AlertRules,
+    external_links: links::ExternalLinks,
 
     scheduled_index: Option<ScheduledIndex>,
+    // later airings per lowercased title and year (rows keep the soonest), and recordings that
+    // clash
+    other_airings: HashMap<RepeatKey, Vec<Airing>>,
+    recording_conflicts: conflicts::RecordingConflicts,
 
     // search/filter/sort controls
     search_query: String,
+    fuzzy_search: bool,
+    search_all_fields: bool,
+    regex_search: search::RegexSearch,
     filter_hd_only: bool,
+    filter_premieres_only: bool,
+    filter_new_only: bool,
+    filter_letterboxd_only: bool,
     filter_owned_before_cutoff: bool,
     owned_before_cutoff_ts: u64,
     owned_before_cutoff_input: String,
@@ -135,26 +251,82 @@ pub struct PexApp {
     // channel filter
     show_channel_filter_popup: bool,
     selected_channels: BTreeSet<String>,
+    favorite_channels: BTreeSet<String>,
+    channel_groups: channel_groups::ChannelGroups,
+    channel_filter_query: String,
+    channel_filter_cursor: usize,
+    channel_filter_focus_pending: bool,
     selected_genres: BTreeSet<String>,
+    title_tags: tags::TitleTags,
+    title_blocklist: blocklist::TitleBlocklist,
+    selected_tags: BTreeSet<String>,
     selected_decades: BTreeSet<i32>,
+    // inclusive release-year bounds from the Filters menu (None = open end)
+    year_min: Option<i32>,
+    year_max: Option<i32>,
     show_genre_filter_popup: bool,
     show_advanced_popup: bool,
+    show_keymap_popup: bool,
+    settings: settings::SettingsEditor,
+    config_watch: config_watch::ConfigWatch,
+    background: background::Background,
+    guide_refresh: guide_refresh::GuideRefresh,
+    seen_listings: new_listings::SeenListings,
+    // built-in sample guide instead of the Plex databases (chosen on the setup gate)
+    demo_mode: bool,
+    // default prefs, no prefetch and no background scans (--safe-mode or repeated crashes)
+    safe_mode: Option<safe_mode::SafeModeReason>,
+    // startup marker cleared once the guide came up
+    startup_confirmed: bool,
+    // downloads switched off (config, Advanced toggle or unreachable servers)
+    offline: Option<net::OfflineReason>,
+    keymap: keymap::Keymap,
+    keymap_capture: Option<keymap::Action>,
+    keymap_feedback: Option<String>,
+    search_focus_pending: bool,
+    /// Enter selected a card; move focus into the detail panel once it shows it.
+    detail_focus_pending: bool,
+    gamepad: gamepad::Gamepad,
+    /// Pad move for the next `handle_keyboard_navigation`.
+    pad_nav: Option<NavDirection>,
+    hover_preview: hover_preview::HoverPreview,
+    density: density::DensityPrefs,
+    resume: resume::Resume,
     advanced_feedback: Option<String>,
     setup_checked: bool,
     setup_errors: Vec<String>,
     setup_warnings: Vec<String>,
+    server_mismatch: Option<String>,
+    health: health::SubsystemHealth,
     stage4_complete_message: Option<String>,
+    export_format: export::ExportFormat,
+    export_path_input: String,
+    export_pick: Option<file_picker::PendingPick<()>>,
+    digest_path_input: String,
+    digest_pick: Option<file_picker::PendingPick<()>>,
+    theme: theme::Theme,
+    /// Theme last pushed into egui; `apply_theme` compares against it.
+    applied_theme: Option<theme::Theme>,
+    /// egui zoom factor (1.0 = 100%), see `apply_ui_scale`.
+    ui_scale: f32,
     channel_icon_textures: HashMap<String, eg::TextureHandle>,
     channel_icon_pending: HashSet<String>,
 
+    // main view (poster grid or channel timeline)
+    view_mode: ViewMode,
+
     // sorting
     sort_key: SortKey,
+    /// Tie-break after `sort_key` (always ascending); `None` falls straight to title.
+    sort_key2: Option<SortKey>,
+    group_by: GroupBy,
+    day_strip: day_strip::DayStrip,
     sort_desc: bool,
 
     // poster size (UI only for now)
     poster_width_ui: f32, // e.g., card width in px
 
-    // concurrency (UI placeholder; not applied to workers yet)
+    // prefetch worker threads (applied live to the running pool)
     worker_count_ui: usize,
 
     // --- prefs autosave ---
@@ -164,6 +336,8 @@ pub struct PexApp {
     last_hotset: Option<std::collections::HashMap<String, PathBuf>>,
 
     selected_idx: Option<usize>,
+    // airing pinned for side-by-side compare with the selection
+    compare_idx: Option<usize>,
     grid_rows: Vec<Vec<usize>>,
     scroll_to_idx: Option<usize>,
     // UI state
@@ -175,6 +349,8 @@ impl Default for PexApp {
         Self {
             rows: Vec::new(),
 
+            clock: Arc::new(SystemClock),
+
             current_range: DayRange::Two,
 
             loading_progress: 0.0,
@@ -198,18 +374,39 @@ impl Default for PexApp {
             total_targets: 0,
             completed: 0,
             failed: 0,
+            prefetch_skipped: 0,
+            revalidate_all_artwork: false,
+            retry_sweep: prefetch::RetrySweep::default(),
 
-            work_tx: None,
+            work_queue: None,
+            prefetch_spawner: None,
             done_rx: None,
 
+            fresh_uploads: VecDeque::new(),
+            visible_idxs: HashSet::new(),
+            variant_upgrades: HashSet::new(),
+            variant_upgrade_failed: HashSet::new(),
+            frame_no: 0,
+            tex_last_used: HashMap::new(),
+            texture_budget_bytes: DEFAULT_TEXTURE_BUDGET_MB as usize * 1024 * 1024,
+
             hide_owned: false,
             dim_owned: false,
+            hide_watched: false,
+            dim_watched: false,
             dim_strength_ui: 0.8, // stronger dimming by default
+            low_bitrate_rule: false,
+            low_bitrate_mbps: 4.0,
 
             owned_rx: None,
             owned_keys: Self::load_owned_keys_sidecar(),
-            owned_hd_keys: Self::load_owned_hd_sidecar(),
+            owned_details: owned::OwnedDetails::load(),
             owned_modified: None,
+            owned_overrides: owned::overrides::OwnedOverrides::load(),
+            owned_link_picker: Default::default(),
+            owned_duplicates: None,
+            owned_browser: Default::default(),
+            owned_rescan: Default::default(),
             owned_scan_in_progress: false,
             owned_scan_messages: VecDeque::new(),
             owned_retry_attempts: 0,
@@ -217,10 +414,29 @@ impl Default for PexApp {
             rating_tx: None,
             rating_rx: None,
             rating_states: HashMap::new(),
+            collections: collections::CollectionsState::default(),
+            bulk_ratings: ratings::BulkRatings::default(),
+            rating_badges: true,
+            channel_logos: true,
+            library_refresh: plex_api::LibraryRefreshState::default(),
+            radarr: radarr::RadarrState::default(),
+            trakt: trakt::TraktState::default(),
+            letterboxd: letterboxd::LetterboxdWatchlist::load(),
+            imdb_lists: imdb_lists::ImdbLists::load(),
+            alerts: alerts::AlertRules::load(),
+            external_links: links::ExternalLinks::default(),
             scheduled_index: None,
+            other_airings: HashMap::new(),
+            recording_conflicts: conflicts::RecordingConflicts::default(),
 
             search_query: String::new(),
+            fuzzy_search: false,
+            search_all_fields: false,
+            regex_search: search::RegexSearch::default(),
             filter_hd_only: false,
+            filter_premieres_only: false,
+            filter_new_only: false,
+            filter_letterboxd_only: false,
             filter_owned_before_cutoff: false,
             owned_before_cutoff_ts: OWNED_BEFORE_CUTOFF_DEFAULT_TS,
             owned_before_cutoff_input: OWNED_BEFORE_CUTOFF_DEFAULT_STR.to_string(),
@@ -228,18 +444,62 @@ impl Default for PexApp {
 
             show_channel_filter_popup: false,
             selected_channels: BTreeSet::new(),
+            favorite_channels: BTreeSet::new(),
+            channel_groups: channel_groups::ChannelGroups::default(),
+            channel_filter_query: String::new(),
+            channel_filter_cursor: 0,
+            channel_filter_focus_pending: false,
             selected_genres: BTreeSet::new(),
+            title_tags: tags::TitleTags::load(),
+            title_blocklist: blocklist::TitleBlocklist::load(),
+            selected_tags: BTreeSet::new(),
             selected_decades: BTreeSet::new(),
+            year_min: None,
+            year_max: None,
             show_genre_filter_popup: false,
             show_advanced_popup: false,
+            show_keymap_popup: false,
+            settings: settings::SettingsEditor::default(),
+            config_watch: config_watch::ConfigWatch::default(),
+            background: background::Background::default(),
+            guide_refresh: guide_refresh::GuideRefresh::default(),
+            seen_listings: new_listings::SeenListings::default(),
+            demo_mode: false,
+            safe_mode: None,
+            startup_confirmed: false,
+            offline: None,
+            keymap: keymap::Keymap::default(),
+            keymap_capture: None,
+            keymap_feedback: None,
+            search_focus_pending: false,
+            detail_focus_pending: false,
+            gamepad: gamepad::Gamepad::default(),
+            pad_nav: None,
+            hover_preview: hover_preview::HoverPreview::default(),
+            resume: resume::Resume::default(),
+            density: density::DensityPrefs::default(),
             advanced_feedback: None,
             setup_checked: false,
             setup_errors: Vec::new(),
             setup_warnings: Vec::new(),
+            server_mismatch: None,
+            health: health::SubsystemHealth::default(),
             stage4_complete_message: None,
+            export_format: export::ExportFormat::Csv,
+            export_path_input: export::default_guide_export_path(export::ExportFormat::Csv),
+            digest_path_input: digest::default_digest_path(),
+            export_pick: None,
+            digest_pick: None,
+            theme: theme::Theme::default(),
+            applied_theme: None,
+            ui_scale: 1.0,
             channel_icon_textures: HashMap::new(),
             channel_icon_pending: HashSet::new(),
+            view_mode: ViewMode::Grid,
             sort_key: SortKey::Time,
+            sort_key2: None,
+            group_by: GroupBy::Day,
+            day_strip: day_strip::DayStrip::default(),
             sort_desc: false,
 
             poster_width_ui: 143.0,        // tuned default card width
@@ -251,6 +511,7 @@ impl Default for PexApp {
             last_hotset: prefs::load_hotset_manifest().ok(),
 
             selected_idx: None,
+            compare_idx: None,
             grid_rows: Vec::new(),
             scroll_to_idx: None,
 
@@ -281,6 +542,7 @@ impl PexApp {
     }
 
     fn handle_keyboard_navigation(&mut self, ctx: &eg::Context) {
+        let pad_nav = self.pad_nav.take();
         if self.grid_rows.is_empty() {
             return;
         }
@@ -289,20 +551,37 @@ impl PexApp {
             return;
         }
 
+        if self.keymap_capture.is_some() {
+            return;
+        }
+
         let mut direction: Option<NavDirection> = None;
         ctx.input(|input| {
-            if input.key_pressed(eg::Key::ArrowUp) {
+            use keymap::Action;
+            if self.keymap.pressed(Action::NavUp, input) {
                 direction = Some(NavDirection::Up);
-            } else if input.key_pressed(eg::Key::ArrowDown) {
+            } else if self.keymap.pressed(Action::NavDown, input) {
                 direction = Some(NavDirection::Down);
-            } else if input.key_pressed(eg::Key::ArrowLeft) {
+            } else if self.keymap.pressed(Action::NavLeft, input) {
                 direction = Some(NavDirection::Left);
-            } else if input.key_pressed(eg::Key::ArrowRight) {
+            } else if self.keymap.pressed(Action::NavRight, input) {
                 direction = Some(NavDirection::Right);
+            } else if self.keymap.pressed(Action::PageUp, input) {
+                direction = Some(NavDirection::PageUp);
+            } else if self.keymap.pressed(Action::PageDown, input) {
+                direction = Some(NavDirection::PageDown);
+            } else if self.keymap.pressed(Action::PrevDay, input) {
+                direction = Some(NavDirection::PrevDay);
+            } else if self.keymap.pressed(Action::NextDay, input)
+                || self.keymap.pressed(Action::DayForward, input)
+            {
+                direction = Some(NavDirection::NextDay);
+            } else if self.keymap.pressed(Action::DayBack, input) {
+                direction = Some(NavDirection::DayBack);
             }
         });
 
-        let Some(dir) = direction else {
+        let Some(dir) = direction.or(pad_nav) else {
             return;
         };
 
@@ -335,6 +614,53 @@ impl PexApp {
         let (row_i, col_i) = self.find_grid_position(current)?;
         let current_row = self.grid_rows.get(row_i)?;
         match dir {
+            NavDirection::PageUp | NavDirection::PageDown => {
+                // Rows with a card on screen last frame make up a page (one kept for context).
+                let page = self
+                    .grid_rows
+                    .iter()
+                    .filter(|row| row.iter().any(|idx| self.visible_idxs.contains(idx)))
+                    .count()
+                    .saturating_sub(1)
+                    .max(1);
+                let target_row = if matches!(dir, NavDirection::PageUp) {
+                    row_i.saturating_sub(page)
+                } else {
+                    (row_i + page).min(self.grid_rows.len() - 1)
+                };
+                let target = &self.grid_rows[target_row];
+                target
+                    .get(col_i.min(target.len().saturating_sub(1)))
+                    .copied()
+                    .or(Some(current))
+            }
+            NavDirection::PrevDay | NavDirection::NextDay | NavDirection::DayBack => {
+                let order: Vec<usize> = self.grid_rows.iter().flatten().copied().collect();
+                // Grid sections by channel / genre jump section to section instead.
+                let by_section = self.view_mode == ViewMode::Grid
+                    && matches!(self.group_by, GroupBy::Channel | GroupBy::Genre);
+                let day = |idx: usize| {
+                    let row = self.rows.get(idx)?;
+                    let bucket = crate::app::utils::day_bucket(row.airing?);
+                    Some(if by_section {
+                        self.group_key(row, bucket)
+                    } else {
+                        GroupKey::Day(bucket)
+                    })
+                };
+                let forward = matches!(dir, NavDirection::NextDay);
+                let target = crate::app::utils::day_jump(&order, day, current, forward);
+                // Home lands on the start of the current day first; Ctrl+Left skips that step.
+                let back_again = matches!(dir, NavDirection::DayBack)
+                    && target.is_some_and(|start| day(start) == day(current));
+                if back_again {
+                    target
+                        .and_then(|start| crate::app::utils::day_jump(&order, day, start, false))
+                        .or(target)
+                } else {
+                    target.or(Some(current))
+                }
+            }
             NavDirection::Left => {
                 if col_i > 0 {
                     Some(current_row[col_i - 1])
@@ -401,7 +727,7 @@ impl PexApp {
         self.grid_rows.iter().any(|row| row.contains(&idx))
     }
 
-    fn sync_selection_with_groups(&mut self, groups: &[(i64, Vec<usize>)]) {
+    fn sync_selection_with_groups(&mut self, groups: &[(GroupKey, Vec<usize>)]) {
         let Some(current) = self.selected_idx else {
             return;
         };
@@ -447,19 +773,41 @@ impl PexApp {
         row.broadcast_hd
     }
 
-    /// Determine whether the owned library already has an HD copy of this title.
-    pub(crate) fn row_owned_is_hd(&self, row: &PosterRow) -> bool {
-        self.owned_hd_keys
-            .as_ref()
-            .is_some_and(|set| set.contains(&row.owned_key))
+    /// Resolution tier of the owned copy (None when the title isn't owned).
+    pub(crate) fn row_owned_tier(&self, row: &PosterRow) -> Option<OwnedTier> {
+        self.owned_details.tier(row)
     }
 
-    fn load_owned_keys_sidecar() -> Option<HashSet<String>> {
-        Self::load_sidecar_file("owned_all.txt")
+    /// Encoding details of the owned copy, when the library scan recorded them.
+    pub(crate) fn row_owned_media(&self, row: &PosterRow) -> Option<&OwnedMedia> {
+        self.owned_details.media(row)
+    }
+
+    /// The "suggest upgrade" bitrate threshold, when that rule is on.
+    const fn low_bitrate_threshold(&self) -> Option<f32> {
+        if self.low_bitrate_rule {
+            Some(self.low_bitrate_mbps)
+        } else {
+            None
+        }
     }
 
-    fn load_owned_hd_sidecar() -> Option<HashSet<String>> {
-        Self::load_sidecar_file("owned_hd.txt")
+    /// Owned HD (not 4K) copy whose bitrate is under the "suggest upgrade" threshold (rule must
+    /// be on).
+    pub(crate) fn row_owned_low_bitrate(&self, row: &PosterRow) -> bool {
+        self.owned_details
+            .low_bitrate(row, self.low_bitrate_threshold())
+    }
+
+    /// Airing in HD while the owned copy is SD, or HD at a bitrate below the threshold. A 4K
+    /// copy is never upgraded by a broadcast.
+    pub(crate) fn row_hd_upgrade_available(&self, row: &PosterRow) -> bool {
+        self.owned_details
+            .hd_upgrade(row, self.low_bitrate_threshold())
+    }
+
+    fn load_owned_keys_sidecar() -> Option<HashSet<String>> {
+        Self::load_sidecar_file("owned_all.txt")
     }
 
     fn load_sidecar_file(file_name: &str) -> Option<HashSet<String>> {
@@ -478,34 +826,41 @@ impl PexApp {
 
 impl PexApp {
     fn refresh_scheduled_index(&mut self) {
-        match crate::app::scheduled::load_scheduled_index() {
+        if self.demo_mode {
+            self.scheduled_index = Some(ScheduledIndex::from_guids(demo::demo_scheduled_guids()));
+            self.health.scheduled = health::Health::Ok;
+            self.apply_scheduled_flags();
+            return;
+        }
+        match crate::app::scheduled::load_scheduled_index(self.clock.as_ref()) {
             Ok(index) => {
                 if index.is_empty() {
                     self.scheduled_index = None;
                 } else {
                     self.scheduled_index = Some(index);
                 }
+                self.health.scheduled =
+                    health::Health::ok_or_stale(&local_library_db_path(), self.now());
             }
             Err(err) => {
                 warn!("Failed to load scheduled recordings: {err}");
                 self.scheduled_index = None;
+                self.health.scheduled = health::Health::Failed(err);
             }
         }
         self.apply_scheduled_flags();
     }
 
     fn apply_scheduled_flags(&mut self) {
-        for row in &mut self.rows {
-            row.scheduled = false;
-        }
-        let Some(index) = self.scheduled_index.as_ref() else {
-            return;
-        };
-        for row in &mut self.rows {
-            if index.is_scheduled(row.guid.as_deref(), &row.title, row.year, row.airing) {
-                row.scheduled = true;
+        match self.scheduled_index.as_ref() {
+            Some(index) => index.flag_rows(&mut self.rows),
+            None => {
+                for row in &mut self.rows {
+                    row.scheduled = false;
+                }
             }
         }
+        self.apply_conflict_flags();
     }
 
     /// Try to upload texture for a single row if a cached file exists (small variant).
@@ -538,26 +893,97 @@ impl PexApp {
         false
     }
 
+    /// Upload posters that just finished downloading, starting with cards that were on screen
+    /// last frame. Off-screen entries stay queued until they scroll into view (or get picked up
+    /// by the regular lazy upload).
+    fn drain_fresh_uploads(&mut self, ctx: &eg::Context) {
+        if self.fresh_uploads.is_empty() {
+            return;
+        }
+        let mut uploaded = 0usize;
+        let mut deferred: VecDeque<usize> = VecDeque::with_capacity(self.fresh_uploads.len());
+        while let Some(idx) = self.fresh_uploads.pop_front() {
+            let pending = self
+                .rows
+                .get(idx)
+                .is_some_and(|row| row.tex.is_none() && !matches!(row.state, PosterState::Failed));
+            if !pending {
+                continue;
+            }
+            if uploaded < MAX_FRESH_UPLOADS_PER_FRAME && self.visible_idxs.contains(&idx) {
+                if self.try_lazy_upload_row(ctx, idx) {
+                    uploaded += 1;
+                }
+            } else {
+                deferred.push_back(idx);
+            }
+        }
+        self.fresh_uploads = deferred;
+        if uploaded > 0 {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Stamp on-screen rows and, when resident poster textures exceed the budget, drop the
+    /// least recently seen ones (the cached file stays; lazy upload brings them back on scroll).
+    fn enforce_texture_budget(&mut self) {
+        self.frame_no += 1;
+        for &idx in &self.visible_idxs {
+            self.tex_last_used.insert(idx, self.frame_no);
+        }
+        if let Some(sel) = self.selected_idx {
+            self.tex_last_used.insert(sel, self.frame_no); // detail panel poster
+        }
+
+        let tex_bytes = |row: &PosterRow| {
+            row.tex
+                .as_ref()
+                .map_or(0, |tex| tex.size()[0] * tex.size()[1] * 4)
+        };
+        let total: usize = self.rows.iter().map(tex_bytes).sum();
+        if total <= self.texture_budget_bytes {
+            return;
+        }
+
+        let idle_before = self.frame_no.saturating_sub(TEXTURE_MIN_IDLE_FRAMES);
+        let mut candidates: Vec<(u64, usize, usize)> = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.tex.is_some())
+            .filter_map(|(idx, row)| {
+                let last = self.tex_last_used.get(&idx).copied().unwrap_or(0);
+                (last <= idle_before).then(|| (last, idx, tex_bytes(row)))
+            })
+            .collect();
+        candidates.sort_unstable();
+
+        let target = self.texture_budget_bytes / 10 * 9;
+        let mut remaining = total;
+        for (_, idx, bytes) in candidates {
+            if remaining <= target {
+                break;
+            }
+            if let Some(row) = self.rows.get_mut(idx) {
+                row.tex = None;
+                row.state = PosterState::Cached;
+                remaining -= bytes;
+            }
+        }
+    }
+
     /// Upload a handful of textures immediately for the first visible window (fast perception).
     fn prewarm_first_screen(&mut self, ctx: &eg::Context) {
         // Only target near-future rows (for 2d/7d/etc.) and take the first PREWARM_UPLOADS
-        let now_bucket = utils::day_bucket(SystemTime::now());
-        let max_bucket_opt = self.current_range.max_bucket(now_bucket);
+        let now = self.now();
+        let range = self.current_range;
 
         let targets: Vec<usize> = self
             .rows
             .iter()
             .enumerate()
             .filter_map(|(idx, row)| {
-                let b = row.airing.map(utils::day_bucket)?;
-                if b < now_bucket {
-                    return None;
-                }
-                if let Some(max_b) = max_bucket_opt {
-                    if b >= max_b {
-                        return None;
-                    }
-                }
+                filters::day_window_bucket(row.airing?, now, range)?;
                 Some(idx)
             })
             .take(PREWARM_UPLOADS * 2) // grab a few extra so we have buffers
@@ -582,14 +1008,15 @@ impl PexApp {
         self.setup_warnings.clear();
         self.set_status("Stage 1/4 – Checking config & cache (validates Plex paths and tools).");
 
-        let cfg_path = Path::new("config.json");
-        if !cfg_path.exists() {
+        if !crate::config::config_path().exists() {
             self.setup_warnings.push(
-                "config.json not found next to the executable; using built-in defaults.".into(),
+                "No config.json or config.toml next to the executable; using built-in defaults."
+                    .into(),
             );
         }
 
         let cfg = load_config();
+        self.setup_errors.extend(cfg.errors.iter().cloned());
         let local_db = local_db_path();
         if !local_db.exists() {
             if cfg.plex_epg_db_source.is_some() {
@@ -605,24 +1032,37 @@ impl PexApp {
             }
         }
 
-        let tmdb_missing = cfg
-            .tmdb_api_key
-            .as_ref()
-            .is_none_or(|k| k.trim().is_empty());
-        if tmdb_missing {
-            self.setup_warnings
-                .push("tmdb_api_key not set; ratings button will be disabled.".into());
-        } else if cfg
-            .tmdb_api_key
-            .as_ref()
-            .is_some_and(|k| k.contains("REPLACE_ME") || k.contains("YOUR"))
-        {
+        self.setup_warnings.extend(ratings::config_warnings(&cfg));
+        self.apply_texture_budget(&cfg);
+
+        if cfg.offline {
+            self.go_offline(net::OfflineReason::Configured);
+        }
+        self.setup_warnings
+            .extend(net::proxy_warnings().iter().cloned());
+
+        let now = self.now();
+        self.library_refresh.configure(&cfg, now);
+        self.guide_refresh.configure(&cfg);
+        self.radarr.configure(&cfg);
+        self.trakt.configure(&cfg);
+        self.owned_rescan.configure(&cfg);
+        self.recording_conflicts.configure(&cfg);
+        self.external_links.configure(&cfg);
+        self.sync_radarr();
+        self.sync_trakt();
+        if cfg.plex_refresh_after_recording && !self.library_refresh.is_configured() {
             self.setup_warnings.push(
-                "tmdb_api_key still uses the placeholder value; replace it with your TMDB V3 API key."
-                    .into(),
+                "plex_refresh_after_recording is on but plex_server_url/plex_token are not set; Plex won't be asked to rescan.".into(),
             );
         }
 
+        self.server_mismatch = server_identity::server_mismatch_warning(&cfg);
+        if let Some(msg) = &self.server_mismatch {
+            warn!("{msg}");
+            self.setup_warnings.push(msg.clone());
+        }
+
         if !self.setup_errors.is_empty() {
             if let Some(first) = self.setup_errors.first() {
                 self.set_status(format!("Setup required: {first}"));
@@ -632,6 +1072,14 @@ impl PexApp {
         }
     }
 
+    fn apply_texture_budget(&mut self, cfg: &crate::config::AppConfig) {
+        let budget_mb = cfg
+            .texture_budget_mb
+            .unwrap_or(DEFAULT_TEXTURE_BUDGET_MB)
+            .max(MIN_TEXTURE_BUDGET_MB);
+        self.texture_budget_bytes = budget_mb as usize * 1024 * 1024;
+    }
+
     fn render_setup_gate(&mut self, ctx: &eg::Context) {
         eg::CentralPanel::default()
             .frame(
@@ -671,13 +1119,71 @@ impl PexApp {
                 }
 
                 ui.add_space(16.0);
-                if ui.button("Retry checks").clicked() {
-                    self.setup_checked = false;
+                ui.horizontal(|ui| {
+                    if ui.button("Retry checks").clicked() {
+                        self.setup_checked = false;
+                    }
+                    if ui.button("Open settings…").clicked() {
+                        self.open_settings();
+                    }
+                });
+                ui.label(
+                    "Fix the paths in Settings (or edit config.json next to the executable), \
+                     then press Retry.",
+                );
+
+                ui.add_space(16.0);
+                ui.separator();
+                ui.label(
+                    "Just looking? Explore Pex with a built-in sample guide: placeholder posters, \
+                     a few owned titles and scheduled recordings. Nothing is read from Plex.",
+                );
+                if ui.button("Explore demo data").clicked() {
+                    self.start_demo_mode();
                 }
-                ui.label("Edit config.json next to the executable, then press Retry.");
             });
     }
 
+    /// Skip the Plex checks and boot with the bundled demo dataset (this session only).
+    fn start_demo_mode(&mut self) {
+        self.demo_mode = true;
+        self.setup_errors.clear();
+        self.setup_warnings
+            .push("Demo mode: showing built-in sample airings, not your Plex guide.".into());
+        self.advanced_feedback = Some(self.setup_warnings.join("\n"));
+        info!("demo mode enabled from the setup gate");
+    }
+
+    /// Persistent warning strip while the EPG and library DBs look like different servers.
+    fn ui_render_server_mismatch_banner(&mut self, ctx: &eg::Context) {
+        let Some(msg) = self.server_mismatch.clone() else {
+            return;
+        };
+        eg::TopBottomPanel::top("server_mismatch_banner")
+            .frame(
+                eg::Frame::default()
+                    .inner_margin(eg::Margin::symmetric(10.0, 6.0))
+                    .fill(eg::Color32::from_rgb(110, 30, 30)),
+            )
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        eg::RichText::new("⚠ Plex server mismatch")
+                            .strong()
+                            .color(eg::Color32::WHITE),
+                    );
+                    ui.add(eg::Label::new(eg::RichText::new(msg).color(eg::Color32::WHITE)).wrap());
+                    if ui.small_button("Dismiss").clicked() {
+                        self.server_mismatch = None;
+                    }
+                });
+            });
+    }
+
+    pub(crate) fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
     fn set_status<S: Into<String>>(&mut self, s: S) {
         let s = s.into();
         let due = self.status_last_emit.elapsed() >= Duration::from_millis(STATUS_EMIT_EVERY_MS);
@@ -747,29 +1253,36 @@ impl PexApp {
             return;
         }
 
-        let cfg = load_config();
-        let Some(api_key) = cfg
-            .tmdb_api_key
-            .filter(|k| !k.trim().is_empty())
-            .map(|k| k.trim().to_string())
-        else {
+        if self.is_offline() {
+            self.rating_states.insert(
+                key,
+                RatingState::Error("offline mode; ratings need the network".into()),
+            );
+            return;
+        }
+
+        let providers = ratings::configured_providers(&load_config());
+        if providers.is_empty() {
             self.rating_states.insert(key, RatingState::MissingApiKey);
             return;
-        };
+        }
 
-        let imdb_id = row.guid.as_deref().and_then(imdb_id_from_guid);
-        let title = row.title.clone();
-        let year = row.year;
+        let query = ratings::RatingQuery {
+            imdb_id: row.guid.as_deref().and_then(imdb_id_from_guid),
+            title: row.title.clone(),
+            year: row.year,
+        };
         let sender = self.ensure_rating_channel();
 
         self.rating_states.insert(key.clone(), RatingState::Pending);
 
         std::thread::spawn(move || {
-            let state = fetch_rating_from_tmdb(api_key, imdb_id, title, year);
+            let state = ratings::fetch_rating(&providers, &query);
             let _ = sender.send(RatingMsg { key, state });
         });
     }
 
+    #[allow(clippy::while_let_loop)]
     fn poll_rating_updates(&mut self) {
         use std::sync::mpsc::TryRecvError;
 
@@ -778,9 +1291,7 @@ impl PexApp {
                 break;
             };
             match rx.try_recv() {
-                Ok(msg) => {
-                    self.rating_states.insert(msg.key, msg.state);
-                }
+                Ok(msg) => self.record_rating(msg),
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => {
                     self.rating_rx = None;
@@ -795,12 +1306,21 @@ impl PexApp {
         self.prep_started = false;
         self.prep_rx = None;
         self.prefetch_started = false;
-        self.work_tx = None;
+        if let Some(queue) = self.work_queue.take() {
+            queue.close();
+        }
+        self.prefetch_spawner = None;
         self.done_rx = None;
+        self.fresh_uploads.clear();
+        self.visible_idxs.clear();
+        self.variant_upgrades.clear();
+        self.variant_upgrade_failed.clear();
+        self.tex_last_used.clear();
         self.rows.clear();
         self.total_targets = 0;
         self.completed = 0;
         self.failed = 0;
+        self.prefetch_skipped = 0;
         self.loading_progress = 0.0;
         self.last_item_msg.clear();
         self.stage4_complete_message = None;
@@ -809,9 +1329,16 @@ impl PexApp {
         self.boot_phase = BootPhase::Starting;
         self.last_hotset = crate::app::prefs::load_hotset_manifest().ok();
         self.selected_idx = None;
+        self.compare_idx = None;
         self.grid_rows.clear();
         self.scroll_to_idx = None;
+        // Keep cached ratings across the restart; drop in-flight and error states.
+        if let Err(err) = self.save_rating_cache() {
+            warn!("failed to save ratings cache: {err}");
+        }
         self.rating_states.clear();
+        self.load_rating_cache();
+        self.reset_bulk_ratings();
         self.channel_icon_textures.clear();
         self.channel_icon_pending.clear();
         self.owned_modified = None;
@@ -884,7 +1411,10 @@ impl PexApp {
                 .and_then(|ext| ext.to_str())
                 .map(|ext| {
                     let ext = ext.to_ascii_lowercase();
-                    matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "webp" | "rgba")
+                    matches!(
+                        ext.as_str(),
+                        "png" | "jpg" | "jpeg" | "webp" | "rgba" | "validators"
+                    )
                 })
                 .unwrap_or(false);
             if !remove {
@@ -901,7 +1431,13 @@ impl PexApp {
     fn clear_owned_cache_files(&self) -> Result<usize, String> {
         let dir = crate::app::cache::cache_dir();
         let mut removed = 0usize;
-        for name in ["owned_all.txt", "owned_hd.txt"] {
+        for name in [
+            "owned_all.txt",
+            "owned_hd.txt",
+            "owned_uhd.txt",
+            "owned_media.txt",
+            owned::manifest::MANIFEST_FILE,
+        ] {
             let path = dir.join(name);
             match fs::remove_file(&path) {
                 Ok(_) => removed += 1,
@@ -931,7 +1467,7 @@ impl PexApp {
         }
         self.owned_rx = None;
         self.owned_keys = None;
-        self.owned_hd_keys = None;
+        self.owned_details = owned::OwnedDetails::default();
         self.owned_modified = None;
         for row in &mut self.rows {
             row.owned = false;
@@ -974,166 +1510,13 @@ fn imdb_id_from_guid(guid: &str) -> Option<String> {
     }
 }
 
-#[derive(Deserialize)]
-struct TmdbFindResponse {
-    #[serde(default)]
-    movie_results: Vec<TmdbMovie>,
-}
-
-#[derive(Deserialize)]
-struct TmdbSearchResponse {
-    #[serde(default)]
-    results: Vec<TmdbMovie>,
-}
-
-#[derive(Deserialize)]
-struct TmdbMovie {
-    #[serde(default)]
-    vote_average: f32,
-    #[serde(default)]
-    vote_count: u32,
-    #[serde(default)]
-    release_date: Option<String>,
-}
-
-fn fetch_rating_from_tmdb(
-    api_key: String,
-    imdb_id: Option<String>,
-    title: String,
-    year: Option<i32>,
-) -> RatingState {
-    if imdb_id.is_none() && title.trim().is_empty() {
-        return RatingState::NotFound;
-    }
-
-    let client = match reqwest::blocking::Client::builder()
-        .user_agent("pex/rating-fetch")
-        .timeout(Duration::from_secs(10))
-        .build()
-    {
-        Ok(c) => c,
-        Err(err) => return RatingState::Error(format!("client: {err}")),
-    };
-
-    if let Some(id) = imdb_id {
-        match tmdb_find_by_imdb(&client, &api_key, &id, year) {
-            Ok(Some(state)) => return state,
-            Ok(None) => {}
-            Err(err) => return err,
-        }
-    }
-
-    let title = title.trim();
-    if title.is_empty() {
-        return RatingState::NotFound;
-    }
-
-    match tmdb_search_by_title(&client, &api_key, title, year) {
-        Ok(Some(state)) => state,
-        Ok(None) => RatingState::NotFound,
-        Err(err) => err,
-    }
-}
-
-fn tmdb_find_by_imdb(
-    client: &reqwest::blocking::Client,
-    api_key: &str,
-    imdb_id: &str,
-    year: Option<i32>,
-) -> Result<Option<RatingState>, RatingState> {
-    let url = format!(
-        "https://api.themoviedb.org/3/find/{imdb_id}?api_key={api_key}&language=en-US&external_source=imdb_id"
-    );
-    let body = tmdb_get(client, &url)?;
-    let parsed: TmdbFindResponse = parse_tmdb_body(&body)?;
-    Ok(extract_tmdb_rating(parsed.movie_results, year))
-}
-
-fn tmdb_search_by_title(
-    client: &reqwest::blocking::Client,
-    api_key: &str,
-    title: &str,
-    year: Option<i32>,
-) -> Result<Option<RatingState>, RatingState> {
-    let mut url = format!(
-        "https://api.themoviedb.org/3/search/movie?api_key={api_key}&language=en-US&include_adult=false&query={}",
-        encode(title)
-    );
-    if let Some(y) = year {
-        url.push_str(&format!("&year={y}"));
-    }
-    let body = tmdb_get(client, &url)?;
-    let parsed: TmdbSearchResponse = parse_tmdb_body(&body)?;
-    Ok(extract_tmdb_rating(parsed.results, year))
-}
-
-fn tmdb_get(client: &reqwest::blocking::Client, url: &str) -> Result<String, RatingState> {
-    let resp = client
-        .get(url)
-        .send()
-        .map_err(|err| RatingState::Error(format!("network: {err}")))?;
-    if !resp.status().is_success() {
-        return Err(RatingState::Error(format!("HTTP {}", resp.status())));
-    }
-    resp.text()
-        .map_err(|err| RatingState::Error(format!("read: {err}")))
-}
-
-fn parse_tmdb_body<T: DeserializeOwned>(body: &str) -> Result<T, RatingState> {
-    let value: serde_json::Value =
-        serde_json::from_str(body).map_err(|err| RatingState::Error(format!("parse: {err}")))?;
-    if let Some(status) = value.get("status_code") {
-        let code = status.as_i64().unwrap_or_default();
-        let message = value
-            .get("status_message")
-            .and_then(|m| m.as_str())
-            .unwrap_or("TMDb request failed");
-        return Err(RatingState::Error(format!("TMDb error {code}: {message}")));
-    }
-    serde_json::from_value(value).map_err(|err| RatingState::Error(format!("parse: {err}")))
-}
-
-fn extract_tmdb_rating(movies: Vec<TmdbMovie>, target_year: Option<i32>) -> Option<RatingState> {
-    let mut fallback: Option<(f32, u32)> = None;
-
-    for movie in movies {
-        if movie.vote_average <= 0.0 || movie.vote_count == 0 {
-            continue;
-        }
-
-        if let Some(target) = target_year {
-            if tmdb_release_year(&movie.release_date) == Some(target) {
-                return Some(format_tmdb_rating(movie.vote_average, movie.vote_count));
-            }
-        }
-
-        if fallback.is_none() {
-            fallback = Some((movie.vote_average, movie.vote_count));
-        }
-    }
-
-    fallback.map(|(avg, count)| format_tmdb_rating(avg, count))
-}
-
-fn tmdb_release_year(date: &Option<String>) -> Option<i32> {
-    let value = date.as_ref()?;
-    let year = value.split('-').next()?;
-    year.parse().ok()
-}
-
-fn format_tmdb_rating(avg: f32, count: u32) -> RatingState {
-    let votes = match count {
-        0 => "0 votes".to_string(),
-        1 => "1 vote".to_string(),
-        _ => format!("{count} votes"),
-    };
-    RatingState::Success(format!("TMDb {:.1}/10 ({})", avg, votes))
-}
-
 impl eframe::App for PexApp {
     fn update(&mut self, ctx: &eg::Context, _frame: &mut eframe::Frame) {
         // Keep frames moving so Windows never flags "Not Responding"
         ctx.request_repaint();
+        self.handle_close_request(ctx);
+        self.apply_theme(ctx);
+        self.apply_ui_scale(ctx);
 
         // First frame
         if !self.did_init {
@@ -1143,10 +1526,14 @@ impl eframe::App for PexApp {
 
             if !self.setup_errors.is_empty() {
                 self.render_setup_gate(ctx);
+                self.ui_render_settings_window(ctx);
                 return;
             }
 
-            self.load_prefs();
+            if !self.in_safe_mode() {
+                self.load_prefs();
+                self.load_rating_cache();
+            }
             self.prefs_dirty = false;
             self.did_init = true;
             self.loading_message = if self.setup_warnings.is_empty() {
@@ -1158,20 +1545,46 @@ impl eframe::App for PexApp {
             self.heartbeat_dots = 0;
 
             // Kick off poster prep first (Stage 2), then owned scan (Stage 3)
-            self.start_poster_prep();
-            self.start_owned_scan();
+            if !self.start_from_snapshot(ctx) {
+                self.start_poster_prep();
+            }
+            if self.in_safe_mode() {
+                self.record_owned_message("Safe mode: owned scan paused (using the last results).");
+            } else {
+                self.start_owned_scan();
+            }
         }
 
         // Drive warm-up progress
         self.poll_prep(ctx);
         self.poll_owned_scan(ctx);
 
-        // Keep prefetch draining while it's running
-        if self.prefetch_started && self.loading_progress < 1.0 {
+        // Keep prefetch draining while it's running (or larger poster variants are in flight)
+        if self.prefetch_started
+            && (self.loading_progress < 1.0 || !self.variant_upgrades.is_empty())
+        {
             self.poll_prefetch_done(ctx);
         }
+        self.sweep_failed_posters();
+        self.check_connectivity();
+        self.drain_fresh_uploads(ctx);
+        self.enforce_texture_budget();
+        self.rerank_prefetch_queue();
+        if self.view_mode == ViewMode::Grid {
+            self.schedule_variant_upgrades(ctx);
+        }
 
         self.poll_rating_updates();
+        self.poll_bulk_ratings();
+        self.poll_collection_updates();
+        self.poll_library_refresh();
+        self.poll_radarr();
+        self.poll_trakt();
+        self.poll_guide_refresh(ctx);
+        self.poll_owned_rescan();
+        self.poll_config_changes(ctx);
+
+        self.ui_render_safe_mode_banner(ctx);
 
         // If warm-up not finished, show calm splash and return
         if self.boot_phase != types::BootPhase::Ready {
@@ -1183,6 +1596,8 @@ impl eframe::App for PexApp {
             return;
         }
 
+        self.confirm_startup();
+
         // If prefetch finished, swap phase
         if self.prefetch_started
             && self.loading_progress >= 1.0
@@ -1221,18 +1636,34 @@ impl eframe::App for PexApp {
             self.heartbeat_dots = (self.heartbeat_dots + 1) % 4;
         }
 
+        self.ui_render_server_mismatch_banner(ctx);
+        self.regex_search.sync(&self.search_query);
+
+        self.poll_gamepad(ctx);
+
         // --- NEW: Right-side detail panel (shown when selected) ---
         self.ui_render_detail_panel(ctx);
+        self.ui_render_compare_panel(ctx);
 
         // ---- Main UI ----
         eg::CentralPanel::default().show(ctx, |ui| {
+            self.handle_shortcuts(ctx);
+
             // Top bar (range/search/sort/workers/owned)
             self.ui_render_topbar(ui);
 
             // Channel & genre filter popups (separate windows)
             self.ui_render_channel_filter_popup(ctx);
             self.ui_render_genre_filter_popup(ctx);
+            self.ui_render_tag_manager(ctx);
+            self.ui_render_alerts_window(ctx);
             self.ui_render_advanced_popup(ctx);
+            self.ui_render_collections_popup(ctx);
+            self.ui_render_owned_link_window(ctx);
+            self.ui_render_owned_duplicates_window(ctx);
+            self.ui_render_owned_browser(ctx);
+            self.ui_render_keymap_popup(ctx);
+            self.ui_render_settings_window(ctx);
 
             // Decide whether to show the early splash (before enough textures ready)
             let show_splash = !self.should_show_grid();
@@ -1260,6 +1691,9 @@ impl eframe::App for PexApp {
                     };
 
                     ui.add(eg::ProgressBar::new(db_phase).show_percentage());
+                    if self.prefetch_running() {
+                        ui.horizontal(|ui| self.ui_render_prefetch_controls(ui));
+                    }
                     ui.separator();
                     ui.add(eg::Spinner::new().size(14.0));
                     ui.separator();
@@ -1279,17 +1713,35 @@ impl eframe::App for PexApp {
                 return;
             }
 
-            // Grouped grid
-            self.ui_render_grouped_grid(ui, ctx);
+            self.apply_resume();
+            match self.view_mode {
+                ViewMode::Grid => self.ui_render_grouped_grid(ui, ctx),
+                ViewMode::Timeline => self.ui_render_timeline(ui, ctx),
+                ViewMode::List => self.ui_render_list(ui, ctx),
+                ViewMode::NowNext => self.ui_render_now_next(ui, ctx),
+            }
         });
 
         self.maybe_save_prefs();
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
-        let _ = self.save_hotset_manifest(180); // remember ~a couple of screens
-        if let Err(err) = self.save_prefs() {
-            warn!("Failed to persist UI preferences on exit: {err}");
+        safe_mode::finish_startup();
+        // Safe mode starts from defaults; only overwrite saved state the user actually changed.
+        if !self.in_safe_mode() {
+            let _ = self.save_hotset_manifest(180); // remember ~a couple of screens
+            if let Err(err) = self.save_guide_snapshot() {
+                warn!("Failed to save the guide snapshot on exit: {err}");
+            }
+        }
+        let _ = cache::save_poster_swatches();
+        if let Err(err) = self.save_rating_cache() {
+            warn!("Failed to save ratings cache on exit: {err}");
+        }
+        if !self.in_safe_mode() || self.prefs_dirty {
+            if let Err(err) = self.save_prefs() {
+                warn!("Failed to persist UI preferences on exit: {err}");
+            }
         }
     }
 }
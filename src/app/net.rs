@@ -0,0 +1,214 @@
+// src/app/net.rs
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use eframe::egui as eg;
+
+// Read by worker threads before any request; mirrors `PexApp::offline`.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+// Consecutive poster requests that could not reach their server (connect errors / timeouts).
+static UNREACHABLE_STREAK: AtomicUsize = AtomicUsize::new(0);
+// This many unreachable requests in a row switches the app to offline mode.
+const UNREACHABLE_LIMIT: usize = 8;
+
+pub(crate) const OFFLINE_ERR: &str = "offline mode";
+
+// Proxies resolved once from config.json / environment, plus any that failed to parse.
+static PROXIES: OnceLock<(Vec<reqwest::Proxy>, Vec<String>)> = OnceLock::new();
+
+/// Why downloads are switched off.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum OfflineReason {
+    /// `"offline": true` in config.json or `PEX_OFFLINE` set.
+    Configured,
+    /// Chosen from the Advanced menu.
+    Manual,
+    /// Poster requests kept failing to connect.
+    Unreachable,
+}
+
+impl OfflineReason {
+    pub const fn describe(&self) -> &'static str {
+        match self {
+            Self::Configured => "Offline mode is set in config.json (or PEX_OFFLINE).",
+            Self::Manual => "Offline mode was switched on in the Advanced menu.",
+            Self::Unreachable => "Poster servers stopped answering, so Pex went offline.",
+        }
+    }
+}
+
+pub(crate) fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Bail out of a download before touching the network while offline.
+pub(crate) fn ensure_online() -> Result<(), String> {
+    if is_offline() {
+        Err(OFFLINE_ERR.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Feed a request outcome into the unreachable-server detector and pass it through.
+pub(crate) fn track<T>(result: reqwest::Result<T>) -> reqwest::Result<T> {
+    match &result {
+        Ok(_) => UNREACHABLE_STREAK.store(0, Ordering::Relaxed),
+        Err(err) if err.is_connect() || err.is_timeout() => {
+            UNREACHABLE_STREAK.fetch_add(1, Ordering::Relaxed);
+        }
+        Err(_) => {}
+    }
+    result
+}
+
+/// `reqwest` client builder with the configured proxies; every HTTP client in Pex starts here.
+pub(crate) fn client_builder() -> reqwest::blocking::ClientBuilder {
+    let mut builder = reqwest::blocking::Client::builder();
+    for proxy in &proxies().0 {
+        builder = builder.proxy(proxy.clone());
+    }
+    builder
+}
+
+/// Proxy settings that could not be used (shown as setup warnings).
+pub(crate) fn proxy_warnings() -> &'static [String] {
+    &proxies().1
+}
+
+/// Config key first, then the usual environment variables (upper and lower case).
+fn pick_proxy(
+    configured: Option<String>,
+    env_names: &[&str],
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    configured.or_else(|| {
+        env_names.iter().find_map(|name| {
+            env(name)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        })
+    })
+}
+
+fn proxies() -> &'static (Vec<reqwest::Proxy>, Vec<String>) {
+    PROXIES.get_or_init(|| {
+        let cfg = crate::config::load_config();
+        let env = |name: &str| std::env::var(name).ok();
+        let no_proxy = reqwest::NoProxy::from_env();
+        let mut proxies = Vec::new();
+        let mut problems = Vec::new();
+        let schemes = [
+            ("http_proxy", cfg.http_proxy, ["HTTP_PROXY", "http_proxy"]),
+            (
+                "https_proxy",
+                cfg.https_proxy,
+                ["HTTPS_PROXY", "https_proxy"],
+            ),
+        ];
+        for (key, configured, env_names) in schemes {
+            let Some(url) = pick_proxy(configured, &env_names, env) else {
+                continue;
+            };
+            let proxy = if key == "https_proxy" {
+                reqwest::Proxy::https(&url)
+            } else {
+                reqwest::Proxy::http(&url)
+            };
+            match proxy {
+                Ok(proxy) => {
+                    tracing::info!("using {key} {url}");
+                    proxies.push(proxy.no_proxy(no_proxy.clone()));
+                }
+                Err(err) => {
+                    problems.push(format!("{key} \"{url}\" is not a usable proxy URL: {err}"))
+                }
+            }
+        }
+        (proxies, problems)
+    })
+}
+
+impl crate::app::PexApp {
+    pub(crate) const fn is_offline(&self) -> bool {
+        self.offline.is_some()
+    }
+
+    /// Stop every download: queued posters are skipped and cached art is shown as-is.
+    pub(crate) fn go_offline(&mut self, reason: OfflineReason) {
+        if self.offline.is_some() {
+            return;
+        }
+        OFFLINE.store(true, Ordering::Relaxed);
+        tracing::info!("going offline: {}", reason.describe());
+        self.offline = Some(reason);
+        self.cancel_prefetch();
+        self.set_status("Offline: showing cached posters only; ratings and downloads are paused.");
+    }
+
+    /// Leave offline mode and queue the posters that were skipped.
+    pub(crate) fn go_online(&mut self, ctx: &eg::Context) {
+        if self.offline.take().is_none() {
+            return;
+        }
+        OFFLINE.store(false, Ordering::Relaxed);
+        UNREACHABLE_STREAK.store(0, Ordering::Relaxed);
+        self.set_status("Back online; fetching missing posters.");
+        if !self.rows.is_empty() {
+            self.restart_prefetch(ctx);
+        }
+    }
+
+    /// Switch to offline mode once poster requests keep failing to connect.
+    pub(crate) fn check_connectivity(&mut self) {
+        if self.offline.is_none() && UNREACHABLE_STREAK.load(Ordering::Relaxed) >= UNREACHABLE_LIMIT
+        {
+            self.go_offline(OfflineReason::Unreachable);
+        }
+    }
+
+    /// Topbar chip while offline; clicking it tries the network again.
+    pub(crate) fn ui_render_offline_indicator(&mut self, ui: &mut eg::Ui) {
+        let Some(reason) = self.offline.as_ref() else {
+            return;
+        };
+        let text = eg::RichText::new("⚡ Offline").color(eg::Color32::from_rgb(230, 170, 40));
+        let hover = format!(
+            "{}\nOnly cached posters are shown; TMDb and Plex requests are skipped.\nClick to go back online.",
+            reason.describe()
+        );
+        if ui
+            .add(eg::Button::new(text).small())
+            .on_hover_text(hover)
+            .clicked()
+        {
+            let ctx = ui.ctx().clone();
+            self.go_online(&ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_proxy_wins_over_environment() {
+        let env = |name: &str| match name {
+            "https_proxy" => Some(" http://corp-proxy:3128 ".to_string()),
+            "HTTP_PROXY" => Some(String::new()),
+            _ => None,
+        };
+        let names = ["HTTPS_PROXY", "https_proxy"];
+        assert_eq!(
+            pick_proxy(Some("http://cfg:8080".into()), &names, env).as_deref(),
+            Some("http://cfg:8080")
+        );
+        assert_eq!(
+            pick_proxy(None, &names, env).as_deref(),
+            Some("http://corp-proxy:3128")
+        );
+        // Empty variables don't count as configured.
+        assert_eq!(pick_proxy(None, &["HTTP_PROXY"], env), None);
+    }
+}
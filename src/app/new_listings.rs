@@ -0,0 +1,117 @@
+// src/app/new_listings.rs
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use tracing::warn;
+
+const SEEN_FILE: &str = "guide_seen.txt";
+
+/// Airings in the guide when Pex last ran, so airings added since can be flagged. Stored as
+/// `title\tchannel\tbegins_at` lines in the cache dir; each run writes the guide it saw.
+#[derive(Default)]
+pub(crate) struct SeenListings {
+    /// The previous session's airings, read once per run on the first guide load.
+    previous: Option<HashSet<String>>,
+}
+
+fn seen_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(SEEN_FILE)
+}
+
+/// One airing's line in the seen file: lowercase title, raw channel and start (Unix seconds).
+fn fingerprint(title: &str, channel: Option<&str>, begins_at: u64) -> String {
+    let clean = |s: &str| s.trim().replace(['\t', '\n'], " ");
+    format!(
+        "{}\t{}\t{begins_at}",
+        clean(&title.to_lowercase()),
+        clean(channel.unwrap_or_default())
+    )
+}
+
+fn begins_at(line: &str) -> Option<u64> {
+    line.rsplit('\t').next()?.parse().ok()
+}
+
+/// What to remember for next time: this guide's airings plus earlier ones that have not aired
+/// yet (they may just be outside the day range loaded this time).
+fn next_seen<'a>(
+    previous: &'a HashSet<String>,
+    current: impl IntoIterator<Item = &'a str>,
+    now: u64,
+) -> BTreeSet<&'a str> {
+    previous
+        .iter()
+        .map(String::as_str)
+        .filter(|line| begins_at(line).is_some_and(|ts| ts >= now))
+        .chain(current)
+        .collect()
+}
+
+fn unix_secs(ts: SystemTime) -> Option<u64> {
+    ts.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+impl crate::app::PexApp {
+    /// Flag airings that were not in the guide the last time Pex ran, then record this guide.
+    /// Nothing is flagged on the first run. The demo guide leaves the file alone.
+    pub(crate) fn apply_new_listing_flags(&mut self) {
+        if self.demo_mode {
+            return;
+        }
+        let now = unix_secs(self.now()).unwrap_or_default();
+        let previous = self.seen_listings.previous.get_or_insert_with(|| {
+            fs::read_to_string(seen_path())
+                .unwrap_or_default()
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(String::from)
+                .collect()
+        });
+        let tracking = !previous.is_empty();
+        let mut current = Vec::new();
+        for row in &mut self.rows {
+            let Some(airing) = row.airing.and_then(unix_secs) else {
+                row.new_in_guide = false;
+                continue;
+            };
+            let line = fingerprint(&row.title, row.channel_raw.as_deref(), airing);
+            row.new_in_guide = tracking && !previous.contains(&line);
+            current.push(line);
+        }
+        let lines: Vec<&str> = next_seen(previous, current.iter().map(String::as_str), now)
+            .into_iter()
+            .collect();
+        if let Err(err) = fs::write(seen_path(), lines.join("\n")) {
+            warn!("Failed to write {}: {err}", seen_path().display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remembers_this_guide_and_upcoming_airings_from_the_last() {
+        let line = fingerprint(" Alien ", Some("ITV2"), 2_000);
+        assert_eq!(line, "alien\tITV2\t2000");
+        assert_eq!(begins_at(&line), Some(2_000));
+
+        let previous: HashSet<String> = [
+            fingerprint("Heat", Some("BBC1"), 500),
+            fingerprint("Alien", Some("ITV2"), 2_000),
+        ]
+        .into_iter()
+        .collect();
+        let current = [fingerprint("Arrival", None, 1_500)];
+        let next = next_seen(&previous, current.iter().map(String::as_str), 1_000);
+        assert_eq!(
+            next.into_iter().collect::<Vec<_>>(),
+            vec!["alien\tITV2\t2000", "arrival\t\t1500"]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+
+use eframe::egui as eg;
+use egui_extras::{Column, TableBuilder};
+
+use crate::app::owned::manifest::{format_size, load_manifest, OwnedFile};
+use crate::app::utils::{format_owned_timestamp, normalize_title};
+use crate::app::PexApp;
+
+/// Columns the owned browser can sort by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum BrowserSort {
+    #[default]
+    Title,
+    Year,
+    Tier,
+    Recorded,
+    Path,
+}
+
+/// "Owned library" window: the manifest from the last owned scan, searchable and sortable.
+#[derive(Default)]
+pub(crate) struct OwnedBrowser {
+    pub open: bool,
+    files: Vec<OwnedFile>,
+    query: String,
+    sort: BrowserSort,
+    descending: bool,
+}
+
+impl OwnedBrowser {
+    /// Open with a fresh copy of the manifest.
+    pub fn open(&mut self) {
+        self.files = load_manifest();
+        self.open = true;
+    }
+}
+
+/// Indices of `files` matching every word of `query` (in the title or the path), sorted.
+fn browse_order(
+    files: &[OwnedFile],
+    query: &str,
+    sort: BrowserSort,
+    descending: bool,
+) -> Vec<usize> {
+    let words: Vec<String> = query
+        .split_whitespace()
+        .map(normalize_title)
+        .filter(|word| !word.is_empty())
+        .collect();
+    let mut idxs: Vec<usize> = files
+        .iter()
+        .enumerate()
+        .filter(|(_, file)| {
+            let haystack = format!(
+                "{} {}",
+                normalize_title(&file.title),
+                file.path.to_lowercase()
+            );
+            words.iter().all(|word| haystack.contains(word.as_str()))
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+    let by_title = |a: &OwnedFile, b: &OwnedFile| {
+        normalize_title(&a.title)
+            .cmp(&normalize_title(&b.title))
+            .then_with(|| a.year.cmp(&b.year))
+    };
+    idxs.sort_by(|&a, &b| {
+        let (a, b) = (&files[a], &files[b]);
+        let order = match sort {
+            BrowserSort::Title => Ordering::Equal,
+            BrowserSort::Year => a.year.cmp(&b.year),
+            BrowserSort::Tier => a.tier().cmp(&b.tier()),
+            BrowserSort::Recorded => a.updated_at.cmp(&b.updated_at),
+            BrowserSort::Path => a.path.cmp(&b.path),
+        }
+        .then_with(|| by_title(a, b));
+        if descending {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+    idxs
+}
+
+impl PexApp {
+    fn owned_browser_header(&mut self, ui: &mut eg::Ui, label: &str, key: BrowserSort) {
+        let browser = &mut self.owned_browser;
+        let active = browser.sort == key;
+        let text = if active {
+            format!("{label} {}", if browser.descending { "⏷" } else { "⏶" })
+        } else {
+            label.to_string()
+        };
+        let resp = ui
+            .add(eg::Label::new(eg::RichText::new(text).strong()).sense(eg::Sense::click()))
+            .on_hover_text("Click to sort");
+        if resp.clicked() {
+            if active {
+                browser.descending = !browser.descending;
+            } else {
+                browser.sort = key;
+                browser.descending = false;
+            }
+        }
+    }
+
+    /// Top bar ▸ Library: every file the owned scan indexed, independent of the guide.
+    pub(crate) fn ui_render_owned_browser(&mut self, ctx: &eg::Context) {
+        if !self.owned_browser.open {
+            return;
+        }
+        let mut open = true;
+        eg::Window::new("Owned library")
+            .default_width(760.0)
+            .default_height(480.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let browser = &self.owned_browser;
+                let idxs = browse_order(
+                    &browser.files,
+                    &browser.query,
+                    browser.sort,
+                    browser.descending,
+                );
+                let total = browser.files.len();
+                let mut reload = false;
+                ui.horizontal(|ui| {
+                    ui.add(
+                        eg::TextEdit::singleline(&mut self.owned_browser.query)
+                            .hint_text("Search titles and paths")
+                            .desired_width(260.0),
+                    );
+                    ui.label(eg::RichText::new(format!("{} of {total} files", idxs.len())).weak());
+                    if ui
+                        .button("Reload")
+                        .on_hover_text("Re-read the file list written by the last owned scan")
+                        .clicked()
+                    {
+                        reload = true;
+                    }
+                });
+                if reload {
+                    // The row order above belongs to the old list; draw the new one next frame.
+                    self.owned_browser.open();
+                    return;
+                }
+                if total == 0 {
+                    ui.add_space(8.0);
+                    ui.label("No file list yet. Run Advanced ▸ Refresh owned scan to build it.");
+                    return;
+                }
+                ui.separator();
+                TableBuilder::new(ui)
+                    .striped(true)
+                    .resizable(true)
+                    .cell_layout(eg::Layout::left_to_right(eg::Align::Center))
+                    .column(Column::initial(220.0).at_least(120.0).clip(true))
+                    .column(Column::auto().at_least(44.0))
+                    .column(Column::auto().at_least(40.0))
+                    .column(Column::auto().at_least(64.0))
+                    .column(Column::auto().at_least(84.0))
+                    .column(Column::remainder().at_least(160.0).clip(true))
+                    .header(22.0, |mut header| {
+                        header.col(|ui| self.owned_browser_header(ui, "Title", BrowserSort::Title));
+                        header.col(|ui| self.owned_browser_header(ui, "Year", BrowserSort::Year));
+                        header.col(|ui| self.owned_browser_header(ui, "Tier", BrowserSort::Tier));
+                        header.col(|ui| {
+                            ui.strong("Size");
+                        });
+                        header.col(|ui| {
+                            self.owned_browser_header(ui, "Recorded", BrowserSort::Recorded)
+                        });
+                        header.col(|ui| self.owned_browser_header(ui, "Path", BrowserSort::Path));
+                    })
+                    .body(|body| {
+                        body.rows(20.0, idxs.len(), |mut row| {
+                            let file = &self.owned_browser.files[idxs[row.index()]];
+                            row.col(|ui| {
+                                ui.add(eg::Label::new(&file.title).truncate());
+                            });
+                            row.col(|ui| {
+                                ui.label(file.year.map(|y| y.to_string()).unwrap_or_default());
+                            });
+                            row.col(|ui| {
+                                ui.label(file.tier().label())
+                                    .on_hover_text(file.resolution());
+                            });
+                            row.col(|ui| {
+                                ui.label(file.size_bytes.map(format_size).unwrap_or_default());
+                            });
+                            row.col(|ui| {
+                                ui.label(
+                                    file.updated_at
+                                        .and_then(format_owned_timestamp)
+                                        .unwrap_or_default(),
+                                );
+                            });
+                            row.col(|ui| {
+                                ui.add(eg::Label::new(&file.path).truncate())
+                                    .on_hover_text(&file.path);
+                            });
+                        });
+                    });
+            });
+        if !open {
+            self.owned_browser = OwnedBrowser::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(title: &str, year: i32, width: u32, path: &str) -> OwnedFile {
+        OwnedFile {
+            key: format!("{}:{year}", normalize_title(title)),
+            title: title.into(),
+            year: Some(year),
+            width: Some(width),
+            height: Some(width * 9 / 16),
+            size_bytes: None,
+            updated_at: Some(year as u64),
+            path: path.into(),
+        }
+    }
+
+    #[test]
+    fn search_matches_titles_and_paths_and_sorts_by_column() {
+        let files = vec![
+            file("Heat", 1995, 1920, "/films/Heat (1995)/Heat.mkv"),
+            file("Alien", 1979, 3840, "/uhd/Alien.mkv"),
+            file("Aliens", 1986, 720, "/films/Aliens.avi"),
+        ];
+        assert_eq!(
+            browse_order(&files, "", BrowserSort::Title, false),
+            vec![1, 2, 0]
+        );
+        assert_eq!(
+            browse_order(&files, "alien", BrowserSort::Year, true),
+            vec![2, 1]
+        );
+        assert_eq!(
+            browse_order(&files, "uhd", BrowserSort::Title, false),
+            vec![1]
+        );
+        assert_eq!(
+            browse_order(&files, "", BrowserSort::Tier, true),
+            vec![1, 0, 2]
+        );
+    }
+}
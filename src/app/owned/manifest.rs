@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use eframe::egui as eg;
+
+use crate::app::owned::owned_scan_plex::{is_hd, is_uhd, OwnedEntry};
+use crate::app::{OwnedTier, PexApp};
+
+pub(crate) const MANIFEST_FILE: &str = "owned_manifest.txt";
+
+/// A further file of the same library film (another edition or resolution).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OwnedPart {
+    pub path: String,
+    pub size_bytes: Option<u64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// One library file as the last owned scan saw it (`<cache>/owned_manifest.txt`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OwnedFile {
+    /// `make_owned_key` of the library title and year; files of one film share it.
+    pub key: String,
+    pub title: String,
+    pub year: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub size_bytes: Option<u64>,
+    pub updated_at: Option<u64>,
+    pub path: String,
+}
+
+impl OwnedFile {
+    /// The entry's file plus its extra parts. Sizes the source didn't report are read from disk
+    /// when the file is reachable from this machine.
+    pub(super) fn from_entry(entry: &OwnedEntry) -> Vec<Self> {
+        let key = PexApp::make_owned_key(&entry.title, entry.year);
+        let main = OwnedPart {
+            path: entry.file_path.clone(),
+            size_bytes: entry.size_bytes,
+            width: entry.width,
+            height: entry.height,
+        };
+        std::iter::once(&main)
+            .chain(&entry.other_parts)
+            .filter(|part| !part.path.trim().is_empty())
+            .map(|part| Self {
+                key: key.clone(),
+                title: entry.title.clone(),
+                year: entry.year,
+                width: part.width,
+                height: part.height,
+                size_bytes: part
+                    .size_bytes
+                    .or_else(|| fs::metadata(&part.path).ok().map(|m| m.len())),
+                updated_at: entry.updated_at,
+                path: part.path.clone(),
+            })
+            .collect()
+    }
+
+    /// `key \t title \t year \t width \t height \t size \t updated_at \t path`.
+    fn to_line(&self) -> String {
+        fn opt<T: ToString>(value: Option<T>) -> String {
+            value.map(|v| v.to_string()).unwrap_or_default()
+        }
+        [
+            self.key.clone(),
+            self.title.replace('\t', " "),
+            opt(self.year),
+            opt(self.width),
+            opt(self.height),
+            opt(self.size_bytes),
+            opt(self.updated_at),
+            self.path.clone(),
+        ]
+        .join("\t")
+    }
+
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut parts = line.trim_end_matches('\r').splitn(8, '\t');
+        let key = parts.next()?.trim();
+        if key.is_empty() {
+            return None;
+        }
+        let title = parts.next()?.to_string();
+        let mut num = || parts.next().and_then(|v| v.trim().parse::<u64>().ok());
+        let year = num().map(|y| y as i32);
+        let width = num().map(|w| w as u32);
+        let height = num().map(|h| h as u32);
+        let size_bytes = num();
+        let updated_at = num();
+        Some(Self {
+            key: key.to_string(),
+            title,
+            year,
+            width,
+            height,
+            size_bytes,
+            updated_at,
+            path: parts.next()?.to_string(),
+        })
+    }
+
+    /// "1920×800", or "?" when the library never measured the file.
+    pub fn resolution(&self) -> String {
+        match (self.width, self.height) {
+            (Some(w), Some(h)) => format!("{w}×{h}"),
+            _ => "?".into(),
+        }
+    }
+
+    /// Resolution tier, by the same rules as `owned_hd.txt` / `owned_uhd.txt`.
+    pub fn tier(&self) -> OwnedTier {
+        if is_uhd(self.width, self.height) {
+            OwnedTier::Uhd
+        } else if is_hd(self.width, self.height) {
+            OwnedTier::Hd
+        } else {
+            OwnedTier::Sd
+        }
+    }
+
+    pub fn title_year(&self) -> String {
+        self.year
+            .map_or_else(|| self.title.clone(), |y| format!("{} ({y})", self.title))
+    }
+}
+
+pub(super) fn persist_manifest(cache_dir: &Path, files: &[OwnedFile]) -> Result<(), String> {
+    let path = cache_dir.join(MANIFEST_FILE);
+    fs::write(
+        &path,
+        files
+            .iter()
+            .map(|f| format!("{}\n", f.to_line()))
+            .collect::<String>(),
+    )
+    .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// Every library file from the last owned scan (empty before the first one).
+pub(crate) fn load_manifest() -> Vec<OwnedFile> {
+    fs::read_to_string(crate::app::cache::cache_dir().join(MANIFEST_FILE))
+        .map(|text| text.lines().filter_map(OwnedFile::parse_line).collect())
+        .unwrap_or_default()
+}
+
+/// "1.4 GB" / "700 MB".
+pub(crate) fn format_size(bytes: u64) -> String {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MB: f64 = 1024.0 * 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes / GB)
+    } else {
+        format!("{:.0} MB", bytes / MB)
+    }
+}
+
+/// A film with more than one library file.
+#[derive(Debug)]
+pub(crate) struct DuplicateGroup {
+    pub title: String,
+    /// Largest first.
+    pub files: Vec<OwnedFile>,
+    /// Bytes freed by keeping only the largest file.
+    pub reclaimable: u64,
+}
+
+/// Films with several files, the most reclaimable space first.
+pub(crate) fn duplicate_groups(files: Vec<OwnedFile>) -> Vec<DuplicateGroup> {
+    let mut by_key: HashMap<String, Vec<OwnedFile>> = HashMap::new();
+    for file in files {
+        by_key.entry(file.key.clone()).or_default().push(file);
+    }
+    let mut groups: Vec<DuplicateGroup> = by_key
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .map(|mut files| {
+            files.sort_by(|a, b| {
+                b.size_bytes
+                    .cmp(&a.size_bytes)
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+            let reclaimable = files.iter().skip(1).filter_map(|f| f.size_bytes).sum();
+            DuplicateGroup {
+                title: files[0].title_year(),
+                files,
+                reclaimable,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.reclaimable
+            .cmp(&a.reclaimable)
+            .then_with(|| a.title.cmp(&b.title))
+    });
+    groups
+}
+
+impl PexApp {
+    /// Read the manifest and open the duplicate report.
+    pub(crate) fn open_owned_duplicates(&mut self) {
+        self.owned_duplicates = Some(duplicate_groups(load_manifest()));
+    }
+
+    /// Advanced ▸ Owned duplicates: films with several library files, their paths and sizes.
+    pub(crate) fn ui_render_owned_duplicates_window(&mut self, ctx: &eg::Context) {
+        let Some(groups) = &self.owned_duplicates else {
+            return;
+        };
+        let mut open = true;
+        eg::Window::new("Owned duplicates")
+            .default_width(560.0)
+            .default_height(420.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if groups.is_empty() {
+                    ui.label(
+                        "No film has more than one file. If you expected some, run \
+                         Refresh owned scan: older scans didn't record every file.",
+                    );
+                    return;
+                }
+                let total: u64 = groups.iter().map(|g| g.reclaimable).sum();
+                ui.label(format!(
+                    "{} films have more than one file. Keeping only the largest of each frees {}.",
+                    groups.len(),
+                    format_size(total)
+                ));
+                ui.separator();
+                eg::ScrollArea::vertical().show(ui, |ui| {
+                    for group in groups {
+                        ui.label(eg::RichText::new(&group.title).strong());
+                        for file in &group.files {
+                            ui.horizontal_wrapped(|ui| {
+                                let size = file.size_bytes.map_or_else(|| "?".into(), format_size);
+                                ui.label(
+                                    eg::RichText::new(format!(
+                                        "{size:>8}  {:>9}",
+                                        file.resolution()
+                                    ))
+                                    .monospace(),
+                                );
+                                if ui
+                                    .add(
+                                        eg::Label::new(eg::RichText::new(&file.path).weak())
+                                            .sense(eg::Sense::click()),
+                                    )
+                                    .on_hover_text("Click to copy the path")
+                                    .clicked()
+                                {
+                                    ui.ctx().copy_text(file.path.clone());
+                                }
+                            });
+                        }
+                        ui.add_space(6.0);
+                    }
+                });
+            });
+        if !open {
+            self.owned_duplicates = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(key: &str, size: Option<u64>, path: &str) -> OwnedFile {
+        OwnedFile {
+            key: key.into(),
+            title: "Heat".into(),
+            year: Some(1995),
+            width: Some(1920),
+            height: Some(800),
+            size_bytes: size,
+            updated_at: Some(1_700_000_000),
+            path: path.into(),
+        }
+    }
+
+    #[test]
+    fn manifest_lines_round_trip_and_duplicates_rank_by_reclaimable_space() {
+        let heat = file(
+            "heat:1995",
+            Some(8_000_000_000),
+            "/films/Heat (1995)/Heat.mkv",
+        );
+        assert_eq!(OwnedFile::parse_line(&heat.to_line()), Some(heat.clone()));
+        assert!(OwnedFile::parse_line("\tno key").is_none());
+
+        let groups = duplicate_groups(vec![
+            heat,
+            file(
+                "heat:1995",
+                Some(2_000_000_000),
+                "/films/Heat (1995)/Heat 720p.mkv",
+            ),
+            file("heat:1995", None, "/films/Heat (1995)/Heat.ts"),
+            file("alien:1979", Some(1), "/films/Alien.mkv"),
+        ]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].title, "Heat (1995)");
+        assert_eq!(groups[0].files[0].path, "/films/Heat (1995)/Heat.mkv");
+        assert_eq!(groups[0].reclaimable, 2_000_000_000);
+        assert_eq!(format_size(2_000_000_000), "1.9 GB");
+        assert_eq!(format_size(700 * 1024 * 1024), "700 MB");
+    }
+}
@@ -1,64 +1,229 @@
+pub(crate) mod browser;
+pub(crate) mod manifest;
+pub(crate) mod nfo;
+pub(crate) mod overrides;
+pub(crate) mod owned_scan_jellyfin;
+pub(crate) mod owned_scan_kodi;
 pub(crate) mod owned_scan_plex;
+pub(crate) mod rescan;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 use eframe::egui as eg;
 
+use self::owned_scan_jellyfin::OwnedScanJellyfin;
+use self::owned_scan_kodi::OwnedScanKodi;
 use self::owned_scan_plex::OwnedScanPlex;
-use crate::app::types::OwnedMsg;
+use crate::app::health::Health;
+use crate::app::types::{OwnedMedia, OwnedMsg, OwnedTier};
+use crate::app::{PexApp, PosterRow};
+use crate::config::{load_config, OwnedSource};
+
+/// What the library sidecars say about owned copies: which are HD / 4K and how they're encoded.
+#[derive(Default)]
+pub(crate) struct OwnedDetails {
+    pub hd_keys: Option<HashSet<String>>,
+    pub uhd_keys: Option<HashSet<String>>,
+    pub media: HashMap<String, OwnedMedia>,
+}
+
+impl OwnedDetails {
+    /// Read back the sidecars the last owned scan wrote to the cache folder.
+    pub fn load() -> Self {
+        Self {
+            hd_keys: PexApp::load_sidecar_file("owned_hd.txt"),
+            uhd_keys: PexApp::load_sidecar_file("owned_uhd.txt"),
+            media: load_owned_media_sidecar(),
+        }
+    }
+
+    /// Whether the owned library already has an HD copy of this title.
+    pub fn is_hd(&self, row: &PosterRow) -> bool {
+        self.hd_keys
+            .as_ref()
+            .is_some_and(|set| set.contains(&row.owned_key))
+    }
+
+    /// Resolution tier of the owned copy (None when the title isn't owned).
+    pub fn tier(&self, row: &PosterRow) -> Option<OwnedTier> {
+        let in_set = |set: &Option<HashSet<String>>| {
+            set.as_ref().is_some_and(|set| set.contains(&row.owned_key))
+        };
+        row.owned.then(|| {
+            if in_set(&self.uhd_keys) {
+                OwnedTier::Uhd
+            } else if in_set(&self.hd_keys) {
+                OwnedTier::Hd
+            } else {
+                OwnedTier::Sd
+            }
+        })
+    }
+
+    /// Encoding details of the owned copy, when the library scan recorded them.
+    pub fn media(&self, row: &PosterRow) -> Option<&OwnedMedia> {
+        row.owned.then(|| self.media.get(&row.owned_key))?
+    }
+
+    /// Owned HD (not 4K) copy whose bitrate is under `min_mbps` (None = the rule is off).
+    pub fn low_bitrate(&self, row: &PosterRow, min_mbps: Option<f32>) -> bool {
+        min_mbps.is_some_and(|mbps| {
+            self.tier(row) == Some(OwnedTier::Hd)
+                && self
+                    .media(row)
+                    .and_then(|m| m.bitrate_kbps)
+                    .is_some_and(|kbps| (kbps as f32) < mbps * 1000.0)
+        })
+    }
+
+    /// Airing in HD while the owned copy is SD, or HD at a bitrate below `min_mbps`. A 4K copy
+    /// is never upgraded by a broadcast.
+    pub fn hd_upgrade(&self, row: &PosterRow, min_mbps: Option<f32>) -> bool {
+        PexApp::row_broadcast_hd(row)
+            && match self.tier(row) {
+                Some(OwnedTier::Sd) => true,
+                Some(OwnedTier::Hd) => self.low_bitrate(row, min_mbps),
+                Some(OwnedTier::Uhd) | None => false,
+            }
+    }
+}
+
+fn load_owned_media_sidecar() -> HashMap<String, OwnedMedia> {
+    let path = crate::app::cache::cache_dir().join("owned_media.txt");
+    std::fs::read_to_string(path)
+        .map(|text| {
+            text.lines()
+                .filter_map(owned_scan_plex::parse_owned_media_line)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Flag the rows whose title the owned library has: a manual link first, then title / year
+/// variants, then IMDb / TMDb ids, then the airing's own key.
+pub(crate) fn flag_owned_rows(
+    rows: &mut [PosterRow],
+    keys: &HashSet<String>,
+    modified: Option<&HashMap<String, Option<u64>>>,
+    overrides: &overrides::OwnedOverrides,
+) {
+    for row in rows {
+        let base_key = row.owned_key.clone();
+        let airing_key = PexApp::make_owned_key(&row.title, row.year);
+        let mut matched_key: Option<String> = overrides
+            .link_for(&airing_key)
+            .filter(|linked| keys.contains(*linked))
+            .map(str::to_string);
+        // "Not the same film": no heuristic match counts for this title and year.
+        let unlinked = overrides.is_unlinked(&airing_key);
+
+        if matched_key.is_none() && !unlinked {
+            matched_key = PexApp::owned_key_variants(&row.title, row.year)
+                .into_iter()
+                .find(|candidate| keys.contains(candidate));
+        }
+
+        if matched_key.is_none() && !unlinked {
+            // Library files identified by an .nfo or agent guid also match by IMDb/TMDb id.
+            let guid = row.guid.as_deref();
+            matched_key = nfo::id_keys(
+                guid.and_then(crate::app::imdb_id_from_guid).as_deref(),
+                guid.and_then(nfo::tmdb_id_from_guid).as_deref(),
+            )
+            .into_iter()
+            .find(|key| keys.contains(key));
+        }
+
+        if matched_key.is_none() && !unlinked && keys.contains(&base_key) {
+            matched_key = Some(base_key.clone());
+        }
+
+        if let Some(found) = matched_key {
+            row.owned = true;
+            row.owned_key = found.clone();
+            row.owned_modified = modified.and_then(|m| m.get(&found)).and_then(|v| *v);
+        } else {
+            row.owned = false;
+            row.owned_key = base_key;
+            row.owned_modified = None;
+        }
+    }
+}
+
+/// Start the owned scan for the configured `owned_source`.
+pub(crate) fn spawn_owned_scan(tx: std::sync::mpsc::Sender<OwnedMsg>) {
+    match load_config().owned_source {
+        OwnedSource::Plex => OwnedScanPlex::spawn_scan(tx),
+        OwnedSource::Kodi => OwnedScanKodi::spawn_scan(tx),
+        OwnedSource::Jellyfin => OwnedScanJellyfin::spawn_scan(tx),
+    }
+}
 
 impl crate::app::PexApp {
-    /// Kick off a non-blocking owned scan against the Plex library database.
+    /// Kick off a non-blocking owned scan against the configured library source.
     pub(crate) fn start_owned_scan(&mut self) {
         if self.owned_rx.is_some() {
             return;
         }
         let (tx, rx) = std::sync::mpsc::channel::<OwnedMsg>();
         self.owned_rx = Some(rx);
+        self.owned_rescan.scan_started();
 
         self.owned_scan_in_progress = true;
+        self.health.owned = Health::Loading;
+
+        let source = load_config().owned_source.label();
+        self.record_owned_message(format!(
+            "Stage 3/4 - Loading owned titles from the {source}."
+        ));
+        self.set_status(format!(
+            "Stage 3/4 - Loading owned titles from the {source} (marks Owned titles and HD upgrades)."
+        ));
+        if self.demo_mode {
+            crate::app::demo::spawn_demo_owned(tx, self.now());
+        } else {
+            spawn_owned_scan(tx);
+        }
+    }
 
-        self.record_owned_message(
-            "Stage 3/4 - Loading owned titles from the Plex library database.",
-        );
-        self.set_status(
-            "Stage 3/4 - Loading owned titles from Plex (marks Owned titles and HD upgrades).",
-        );
-        OwnedScanPlex::spawn_scan(tx);
+    /// Take a finished scan's keys, with the HD/media details its sidecars hold, and flag rows.
+    pub(crate) fn set_owned_library(
+        &mut self,
+        keys: HashSet<String>,
+        modified: std::collections::HashMap<String, Option<u64>>,
+    ) {
+        self.owned_keys = Some(keys);
+        self.owned_details = if self.demo_mode {
+            OwnedDetails {
+                hd_keys: Some(crate::app::demo::demo_owned_hd_keys()),
+                ..OwnedDetails::default()
+            }
+        } else {
+            OwnedDetails::load()
+        };
+        self.owned_modified = Some(modified);
+        self.apply_owned_flags();
+        self.evaluate_alerts();
     }
 
     /// Apply the owned flags using the computed key set (no-ops if not ready).
     pub(crate) fn apply_owned_flags(&mut self) {
+        self.apply_owned_flags_from(0);
+    }
+
+    /// Same as `apply_owned_flags` for the rows from `start` on (rows streamed in by prep).
+    pub(crate) fn apply_owned_flags_from(&mut self, start: usize) {
         let Some(keys) = &self.owned_keys else {
             return;
         };
-        let modified = self.owned_modified.as_ref();
-        for row in &mut self.rows {
-            let base_key = row.owned_key.clone();
-            let mut matched_key: Option<String> = None;
-
-            for candidate in Self::owned_key_variants(&row.title, row.year) {
-                if keys.contains(&candidate) {
-                    matched_key = Some(candidate);
-                    break;
-                }
-            }
-
-            if matched_key.is_none() && keys.contains(&base_key) {
-                matched_key = Some(base_key.clone());
-            }
-
-            if let Some(found) = matched_key {
-                row.owned = true;
-                row.owned_key = found.clone();
-                row.owned_modified = modified.and_then(|m| m.get(&found)).and_then(|v| *v);
-            } else {
-                row.owned = false;
-                row.owned_key = base_key;
-                row.owned_modified = None;
-            }
-        }
+        let rows = self.rows.get_mut(start..).unwrap_or_default();
+        flag_owned_rows(
+            rows,
+            keys,
+            self.owned_modified.as_ref(),
+            &self.owned_overrides,
+        );
     }
 
     pub(crate) fn owned_key_variants(title: &str, year: Option<i32>) -> Vec<String> {
@@ -267,6 +432,7 @@ impl crate::app::PexApp {
                         ));
                     } else {
                         self.owned_retry_next = None;
+                        self.health.owned = Health::Failed(e);
                     }
 
                     if !matches!(self.boot_phase, crate::app::BootPhase::Ready) {
@@ -302,6 +468,10 @@ impl crate::app::PexApp {
                                 );
                                 self.owned_keys = Some(HashSet::new());
                                 self.owned_retry_next = None;
+                                self.health.owned = Health::Failed(
+                                    "The library scan found no movies after automatic retries."
+                                        .into(),
+                                );
                             }
                         } else {
                             self.record_owned_message(
@@ -310,6 +480,8 @@ impl crate::app::PexApp {
                             self.set_status(crate::app::OWNED_SCAN_COMPLETE_STATUS);
                             self.owned_keys = Some(HashSet::new());
                             self.owned_retry_next = None;
+                            self.health.owned =
+                                Health::Failed("plex_library_db_source is not configured.".into());
                         }
 
                         if !matches!(self.boot_phase, crate::app::BootPhase::Ready) {
@@ -322,12 +494,14 @@ impl crate::app::PexApp {
                     self.owned_retry_next = None;
 
                     let count = keys.len();
-                    self.owned_keys = Some(keys);
-                    self.owned_hd_keys = Self::load_owned_hd_sidecar();
-                    self.owned_modified = Some(modified);
-                    self.apply_owned_flags();
+                    self.set_owned_library(keys, modified);
                     self.mark_dirty();
                     self.owned_scan_in_progress = false;
+                    self.health.owned = if self.demo_mode {
+                        Health::Ok
+                    } else {
+                        Health::ok_or_stale(&crate::config::local_library_db_path(), self.now())
+                    };
                     self.record_owned_message(format!("Owned scan complete ({count} titles)."));
                     if let Some(msg) = self.stage4_complete_message.clone() {
                         self.set_status(msg);
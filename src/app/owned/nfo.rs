@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::Path;
+
+/// The parts of a Kodi-style movie `.nfo` the owned scan uses.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct NfoInfo {
+    pub title: Option<String>,
+    pub original_title: Option<String>,
+    pub year: Option<i32>,
+    pub imdb: Option<String>,
+    pub tmdb: Option<String>,
+}
+
+/// Text of the first `<tag>` element (attributes allowed), unescaped and trimmed.
+fn element<'a>(text: &'a str, tag: &str) -> Option<&'a str> {
+    let mut rest = text;
+    loop {
+        let start = rest.find(&format!("<{tag}"))?;
+        let after = &rest[start + tag.len() + 1..];
+        // Skip longer tags sharing the prefix (`<title>` vs `<titles>`).
+        if after.starts_with(['>', ' ', '\t', '\n', '\r']) {
+            let open_end = after.find('>')?;
+            let body = &after[open_end + 1..];
+            let close = body.find(&format!("</{tag}>"))?;
+            let value = body[..close].trim();
+            return (!value.is_empty()).then_some(value);
+        }
+        rest = after;
+    }
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// `<uniqueid type="…">` values plus the older `<id>` / `<imdbid>` / `<tmdbid>` elements.
+fn unique_ids(text: &str) -> (Option<String>, Option<String>) {
+    let mut imdb = None;
+    let mut tmdb = None;
+    for chunk in text.split("<uniqueid").skip(1) {
+        let Some(open_end) = chunk.find('>') else {
+            continue;
+        };
+        let attrs = chunk[..open_end].to_ascii_lowercase();
+        let Some(close) = chunk.find("</uniqueid>") else {
+            continue;
+        };
+        let value = chunk[open_end + 1..close].trim().to_string();
+        if value.is_empty() {
+            continue;
+        }
+        if attrs.contains("\"imdb\"") || attrs.contains("'imdb'") {
+            imdb.get_or_insert(value);
+        } else if attrs.contains("\"tmdb\"") || attrs.contains("'tmdb'") {
+            tmdb.get_or_insert(value);
+        }
+    }
+    if imdb.is_none() {
+        imdb = ["imdbid", "id"]
+            .into_iter()
+            .filter_map(|tag| element(text, tag))
+            .find(|value| value.starts_with("tt"))
+            .map(String::from);
+    }
+    if tmdb.is_none() {
+        tmdb = element(text, "tmdbid").map(String::from);
+    }
+    (
+        imdb,
+        tmdb.filter(|id| id.chars().all(|c| c.is_ascii_digit())),
+    )
+}
+
+pub(crate) fn parse_nfo(text: &str) -> NfoInfo {
+    let (imdb, tmdb) = unique_ids(text);
+    NfoInfo {
+        title: element(text, "title").map(unescape),
+        original_title: element(text, "originaltitle").map(unescape),
+        year: element(text, "year")
+            .or_else(|| element(text, "premiered").map(|d| d.get(..4).unwrap_or(d)))
+            .and_then(|y| y.parse().ok()),
+        imdb,
+        tmdb,
+    }
+}
+
+/// The `.nfo` beside a library file: `<file stem>.nfo`, else `movie.nfo` in the same folder.
+/// None when neither is readable from this machine (e.g. the library lives on another host).
+pub(crate) fn read_movie_nfo(file_path: &str) -> Option<NfoInfo> {
+    let path = Path::new(file_path);
+    let dir = path.parent()?;
+    let candidates = [
+        path.file_stem()
+            .map(|stem| dir.join(format!("{}.nfo", stem.to_string_lossy()))),
+        Some(dir.join("movie.nfo")),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find_map(|nfo| fs::read_to_string(nfo).ok())
+        .map(|text| parse_nfo(&text))
+        .filter(|info| info.title.is_some() || info.imdb.is_some() || info.tmdb.is_some())
+}
+
+/// Owned-set keys for a title's external IDs, e.g. `imdb:tt0114369` and `tmdb:807`.
+pub(crate) fn id_keys(imdb: Option<&str>, tmdb: Option<&str>) -> Vec<String> {
+    let mut keys = Vec::new();
+    if let Some(id) = imdb {
+        keys.push(format!("imdb:{}", id.to_ascii_lowercase()));
+    }
+    if let Some(id) = tmdb {
+        keys.push(format!("tmdb:{id}"));
+    }
+    keys
+}
+
+/// TMDb id from an EPG/library guid such as `tmdb://807` or
+/// `com.plexapp.agents.themoviedb://807?lang=en`.
+pub(crate) fn tmdb_id_from_guid(guid: &str) -> Option<String> {
+    let lower = guid.to_ascii_lowercase();
+    let pos = ["tmdb://", "themoviedb://"]
+        .into_iter()
+        .find_map(|scheme| lower.find(scheme).map(|pos| pos + scheme.len()))?;
+    let id: String = guid[pos..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    (!id.is_empty()).then_some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kodi_movie_nfo_yields_title_year_and_ids() {
+        let nfo = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>
+<movie>
+    <title>Se7en</title>
+    <originaltitle>Seven</originaltitle>
+    <sorttitle>Seven</sorttitle>
+    <year>1995</year>
+    <uniqueid type="tmdb">807</uniqueid>
+    <uniqueid type="imdb" default="true">tt0114369</uniqueid>
+    <set><name>Fincher &amp; Friends</name></set>
+</movie>"#;
+        assert_eq!(
+            parse_nfo(nfo),
+            NfoInfo {
+                title: Some("Se7en".into()),
+                original_title: Some("Seven".into()),
+                year: Some(1995),
+                imdb: Some("tt0114369".into()),
+                tmdb: Some("807".into()),
+            }
+        );
+
+        let legacy = "<movie><title>Heat</title><premiered>1995-12-15</premiered>\
+                      <id>tt0113277</id></movie>";
+        let info = parse_nfo(legacy);
+        assert_eq!(info.year, Some(1995));
+        assert_eq!(info.imdb.as_deref(), Some("tt0113277"));
+        assert_eq!(
+            id_keys(info.imdb.as_deref(), Some("949")),
+            vec!["imdb:tt0113277", "tmdb:949"]
+        );
+        assert_eq!(
+            tmdb_id_from_guid("com.plexapp.agents.themoviedb://807?lang=en").as_deref(),
+            Some("807")
+        );
+        assert_eq!(tmdb_id_from_guid("plex://movie/5d776"), None);
+    }
+}
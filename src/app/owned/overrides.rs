@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui as eg;
+use tracing::warn;
+
+use crate::app::PexApp;
+
+const OVERRIDES_FILE: &str = "owned_overrides.txt";
+/// Owned keys listed at once in the link picker.
+const PICKER_LIMIT: usize = 200;
+
+/// Manual corrections to owned matching, kept in `<cache>/owned_overrides.txt` as
+/// `link\t<airing key>\t<owned key>` and `unlink\t<airing key>` ("not the same film") lines.
+/// The airing key is `make_owned_key` of the guide title and year, so an override survives
+/// restarts and rescans.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct OwnedOverrides {
+    links: HashMap<String, String>,
+    unlinked: HashSet<String>,
+}
+
+fn overrides_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(OVERRIDES_FILE)
+}
+
+impl OwnedOverrides {
+    fn parse(text: &str) -> Self {
+        let mut overrides = Self::default();
+        for line in text.lines() {
+            let mut parts = line.trim_end_matches('\r').split('\t');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("link"), Some(airing), Some(owned)) => {
+                    overrides
+                        .links
+                        .insert(airing.to_string(), owned.to_string());
+                }
+                (Some("unlink"), Some(airing), None) => {
+                    overrides.unlinked.insert(airing.to_string());
+                }
+                _ => {}
+            }
+        }
+        overrides
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines: Vec<String> = self
+            .links
+            .iter()
+            .map(|(airing, owned)| format!("link\t{airing}\t{owned}"))
+            .chain(
+                self.unlinked
+                    .iter()
+                    .map(|airing| format!("unlink\t{airing}")),
+            )
+            .collect();
+        lines.sort();
+        lines.join("\n")
+    }
+
+    pub fn load() -> Self {
+        fs::read_to_string(overrides_path())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Err(err) = fs::write(overrides_path(), self.to_text()) {
+            warn!("Failed to write {}: {err}", overrides_path().display());
+        }
+    }
+
+    /// The owned key an airing was linked to by hand, if any.
+    pub fn link_for(&self, airing_key: &str) -> Option<&str> {
+        self.links.get(airing_key).map(String::as_str)
+    }
+
+    /// Whether the airing was marked "not the same film": it never counts as owned.
+    pub fn is_unlinked(&self, airing_key: &str) -> bool {
+        self.unlinked.contains(airing_key)
+    }
+}
+
+/// "Link to owned file…" picker: which row it is for and the filter text.
+#[derive(Default)]
+pub(crate) struct OwnedLinkPicker {
+    row: Option<usize>,
+    query: String,
+}
+
+/// Owned keys containing every word of `query`, sorted, at most `limit` of them.
+fn matching_keys<'a>(
+    keys: impl IntoIterator<Item = &'a String>,
+    query: &str,
+    limit: usize,
+) -> Vec<&'a str> {
+    let words: Vec<String> = query
+        .split_whitespace()
+        .map(crate::app::utils::normalize_title)
+        .filter(|word| !word.is_empty())
+        .collect();
+    let mut found: Vec<&str> = keys
+        .into_iter()
+        .map(String::as_str)
+        .filter(|key| !key.starts_with("imdb:") && !key.starts_with("tmdb:"))
+        .filter(|key| words.iter().all(|word| key.contains(word.as_str())))
+        .collect();
+    found.sort_unstable();
+    found.truncate(limit);
+    found
+}
+
+impl PexApp {
+    /// Replace the override for the airing at `idx` (linking clears "not the same film" and vice
+    /// versa), save it and re-flag rows.
+    fn set_owned_override(&mut self, idx: usize, link: Option<String>, unlink: bool) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let airing_key = Self::make_owned_key(&row.title, row.year);
+        let overrides = &mut self.owned_overrides;
+        overrides.links.remove(&airing_key);
+        overrides.unlinked.remove(&airing_key);
+        if let Some(key) = link {
+            overrides.links.insert(airing_key, key);
+        } else if unlink {
+            overrides.unlinked.insert(airing_key);
+        }
+        overrides.save();
+        self.apply_owned_flags();
+    }
+
+    /// Detail-panel line: "Link to owned file…" / "Not the same film", or the manual override
+    /// with a way to remove it.
+    pub(crate) fn ui_render_owned_link_action(&mut self, ui: &mut eg::Ui, idx: usize) {
+        if self.owned_keys.is_none() {
+            return;
+        }
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let owned = row.owned;
+        let airing_key = Self::make_owned_key(&row.title, row.year);
+        let linked = self
+            .owned_overrides
+            .link_for(&airing_key)
+            .map(str::to_string);
+        let unlinked = self.owned_overrides.is_unlinked(&airing_key);
+        ui.horizontal_wrapped(|ui| {
+            if let Some(owned_key) = linked {
+                ui.label(eg::RichText::new(format!("Linked by hand to {owned_key}")).weak());
+                if ui
+                    .small_button("Remove link")
+                    .on_hover_text("Go back to matching this airing by title and year")
+                    .clicked()
+                {
+                    self.set_owned_override(idx, None, false);
+                }
+                return;
+            }
+            if unlinked {
+                ui.label(eg::RichText::new("Marked as not the film you own").weak());
+                if ui
+                    .small_button("Undo")
+                    .on_hover_text("Go back to matching this airing by title and year")
+                    .clicked()
+                {
+                    self.set_owned_override(idx, None, false);
+                }
+            } else if owned
+                && ui
+                    .small_button("Not the same film")
+                    .on_hover_text(
+                        "This airing matched a library file that is a different film (e.g. a remake); stop counting it as owned",
+                    )
+                    .clicked()
+            {
+                self.set_owned_override(idx, None, true);
+            }
+            if ui
+                .small_button("Link to owned file…")
+                .on_hover_text(
+                    "Pick the library entry this airing is, when title matching misses it",
+                )
+                .clicked()
+            {
+                self.owned_link_picker = OwnedLinkPicker {
+                    row: Some(idx),
+                    query: self.rows[idx].title.clone(),
+                };
+            }
+        });
+    }
+
+    /// Window listing owned keys that match the filter; clicking one links the airing to it.
+    pub(crate) fn ui_render_owned_link_window(&mut self, ctx: &eg::Context) {
+        let Some(idx) = self.owned_link_picker.row else {
+            return;
+        };
+        let (Some(row), Some(keys)) = (self.rows.get(idx), self.owned_keys.as_ref()) else {
+            self.owned_link_picker.row = None;
+            return;
+        };
+        let title = row.year.map_or_else(
+            || row.title.clone(),
+            |year| format!("{} ({year})", row.title),
+        );
+        let mut open = true;
+        let mut picked: Option<String> = None;
+        eg::Window::new("Link to owned file")
+            .collapsible(false)
+            .default_width(420.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(format!("Which library entry is \"{title}\"?"));
+                ui.add(
+                    eg::TextEdit::singleline(&mut self.owned_link_picker.query)
+                        .hint_text("Filter owned titles"),
+                );
+                let found = matching_keys(keys, &self.owned_link_picker.query, PICKER_LIMIT);
+                if found.is_empty() {
+                    ui.label(eg::RichText::new("No owned titles match.").weak());
+                }
+                eg::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for key in &found {
+                        if ui.selectable_label(false, *key).clicked() {
+                            picked = Some((*key).to_string());
+                        }
+                    }
+                });
+                if found.len() == PICKER_LIMIT {
+                    ui.label(
+                        eg::RichText::new(format!(
+                            "Showing the first {PICKER_LIMIT}; type more to narrow it down."
+                        ))
+                        .weak(),
+                    );
+                }
+            });
+        if let Some(key) = picked {
+            self.set_owned_override(idx, Some(key), false);
+            self.owned_link_picker.row = None;
+        } else if !open {
+            self.owned_link_picker.row = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_round_trip_and_picker_filters_by_words() {
+        let text = "link\tbirdman or the unexpected virtue of ignorance:2014\tbirdman:2014\n\
+                    unlink\tthe thing:1982\n\
+                    junk line\n";
+        let overrides = OwnedOverrides::parse(text);
+        assert_eq!(
+            overrides.link_for("birdman or the unexpected virtue of ignorance:2014"),
+            Some("birdman:2014")
+        );
+        assert!(overrides.is_unlinked("the thing:1982"));
+        assert!(!overrides.is_unlinked("birdman:2014"));
+        assert_eq!(OwnedOverrides::parse(&overrides.to_text()), overrides);
+
+        let keys: Vec<String> = [
+            "birdman:2014",
+            "bird box:2018",
+            "imdb:tt2562232",
+            "heat:1995",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            matching_keys(&keys, "Bird", 10),
+            vec!["bird box:2018", "birdman:2014"]
+        );
+        assert_eq!(matching_keys(&keys, "bird man", 10), vec!["birdman:2014"]);
+        assert_eq!(matching_keys(&keys, "", 1), vec!["bird box:2018"]);
+    }
+}
@@ -0,0 +1,266 @@
+use chrono::DateTime;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use crate::app::owned::owned_scan_plex::{
+    in_excluded_dir, non_empty, send_owned_entries, OwnedEntry,
+};
+use crate::app::types::{OwnedMedia, OwnedMsg};
+use crate::config::load_config;
+
+/// Movies fetched per `/Items` request.
+const PAGE_SIZE: usize = 500;
+
+/// Owned scan against a Jellyfin or Emby server's movie libraries (`jellyfin_url` +
+/// `jellyfin_api_key`); both serve the same `/Items` API.
+pub struct OwnedScanJellyfin;
+
+impl OwnedScanJellyfin {
+    pub(crate) fn spawn_scan(tx: Sender<OwnedMsg>) {
+        thread::spawn(move || {
+            use OwnedMsg::{Error, Info};
+
+            let _ = tx.send(Info(
+                "Stage 3/4 - Loading owned titles from the Jellyfin/Emby server.".into(),
+            ));
+            let cfg = load_config();
+            let (Some(url), Some(key)) = (cfg.jellyfin_url, cfg.jellyfin_api_key) else {
+                let _ = tx.send(Error(
+                    "owned_source is \"jellyfin\" but `jellyfin_url` or `jellyfin_api_key` is not set."
+                        .into(),
+                ));
+                return;
+            };
+            if let Err(err) = crate::app::net::ensure_online() {
+                let _ = tx.send(Error(format!("Jellyfin/Emby owned scan skipped: {err}")));
+                return;
+            }
+
+            match fetch_jellyfin_owned_entries(&url, &key, &cfg.owned_exclude_dirs) {
+                Ok((entries, excluded)) => {
+                    if excluded > 0 {
+                        let _ = tx.send(Info(format!(
+                            "Stage 3/4 - Skipped {excluded} Jellyfin/Emby files in excluded folders ({}).",
+                            cfg.owned_exclude_dirs.join(", ")
+                        )));
+                    }
+                    send_owned_entries(&tx, entries, "Jellyfin/Emby library");
+                }
+                Err(err) => {
+                    let _ = tx.send(Error(format!("Jellyfin/Emby owned scan failed: {err}")));
+                }
+            }
+        });
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ItemsResponse {
+    #[serde(default)]
+    items: Vec<MovieItem>,
+    #[serde(default)]
+    total_record_count: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MovieItem {
+    #[serde(default)]
+    name: String,
+    original_title: Option<String>,
+    production_year: Option<i32>,
+    #[serde(default)]
+    provider_ids: HashMap<String, String>,
+    path: Option<String>,
+    container: Option<String>,
+    date_created: Option<String>,
+    #[serde(default)]
+    media_streams: Vec<MediaStream>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MediaStream {
+    #[serde(rename = "Type", default)]
+    kind: String,
+    width: Option<u32>,
+    height: Option<u32>,
+    codec: Option<String>,
+    bit_rate: Option<u64>,
+    channels: Option<u32>,
+}
+
+/// Provider id by name, ignoring case (`Imdb`, `IMDB`, `Tmdb`, …) and empty values.
+fn provider_id(ids: &HashMap<String, String>, name: &str) -> Option<String> {
+    ids.iter()
+        .find(|(key, value)| key.eq_ignore_ascii_case(name) && !value.trim().is_empty())
+        .map(|(_, value)| value.trim().to_string())
+}
+
+impl MovieItem {
+    fn into_entry(self, idx: usize) -> OwnedEntry {
+        let video = self
+            .media_streams
+            .iter()
+            .filter(|stream| stream.kind.eq_ignore_ascii_case("video"))
+            .max_by_key(|stream| stream.width.unwrap_or(0));
+        let audio = self
+            .media_streams
+            .iter()
+            .find(|stream| stream.kind.eq_ignore_ascii_case("audio"));
+        OwnedEntry {
+            metadata_id: idx as i64,
+            imdb: provider_id(&self.provider_ids, "imdb"),
+            tmdb: provider_id(&self.provider_ids, "tmdb"),
+            title: self.name,
+            original_title: self.original_title,
+            year: self.production_year,
+            width: video.and_then(|v| v.width),
+            height: video.and_then(|v| v.height),
+            media: OwnedMedia {
+                bitrate_kbps: video
+                    .and_then(|v| v.bit_rate)
+                    .filter(|&bps| bps > 0)
+                    .map(|bps| (bps / 1000).min(u32::MAX as u64) as u32),
+                video_codec: non_empty(video.and_then(|v| v.codec.clone())),
+                // A comma list when the server can't narrow it down ("mov,mp4,m4a,…").
+                container: non_empty(self.container).filter(|c| !c.contains(',')),
+                audio_codec: non_empty(audio.and_then(|a| a.codec.clone())),
+                audio_channels: audio.and_then(|a| a.channels),
+                ..OwnedMedia::default()
+            },
+            updated_at: self
+                .date_created
+                .as_deref()
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .and_then(|ts| u64::try_from(ts.timestamp()).ok()),
+            file_path: self.path.unwrap_or_default(),
+            size_bytes: None,
+            other_parts: Vec::new(),
+        }
+    }
+}
+
+/// One `/Items` page as owned entries (numbered from `first`), plus the server's total count
+/// and how many were skipped by `exclude_dirs`.
+fn parse_items_page(
+    body: &str,
+    first: usize,
+    exclude_dirs: &[String],
+) -> Result<(Vec<OwnedEntry>, usize, usize), String> {
+    let parsed: ItemsResponse =
+        serde_json::from_str(body).map_err(|err| format!("parse items: {err}"))?;
+    let mut entries = Vec::with_capacity(parsed.items.len());
+    let mut excluded = 0usize;
+    for (offset, item) in parsed.items.into_iter().enumerate() {
+        let entry = item.into_entry(first + offset);
+        if in_excluded_dir(&entry.file_path, exclude_dirs) {
+            excluded += 1;
+            continue;
+        }
+        if entry.title.trim().is_empty() {
+            continue;
+        }
+        entries.push(entry);
+    }
+    Ok((entries, parsed.total_record_count, excluded))
+}
+
+fn fetch_jellyfin_owned_entries(
+    url: &str,
+    key: &str,
+    exclude_dirs: &[String],
+) -> Result<(Vec<OwnedEntry>, usize), String> {
+    let client = crate::app::net::client_builder()
+        .user_agent("pex/owned-scan")
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|err| format!("client: {err}"))?;
+
+    let mut entries = Vec::new();
+    let mut excluded = 0usize;
+    let mut start = 0usize;
+    loop {
+        let resp = crate::app::net::track(
+            client
+                .get(format!("{url}/Items"))
+                .header("X-Emby-Token", key)
+                .header("Accept", "application/json")
+                .query(&[
+                    ("Recursive", "true"),
+                    ("IncludeItemTypes", "Movie"),
+                    (
+                        "Fields",
+                        "ProviderIds,Path,MediaStreams,DateCreated,OriginalTitle,Container",
+                    ),
+                    ("StartIndex", &start.to_string()),
+                    ("Limit", &PAGE_SIZE.to_string()),
+                ])
+                .send(),
+        )
+        .map_err(|err| format!("network: {err}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("/Items: HTTP {}", resp.status()));
+        }
+        let body = resp.text().map_err(|err| format!("read: {err}"))?;
+        let (page, total, skipped) = parse_items_page(&body, start, exclude_dirs)?;
+        let fetched = page.len() + skipped;
+        entries.extend(page);
+        excluded += skipped;
+        start += PAGE_SIZE;
+        if fetched == 0 || start >= total {
+            break;
+        }
+    }
+    Ok((entries, excluded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_page_maps_ids_resolution_and_exclusions() {
+        let body = r#"{
+            "Items": [
+                {"Name": "Se7en", "ProductionYear": 1995,
+                 "ProviderIds": {"Imdb": "tt0114369", "Tmdb": "807"},
+                 "Path": "/films/Se7en (1995)/Se7en - Extended - 1080p.mkv",
+                 "DateCreated": "2024-01-02T03:04:05.0000000Z",
+                 "Container": "mkv",
+                 "MediaStreams": [
+                     {"Type": "Audio", "Codec": "ac3", "Channels": 6},
+                     {"Type": "Video", "Width": 1920, "Height": 800, "Codec": "hevc",
+                      "BitRate": 6500000}
+                 ]},
+                {"Name": "Heat", "Path": "/films/.grab/Heat.ts"},
+                {"Name": "", "Path": "/films/untitled.mkv"}
+            ],
+            "TotalRecordCount": 1203
+        }"#;
+        let exclude = vec![".grab".to_string()];
+        let (entries, total, excluded) = parse_items_page(body, 500, &exclude).unwrap();
+        assert_eq!((entries.len(), total, excluded), (1, 1203, 1));
+        let se7en = &entries[0];
+        assert_eq!(se7en.metadata_id, 500);
+        assert_eq!(se7en.imdb.as_deref(), Some("tt0114369"));
+        assert_eq!(se7en.tmdb.as_deref(), Some("807"));
+        assert_eq!((se7en.width, se7en.height), (Some(1920), Some(800)));
+        assert_eq!(se7en.media.bitrate_kbps, Some(6500));
+        assert_eq!(se7en.media.video_codec.as_deref(), Some("hevc"));
+        assert_eq!(se7en.media.container.as_deref(), Some("mkv"));
+        assert_eq!(
+            (
+                se7en.media.audio_codec.as_deref(),
+                se7en.media.audio_channels
+            ),
+            (Some("ac3"), Some(6))
+        );
+        assert_eq!(se7en.updated_at, Some(1_704_164_645));
+        assert!(parse_items_page("<html>", 0, &exclude).is_err());
+    }
+}
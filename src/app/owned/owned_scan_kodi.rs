@@ -0,0 +1,251 @@
+use chrono::NaiveDateTime;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use crate::app::owned::owned_scan_plex::{
+    in_excluded_dir, non_empty, send_owned_entries, OwnedEntry,
+};
+use crate::app::types::{OwnedMedia, OwnedMsg};
+use crate::config::load_config;
+
+/// Owned scan against Kodi's video library (`MyVideos<N>.db`, Kodi 17 or newer), read in place.
+pub struct OwnedScanKodi;
+
+impl OwnedScanKodi {
+    pub(crate) fn spawn_scan(tx: Sender<OwnedMsg>) {
+        thread::spawn(move || {
+            use OwnedMsg::{Error, Info};
+
+            let _ = tx.send(Info(
+                "Stage 3/4 - Loading owned titles from the Kodi video database.".into(),
+            ));
+            let cfg = load_config();
+            let Some(configured) = cfg.kodi_db_path else {
+                let _ = tx.send(Error(
+                    "owned_source is \"kodi\" but `kodi_db_path` is not set.".into(),
+                ));
+                return;
+            };
+            let db_path = match resolve_kodi_db(&configured) {
+                Ok(path) => path,
+                Err(err) => {
+                    let _ = tx.send(Error(err));
+                    return;
+                }
+            };
+            let conn = match Connection::open_with_flags(
+                &db_path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            ) {
+                Ok(conn) => conn,
+                Err(err) => {
+                    let _ = tx.send(Error(format!(
+                        "Failed to open Kodi video DB {}: {err}",
+                        db_path.display()
+                    )));
+                    return;
+                }
+            };
+            // Kodi keeps the file open and may be writing to it.
+            let _ = conn.busy_timeout(Duration::from_secs(5));
+
+            match collect_kodi_owned_entries(&conn, &cfg.owned_exclude_dirs) {
+                Ok((entries, excluded)) => {
+                    if excluded > 0 {
+                        let _ = tx.send(Info(format!(
+                            "Stage 3/4 - Skipped {excluded} Kodi files in excluded folders ({}).",
+                            cfg.owned_exclude_dirs.join(", ")
+                        )));
+                    }
+                    send_owned_entries(&tx, entries, "Kodi library");
+                }
+                Err(err) => {
+                    let _ = tx.send(Error(err));
+                }
+            }
+        });
+    }
+}
+
+/// Schema version in a `MyVideos<N>.db` file name.
+fn myvideos_version(name: &str) -> Option<u32> {
+    name.strip_prefix("MyVideos")?
+        .strip_suffix(".db")?
+        .parse()
+        .ok()
+}
+
+/// The newest `MyVideos<N>.db` among `names` (Kodi leaves older versions behind on upgrade).
+fn newest_myvideos<'a>(names: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    names
+        .into_iter()
+        .filter_map(|name| Some((myvideos_version(name)?, name)))
+        .max_by_key(|&(version, _)| version)
+        .map(|(_, name)| name)
+}
+
+/// `kodi_db_path` may name the database itself or Kodi's `userdata/Database` folder.
+fn resolve_kodi_db(configured: &Path) -> Result<PathBuf, String> {
+    if configured.is_file() {
+        return Ok(configured.to_path_buf());
+    }
+    if !configured.is_dir() {
+        return Err(format!(
+            "Kodi video DB not found at {}. Point `kodi_db_path` at MyVideos<N>.db or Kodi's userdata/Database folder.",
+            configured.display()
+        ));
+    }
+    let names: Vec<String> = fs::read_dir(configured)
+        .map_err(|err| format!("Failed to list {}: {err}", configured.display()))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect();
+    newest_myvideos(names.iter().map(String::as_str))
+        .map(|name| configured.join(name))
+        .ok_or_else(|| format!("No MyVideos*.db in {}.", configured.display()))
+}
+
+/// `premiered` is `YYYY-MM-DD` (or just `YYYY` on some scrapers).
+fn kodi_year(premiered: Option<&str>) -> Option<i32> {
+    premiered?.trim().get(..4)?.parse().ok()
+}
+
+/// `dateAdded` as Unix seconds (`YYYY-MM-DD HH:MM:SS`, Kodi's local time read as UTC).
+fn kodi_date_added(value: Option<&str>) -> Option<u64> {
+    let parsed = NaiveDateTime::parse_from_str(value?.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+    u64::try_from(parsed.and_utc().timestamp()).ok()
+}
+
+/// Owned entries (best video stream per film) and how many films were skipped by `exclude_dirs`.
+fn collect_kodi_owned_entries(
+    conn: &Connection,
+    exclude_dirs: &[String],
+) -> Result<(Vec<OwnedEntry>, usize), String> {
+    let sql = r#"
+        SELECT
+            m.idMovie       AS movie_id,
+            m.c00           AS title,
+            m.c16           AS original_title,
+            m.premiered     AS premiered,
+            f.strFilename   AS file_name,
+            p.strPath       AS dir_path,
+            f.dateAdded     AS date_added,
+            (SELECT u.value FROM uniqueid u
+              WHERE u.media_id = m.idMovie AND u.media_type = 'movie' AND u.type = 'imdb'
+              LIMIT 1)      AS imdb,
+            (SELECT u.value FROM uniqueid u
+              WHERE u.media_id = m.idMovie AND u.media_type = 'movie' AND u.type = 'tmdb'
+              LIMIT 1)      AS tmdb,
+            s.iVideoWidth   AS width,
+            s.iVideoHeight  AS height,
+            s.strVideoCodec AS video_codec,
+            (SELECT a.strAudioCodec FROM streamdetails a
+              WHERE a.idFile = m.idFile AND a.iStreamType = 1
+              ORDER BY a.iAudioChannels DESC LIMIT 1) AS audio_codec,
+            (SELECT MAX(a.iAudioChannels) FROM streamdetails a
+              WHERE a.idFile = m.idFile AND a.iStreamType = 1) AS audio_channels
+        FROM movie m
+        JOIN files f ON f.idFile = m.idFile
+        LEFT JOIN path p ON p.idPath = f.idPath
+        LEFT JOIN streamdetails s ON s.idFile = m.idFile AND s.iStreamType = 0
+        ORDER BY
+            m.idMovie ASC,
+            COALESCE(s.iVideoWidth, 0) DESC,
+            COALESCE(s.iVideoHeight, 0) DESC
+    "#;
+
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|err| format!("Failed to prepare Kodi video query (Kodi 17+ needed): {err}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            let file_name: String = row
+                .get::<_, Option<String>>("file_name")?
+                .unwrap_or_default();
+            let dir_path: Option<String> = row.get("dir_path")?;
+            // Stacked and some network files carry their full location in strFilename.
+            let file_path = if file_name.contains("://") {
+                file_name
+            } else {
+                format!("{}{file_name}", dir_path.unwrap_or_default())
+            };
+            let premiered: Option<String> = row.get("premiered")?;
+            let date_added: Option<String> = row.get("date_added")?;
+            let width: Option<i64> = row.get("width")?;
+            let height: Option<i64> = row.get("height")?;
+            let video_codec: Option<String> = row.get("video_codec")?;
+            let audio_codec: Option<String> = row.get("audio_codec")?;
+            let audio_channels: Option<i64> = row.get("audio_channels")?;
+            Ok(OwnedEntry {
+                metadata_id: row.get("movie_id")?,
+                imdb: row.get("imdb")?,
+                tmdb: row.get("tmdb")?,
+                title: row.get::<_, Option<String>>("title")?.unwrap_or_default(),
+                original_title: row.get("original_title")?,
+                year: kodi_year(premiered.as_deref()),
+                width: width.map(|v| v.max(0) as u32),
+                height: height.map(|v| v.max(0) as u32),
+                // Kodi does not record bitrates.
+                media: OwnedMedia {
+                    bitrate_kbps: None,
+                    video_codec: non_empty(video_codec),
+                    audio_codec: non_empty(audio_codec),
+                    audio_channels: audio_channels.filter(|&n| n > 0).map(|n| n as u32),
+                    ..OwnedMedia::default()
+                },
+                updated_at: kodi_date_added(date_added.as_deref()),
+                file_path,
+                size_bytes: None,
+                other_parts: Vec::new(),
+            })
+        })
+        .map_err(|err| format!("Failed to iterate Kodi video rows: {err}"))?;
+
+    let mut seen_ids: HashSet<i64> = HashSet::new();
+    let mut results = Vec::new();
+    let mut excluded = 0usize;
+    for row in rows {
+        let entry = row.map_err(|err| format!("Failed to read Kodi video row: {err}"))?;
+        if !seen_ids.insert(entry.metadata_id) {
+            continue;
+        }
+        if in_excluded_dir(&entry.file_path, exclude_dirs) {
+            excluded += 1;
+            continue;
+        }
+        if entry.title.trim().is_empty() {
+            continue;
+        }
+        results.push(entry);
+    }
+    Ok((results, excluded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newest_myvideos_db_wins_and_kodi_dates_parse() {
+        let names = [
+            "Addons33.db",
+            "MyVideos119.db",
+            "MyVideos131.db",
+            "MyMusic82.db",
+        ];
+        assert_eq!(newest_myvideos(names), Some("MyVideos131.db"));
+        assert_eq!(newest_myvideos(["Textures13.db"]), None);
+
+        assert_eq!(kodi_year(Some("1995-09-22")), Some(1995));
+        assert_eq!(kodi_year(Some("")), None);
+        assert_eq!(
+            kodi_date_added(Some("2024-01-02 03:04:05")),
+            Some(1_704_164_645)
+        );
+        assert_eq!(kodi_date_added(Some("yesterday")), None);
+    }
+}
@@ -9,16 +9,18 @@ use std::time::{Duration, Instant};
 use tracing::warn;
 
 use crate::app::cache;
-use crate::app::types::OwnedMsg;
+use crate::app::owned::manifest::{persist_manifest, OwnedFile, OwnedPart};
+use crate::app::owned::nfo;
+use crate::app::types::{OwnedMedia, OwnedMsg};
 use crate::app::PexApp;
-use crate::config::local_library_db_path;
+use crate::config::{load_config, local_library_db_path};
 
 pub struct OwnedScanPlex;
 
 impl OwnedScanPlex {
     pub(crate) fn spawn_scan(tx: Sender<OwnedMsg>) {
         thread::spawn(move || {
-            use OwnedMsg::{Done, Error, Info};
+            use OwnedMsg::{Error, Info};
 
             let _ = tx.send(Info(
                 "Stage 3/4 - Loading owned titles from Plex library database.".into(),
@@ -68,32 +70,16 @@ impl OwnedScanPlex {
                 std::thread::sleep(Duration::from_millis(300));
             };
 
-            match collect_plex_owned_entries(&conn) {
-                Ok(entries) => {
-                    let mut owned: HashSet<String> = HashSet::new();
-                    let mut hd_keys: HashSet<String> = HashSet::new();
-                    let mut owned_dates: HashMap<String, Option<u64>> = HashMap::new();
-
-                    for entry in entries {
-                        accumulate_owned_entry(&entry, &mut owned, &mut hd_keys, &mut owned_dates);
-                    }
-
-                    let cache_dir = cache::cache_dir();
-                    if let Err(err) = persist_owned_keys_sidecar(&cache_dir, &owned) {
-                        warn!("Failed to persist owned sidecar: {err}");
+            let exclude_dirs = load_config().owned_exclude_dirs;
+            match collect_plex_owned_entries(&conn, &exclude_dirs) {
+                Ok((entries, excluded)) => {
+                    if excluded > 0 {
+                        let _ = tx.send(Info(format!(
+                            "Stage 3/4 - Skipped {excluded} library files in excluded folders ({}).",
+                            exclude_dirs.join(", ")
+                        )));
                     }
-                    if let Err(err) = persist_owned_hd_sidecar(&cache_dir, &hd_keys) {
-                        warn!("Failed to persist owned HD sidecar: {err}");
-                    }
-
-                    let count = owned.len();
-                    let _ = tx.send(Info(format!(
-                        "Stage 3/4 - Plex library owned scan complete ({count} keys)."
-                    )));
-                    let _ = tx.send(Done {
-                        keys: owned,
-                        modified: owned_dates,
-                    });
+                    send_owned_entries(&tx, entries, "Plex library");
                 }
                 Err(err) => {
                     let _ = tx.send(Error(err));
@@ -103,21 +89,98 @@ impl OwnedScanPlex {
     }
 }
 
+/// One owned film as a library source reports it (best file per film).
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
-struct PlexOwnedEntry {
-    metadata_id: i64,
-    guid: Option<String>,
-    title: String,
-    original_title: Option<String>,
-    year: Option<i32>,
-    width: Option<u32>,
-    height: Option<u32>,
-    updated_at: Option<u64>,
-    file_path: String,
+pub(super) struct OwnedEntry {
+    pub metadata_id: i64,
+    pub imdb: Option<String>,
+    pub tmdb: Option<String>,
+    pub title: String,
+    pub original_title: Option<String>,
+    pub year: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub media: OwnedMedia,
+    pub updated_at: Option<u64>,
+    pub file_path: String,
+    pub size_bytes: Option<u64>,
+    /// Further files of the same library film, for the duplicate report.
+    pub other_parts: Vec<OwnedPart>,
+}
+
+/// Turn a source's entries into owned keys, write the sidecars and finish the scan.
+pub(super) fn send_owned_entries(tx: &Sender<OwnedMsg>, entries: Vec<OwnedEntry>, source: &str) {
+    let mut owned: HashSet<String> = HashSet::new();
+    let mut hd_keys: HashSet<String> = HashSet::new();
+    let mut uhd_keys: HashSet<String> = HashSet::new();
+    let mut owned_dates: HashMap<String, Option<u64>> = HashMap::new();
+    let mut media: HashMap<String, OwnedMedia> = HashMap::new();
+    let mut files: Vec<OwnedFile> = Vec::new();
+
+    for entry in entries {
+        files.extend(OwnedFile::from_entry(&entry));
+        accumulate_owned_entry(
+            &entry,
+            &mut owned,
+            &mut hd_keys,
+            &mut uhd_keys,
+            &mut owned_dates,
+            &mut media,
+        );
+    }
+
+    let cache_dir = cache::cache_dir();
+    if let Err(err) = persist_owned_keys_sidecar(&cache_dir, &owned) {
+        warn!("Failed to persist owned sidecar: {err}");
+    }
+    if let Err(err) = persist_key_sidecar(&cache_dir, "owned_hd.txt", &hd_keys) {
+        warn!("Failed to persist owned HD sidecar: {err}");
+    }
+    if let Err(err) = persist_key_sidecar(&cache_dir, "owned_uhd.txt", &uhd_keys) {
+        warn!("Failed to persist owned UHD sidecar: {err}");
+    }
+    if let Err(err) = persist_owned_media_sidecar(&cache_dir, &media) {
+        warn!("Failed to persist owned media sidecar: {err}");
+    }
+    if let Err(err) = persist_manifest(&cache_dir, &files) {
+        warn!("Failed to persist owned manifest: {err}");
+    }
+
+    let count = owned.len();
+    let _ = tx.send(OwnedMsg::Info(format!(
+        "Stage 3/4 - {source} owned scan complete ({count} keys)."
+    )));
+    let _ = tx.send(OwnedMsg::Done {
+        keys: owned,
+        modified: owned_dates,
+    });
 }
 
-fn collect_plex_owned_entries(conn: &Connection) -> Result<Vec<PlexOwnedEntry>, String> {
+/// Trimmed text, None when missing or blank.
+pub(super) fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// True when any folder in `file_path` is one of `exclude_dirs` (e.g. Plex DVR's `.grab/`
+/// holding a recording that is still in progress). Handles `/` and `\` separators.
+pub(super) fn in_excluded_dir(file_path: &str, exclude_dirs: &[String]) -> bool {
+    let mut parts: Vec<&str> = file_path.split(['/', '\\']).collect();
+    parts.pop(); // the file name itself
+    parts.iter().any(|part| {
+        exclude_dirs
+            .iter()
+            .any(|dir| dir.eq_ignore_ascii_case(part))
+    })
+}
+
+/// Owned entries (best part per film) and how many parts were skipped by `exclude_dirs`.
+fn collect_plex_owned_entries(
+    conn: &Connection,
+    exclude_dirs: &[String],
+) -> Result<(Vec<OwnedEntry>, usize), String> {
     let sql = r#"
         SELECT
             m.id            AS metadata_id,
@@ -130,6 +193,11 @@ fn collect_plex_owned_entries(conn: &Connection) -> Result<Vec<PlexOwnedEntry>,
             mi.id           AS media_item_id,
             mi.width        AS width,
             mi.height       AS height,
+            mi.bitrate      AS bitrate,
+            mi.video_codec  AS video_codec,
+            mi.container    AS container,
+            mi.audio_codec  AS audio_codec,
+            mi.audio_channels AS audio_channels,
             mi.updated_at   AS media_updated_at,
             mp.id           AS media_part_id,
             mp.file         AS file_path,
@@ -163,12 +231,17 @@ fn collect_plex_owned_entries(conn: &Connection) -> Result<Vec<PlexOwnedEntry>,
             let year: Option<i32> = row.get("year")?;
             let width: Option<i64> = row.get("width")?;
             let height: Option<i64> = row.get("height")?;
+            let bitrate: Option<i64> = row.get("bitrate")?;
+            let video_codec: Option<String> = row.get("video_codec")?;
+            let container: Option<String> = row.get("container")?;
+            let audio_codec: Option<String> = row.get("audio_codec")?;
+            let audio_channels: Option<i64> = row.get("audio_channels")?;
             let part_updated_at: Option<i64> = row.get("part_updated_at")?;
             let media_updated_at: Option<i64> = row.get("media_updated_at")?;
             let meta_updated_at: Option<i64> = row.get("meta_updated_at")?;
             let meta_added_at: Option<i64> = row.get("meta_added_at")?;
             let file_path: String = row.get("file_path")?;
-            let _size: Option<i64> = row.get("file_size")?;
+            let file_size: Option<i64> = row.get("file_size")?;
 
             Ok((
                 metadata_id,
@@ -178,16 +251,19 @@ fn collect_plex_owned_entries(conn: &Connection) -> Result<Vec<PlexOwnedEntry>,
                 year,
                 width,
                 height,
+                bitrate,
+                (video_codec, container, audio_codec, audio_channels),
                 part_updated_at,
                 media_updated_at,
                 meta_updated_at,
                 meta_added_at,
-                file_path,
+                (file_path, file_size),
             ))
         })
         .map_err(|err| format!("Failed to iterate Plex library rows: {err}"))?;
 
-    let mut results: Vec<PlexOwnedEntry> = Vec::new();
+    let mut results: Vec<OwnedEntry> = Vec::new();
+    let mut excluded = 0usize;
 
     for row in rows {
         let (
@@ -198,52 +274,108 @@ fn collect_plex_owned_entries(conn: &Connection) -> Result<Vec<PlexOwnedEntry>,
             year,
             width,
             height,
+            bitrate,
+            (video_codec, container, audio_codec, audio_channels),
             part_updated_at,
             media_updated_at,
             meta_updated_at,
             meta_added_at,
-            file_path,
+            (file_path, file_size),
         ) = row.map_err(|err| format!("Failed to read Plex library row: {err}"))?;
 
+        // Checked before de-duplication so a finished copy of the same film still counts.
+        if in_excluded_dir(&file_path, exclude_dirs) {
+            excluded += 1;
+            continue;
+        }
+
+        let width = width.map(|v| v.max(0) as u32);
+        let height = height.map(|v| v.max(0) as u32);
+        let size_bytes = file_size.filter(|&s| s > 0).map(|s| s as u64);
+
         if !seen_ids.insert(metadata_id) {
+            // Rows come ordered by film, so a repeat is another file of the entry just pushed.
+            if let Some(entry) = results
+                .last_mut()
+                .filter(|entry| entry.metadata_id == metadata_id)
+            {
+                entry.other_parts.push(OwnedPart {
+                    path: file_path,
+                    size_bytes,
+                    width,
+                    height,
+                });
+            }
             continue;
         }
 
         if title.trim().is_empty() {
             continue;
         }
-
-        let width = width.map(|v| v.max(0) as u32);
-        let height = height.map(|v| v.max(0) as u32);
+        // Plex stores bits per second; 0 means it never probed the file.
+        let media = OwnedMedia {
+            bitrate_kbps: bitrate
+                .filter(|&bps| bps > 0)
+                .map(|bps| (bps / 1000).min(u32::MAX as i64) as u32),
+            video_codec: non_empty(video_codec),
+            container: non_empty(container),
+            audio_codec: non_empty(audio_codec),
+            audio_channels: audio_channels
+                .filter(|&n| n > 0)
+                .map(|n| n.min(u32::MAX as i64) as u32),
+            plex_rating_key: u64::try_from(metadata_id).ok(),
+            ..OwnedMedia::default()
+        };
         let updated_at = part_updated_at
             .or(media_updated_at)
             .or(meta_updated_at)
             .or(meta_added_at)
             .map(|ts| ts.max(0) as u64);
 
-        results.push(PlexOwnedEntry {
+        let guid = guid.as_deref();
+        results.push(OwnedEntry {
             metadata_id,
-            guid,
+            imdb: guid.and_then(crate::app::imdb_id_from_guid),
+            tmdb: guid.and_then(nfo::tmdb_id_from_guid),
             title,
             original_title,
             year,
             width,
             height,
+            media,
             updated_at,
             file_path,
+            size_bytes,
+            other_parts: Vec::new(),
         });
     }
 
-    Ok(results)
+    Ok((results, excluded))
 }
 
 fn accumulate_owned_entry(
-    entry: &PlexOwnedEntry,
+    entry: &OwnedEntry,
     owned: &mut HashSet<String>,
     hd_keys: &mut HashSet<String>,
+    uhd_keys: &mut HashSet<String>,
     owned_dates: &mut HashMap<String, Option<u64>>,
+    media: &mut HashMap<String, OwnedMedia>,
 ) {
     let hd = is_hd(entry.width, entry.height);
+    let uhd = is_uhd(entry.width, entry.height);
+    // Resolution and location come from the entry; the container falls back to the extension.
+    let path = Path::new(&entry.file_path);
+    let entry_media = OwnedMedia {
+        container: entry.media.container.clone().or_else(|| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_ascii_lowercase)
+        }),
+        width: entry.width,
+        height: entry.height,
+        file_path: (!entry.file_path.trim().is_empty()).then(|| entry.file_path.clone()),
+        ..entry.media.clone()
+    };
     let mut inserted_keys: HashSet<String> = HashSet::new();
 
     let mut insert_key = |key: String| {
@@ -252,40 +384,71 @@ fn accumulate_owned_entry(
             if hd {
                 hd_keys.insert(key.clone());
             }
+            if uhd {
+                uhd_keys.insert(key.clone());
+            }
+            if entry_media != OwnedMedia::default() {
+                media.insert(key.clone(), entry_media.clone());
+            }
             owned_dates.insert(key, entry.updated_at);
         }
     };
 
-    let mut push_keys_for = |title: &str| {
+    let mut push_keys_for = |title: &str, year: Option<i32>| {
         let trimmed = title.trim();
         if trimmed.is_empty() {
             return;
         }
-        for key in PexApp::owned_key_variants(trimmed, entry.year) {
+        for key in PexApp::owned_key_variants(trimmed, year) {
             insert_key(key);
         }
     };
 
-    push_keys_for(&entry.title);
+    push_keys_for(&entry.title, entry.year);
     if let Some(original) = entry.original_title.as_deref() {
-        push_keys_for(original);
+        push_keys_for(original, entry.year);
     }
 
-    if !entry.file_path.trim().is_empty() {
-        let path = Path::new(&entry.file_path);
+    let mut imdb = entry.imdb.clone();
+    let mut tmdb = entry.tmdb.clone();
+
+    // A Kodi-style .nfo names the film outright, so the file name (which may carry edition
+    // and quality tags like "Se7en - Extended - 1080p") is only a fallback.
+    let sidecar = nfo::read_movie_nfo(&entry.file_path);
+    if let Some(info) = sidecar.as_ref() {
+        let year = info.year.or(entry.year);
+        for title in [&info.title, &info.original_title].into_iter().flatten() {
+            push_keys_for(title, year);
+        }
+        imdb = imdb.or_else(|| info.imdb.clone());
+        tmdb = tmdb.or_else(|| info.tmdb.clone());
+    }
+    let id_keys = nfo::id_keys(imdb.as_deref(), tmdb.as_deref());
+
+    let named_by_nfo = sidecar.is_some_and(|info| info.title.is_some());
+    if !named_by_nfo && !entry.file_path.trim().is_empty() {
         if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-            push_keys_for(stem);
+            push_keys_for(stem, entry.year);
         }
         if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
-            push_keys_for(name);
+            push_keys_for(name, entry.year);
         }
     }
+
+    for key in id_keys {
+        insert_key(key);
+    }
 }
 
-fn is_hd(width: Option<u32>, height: Option<u32>) -> bool {
+pub(super) fn is_hd(width: Option<u32>, height: Option<u32>) -> bool {
     width.map(|w| w >= 1280).unwrap_or(false) || height.map(|h| h >= 720).unwrap_or(false)
 }
 
+/// 4K/UHD: 3840 wide (scope films are cropped to ~1600 high) or a full 2160 lines.
+pub(super) fn is_uhd(width: Option<u32>, height: Option<u32>) -> bool {
+    width.is_some_and(|w| w >= 3840) || height.is_some_and(|h| h >= 2160)
+}
+
 fn persist_owned_keys_sidecar(
     cache_dir: &std::path::Path,
     owned_keys: &HashSet<String>,
@@ -301,14 +464,154 @@ fn persist_owned_keys_sidecar(
     .map_err(|err| format!("Failed to write {}: {err}", path.display()))
 }
 
-fn persist_owned_hd_sidecar(
+/// One key per line (`owned_hd.txt`, `owned_uhd.txt`).
+fn persist_key_sidecar(
+    cache_dir: &std::path::Path,
+    file_name: &str,
+    keys: &HashSet<String>,
+) -> Result<(), String> {
+    let path = cache_dir.join(file_name);
+    fs::write(
+        &path,
+        keys.iter().map(|k| format!("{k}\n")).collect::<String>(),
+    )
+    .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+fn persist_owned_media_sidecar(
     cache_dir: &std::path::Path,
-    hd_keys: &HashSet<String>,
+    media: &HashMap<String, OwnedMedia>,
 ) -> Result<(), String> {
-    let path = cache_dir.join("owned_hd.txt");
+    let path = cache_dir.join("owned_media.txt");
     fs::write(
         &path,
-        hd_keys.iter().map(|k| format!("{k}\n")).collect::<String>(),
+        media
+            .iter()
+            .map(|(k, m)| format!("{}\n", format_owned_media_line(k, m)))
+            .collect::<String>(),
     )
     .map_err(|err| format!("Failed to write {}: {err}", path.display()))
 }
+
+/// `key \t bitrate_kbps \t video codec \t container \t audio codec \t audio channels \t width
+/// \t height \t file path \t Plex rating key`; any value may be empty. Older sidecars stop after
+/// the video codec or the file path.
+fn format_owned_media_line(key: &str, media: &OwnedMedia) -> String {
+    fn opt<T: ToString>(value: Option<T>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+    [
+        key.to_string(),
+        opt(media.bitrate_kbps),
+        opt(media.video_codec.as_deref()),
+        opt(media.container.as_deref()),
+        opt(media.audio_codec.as_deref()),
+        opt(media.audio_channels),
+        opt(media.width),
+        opt(media.height),
+        opt(media.file_path.as_deref()),
+        opt(media.plex_rating_key),
+    ]
+    .join("\t")
+}
+
+pub(crate) fn parse_owned_media_line(line: &str) -> Option<(String, OwnedMedia)> {
+    let mut parts = line.trim_end_matches(['\r', '\n']).splitn(10, '\t');
+    let key = parts.next()?.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let bitrate = parts.next()?.trim();
+    let mut text = || non_empty(parts.next().map(String::from));
+    let video_codec = text();
+    let container = text();
+    let audio_codec = text();
+    let audio_channels = text();
+    let width = text();
+    let height = text();
+    let file_path = text();
+    let plex_rating_key = text();
+    Some((
+        key.to_string(),
+        OwnedMedia {
+            bitrate_kbps: bitrate.parse().ok(),
+            video_codec,
+            container,
+            audio_codec,
+            audio_channels: audio_channels.and_then(|n| n.parse().ok()),
+            width: width.and_then(|n| n.parse().ok()),
+            height: height.and_then(|n| n.parse().ok()),
+            file_path,
+            plex_rating_key: plex_rating_key.and_then(|n| n.parse().ok()),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grab_folders_are_excluded_on_any_platform() {
+        let dirs: Vec<String> = crate::config::DEFAULT_OWNED_EXCLUDE_DIRS
+            .map(String::from)
+            .to_vec();
+        assert!(in_excluded_dir(
+            "/data/Movies/.grab/5f1c2a/Casablanca (1942).ts",
+            &dirs
+        ));
+        assert!(in_excluded_dir(r"D:\Movies\.GRAB\abc\Film.ts", &dirs));
+        assert!(!in_excluded_dir(
+            "/data/Movies/Casablanca (1942)/Casablanca.mkv",
+            &dirs
+        ));
+        // Only folders count, not the file name.
+        assert!(!in_excluded_dir("/data/Movies/.grab", &dirs));
+        assert!(!in_excluded_dir("/data/.grab/Film.ts", &[]));
+    }
+
+    #[test]
+    fn owned_media_lines_round_trip() {
+        let media = OwnedMedia {
+            bitrate_kbps: Some(2460),
+            video_codec: Some("h264".into()),
+            container: Some("mkv".into()),
+            audio_codec: Some("ac3".into()),
+            audio_channels: Some(6),
+            width: Some(1920),
+            height: Some(800),
+            file_path: Some("/films/Casablanca (1942)/Casablanca.mkv".into()),
+            plex_rating_key: Some(4242),
+        };
+        let line = format_owned_media_line("casablanca|1942", &media);
+        assert_eq!(
+            parse_owned_media_line(&line),
+            Some(("casablanca|1942".into(), media.clone()))
+        );
+        assert_eq!(media.describe().as_deref(), Some("H264 · 2.5 Mbps"));
+        assert_eq!(
+            media.file_details(),
+            vec![
+                ("Path", "/films/Casablanca (1942)/Casablanca.mkv".into()),
+                ("Container", "MKV".into()),
+                ("Video", "H264 · 1920×800".into()),
+                ("Audio", "AC3 5.1".into()),
+                ("Bitrate", "2.5 Mbps".into()),
+            ]
+        );
+        let (_, codec_only) = parse_owned_media_line("key\t\thevc").unwrap();
+        assert_eq!(codec_only.bitrate_kbps, None);
+        assert_eq!(codec_only.video_codec.as_deref(), Some("hevc"));
+        assert_eq!(codec_only.file_path, None);
+        assert!(parse_owned_media_line("\t100\th264").is_none());
+    }
+
+    #[test]
+    fn scope_4k_counts_as_uhd_and_1080p_does_not() {
+        assert!(is_uhd(Some(3840), Some(1606)));
+        assert!(is_uhd(None, Some(2160)));
+        assert!(!is_uhd(Some(1920), Some(1080)));
+        assert!(is_hd(Some(1920), Some(800)));
+        assert!(!is_hd(Some(720), Some(576)));
+    }
+}
@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use tracing::info;
+use walkdir::WalkDir;
+
+use crate::config::AppConfig;
+
+/// How often `library_roots` are walked for changes.
+const WATCH_EVERY: Duration = Duration::from_secs(120);
+/// Quiet time after the last change before rescanning, so the library server can index the
+/// new file (and a copy in progress can finish) first.
+const SETTLE_FOR: Duration = Duration::from_secs(300);
+
+/// File count and newest modification time under the library roots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct RootsSignature {
+    files: usize,
+    newest: Option<SystemTime>,
+}
+
+/// Re-runs the owned scan every `owned_rescan_hours`, and shortly after files change under
+/// `library_roots` (polled, like `config_watch`), so new recordings flip to Owned on their own.
+pub(crate) struct OwnedRescan {
+    /// None when timed rescans are off (`owned_rescan_hours` unset or 0).
+    every: Option<Duration>,
+    last: Instant,
+    roots: Vec<PathBuf>,
+    exclude_dirs: Vec<String>,
+    last_walk: Option<Instant>,
+    walk_rx: Option<Receiver<RootsSignature>>,
+    signature: Option<RootsSignature>,
+    /// A change was seen; rescan once this passes without another one.
+    due: Option<Instant>,
+}
+
+impl Default for OwnedRescan {
+    fn default() -> Self {
+        Self {
+            every: None,
+            last: Instant::now(),
+            roots: Vec::new(),
+            exclude_dirs: Vec::new(),
+            last_walk: None,
+            walk_rx: None,
+            signature: None,
+            due: None,
+        }
+    }
+}
+
+/// Walk `roots`, skipping `exclude_dirs` (a recording in `.grab/` changes every few seconds).
+fn roots_signature(roots: &[PathBuf], exclude_dirs: &[String]) -> RootsSignature {
+    let mut signature = RootsSignature::default();
+    for root in roots {
+        let walk = WalkDir::new(root).into_iter().filter_entry(|entry| {
+            !entry.file_type().is_dir()
+                || entry.depth() == 0
+                || !exclude_dirs
+                    .iter()
+                    .any(|dir| dir.eq_ignore_ascii_case(&entry.file_name().to_string_lossy()))
+        });
+        for entry in walk.filter_map(Result::ok) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            signature.files += 1;
+            let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+            signature.newest = signature.newest.max(modified);
+        }
+    }
+    signature
+}
+
+impl OwnedRescan {
+    pub fn configure(&mut self, cfg: &AppConfig) {
+        self.every = cfg
+            .owned_rescan_hours
+            .filter(|hours| *hours > 0)
+            .map(|hours| Duration::from_secs(u64::from(hours) * 3600));
+        if self.roots != cfg.library_roots || self.exclude_dirs != cfg.owned_exclude_dirs {
+            self.roots = cfg.library_roots.clone();
+            self.exclude_dirs = cfg.owned_exclude_dirs.clone();
+            self.signature = None;
+            self.due = None;
+            self.walk_rx = None;
+            self.last_walk = None;
+        }
+    }
+
+    /// Time left until the next timed rescan and the interval, if timed rescans are on.
+    pub fn next_due(&self) -> Option<(Duration, Duration)> {
+        self.every
+            .map(|every| (every.saturating_sub(self.last.elapsed()), every))
+    }
+
+    /// An owned scan just started for any reason; restart the interval.
+    pub fn scan_started(&mut self) {
+        self.last = Instant::now();
+        self.due = None;
+    }
+
+    /// Why a rescan is due now, if it is. Also drives the background walk of the roots.
+    fn poll(&mut self) -> Option<&'static str> {
+        if let Some(rx) = &self.walk_rx {
+            match rx.try_recv() {
+                Ok(fresh) => {
+                    self.walk_rx = None;
+                    if self.signature.is_some_and(|old| old != fresh) {
+                        info!("Library files changed; owned rescan in {SETTLE_FOR:?}.");
+                        self.due = Some(Instant::now() + SETTLE_FOR);
+                    }
+                    self.signature = Some(fresh);
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.walk_rx = None,
+            }
+        }
+        if !self.roots.is_empty()
+            && self.walk_rx.is_none()
+            && self.last_walk.is_none_or(|t| t.elapsed() >= WATCH_EVERY)
+        {
+            self.last_walk = Some(Instant::now());
+            let (tx, rx) = mpsc::channel();
+            let roots = self.roots.clone();
+            let exclude_dirs = self.exclude_dirs.clone();
+            thread::spawn(move || {
+                let _ = tx.send(roots_signature(&roots, &exclude_dirs));
+            });
+            self.walk_rx = Some(rx);
+        }
+
+        if self.due.is_some_and(|due| Instant::now() >= due) {
+            return Some("files changed under library_roots");
+        }
+        if self.every.is_some_and(|every| self.last.elapsed() >= every) {
+            return Some("scheduled");
+        }
+        None
+    }
+}
+
+impl crate::app::PexApp {
+    /// Start a timed or change-triggered owned rescan when one is due. Rows keep their
+    /// current owned flags until the new scan finishes.
+    pub(crate) fn poll_owned_rescan(&mut self) {
+        if self.demo_mode || self.in_safe_mode() || !self.did_init {
+            return;
+        }
+        if self.owned_rx.is_some() {
+            return;
+        }
+        let Some(reason) = self.owned_rescan.poll() else {
+            return;
+        };
+        self.record_owned_message(format!("Owned rescan started ({reason})."));
+        match crate::app::prep::sync_library_db_from_source(true) {
+            Ok(true) => {
+                self.record_owned_message("Copied Plex library DB from plex_library_db_source.")
+            }
+            Ok(false) => {}
+            Err(err) => {
+                self.record_owned_message(format!("Plex library DB refresh skipped: {err}"))
+            }
+        }
+        self.refresh_scheduled_index();
+        self.start_owned_scan();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn roots_signature_counts_files_and_skips_excluded_folders() {
+        let dir = tempfile::tempdir().unwrap();
+        let films = dir.path().join("Films");
+        fs::create_dir_all(films.join(".grab/123")).unwrap();
+        fs::write(films.join("Heat.mkv"), b"x").unwrap();
+        fs::write(films.join(".grab/123/Alien.ts"), b"x").unwrap();
+        let exclude = vec![".grab".to_string()];
+
+        let before = roots_signature(std::slice::from_ref(&films), &exclude);
+        assert_eq!(before.files, 1);
+        assert!(before.newest.is_some());
+
+        fs::write(films.join("Alien.mkv"), b"x").unwrap();
+        let after = roots_signature(std::slice::from_ref(&films), &exclude);
+        assert_eq!(after.files, 2);
+        assert_ne!(before, after);
+        assert_eq!(roots_signature(&[films], &[]).files, 3);
+    }
+}
@@ -0,0 +1,241 @@
+// src/app/plex_api.rs
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Deserialize;
+
+use crate::config::AppConfig;
+
+// Give Plex time to finish post-processing before asking for a rescan.
+const REFRESH_AFTER_END: Duration = Duration::from_secs(10 * 60);
+const RECORDING_CHECK_EVERY: Duration = Duration::from_secs(60);
+
+/// Plex Media Server HTTP endpoint (`plex_server_url` + `plex_token`).
+#[derive(Clone, Debug)]
+pub(crate) struct PlexServer {
+    pub url: String,
+    pub token: String,
+}
+
+impl PlexServer {
+    pub fn from_config(cfg: &AppConfig) -> Option<Self> {
+        Some(Self {
+            url: cfg.plex_server_url.clone()?,
+            token: cfg.plex_token.clone()?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct SectionsResponse {
+    #[serde(rename = "MediaContainer")]
+    container: SectionsContainer,
+}
+
+#[derive(Deserialize)]
+struct SectionsContainer {
+    #[serde(rename = "Directory", default)]
+    directories: Vec<SectionDirectory>,
+}
+
+#[derive(Deserialize)]
+struct SectionDirectory {
+    key: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    title: String,
+}
+
+/// (key, title) of every movie library in a `/library/sections` JSON response.
+fn movie_sections(body: &str) -> Result<Vec<(String, String)>, String> {
+    let parsed: SectionsResponse =
+        serde_json::from_str(body).map_err(|err| format!("parse sections: {err}"))?;
+    Ok(parsed
+        .container
+        .directories
+        .into_iter()
+        .filter(|dir| dir.kind == "movie")
+        .map(|dir| (dir.key, dir.title))
+        .collect())
+}
+
+/// Ask Plex to rescan every movie library. Returns the titles of the sections refreshed.
+pub(crate) fn refresh_movie_sections(server: &PlexServer) -> Result<Vec<String>, String> {
+    let client = crate::app::net::client_builder()
+        .user_agent("pex/library-refresh")
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|err| format!("client: {err}"))?;
+    let get = |path: &str| {
+        let resp = client
+            .get(format!("{}{path}", server.url))
+            .header("X-Plex-Token", &server.token)
+            .header("Accept", "application/json")
+            .send()
+            .map_err(|err| format!("network: {err}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("{path}: HTTP {}", resp.status()));
+        }
+        resp.text().map_err(|err| format!("read: {err}"))
+    };
+
+    let sections = movie_sections(&get("/library/sections")?)?;
+    if sections.is_empty() {
+        return Err("the server has no movie libraries".into());
+    }
+    let mut refreshed = Vec::with_capacity(sections.len());
+    for (key, title) in sections {
+        get(&format!("/library/sections/{key}/refresh"))?;
+        refreshed.push(title);
+    }
+    Ok(refreshed)
+}
+
+/// Library refresh requests (manual or after recordings finish) and their results.
+pub(crate) struct LibraryRefreshState {
+    server: Option<PlexServer>,
+    after_recording: bool,
+    rx: Option<Receiver<Result<Vec<String>, String>>>,
+    started: SystemTime,
+    last_check: Instant,
+    handled: HashSet<(String, SystemTime)>,
+}
+
+impl Default for LibraryRefreshState {
+    fn default() -> Self {
+        Self {
+            server: None,
+            after_recording: false,
+            rx: None,
+            started: SystemTime::now(),
+            last_check: Instant::now(),
+            handled: HashSet::new(),
+        }
+    }
+}
+
+impl LibraryRefreshState {
+    /// Apply the Plex server settings; only recordings ending after `now` trigger a refresh.
+    pub fn configure(&mut self, cfg: &AppConfig, now: SystemTime) {
+        self.server = PlexServer::from_config(cfg);
+        self.after_recording = cfg.plex_refresh_after_recording;
+        self.started = now;
+    }
+
+    pub fn in_flight(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    pub const fn is_configured(&self) -> bool {
+        self.server.is_some()
+    }
+}
+
+impl crate::app::PexApp {
+    /// Kick off a Plex library rescan in the background (no-op without a server or while one runs).
+    pub(crate) fn request_library_refresh(&mut self, reason: &str) {
+        if self.library_refresh.in_flight() {
+            return;
+        }
+        if self.is_offline() {
+            self.record_owned_message(format!("Plex library refresh skipped ({reason}): offline."));
+            return;
+        }
+        let Some(server) = self.library_refresh.server.clone() else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        self.library_refresh.rx = Some(rx);
+        self.record_owned_message(format!("Asking Plex to rescan movie libraries ({reason})."));
+        std::thread::spawn(move || {
+            let _ = tx.send(refresh_movie_sections(&server));
+        });
+    }
+
+    pub(crate) fn poll_library_refresh(&mut self) {
+        if !self.in_safe_mode() {
+            self.check_finished_recordings();
+        }
+
+        let Some(rx) = self.library_refresh.rx.as_ref() else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("refresh worker exited".into()),
+        };
+        self.library_refresh.rx = None;
+        let msg = match result {
+            Ok(titles) => format!(
+                "Plex is rescanning {}. New files show up after the next owned scan.",
+                titles.join(", ")
+            ),
+            Err(err) => format!("Plex library refresh failed: {err}"),
+        };
+        self.record_owned_message(msg.clone());
+        self.advanced_feedback = Some(msg);
+    }
+
+    /// With `plex_refresh_after_recording`, request one rescan once scheduled airings that ended
+    /// during this session are a few minutes past their end time.
+    fn check_finished_recordings(&mut self) {
+        let now = self.now();
+        let state = &mut self.library_refresh;
+        if !state.after_recording
+            || self.offline.is_some()
+            || state.server.is_none()
+            || state.rx.is_some()
+            || state.last_check.elapsed() < RECORDING_CHECK_EVERY
+        {
+            return;
+        }
+        state.last_check = Instant::now();
+
+        let mut finished = 0usize;
+        for row in self.rows.iter().filter(|row| row.scheduled) {
+            let Some(ends) = row.ends else {
+                continue;
+            };
+            if ends < state.started || ends + REFRESH_AFTER_END > now {
+                continue;
+            }
+            if state.handled.insert((row.key.clone(), ends)) {
+                finished += 1;
+            }
+        }
+        if finished > 0 {
+            self.request_library_refresh(&format!(
+                "{finished} recording{} finished",
+                if finished == 1 { "" } else { "s" }
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_movie_sections_only() {
+        let body = r#"{"MediaContainer":{"size":3,"Directory":[
+            {"key":"1","type":"movie","title":"Movies"},
+            {"key":"2","type":"show","title":"TV"},
+            {"key":"7","type":"movie","title":"Recorded Films"}
+        ]}}"#;
+        assert_eq!(
+            movie_sections(body).unwrap(),
+            vec![
+                ("1".to_string(), "Movies".to_string()),
+                ("7".to_string(), "Recorded Films".to_string())
+            ]
+        );
+        assert!(movie_sections(r#"{"MediaContainer":{"size":0}}"#)
+            .unwrap()
+            .is_empty());
+        assert!(movie_sections("<xml/>").is_err());
+    }
+}
@@ -1,10 +1,152 @@
 // src/app/prefetch.rs
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::mpsc;
-use std::time::Duration;
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
 
 use eframe::egui as eg;
 
+use crate::app::prefetch_queue::PrefetchQueue;
+
+const RANK_VISIBLE: u32 = 0;
+// Rows outside the current view start here (+ days until airing).
+const RANK_OFF_VIEW: u32 = 10_000;
+const RANK_UNKNOWN: u32 = u32::MAX;
+const RERANK_EVERY_FRAMES: u64 = 15;
+// Per-poster attempts inside a worker, waiting 2s, 4s, 8s… between them.
+const DOWNLOAD_ATTEMPTS: u32 = 4;
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+// After the pass, failed posters are probed on this schedule (doubling while still offline).
+const SWEEP_FIRST_DELAY: Duration = Duration::from_secs(60);
+const SWEEP_MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+
+/// Re-queues failed posters once the prefetch pass is over: one failure is retried as a probe,
+/// and only when it succeeds (connectivity is back) are the rest queued again.
+pub(crate) struct RetrySweep {
+    next_at: Instant,
+    delay: Duration,
+    probe: Option<usize>,
+    // rows whose failure won't fix itself (404, oversized art)
+    gave_up: HashSet<usize>,
+}
+
+impl Default for RetrySweep {
+    fn default() -> Self {
+        Self {
+            next_at: Instant::now() + SWEEP_FIRST_DELAY,
+            delay: SWEEP_FIRST_DELAY,
+            probe: None,
+            gave_up: HashSet::new(),
+        }
+    }
+}
+
+/// Wait before download attempt `attempt + 1`.
+fn retry_delay(attempt: u32) -> Duration {
+    RETRY_BASE_DELAY * 2u32.saturating_pow(attempt.saturating_sub(1))
+}
+
+/// Client errors (other than timeouts / rate limits) and oversized artwork won't succeed on retry.
+fn is_permanent_failure(err: &str) -> bool {
+    if err.contains("poster too large") {
+        return true;
+    }
+    let status = ["HTTP ", "client error ("].iter().find_map(|prefix| {
+        let (_, rest) = err.split_once(prefix)?;
+        rest.get(..3)?.parse::<u16>().ok()
+    });
+    matches!(status, Some(400..=499)) && !matches!(status, Some(408 | 429))
+}
+
+/// Download a missing poster, retrying transient failures with exponential backoff.
+fn download_with_retries(
+    queue: &PrefetchQueue,
+    client: &reqwest::blocking::Client,
+    url: &str,
+    key: &str,
+    variant_w: Option<u32>,
+) -> Result<PathBuf, String> {
+    let mut attempt = 1;
+    loop {
+        let result = crate::app::cache::download_and_store_resized_with_client(
+            client,
+            url,
+            key,
+            variant_w.unwrap_or(super::RESIZE_MAX_W),
+            super::RESIZE_QUALITY,
+        )
+        .or_else(|_e| crate::app::cache::download_and_store(url, key));
+        match result {
+            Err(e)
+                if attempt < DOWNLOAD_ATTEMPTS
+                    && !is_permanent_failure(&e)
+                    && !queue.is_closed()
+                    && !crate::app::net::is_offline() =>
+            {
+                let delay = retry_delay(attempt);
+                tracing::debug!("poster download failed ({e}); retry {attempt} in {delay:?}");
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            other => return other,
+        }
+    }
+}
+
+/// What a new prefetch worker needs; kept so the pool can grow while prefetch runs.
+pub(crate) struct WorkerSpawner {
+    done_tx: mpsc::Sender<crate::app::PrefetchDone>,
+    client: Arc<reqwest::blocking::Client>,
+    // Cached posters at least this old are revalidated (ETag / Last-Modified) before reuse.
+    stale_after: Duration,
+}
+
+impl WorkerSpawner {
+    fn spawn(&self, queue: Arc<PrefetchQueue>) {
+        let done_tx = self.done_tx.clone();
+        let client = Arc::clone(&self.client);
+        let stale_after = self.stale_after;
+        std::thread::spawn(move || {
+            while let Some((row_idx, key, url, cached_path, variant_w)) = queue.pop_blocking() {
+                let (result, refreshed) = match cached_path {
+                    None => (
+                        download_with_retries(&queue, &client, &url, &key, variant_w),
+                        false,
+                    ),
+                    Some(path) if crate::app::cache::poster_is_stale(&path, stale_after) => {
+                        revalidate(&client, &url, path)
+                    }
+                    Some(path) => (Ok(path), false),
+                };
+
+                let _ = done_tx.send(crate::app::PrefetchDone {
+                    row_idx,
+                    result,
+                    variant_w,
+                    refreshed,
+                });
+            }
+        });
+    }
+}
+
+/// Conditional re-fetch of a stale cached poster. Network trouble keeps the cached copy.
+fn revalidate(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    path: PathBuf,
+) -> (Result<PathBuf, String>, bool) {
+    use crate::app::cache::Revalidated;
+    match crate::app::cache::revalidate_poster(client, url, &path, super::RESIZE_QUALITY) {
+        Ok(Revalidated::Unchanged(path)) => (Ok(path), false),
+        Ok(Revalidated::Updated(path)) => (Ok(path), true),
+        Err(e) => {
+            tracing::debug!("poster revalidation failed for {url}: {e}; keeping cached copy");
+            (Ok(path), false)
+        }
+    }
+}
+
 impl crate::app::PexApp {
     /// Start prefetch: queue all rows, but avoid repeated disk lookups by reusing row.path.
     /// Workers will download the SMALL variant (key `__s`) if missing.
@@ -14,10 +156,22 @@ impl crate::app::PexApp {
         }
 
         // Allow opting out instead of opting in.
-        let prefetch_disabled = std::env::var_os("PEX_DISABLE_PREFETCH").is_some();
+        let prefetch_disabled =
+            std::env::var_os("PEX_DISABLE_PREFETCH").is_some() || self.in_safe_mode();
 
-        if prefetch_disabled || self.rows.is_empty() {
-            let message = if prefetch_disabled {
+        if prefetch_disabled || self.is_offline() || self.rows.is_empty() {
+            let message = if self.is_offline() {
+                format!(
+                    "Stage 4/4 - Offline: showing cached posters only ({} of {} on disk).",
+                    self.rows.iter().filter(|row| row.path.is_some()).count(),
+                    self.rows.len()
+                )
+            } else if self.in_safe_mode() {
+                format!(
+                    "Stage 4/4 - Safe mode: prefetch paused (posters will load on demand). {} items queued.",
+                    self.rows.len()
+                )
+            } else if prefetch_disabled {
                 format!(
                     "Stage 4/4 - Prefetch disabled via PEX_DISABLE_PREFETCH (posters will load on demand). {} items queued.",
                     self.rows.len()
@@ -44,6 +198,8 @@ impl crate::app::PexApp {
 
         self.completed = 0;
         self.failed = 0;
+        self.prefetch_skipped = 0;
+        self.retry_sweep = RetrySweep::default();
         self.total_targets = self.rows.len();
         self.loading_progress = if self.total_targets == 0 { 1.0 } else { 0.0 };
         self.last_item_msg = if self.total_targets > 0 {
@@ -57,15 +213,13 @@ impl crate::app::PexApp {
         self.set_phase(super::Phase::Prefetching);
         self.stage4_complete_message = None;
 
-        let (work_tx, work_rx) = mpsc::channel::<super::WorkItem>();
+        let queue = Arc::new(PrefetchQueue::default());
         let (done_tx, done_rx) = mpsc::channel::<crate::app::PrefetchDone>();
-        self.work_tx = Some(work_tx.clone());
+        self.work_queue = Some(Arc::clone(&queue));
         self.done_rx = Some(done_rx);
 
-        let work_rx = std::sync::Arc::new(std::sync::Mutex::new(work_rx));
-
         // One shared HTTP client.
-        let client = match reqwest::blocking::Client::builder()
+        let client = match crate::app::net::client_builder()
             .user_agent("pex/prefetch")
             .timeout(Duration::from_secs(20))
             .pool_max_idle_per_host(16)
@@ -80,7 +234,7 @@ impl crate::app::PexApp {
             })
             .build()
         {
-            Ok(c) => std::sync::Arc::new(c),
+            Ok(c) => Arc::new(c),
             Err(e) => {
                 self.set_status(format!("http client build failed: {e}"));
                 self.failed = self.total_targets;
@@ -90,71 +244,156 @@ impl crate::app::PexApp {
             }
         };
 
-        for _ in 0..self.worker_count_ui {
-            let work_rx = std::sync::Arc::clone(&work_rx);
-            let done_tx = done_tx.clone();
-            let client = std::sync::Arc::clone(&client);
+        let stale_after = if std::mem::take(&mut self.revalidate_all_artwork) {
+            Duration::ZERO
+        } else {
+            crate::app::cache::POSTER_REVALIDATE_AFTER
+        };
+        self.prefetch_spawner = Some(WorkerSpawner {
+            done_tx,
+            client,
+            stale_after,
+        });
+        self.apply_worker_count();
 
-            std::thread::spawn(move || loop {
-                let job = {
-                    let rx = work_rx.lock().unwrap();
-                    rx.recv()
-                };
-                let (row_idx, key, url, cached_path) = match job {
-                    Ok(t) => t,
-                    Err(_) => break,
-                };
+        // Before the first paint there is no view yet, so this falls back to soonest airing first.
+        let ranks = self.prefetch_ranks();
+        for (idx, row) in self.rows.iter_mut().enumerate() {
+            row.state = if row.path.is_some() {
+                super::PosterState::Cached
+            } else {
+                super::PosterState::Pending
+            };
+            let job = (
+                idx,
+                row.key.clone(),
+                row.url.clone(),
+                row.path.clone(),
+                None,
+            );
+            queue.push(job, ranks.get(&idx).copied().unwrap_or(RANK_UNKNOWN));
+        }
 
-                let result: Result<PathBuf, String> = cached_path.map_or_else(
-                    || {
-                        crate::app::cache::download_and_store_resized_with_client(
-                            &client,
-                            &url,
-                            &key,
-                            super::RESIZE_MAX_W,
-                            super::RESIZE_QUALITY,
-                        )
-                        .or_else(|_e| crate::app::cache::download_and_store(&url, &key))
-                    },
-                    Ok,
-                );
+        // Perceptual boost
+        self.prewarm_first_screen(ctx);
+        ctx.request_repaint();
+    }
 
-                let _ = done_tx.send(crate::app::PrefetchDone { row_idx, result });
-            });
+    /// Queue a larger poster variant for visible cards once the on-screen width (in physical
+    /// pixels) outgrows the cached one. Variants already on disk are swapped in directly.
+    pub(crate) fn schedule_variant_upgrades(&mut self, ctx: &eg::Context) {
+        let display_px = self.poster_width_ui * ctx.pixels_per_point();
+        let mut visible: Vec<usize> = self.visible_idxs.iter().copied().collect();
+        visible.sort_unstable();
+        for idx in visible {
+            if self.variant_upgrades.contains(&idx) || self.variant_upgrade_failed.contains(&idx) {
+                continue;
+            }
+            let Some(row) = self.rows.get_mut(idx) else {
+                continue;
+            };
+            if row.path.is_none() || matches!(row.state, super::PosterState::Failed) {
+                continue;
+            }
+            let Some((suffix, width)) =
+                crate::app::cache::variant_upgrade_for(row.variant_w, display_px)
+            else {
+                continue;
+            };
+            let key = crate::app::cache::variant_key(&row.key, suffix);
+            if let Some(path) = crate::app::cache::find_any_by_key(&key) {
+                row.path = Some(path);
+                row.variant_w = width;
+                row.tex = None;
+                self.fresh_uploads.push_back(idx);
+                continue;
+            }
+            let Some(queue) = self.work_queue.as_ref() else {
+                return; // no worker pool (prefetch disabled); keep the small variant
+            };
+            if queue.push((idx, key, row.url.clone(), None, Some(width)), RANK_VISIBLE) {
+                self.variant_upgrades.insert(idx);
+            }
         }
+    }
 
-        // Prioritize “soon” items (next 2 days), then the rest.
-        let now_bucket = crate::app::utils::day_bucket(std::time::SystemTime::now());
-        let soon_cutoff = now_bucket + 2;
+    fn finish_variant_upgrade(
+        &mut self,
+        row_idx: usize,
+        variant_w: u32,
+        result: Result<PathBuf, String>,
+    ) {
+        self.variant_upgrades.remove(&row_idx);
+        let Some(row) = self.rows.get_mut(row_idx) else {
+            return;
+        };
+        match result {
+            Ok(path) => {
+                row.path = Some(path);
+                row.variant_w = variant_w;
+                row.tex = None; // re-uploaded visible-first via fresh_uploads
+                self.fresh_uploads.push_back(row_idx);
+            }
+            Err(e) => {
+                // Keep showing the smaller poster; a prefetch restart tries again.
+                self.variant_upgrade_failed.insert(row_idx);
+                tracing::debug!("poster variant upgrade failed for {}: {e}", row.title);
+            }
+        }
+    }
 
-        let mut indices: Vec<(bool, usize)> = self
+    /// Download priority for every row (lower first): on-screen cards, then cards ordered by
+    /// distance from the viewport in the current view, then filtered-out rows by airing day.
+    fn prefetch_ranks(&self) -> HashMap<usize, u32> {
+        let now_bucket = crate::app::utils::day_bucket(self.now());
+        let mut ranks: HashMap<usize, u32> = self
             .rows
             .iter()
             .enumerate()
-            .map(|(i, r)| {
-                let prio = r
-                    .airing
-                    .map(|ts| crate::app::utils::day_bucket(ts) < soon_cutoff)
-                    .unwrap_or(false);
-                (prio, i)
+            .map(|(idx, row)| {
+                let rank = row.airing.map_or(RANK_UNKNOWN, |ts| {
+                    let days = (crate::app::utils::day_bucket(ts) - now_bucket).clamp(0, 365);
+                    RANK_OFF_VIEW + days as u32
+                });
+                (idx, rank)
             })
             .collect();
 
-        indices.sort_by_key(|(prio, i)| (std::cmp::Reverse(*prio), *i));
-
-        for (_, idx) in indices {
-            let row = &mut self.rows[idx];
-            row.state = if row.path.is_some() {
-                super::PosterState::Cached
-            } else {
-                super::PosterState::Pending
-            };
-            let _ = work_tx.send((idx, row.key.clone(), row.url.clone(), row.path.clone()));
+        let viewport_row = self
+            .grid_rows
+            .iter()
+            .position(|row| row.iter().any(|idx| self.visible_idxs.contains(idx)))
+            .unwrap_or(0);
+        for (row_i, row) in self.grid_rows.iter().enumerate() {
+            let distance = row_i.abs_diff(viewport_row).min(RANK_OFF_VIEW as usize - 2) as u32;
+            for &idx in row {
+                ranks.insert(idx, 1 + distance);
+            }
+        }
+        for &idx in &self.visible_idxs {
+            ranks.insert(idx, RANK_VISIBLE);
         }
+        ranks
+    }
 
-        // Perceptual boost
-        self.prewarm_first_screen(ctx);
-        ctx.request_repaint();
+    /// Re-rank pending downloads every few frames so the queue follows scrolling/filters.
+    pub(crate) fn rerank_prefetch_queue(&mut self) {
+        if !self.frame_no.is_multiple_of(RERANK_EVERY_FRAMES) {
+            return;
+        }
+        let Some(queue) = self.work_queue.as_ref() else {
+            return;
+        };
+        if queue.is_empty() {
+            return;
+        }
+        let ranks = self.prefetch_ranks();
+        let pending = queue.pending_rows();
+        queue.rerank(
+            pending
+                .into_iter()
+                .filter_map(|idx| ranks.get(&idx).map(|rank| (idx, *rank))),
+        );
     }
 
     /// Poll prefetch completions and update progress/splash.
@@ -169,18 +408,38 @@ impl crate::app::PexApp {
             match rx.try_recv() {
                 Ok(msg) => {
                     drained += 1;
+                    if let Some(variant_w) = msg.variant_w {
+                        self.finish_variant_upgrade(msg.row_idx, variant_w, msg.result);
+                        continue;
+                    }
+                    let probe = self.retry_sweep.probe == Some(msg.row_idx);
+                    if probe {
+                        self.finish_retry_probe(msg.result.is_ok());
+                    }
                     match msg.result {
                         Ok(path) => {
                             if let Some(row) = self.rows.get_mut(msg.row_idx) {
-                                row.path = Some(path);
-                                row.state = super::PosterState::Cached; // will be uploaded lazily during paint
+                                if msg.refreshed {
+                                    row.tex = None; // the server sent newer artwork
+                                }
+                                // A variant upgrade that finished first already swapped in a
+                                // larger poster; keep it unless the artwork changed.
+                                if msg.refreshed || row.variant_w <= super::RESIZE_MAX_W {
+                                    row.path = Some(path);
+                                    row.variant_w = super::RESIZE_MAX_W;
+                                }
+                                row.state = super::PosterState::Cached; // uploaded via fresh_uploads / lazily during paint
                                 self.completed += 1;
                                 self.last_item_msg = format!("Cached: {}", row.title);
+                                self.fresh_uploads.push_back(msg.row_idx);
                             } else {
                                 self.failed += 1;
                             }
                         }
                         Err(e) => {
+                            if is_permanent_failure(&e) {
+                                self.retry_sweep.gave_up.insert(msg.row_idx);
+                            }
                             if let Some(row) = self.rows.get_mut(msg.row_idx) {
                                 row.state = super::PosterState::Failed;
                                 self.failed += 1;
@@ -197,32 +456,269 @@ impl crate::app::PexApp {
             }
         }
 
-        if self.total_targets > 0 {
-            self.loading_progress =
-                ((self.completed + self.failed) as f32 / self.total_targets as f32).clamp(0.0, 1.0);
+        self.refresh_prefetch_progress();
 
+        if drained > 0 {
+            ctx.request_repaint();
+        }
+    }
+    fn refresh_prefetch_progress(&mut self) {
+        let done = self.completed + self.failed;
+        if self.total_targets > 0 && done < self.total_targets {
+            self.loading_progress = (done as f32 / self.total_targets as f32).clamp(0.0, 1.0);
             self.last_item_msg = format!(
                 "Artwork cache progress: {}/{} cached ({} failed).",
                 self.completed, self.total_targets, self.failed
             );
+            if self.prefetch_paused() {
+                self.last_item_msg.push_str(" Paused.");
+            }
+            return;
+        }
 
-            if (self.completed + self.failed) >= self.total_targets {
-                let message = format!(
-                    "Stage 4/4 - Artwork cache ready ({} posters cached, {} failed).",
-                    self.completed, self.failed
-                );
-                self.stage4_complete_message = Some(message.clone());
-                if !self.owned_scan_in_progress {
-                    self.set_status(message.clone());
-                }
-                self.last_item_msg = message;
+        self.loading_progress = 1.0;
+        if self.total_targets == 0 && self.prefetch_skipped == 0 {
+            return;
+        }
+        let message = if self.prefetch_skipped > 0 && self.is_offline() {
+            format!(
+                "Stage 4/4 - Offline: showing cached posters only ({} cached, {} failed, {} not downloaded).",
+                self.completed, self.failed, self.prefetch_skipped
+            )
+        } else if self.prefetch_skipped > 0 {
+            format!(
+                "Stage 4/4 - Prefetch cancelled ({} posters cached, {} failed, {} skipped).",
+                self.completed, self.failed, self.prefetch_skipped
+            )
+        } else {
+            format!(
+                "Stage 4/4 - Artwork cache ready ({} posters cached, {} failed).",
+                self.completed, self.failed
+            )
+        };
+        if self.stage4_complete_message.is_none() {
+            if let Err(err) = crate::app::cache::save_poster_swatches() {
+                tracing::warn!("failed to save poster swatches: {err}");
             }
+            let (_, peak) = crate::app::cache::poster_decode_memory();
+            tracing::info!(
+                "prefetch finished; peak poster decode memory {:.1} MB",
+                peak as f64 / 1_048_576.0
+            );
+        }
+        self.stage4_complete_message = Some(message.clone());
+        if !self.owned_scan_in_progress {
+            self.set_status(message.clone());
+        }
+        self.last_item_msg = message;
+    }
+
+    /// Grow or shrink the running worker pool to `worker_count_ui`.
+    pub(crate) fn apply_worker_count(&mut self) {
+        let (Some(queue), Some(spawner)) =
+            (self.work_queue.as_ref(), self.prefetch_spawner.as_ref())
+        else {
+            return;
+        };
+        let spawn = queue.resize_workers(self.worker_count_ui.max(1));
+        for _ in 0..spawn {
+            spawner.spawn(Arc::clone(queue));
+        }
+        if spawn > 0 {
+            tracing::debug!("prefetch pool grown by {spawn} to {}", self.worker_count_ui);
+        }
+    }
+
+    /// True while the initial Stage 4 download pass still has posters outstanding.
+    pub(crate) fn prefetch_running(&self) -> bool {
+        self.prefetch_started && self.work_queue.is_some() && self.loading_progress < 1.0
+    }
+
+    pub(crate) fn prefetch_paused(&self) -> bool {
+        self.work_queue
+            .as_ref()
+            .is_some_and(|queue| queue.is_paused())
+    }
+
+    /// Hold the workers after their current download (e.g. on a metered connection).
+    pub(crate) fn set_prefetch_paused(&mut self, paused: bool) {
+        let Some(queue) = self.work_queue.as_ref() else {
+            return;
+        };
+        queue.set_paused(paused);
+        self.refresh_prefetch_progress();
+        self.set_status(if paused {
+            "Poster prefetch paused."
         } else {
-            self.loading_progress = 1.0;
+            "Poster prefetch resumed."
+        });
+    }
+
+    /// Drop every queued download and shut the workers down. Downloads already in flight
+    /// still report back; skipped posters leave `total_targets` so progress can finish.
+    pub(crate) fn cancel_prefetch(&mut self) {
+        let Some(queue) = self.work_queue.take() else {
+            return;
+        };
+        let skipped = queue.close();
+        self.prefetch_spawner = None;
+        self.prefetch_skipped += skipped;
+        self.total_targets = self.total_targets.saturating_sub(skipped);
+        // Queued upgrades were dropped with the rest; in-flight ones are applied if they land
+        // before the drain stops.
+        self.variant_upgrades.clear();
+        tracing::info!("poster prefetch cancelled; {skipped} queued downloads dropped");
+        self.refresh_prefetch_progress();
+    }
+
+    /// Start a fresh prefetch pass over the current rows (after a cancel, or to retry failures).
+    pub(crate) fn restart_prefetch(&mut self, ctx: &eg::Context) {
+        if let Some(queue) = self.work_queue.take() {
+            queue.close();
         }
+        self.prefetch_spawner = None;
+        self.done_rx = None;
+        self.variant_upgrades.clear();
+        self.variant_upgrade_failed.clear();
+        self.prefetch_started = false;
+        self.start_prefetch(ctx);
+    }
 
-        if drained > 0 {
-            ctx.request_repaint();
+    /// Restart prefetch with every cached poster revalidated against the server, so changed
+    /// artwork is replaced and unchanged posters only cost a 304.
+    pub(crate) fn revalidate_artwork(&mut self, ctx: &eg::Context) {
+        self.revalidate_all_artwork = true;
+        self.restart_prefetch(ctx);
+    }
+
+    /// Queue rows added after the pass started (streamed in by prep, or from a guide refresh).
+    pub(crate) fn queue_new_posters(&mut self, idxs: &[usize]) {
+        let Some(queue) = self.work_queue.as_ref() else {
+            return; // no worker pool; these posters load on demand
+        };
+        let ranks = self.prefetch_ranks();
+        let mut queued = 0usize;
+        for &idx in idxs {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            let job = (
+                idx,
+                row.key.clone(),
+                row.url.clone(),
+                row.path.clone(),
+                None,
+            );
+            if queue.push(job, ranks.get(&idx).copied().unwrap_or(RANK_UNKNOWN)) {
+                queued += 1;
+            }
+        }
+        if queued > 0 {
+            self.total_targets += queued;
+            self.refresh_prefetch_progress();
+        }
+    }
+
+    /// Once the pass is over, periodically probe one failed poster; see `RetrySweep`.
+    pub(crate) fn sweep_failed_posters(&mut self) {
+        if self.failed == 0
+            || self.retry_sweep.probe.is_some()
+            || self.prefetch_running()
+            || self.prefetch_paused()
+            || Instant::now() < self.retry_sweep.next_at
+        {
+            return;
         }
+        let Some(queue) = self.work_queue.as_ref() else {
+            return; // cancelled or never started
+        };
+        let sweep = &self.retry_sweep;
+        let mut failed = self.rows.iter().enumerate().filter(|(idx, row)| {
+            matches!(row.state, super::PosterState::Failed) && !sweep.gave_up.contains(idx)
+        });
+        let Some((idx, row)) = failed
+            .clone()
+            .find(|(idx, _)| self.visible_idxs.contains(idx))
+            .or_else(|| failed.next())
+        else {
+            return;
+        };
+        let job = (idx, row.key.clone(), row.url.clone(), None, None);
+        if !queue.push(job, RANK_VISIBLE) {
+            return;
+        }
+        tracing::debug!(
+            "retrying failed poster {} as a connectivity probe",
+            row.title
+        );
+        self.rows[idx].state = super::PosterState::Pending;
+        self.failed -= 1;
+        self.retry_sweep.probe = Some(idx);
+        self.refresh_prefetch_progress(); // back below 1.0, so completions are drained again
+    }
+
+    fn finish_retry_probe(&mut self, succeeded: bool) {
+        let sweep = &mut self.retry_sweep;
+        sweep.probe = None;
+        if !succeeded {
+            sweep.delay = (sweep.delay * 2).min(SWEEP_MAX_DELAY);
+            sweep.next_at = Instant::now() + sweep.delay;
+            return;
+        }
+        sweep.delay = SWEEP_FIRST_DELAY;
+        sweep.next_at = Instant::now() + SWEEP_FIRST_DELAY;
+
+        let Some(queue) = self.work_queue.as_ref() else {
+            return;
+        };
+        let ranks = self.prefetch_ranks();
+        let mut requeued = 0usize;
+        for (idx, row) in self.rows.iter_mut().enumerate() {
+            if !matches!(row.state, super::PosterState::Failed)
+                || self.retry_sweep.gave_up.contains(&idx)
+            {
+                continue;
+            }
+            let job = (idx, row.key.clone(), row.url.clone(), None, None);
+            if queue.push(job, ranks.get(&idx).copied().unwrap_or(RANK_UNKNOWN)) {
+                row.state = super::PosterState::Pending;
+                requeued += 1;
+            }
+        }
+        self.failed -= requeued;
+        if requeued > 0 {
+            self.set_status(format!(
+                "Poster downloads working again; retrying {requeued} failed poster{}.",
+                if requeued == 1 { "" } else { "s" }
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_client_errors_are_final() {
+        assert_eq!(retry_delay(1), Duration::from_secs(2));
+        assert_eq!(retry_delay(3), Duration::from_secs(8));
+
+        assert!(is_permanent_failure(
+            "HTTP 404 Not Found for http://plex/photo"
+        ));
+        assert!(is_permanent_failure(
+            "download bytes: HTTP status client error (410 Gone) for url (http://plex/photo)"
+        ));
+        assert!(is_permanent_failure("poster too large: limits exceeded"));
+        assert!(!is_permanent_failure(
+            "HTTP 429 Too Many Requests for http://plex/photo"
+        ));
+        assert!(!is_permanent_failure(
+            "HTTP 503 Service Unavailable for http://plex/photo"
+        ));
+        assert!(!is_permanent_failure(
+            "GET http://plex/photo: error sending request: connection refused"
+        ));
     }
 }
@@ -0,0 +1,221 @@
+// src/app/prefetch_queue.rs
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Condvar, Mutex};
+
+use crate::app::WorkItem;
+
+/// Work queue shared by the prefetch workers. Items are keyed by row index and job kind (first
+/// pass or variant upgrade) and popped lowest rank first (ties in push order); the UI re-ranks
+/// pending rows as the view moves.
+#[derive(Default)]
+pub(crate) struct PrefetchQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+}
+
+/// Row index plus whether the job is a variant upgrade, so an upgrade never replaces the
+/// row's pending first-pass job (which the Stage 4 progress still waits for).
+type JobKey = (usize, bool);
+
+#[derive(Default)]
+struct QueueState {
+    order: BTreeSet<(u32, u64, JobKey)>,
+    items: HashMap<JobKey, (u32, u64, WorkItem)>,
+    next_seq: u64,
+    // live worker threads vs. how many the UI asked for; extras retire after their job
+    workers: usize,
+    worker_target: usize,
+    paused: bool,
+    closed: bool,
+}
+
+impl PrefetchQueue {
+    /// Queue (or replace) the job of the same kind for `item.0` at `rank`. Returns false once
+    /// closed.
+    pub fn push(&self, item: WorkItem, rank: u32) -> bool {
+        let mut st = self.state.lock().unwrap();
+        if st.closed {
+            return false;
+        }
+        let key = (item.0, item.4.is_some());
+        if let Some((old_rank, old_seq, _)) = st.items.remove(&key) {
+            st.order.remove(&(old_rank, old_seq, key));
+        }
+        let seq = st.next_seq;
+        st.next_seq += 1;
+        st.order.insert((rank, seq, key));
+        st.items.insert(key, (rank, seq, item));
+        drop(st);
+        self.ready.notify_one();
+        true
+    }
+
+    /// Block until a job is available and the queue isn't paused; None after `close()`.
+    pub fn pop_blocking(&self) -> Option<WorkItem> {
+        let mut st = self.state.lock().unwrap();
+        loop {
+            if st.closed {
+                return None;
+            }
+            if st.workers > st.worker_target {
+                st.workers -= 1;
+                return None;
+            }
+            if st.paused {
+                st = self.ready.wait(st).unwrap();
+                continue;
+            }
+            if let Some(first) = st.order.pop_first() {
+                return st.items.remove(&first.2).map(|(_, _, item)| item);
+            }
+            st = self.ready.wait(st).unwrap();
+        }
+    }
+
+    /// Apply new ranks to rows that are still pending (both kinds of job); unknown rows are
+    /// ignored.
+    pub fn rerank(&self, ranks: impl IntoIterator<Item = (usize, u32)>) {
+        let mut st = self.state.lock().unwrap();
+        let QueueState { order, items, .. } = &mut *st;
+        for (idx, rank) in ranks {
+            for key in [(idx, false), (idx, true)] {
+                if let Some((old_rank, seq, _)) = items.get_mut(&key) {
+                    if *old_rank != rank {
+                        order.remove(&(*old_rank, *seq, key));
+                        order.insert((rank, *seq, key));
+                        *old_rank = rank;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn pending_rows(&self) -> Vec<usize> {
+        let st = self.state.lock().unwrap();
+        let rows: BTreeSet<usize> = st.items.keys().map(|&(idx, _)| idx).collect();
+        rows.into_iter().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.lock().unwrap().items.is_empty()
+    }
+
+    /// Set the desired worker count. Surplus workers exit after their current job; the return
+    /// value is how many new workers the caller must spawn (already counted as live).
+    pub fn resize_workers(&self, target: usize) -> usize {
+        let mut st = self.state.lock().unwrap();
+        if st.closed {
+            return 0;
+        }
+        st.worker_target = target;
+        let spawn = target.saturating_sub(st.workers);
+        st.workers += spawn;
+        drop(st);
+        self.ready.notify_all();
+        spawn
+    }
+
+    pub fn live_workers(&self) -> usize {
+        self.state.lock().unwrap().workers
+    }
+
+    /// Hold workers after their current job until resumed.
+    pub fn set_paused(&self, paused: bool) {
+        self.state.lock().unwrap().paused = paused;
+        if !paused {
+            self.ready.notify_all();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().paused
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    /// Drop pending jobs and release every waiting worker. Returns how many initial
+    /// prefetch jobs (not variant upgrades) were dropped.
+    pub fn close(&self) -> usize {
+        let mut st = self.state.lock().unwrap();
+        st.closed = true;
+        let dropped = st
+            .items
+            .values()
+            .filter(|(_, _, job)| job.4.is_none())
+            .count();
+        st.order.clear();
+        st.items.clear();
+        drop(st);
+        self.ready.notify_all();
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(idx: usize) -> WorkItem {
+        (idx, format!("k{idx}"), String::new(), None, None)
+    }
+
+    #[test]
+    fn pops_by_rank_then_push_order_and_honours_rerank() {
+        let q = PrefetchQueue::default();
+        q.push(job(0), 10);
+        q.push(job(1), 5);
+        q.push(job(2), 5);
+        q.push(job(3), 20);
+
+        q.rerank([(3, 0), (42, 0)]);
+        let order: Vec<usize> = (0..4).map(|_| q.pop_blocking().unwrap().0).collect();
+        assert_eq!(order, vec![3, 1, 2, 0]);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn upgrade_does_not_replace_a_pending_first_pass_job() {
+        let q = PrefetchQueue::default();
+        q.push(job(7), 10);
+        q.push((7, "k7".into(), String::new(), None, Some(480)), 0);
+        q.push((7, "k7".into(), String::new(), None, Some(640)), 0);
+        assert_eq!(q.pending_rows(), vec![7]);
+
+        let upgrade = q.pop_blocking().unwrap();
+        assert_eq!((upgrade.0, upgrade.4), (7, Some(640)));
+        let first_pass = q.pop_blocking().unwrap();
+        assert_eq!((first_pass.0, first_pass.4), (7, None));
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn shrinking_retires_surplus_workers() {
+        let q = PrefetchQueue::default();
+        assert_eq!(q.resize_workers(3), 3);
+        q.push(job(1), 0);
+        assert_eq!(q.resize_workers(2), 0);
+        // The surplus worker retires before taking the job.
+        assert!(q.pop_blocking().is_none());
+        assert_eq!(q.live_workers(), 2);
+        assert_eq!(q.pop_blocking().map(|j| j.0), Some(1));
+        assert_eq!(q.resize_workers(4), 2);
+    }
+
+    #[test]
+    fn close_releases_paused_workers_and_counts_dropped_jobs() {
+        let q = std::sync::Arc::new(PrefetchQueue::default());
+        q.set_paused(true);
+        q.push(job(1), 0);
+        q.push((2, "k2".into(), String::new(), None, Some(640)), 0);
+        let worker = {
+            let q = std::sync::Arc::clone(&q);
+            std::thread::spawn(move || q.pop_blocking())
+        };
+        // Paused: the worker must not take a job before the queue is closed.
+        assert_eq!(q.close(), 1);
+        assert!(worker.join().unwrap().is_none());
+        assert!(!q.push(job(3), 0));
+    }
+}
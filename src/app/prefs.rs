@@ -51,17 +51,40 @@ impl crate::app::PexApp {
                     }
                 }
                 "search" => self.search_query = v.to_string(),
+                "fuzzy_search" => self.fuzzy_search = matches!(v, "1" | "true" | "yes"),
+                "regex_search" => self.regex_search.enabled = matches!(v, "1" | "true" | "yes"),
+                "search_all_fields" => {
+                    self.search_all_fields = matches!(v, "1" | "true" | "yes");
+                }
+                "view_mode" => {
+                    if let Ok(mode) = v.parse::<super::ViewMode>() {
+                        self.view_mode = mode;
+                    }
+                }
                 "sort_key" => {
                     if let Ok(sk) = v.parse::<super::SortKey>() {
                         self.sort_key = sk;
                     }
                 }
+                "sort_key2" => self.sort_key2 = v.parse::<super::SortKey>().ok(),
+                "group_by" => {
+                    if let Ok(group_by) = v.parse::<super::GroupBy>() {
+                        self.group_by = group_by;
+                    }
+                }
                 "sort_desc" => self.sort_desc = matches!(v, "1" | "true" | "yes"),
                 "poster_w" => {
                     if let Ok(n) = v.parse::<f32>() {
-                        self.poster_width_ui = n.clamp(120.0, 220.0);
+                        let range = crate::app::density::CARD_WIDTH_RANGE;
+                        self.poster_width_ui = n.clamp(*range.start(), *range.end());
                     }
                 }
+                "density" => {
+                    if let Ok(preset) = v.parse() {
+                        self.density.preset = preset;
+                    }
+                }
+                "density_widths" => self.density.load_widths(v),
                 "detail_w" => {
                     if let Ok(n) = v.parse::<f32>() {
                         self.detail_panel_width = n.clamp(260.0, 600.0);
@@ -74,6 +97,8 @@ impl crate::app::PexApp {
                 }
                 "hide_owned" => self.hide_owned = matches!(v, "1" | "true" | "yes"),
                 "dim_owned" => self.dim_owned = matches!(v, "1" | "true" | "yes"),
+                "hide_watched" => self.hide_watched = matches!(v, "1" | "true" | "yes"),
+                "dim_watched" => self.dim_watched = matches!(v, "1" | "true" | "yes"),
                 "dim_strength" => {
                     if let Ok(n) = v.parse::<f32>() {
                         self.dim_strength_ui = n.clamp(0.10, 0.90);
@@ -85,12 +110,28 @@ impl crate::app::PexApp {
                         self.selected_channels.insert(ch.to_string());
                     }
                 }
+                "keybindings" => {
+                    self.keymap = super::keymap::Keymap::from_pref_string(v);
+                }
+                "favorite_channels" => {
+                    self.favorite_channels.clear();
+                    for ch in v.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                        self.favorite_channels.insert(ch.to_string());
+                    }
+                }
+                "channel_group" => self.channel_groups.load_pref(v),
                 "genres" => {
                     self.selected_genres.clear();
                     for g in v.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
                         self.selected_genres.insert(g.to_string());
                     }
                 }
+                "tags" => {
+                    self.selected_tags.clear();
+                    for t in v.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                        self.selected_tags.insert(t.to_string());
+                    }
+                }
                 "decades" => {
                     self.selected_decades.clear();
                     for d in v.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
@@ -101,9 +142,71 @@ impl crate::app::PexApp {
                         }
                     }
                 }
+                "year_min" => self.year_min = v.parse::<i32>().ok(),
+                "year_max" => self.year_max = v.parse::<i32>().ok(),
+                "bulk_ratings" => {
+                    self.bulk_ratings.enabled = matches!(v, "1" | "true" | "yes");
+                }
+                "low_bitrate_rule" => {
+                    self.low_bitrate_rule = matches!(v, "1" | "true" | "yes");
+                }
+                "low_bitrate_mbps" => {
+                    if let Ok(mbps) = v.parse::<f32>() {
+                        self.low_bitrate_mbps = mbps.clamp(0.5, 50.0);
+                    }
+                }
+                "rating_badges" => {
+                    self.rating_badges = matches!(v, "1" | "true" | "yes");
+                }
                 "filter_hd_only" => {
                     self.filter_hd_only = matches!(v, "1" | "true" | "yes");
                 }
+                "channel_logos" => {
+                    self.channel_logos = matches!(v, "1" | "true" | "yes");
+                }
+                "hover_preview" => {
+                    self.hover_preview.enabled = matches!(v, "1" | "true" | "yes");
+                }
+                "resume_key" => self.resume.load_key(v),
+                "resume_scroll" => {
+                    if let Ok(y) = v.parse::<f32>() {
+                        self.resume.load_scroll(y);
+                    }
+                }
+                "gamepad" => {
+                    self.gamepad.enabled = matches!(v, "1" | "true" | "yes");
+                }
+                "keep_running" => {
+                    self.background.enabled = matches!(v, "1" | "true" | "yes");
+                }
+                "filter_premieres_only" => {
+                    self.filter_premieres_only = matches!(v, "1" | "true" | "yes");
+                }
+                "radarr_profile" => self.radarr.profile_id = v.parse().ok(),
+                "digest_path" if !v.is_empty() => self.digest_path_input = v.to_string(),
+                "theme" => {
+                    if let Ok(mode) = v.parse() {
+                        self.theme.mode = mode;
+                    }
+                }
+                "high_contrast" => {
+                    self.theme.high_contrast = matches!(v, "1" | "true" | "yes");
+                }
+                "ui_scale" => {
+                    if let Ok(scale) = v.parse::<f32>() {
+                        self.ui_scale = scale.clamp(
+                            crate::app::theme::UI_SCALE_MIN,
+                            crate::app::theme::UI_SCALE_MAX,
+                        );
+                    }
+                }
+                "accent" => self.theme.accent = crate::app::theme::parse_hex_color(v),
+                "filter_new_only" => {
+                    self.filter_new_only = matches!(v, "1" | "true" | "yes");
+                }
+                "filter_letterboxd_only" => {
+                    self.filter_letterboxd_only = matches!(v, "1" | "true" | "yes");
+                }
                 "filter_owned_before_cutoff" => {
                     self.filter_owned_before_cutoff = matches!(v, "1" | "true" | "yes");
                 }
@@ -133,6 +236,13 @@ impl crate::app::PexApp {
                 .join(",")
         };
 
+        let favorites_csv = self
+            .favorite_channels
+            .iter()
+            .map(|s| s.replace(',', " "))
+            .collect::<Vec<_>>()
+            .join(",");
+
         let decades_csv = if self.selected_decades.is_empty() {
             String::new()
         } else {
@@ -153,45 +263,128 @@ impl crate::app::PexApp {
                 .join(",")
         };
 
-        let txt = format!(
+        let mut txt = format!(
             "# pex ui prefs\n\
              day_range={}\n\
              search={}\n\
+             fuzzy_search={}\n\
+             search_all_fields={}\n\
+             regex_search={}\n\
+             view_mode={}\n\
              sort_key={}\n\
+             sort_key2={}\n\
+             group_by={}\n\
              sort_desc={}\n\
              poster_w={:.1}\n\
+             density={}\n\
+             density_widths={}\n\
              detail_w={:.1}\n\
              workers={}\n\
              hide_owned={}\n\
              dim_owned={}\n\
+             hide_watched={}\n\
+             dim_watched={}\n\
              dim_strength={:.2}\n\
+             low_bitrate_rule={}\n\
+             low_bitrate_mbps={:.1}\n\
              channels={}\n\
+             favorite_channels={}\n\
+             keybindings={}\n\
              genres={}\n\
+             tags={}\n\
              decades={}\n\
+             year_min={}\n\
+             year_max={}\n\
+             bulk_ratings={}\n\
+             rating_badges={}\n\
              filter_hd_only={}\n\
+             filter_premieres_only={}\n\
+             filter_new_only={}\n\
+             filter_letterboxd_only={}\n\
              filter_owned_before_cutoff={}\n\
-             owned_before_cutoff={}\n",
+             owned_before_cutoff={}\n\
+             radarr_profile={}\n\
+             digest_path={}\n\
+             theme={}\n\
+             accent={}\n\
+             high_contrast={}\n\
+             ui_scale={:.2}\n\
+             channel_logos={}\n\
+             hover_preview={}\n\
+             gamepad={}\n\
+             resume_key={}\n\
+             resume_scroll={:.0}\n\
+             keep_running={}\n",
             self.current_range.as_str(),
             self.search_query,
+            if self.fuzzy_search { "1" } else { "0" },
+            if self.search_all_fields { "1" } else { "0" },
+            if self.regex_search.enabled { "1" } else { "0" },
+            self.view_mode.as_str(),
             self.sort_key.as_str(),
+            self.sort_key2.map_or("", super::SortKey::as_str),
+            self.group_by.as_str(),
             if self.sort_desc { "1" } else { "0" },
             self.poster_width_ui,
+            self.density.preset.as_str(),
+            self.density.widths_pref(),
             self.detail_panel_width,
             self.worker_count_ui,
             if self.hide_owned { "1" } else { "0" },
             if self.dim_owned { "1" } else { "0" },
+            if self.hide_watched { "1" } else { "0" },
+            if self.dim_watched { "1" } else { "0" },
             self.dim_strength_ui,
+            if self.low_bitrate_rule { "1" } else { "0" },
+            self.low_bitrate_mbps,
             channels_csv,
+            favorites_csv,
+            self.keymap.to_pref_string(),
             genres_csv,
+            self.selected_tags
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(","),
             decades_csv,
+            self.year_min.map(|y| y.to_string()).unwrap_or_default(),
+            self.year_max.map(|y| y.to_string()).unwrap_or_default(),
+            if self.bulk_ratings.enabled { "1" } else { "0" },
+            if self.rating_badges { "1" } else { "0" },
             if self.filter_hd_only { "1" } else { "0" },
+            if self.filter_premieres_only { "1" } else { "0" },
+            if self.filter_new_only { "1" } else { "0" },
+            if self.filter_letterboxd_only {
+                "1"
+            } else {
+                "0"
+            },
             if self.filter_owned_before_cutoff {
                 "1"
             } else {
                 "0"
             },
             self.owned_before_cutoff_input,
+            self.radarr
+                .profile_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            self.digest_path_input.trim(),
+            self.theme.mode.as_str(),
+            self.theme
+                .accent
+                .map(crate::app::theme::hex_color)
+                .unwrap_or_default(),
+            if self.theme.high_contrast { "1" } else { "0" },
+            self.ui_scale,
+            if self.channel_logos { "1" } else { "0" },
+            if self.hover_preview.enabled { "1" } else { "0" },
+            if self.gamepad.enabled { "1" } else { "0" },
+            self.resume_key(),
+            self.resume.scroll_y,
+            if self.background.enabled { "1" } else { "0" },
         );
+        txt.push_str(&self.channel_groups.pref_lines());
 
         fs::write(path, txt)?;
         Ok(())
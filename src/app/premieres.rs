@@ -0,0 +1,169 @@
+// src/app/premieres.rs
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use tracing::warn;
+
+const HISTORY_FILE: &str = "airing_history.txt";
+// Titles first seen longer ago than this are forgotten, so the file stays small.
+const HISTORY_KEEP_SECS: u64 = 2 * 365 * 86_400;
+
+/// Earliest airing Pex has seen for a title.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct HistoryEntry {
+    first_airing: u64,
+    /// The title first turned up after history tracking had started, i.e. it is new to the
+    /// guide rather than something Pex simply had not recorded yet.
+    new_title: bool,
+}
+
+/// Every title seen in earlier guides, keyed by the owned key (normalized title + year),
+/// stored as `key\tfirst_airing\tnew` lines in the cache dir.
+#[derive(Default)]
+pub(crate) struct AiringHistory {
+    entries: HashMap<String, HistoryEntry>,
+    /// False on the very first run: everything is then recorded but nothing counts as new.
+    tracking: bool,
+}
+
+fn history_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(HISTORY_FILE)
+}
+
+fn parse_history_line(line: &str) -> Option<(String, HistoryEntry)> {
+    let mut parts = line.split('\t');
+    let key = parts.next()?.trim();
+    let first_airing = parts.next()?.trim().parse().ok()?;
+    let new_title = parts.next().is_some_and(|v| v.trim() == "1");
+    (!key.is_empty()).then(|| {
+        (
+            key.to_string(),
+            HistoryEntry {
+                first_airing,
+                new_title,
+            },
+        )
+    })
+}
+
+fn format_history_line(key: &str, entry: HistoryEntry) -> String {
+    format!(
+        "{key}\t{}\t{}",
+        entry.first_airing,
+        u8::from(entry.new_title)
+    )
+}
+
+impl AiringHistory {
+    pub(crate) fn load() -> Self {
+        Self::from_text(&fs::read_to_string(history_path()).unwrap_or_default())
+    }
+
+    fn from_text(text: &str) -> Self {
+        let entries: HashMap<String, HistoryEntry> =
+            text.lines().filter_map(parse_history_line).collect();
+        Self {
+            tracking: !entries.is_empty(),
+            entries,
+        }
+    }
+
+    /// Record an airing at `airing` (Unix seconds); true when it is the first showing of a
+    /// title that was not in any earlier guide.
+    pub(crate) fn observe(&mut self, key: &str, airing: u64) -> bool {
+        if let Some(entry) = self.entries.get_mut(key) {
+            entry.first_airing = entry.first_airing.min(airing);
+            return entry.new_title && airing <= entry.first_airing;
+        }
+        let new_title = self.tracking;
+        self.entries.insert(
+            key.to_string(),
+            HistoryEntry {
+                first_airing: airing,
+                new_title,
+            },
+        );
+        new_title
+    }
+
+    /// Drop titles whose first airing is older than the retention window.
+    fn prune(&mut self, now: u64) {
+        let cutoff = now.saturating_sub(HISTORY_KEEP_SECS);
+        self.entries.retain(|_, e| e.first_airing >= cutoff);
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let mut lines: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| format_history_line(key, *entry))
+            .collect();
+        lines.sort();
+        fs::write(history_path(), lines.join("\n"))
+    }
+}
+
+fn unix_secs(ts: SystemTime) -> Option<u64> {
+    ts.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+impl crate::app::PexApp {
+    /// Mark first showings from the airing history (on top of the EPG premiere flag) and
+    /// record this guide. The demo guide only uses its own flags and leaves the history alone.
+    pub(crate) fn apply_premiere_flags(&mut self) {
+        if !self.demo_mode {
+            let now = self.now();
+            flag_first_showings(&mut self.rows, now);
+        }
+    }
+}
+
+/// Mark the rows that are a title's first showing and save the guide into the airing history.
+pub(crate) fn flag_first_showings(rows: &mut [crate::app::PosterRow], now: SystemTime) {
+    let mut history = AiringHistory::load();
+    for row in rows {
+        let Some(airing) = row.airing.and_then(unix_secs) else {
+            continue;
+        };
+        if history.observe(&row.owned_key, airing) {
+            row.premiere = true;
+        }
+    }
+    if let Some(now) = unix_secs(now) {
+        history.prune(now);
+    }
+    if let Err(err) = history.save() {
+        warn!("Failed to write {}: {err}", history_path().display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_titles_new_to_the_guide_count_as_first_showings() {
+        // First run: record, but nothing is new.
+        let mut history = AiringHistory::default();
+        assert!(!history.observe("alien|1979", 1_000));
+
+        let line = format_history_line("alien|1979", history.entries["alien|1979"]);
+        let mut history = AiringHistory::from_text(&line);
+        assert!(!history.observe("alien|1979", 5_000));
+        // A title that was never in a previous guide is new for its first airing only.
+        assert!(history.observe("arrival|2016", 6_000));
+        assert!(history.observe("arrival|2016", 6_000));
+        assert!(!history.observe("arrival|2016", 9_000));
+        let saved = format_history_line("arrival|2016", history.entries["arrival|2016"]);
+        assert_eq!(parse_history_line(&saved).unwrap().1.first_airing, 6_000);
+
+        history.prune(HISTORY_KEEP_SECS + 5_500);
+        assert!(!history.entries.contains_key("alien|1979"));
+        assert!(history.entries.contains_key("arrival|2016"));
+    }
+}
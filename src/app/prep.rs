@@ -1,4 +1,5 @@
 // src/app/prep.rs
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::Sender;
@@ -9,11 +10,13 @@ use tracing::{info, warn};
 use rusqlite::{Connection, OpenFlags};
 
 use crate::app::cache::url_to_cache_key;
-use crate::app::{PrepItem, PrepMsg}; // <- use the re-export from app::types
+use crate::app::utils::day_bucket;
+use crate::app::{Airing, DayRange, PrepItem, PrepMsg, RepeatKey}; // <- use the re-export from app::types
 use crate::config::{load_config, local_db_path, local_library_db_path};
 use eframe::egui as eg; // <- gives us eg::Context
 
 // --- local SQL (newer plex uses user_thumb_url; older uses thumb_url) ---
+// ?1 = row limit, ?2..?3 = begins_at window from `airing_window`.
 const SQL_POSTERS_USER_THUMB: &str = r#"
 SELECT
   m.title,
@@ -25,12 +28,15 @@ SELECT
   m.guid,
   m.summary,
   m.audience_rating,
-  m.rating
+  m.rating,
+  mi.ends_at
 FROM metadata_items m
 LEFT JOIN media_items mi ON mi.metadata_item_id = m.id
 WHERE m.metadata_type = 1
   AND m.user_thumb_url IS NOT NULL
   AND m.user_thumb_url <> ''
+  AND mi.begins_at >= ?2
+  AND mi.begins_at < ?3
 ORDER BY COALESCE(mi.begins_at, m.added_at) ASC
 LIMIT ?1
 "#;
@@ -46,12 +52,15 @@ SELECT
   m.guid,
   m.summary,
   m.audience_rating,
-  m.rating
+  m.rating,
+  mi.ends_at
 FROM metadata_items m
 LEFT JOIN media_items mi ON mi.metadata_item_id = m.id
 WHERE m.metadata_type = 1
   AND m.thumb_url IS NOT NULL
   AND m.thumb_url <> ''
+  AND mi.begins_at >= ?2
+  AND mi.begins_at < ?3
 ORDER BY COALESCE(mi.begins_at, m.added_at) ASC
 LIMIT ?1
 "#;
@@ -71,9 +80,29 @@ struct ChannelMeta {
     call_sign: Option<String>,
     title: Option<String>,
     thumb: Option<String>,
+    premiere: bool,
 }
 
-/// Extract channel metadata from `media_items.extra_data`
+/// Channel name shown on cards: the EPG's channel title, else the call sign, humanized.
+fn channel_display(channel_title: Option<&str>, channel_raw: Option<&str>) -> Option<String> {
+    channel_title
+        .filter(|s| !s.trim().is_empty())
+        .or(channel_raw)
+        .map(crate::app::utils::humanize_channel)
+}
+
+/// `"key":"1"`, `"key":1` or `"key":true` in `extra_data`.
+fn find_flag(hay: &str, key: &str) -> bool {
+    let needle = format!("\"{key}\":");
+    hay.find(&needle).is_some_and(|start| {
+        let rest = hay[start + needle.len()..]
+            .trim_start()
+            .trim_start_matches('"');
+        rest.starts_with('1') || rest.starts_with("true")
+    })
+}
+
+/// Extract channel metadata (and the premiere flag) from `media_items.extra_data`
 fn parse_channel_meta(extra: &str) -> ChannelMeta {
     fn find_val(hay: &str, key: &str) -> Option<String> {
         let needle = format!("\"{}\":\"", key);
@@ -90,11 +119,13 @@ fn parse_channel_meta(extra: &str) -> ChannelMeta {
     let call_sign = find_val(extra, "at:channelCallSign");
     let title = find_val(extra, "at:channelTitle");
     let thumb = find_val(extra, "at:channelThumb");
+    let premiere = find_flag(extra, "at:premiere");
 
     ChannelMeta {
         call_sign,
         title,
         thumb,
+        premiere,
     }
 }
 const MIN_COPY_INTERVAL_HOURS: u64 = 24;
@@ -115,6 +146,12 @@ fn touch_last_sync(marker_path: &Path) -> io::Result<()> {
     fs::write(marker_path, b"ok")
 }
 
+/// Make the next prep copy the EPG DB from source even if today's copy was already made
+/// (used when `plex_epg_db_source` points somewhere new).
+pub(crate) fn forget_epg_sync() {
+    let _ = fs::remove_file(last_sync_marker_path(&local_db_path()));
+}
+
 fn needs_db_update_daily(src: &Path, dst: &Path) -> io::Result<bool> {
     if fresh_enough(&last_sync_marker_path(dst))? {
         return Ok(false);
@@ -243,11 +280,23 @@ pub(crate) fn sync_library_db_from_source(force: bool) -> Result<bool, String> {
     Ok(true)
 }
 
+// Rows per `PrepMsg::Batch`, so the grid can show the first day while later days still parse.
+const PREP_BATCH_ROWS: usize = 500;
+
 // Set to true if you want to synthesize a tiny fake list for debugging.
 const DIAG_FAKE_STARTUP: bool = false;
 
-/// Spawn the background thread that prepares the poster list (no downloads here).
-pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
+/// Unix-seconds `[from, to)` span of airings the grid can show for `range`: the start of today
+/// through the end of the last day (UTC day buckets, as the grid groups them).
+pub(crate) fn airing_window(range: DayRange, now: SystemTime) -> (i64, i64) {
+    let today = day_bucket(now);
+    let end = range.max_bucket(today).unwrap_or(i64::MAX / 86_400);
+    (today * 86_400, end * 86_400)
+}
+
+/// Spawn the background thread that prepares the poster list for airings inside `window`
+/// (see `airing_window`); no downloads here.
+pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>, window: (i64, i64)) {
     std::thread::spawn(move || {
         let send = |m: PrepMsg| {
             let _ = tx.send(m);
@@ -263,6 +312,7 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
                     thumb_url: "https://example.com/a.jpg".into(),
                     key: url_to_cache_key("https://example.com/a.jpg"),
                     begins_at: None,
+                    ends_at: None,
                     year: Some(1982),
                     tags_genre: Some("Sci-Fi|Thriller".into()),
                     channel_call_sign: Some("ITV2".into()),
@@ -272,12 +322,14 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
                     summary: Some("In the future, blade runners hunt replicants.".into()),
                     audience_rating: Some(8.5),
                     critic_rating: Some(8.9),
+                    premiere: false,
                 },
                 PrepItem {
                     title: "Alien".into(),
                     thumb_url: "https://example.com/b.jpg".into(),
                     key: url_to_cache_key("https://example.com/b.jpg"),
                     begins_at: None,
+                    ends_at: None,
                     year: Some(1979),
                     tags_genre: Some("Sci-Fi|Horror".into()),
                     channel_call_sign: Some("ITV2".into()),
@@ -287,12 +339,14 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
                     summary: Some("The crew of the Nostromo encounters a deadly alien.".into()),
                     audience_rating: Some(8.4),
                     critic_rating: Some(9.0),
+                    premiere: false,
                 },
                 PrepItem {
                     title: "Arrival".into(),
                     thumb_url: "https://example.com/c.jpg".into(),
                     key: url_to_cache_key("https://example.com/c.jpg"),
                     begins_at: None,
+                    ends_at: None,
                     year: Some(2016),
                     tags_genre: Some("Sci-Fi|Drama".into()),
                     channel_call_sign: Some("ITV2".into()),
@@ -302,6 +356,7 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
                     summary: Some("A linguist communicates with extraterrestrial visitors.".into()),
                     audience_rating: Some(8.0),
                     critic_rating: Some(8.4),
+                    premiere: false,
                 },
             ];
             send(PrepMsg::Done(fake));
@@ -475,7 +530,7 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
             "Stage 2/4 - Parsing Plex guide data (collecting posters and metadata for the grid)."
                 .into(),
         ));
-        let mut q = match st.query([1_000_000_i64]) {
+        let mut q = match st.query(rusqlite::params![1_000_000_i64, window.0, window.1]) {
             Ok(r) => r,
             Err(e) => {
                 send(PrepMsg::Error(format!("query failed: {e}")));
@@ -483,7 +538,13 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
             }
         };
 
+        // Titles are de-duplicated as they stream in; the first (soonest) airing wins and the
+        // later ones are kept aside as repeats, by title and year so a same-named film from
+        // another year isn't listed as one.
+        let mut seen = std::collections::HashSet::new();
+        let mut repeats: HashMap<RepeatKey, Vec<Airing>> = HashMap::new();
         let mut list: Vec<PrepItem> = Vec::new();
+        let mut total = 0usize;
         let mut last_emit = Instant::now();
 
         while let Ok(Some(row)) = q.next() {
@@ -505,18 +566,42 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
                 .ok()
                 .flatten()
                 .map(|v| v as f32);
+            let ends: Option<i64> = row.get(10).ok().flatten();
 
             if let (Some(t), Some(u)) = (title, url) {
                 let tt = t.trim();
                 if !tt.is_empty() && (u.starts_with("http://") || u.starts_with("https://")) {
-                    let key = url_to_cache_key(&u);
                     let channel_meta = extra.as_deref().map(parse_channel_meta).unwrap_or_default();
+                    let lowered = tt.to_ascii_lowercase();
+                    if seen.contains(&lowered) {
+                        if let Some(begins_at) = begins {
+                            let raw = channel_meta
+                                .call_sign
+                                .clone()
+                                .or_else(|| crate::app::utils::host_from_url(&u));
+                            let channel =
+                                channel_display(channel_meta.title.as_deref(), raw.as_deref());
+                            repeats.entry((lowered, year)).or_default().push(Airing {
+                                begins_at,
+                                ends_at: ends,
+                                broadcast_hd: crate::app::utils::infer_broadcast_hd(
+                                    tags.as_deref(),
+                                    channel.as_deref(),
+                                ),
+                                channel,
+                            });
+                        }
+                        continue;
+                    }
+                    seen.insert(lowered);
+                    let key = url_to_cache_key(&u);
 
                     list.push(crate::app::types::PrepItem {
                         title: tt.to_owned(),
                         thumb_url: u,
                         key,
                         begins_at: begins,
+                        ends_at: ends,
                         year,
                         tags_genre: tags,
                         channel_call_sign: channel_meta.call_sign,
@@ -526,27 +611,29 @@ pub(crate) fn spawn_poster_prep(tx: Sender<PrepMsg>) {
                         summary,
                         audience_rating,
                         critic_rating,
+                        premiere: channel_meta.premiere,
                     });
+                    total += 1;
+                    if list.len() >= PREP_BATCH_ROWS {
+                        send(PrepMsg::Batch(std::mem::take(&mut list)));
+                    }
                     if last_emit.elapsed() >= Duration::from_millis(600) {
-                        send(PrepMsg::Info(format!("Stage 2/4 - Parsing Plex guide data ({} posters discovered so far; powers the main grid).", list.len())));
+                        send(PrepMsg::Info(format!("Stage 2/4 - Parsing Plex guide data ({total} posters discovered so far; powers the main grid).")));
                         last_emit = Instant::now();
                     }
                 }
             }
         }
 
-        // Dedupe by title (stable)
-        let mut seen = std::collections::HashSet::new();
-        list.retain(|item| seen.insert(item.title.to_ascii_lowercase()));
-
-        info!("prep: final poster rows after dedupe = {}", list.len());
-        if list.is_empty() {
+        info!("prep: final poster rows after dedupe = {total}");
+        if total == 0 {
             warn!("prep: no posters found — likely DB path/columns mismatch");
             send(PrepMsg::Info(
                 "No posters found — check DB path/type in config.json".into(),
             ));
         }
 
+        send(PrepMsg::Repeats(repeats));
         send(PrepMsg::Done(list));
     });
 }
@@ -558,15 +645,180 @@ impl crate::app::PexApp {
             return;
         }
         self.prep_started = true;
+        self.health.guide = crate::app::health::Health::Loading;
         self.boot_phase = super::BootPhase::CheckingNew;
         self.set_status("Stage 2/4 - Preparing Plex guide data (scans the EPG so the grid knows what's airing).");
         self.last_item_msg.clear();
 
         let (tx, rx) = std::sync::mpsc::channel::<crate::app::PrepMsg>();
         self.prep_rx = Some(rx);
+        let window = airing_window(self.current_range, self.now());
+        self.guide_refresh.covers = Some(self.current_range);
 
         // Hand off all the work to the prep module
-        crate::app::prep::spawn_poster_prep(tx);
+        if self.demo_mode {
+            crate::app::demo::spawn_demo_prep(tx, self.now());
+        } else {
+            crate::app::prep::spawn_poster_prep(tx, window);
+        }
+    }
+
+    /// Turn one prepared guide item into a grid row (owned/REC flags are applied afterwards).
+    pub(crate) fn row_from_prep(
+        item: crate::app::PrepItem,
+        genre_aliases: &std::collections::HashMap<String, String>,
+    ) -> crate::app::PosterRow {
+        let airing = item.begins_at.map(|ts| {
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(ts as u64)
+        });
+        let ends = item.ends_at.map(|ts| {
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(ts as u64)
+        });
+
+        let channel_raw = item
+            .channel_call_sign
+            .clone()
+            .or_else(|| crate::app::utils::host_from_url(&item.thumb_url));
+
+        let channel_title_original = item.channel_title.clone().filter(|s| !s.trim().is_empty());
+        let channel_display =
+            channel_display(channel_title_original.as_deref(), channel_raw.as_deref());
+
+        let small_k = Self::small_key(&item.key);
+        let path = crate::app::cache::find_any_by_key(&small_k);
+        let state = if path.is_some() {
+            crate::app::PosterState::Cached
+        } else {
+            crate::app::PosterState::Pending
+        };
+        let genres = item
+            .tags_genre
+            .as_deref()
+            .map(|tags| crate::app::utils::parse_genres(tags, genre_aliases))
+            .unwrap_or_default();
+        let tags_joined = (!genres.is_empty()).then(|| genres.join("|"));
+        let broadcast_hd = crate::app::utils::infer_broadcast_hd(
+            tags_joined.as_deref(),
+            channel_display.as_deref(),
+        );
+        let owned_key = Self::make_owned_key(&item.title, item.year);
+        let summary = item
+            .summary
+            .as_deref()
+            .and_then(crate::app::utils::sanitize_summary);
+
+        crate::app::PosterRow {
+            title: item.title,
+            url: item.thumb_url,
+            key: small_k,
+            airing,
+            ends,
+            year: item.year,
+            channel: channel_display,
+            channel_raw,
+            channel_title: channel_title_original,
+            channel_thumb: item.channel_thumb,
+            genres,
+            guid: item.guid,
+            summary,
+            audience_rating: item.audience_rating,
+            critic_rating: item.critic_rating,
+            path,
+            variant_w: crate::app::RESIZE_MAX_W,
+            tex: None,
+            state,
+            owned: false, // filled in by apply_owned_flags()
+            owned_modified: None,
+            owned_key,
+            broadcast_hd,
+            scheduled: false,
+            conflict: false,
+            premiere: item.premiere,
+            new_in_guide: false,
+            radarr: false,
+            watched: false,
+            trakt_watchlist: false,
+            letterboxd: false,
+            my_rating: None,
+            imdb_watchlist: false,
+        }
+    }
+
+    /// Append prepared rows to the grid. The first rows of a prep start the poster prefetch (and
+    /// show the grid once the owned scan is done); later ones join the running download queue.
+    pub(crate) fn absorb_prep_rows(
+        &mut self,
+        ctx: &eg::Context,
+        mut items: Vec<crate::app::PrepItem>,
+    ) {
+        self.title_blocklist.strip(&mut items);
+        let first = self.rows.is_empty();
+        if first {
+            self.rating_states.clear();
+            self.channel_icon_textures.clear();
+        }
+        let start = self.rows.len();
+        let genre_aliases = crate::config::load_config().genre_aliases;
+        self.rows.extend(
+            items
+                .into_iter()
+                .map(|item| Self::row_from_prep(item, &genre_aliases)),
+        );
+
+        let icon_urls: Vec<String> = self.rows[start..]
+            .iter()
+            .filter_map(|row| row.channel_thumb.clone())
+            .filter(|url| !url.is_empty() && self.channel_icon_pending.insert(url.clone()))
+            .collect();
+        if !icon_urls.is_empty() {
+            Self::spawn_channel_icon_prefetch(icon_urls);
+        }
+
+        // Warm-start: attach last session's hotset paths; the first rows upload some right away.
+        if let Some(hs) = self.last_hotset.as_ref() {
+            for row in &mut self.rows[start..] {
+                if let Some(p) = hs.get(&row.key) {
+                    if p.exists() {
+                        row.path = Some(p.clone());
+                        row.state = crate::app::PosterState::Cached;
+                    }
+                }
+            }
+        }
+        if first {
+            let hot: Vec<usize> = self.last_hotset.as_ref().map_or_else(Vec::new, |hs| {
+                (0..self.rows.len())
+                    .filter(|&i| hs.contains_key(&self.rows[i].key))
+                    .collect()
+            });
+            let mut uploaded = 0usize;
+            for i in hot {
+                if uploaded >= crate::app::PREWARM_UPLOADS {
+                    break;
+                }
+                if self.try_lazy_upload_row(ctx, i) {
+                    uploaded += 1;
+                }
+            }
+        }
+
+        self.apply_owned_flags_from(start);
+        self.apply_radarr_flags_from(start);
+        self.apply_trakt_flags_from(start);
+        self.apply_letterboxd_flags_from(start);
+        self.apply_imdb_flags_from(start);
+        if first {
+            if self.owned_keys.is_some() {
+                self.boot_phase = crate::app::BootPhase::Ready;
+            } else if self.boot_phase != crate::app::BootPhase::Ready {
+                self.boot_phase = crate::app::BootPhase::Caching;
+            }
+            self.start_prefetch(ctx);
+            self.prewarm_first_screen(ctx);
+        } else {
+            let added: Vec<usize> = (start..self.rows.len()).collect();
+            self.queue_new_posters(&added);
+        }
     }
 
     pub(crate) fn poll_prep(&mut self, ctx: &eg::Context) {
@@ -595,146 +847,53 @@ impl crate::app::PexApp {
                         processed += 1;
                         seen_any = true;
                     }
-                    Ok(crate::app::PrepMsg::Done(list)) => {
-                        // Convert manifest rows into UI rows
-                        self.rating_states.clear();
-                        self.channel_icon_textures.clear();
-                        self.rows = list
-                            .into_iter()
-                            .map(|item| {
-                                let airing = item.begins_at.map(|ts| {
-                                    std::time::SystemTime::UNIX_EPOCH
-                                        + std::time::Duration::from_secs(ts as u64)
-                                });
-
-                                let channel_raw = item
-                                    .channel_call_sign
-                                    .clone()
-                                    .or_else(|| crate::app::utils::host_from_url(&item.thumb_url));
-
-                                let channel_title_original =
-                                    item.channel_title.clone().filter(|s| !s.trim().is_empty());
-
-                                let normalized_title = channel_title_original
-                                    .as_ref()
-                                    .map(|s| crate::app::utils::humanize_channel(s));
-
-                                let channel_display =
-                                    normalized_title.as_ref().cloned().or_else(|| {
-                                        channel_raw
-                                            .as_ref()
-                                            .map(|c| crate::app::utils::humanize_channel(c))
-                                    });
-
-                                let small_k = Self::small_key(&item.key);
-                                let path = crate::app::cache::find_any_by_key(&small_k);
-                                let state = if path.is_some() {
-                                    crate::app::PosterState::Cached
-                                } else {
-                                    crate::app::PosterState::Pending
-                                };
-                                let genres = item
-                                    .tags_genre
-                                    .as_deref()
-                                    .map(crate::app::utils::parse_genres)
-                                    .unwrap_or_default();
-                                let tags_joined = (!genres.is_empty()).then(|| genres.join("|"));
-                                let broadcast_hd = crate::app::utils::infer_broadcast_hd(
-                                    tags_joined.as_deref(),
-                                    channel_display.as_deref(),
-                                );
-                                let owned_key = Self::make_owned_key(&item.title, item.year);
-                                let summary = item.summary.and_then(|s| {
-                                    let trimmed = s.trim();
-                                    if trimmed.is_empty() {
-                                        None
-                                    } else {
-                                        Some(trimmed.to_string())
-                                    }
-                                });
-
-                                crate::app::PosterRow {
-                                    title: item.title,
-                                    url: item.thumb_url,
-                                    key: small_k,
-                                    airing,
-                                    year: item.year,
-                                    channel: channel_display,
-                                    channel_raw,
-                                    channel_title: channel_title_original,
-                                    channel_thumb: item.channel_thumb,
-                                    genres,
-                                    guid: item.guid,
-                                    summary,
-                                    audience_rating: item.audience_rating,
-                                    critic_rating: item.critic_rating,
-                                    path,
-                                    tex: None,
-                                    state,
-                                    owned: false, // filled in by apply_owned_flags()
-                                    owned_modified: None,
-                                    owned_key,
-                                    broadcast_hd,
-                                    scheduled: false,
-                                }
-                            })
-                            .collect();
-
-                        let mut seen_icons = std::collections::HashSet::new();
-                        let icon_urls: Vec<String> = self
-                            .rows
-                            .iter()
-                            .filter_map(|row| row.channel_thumb.clone())
-                            .filter(|url| !url.is_empty() && seen_icons.insert(url.clone()))
-                            .collect();
-                        if !icon_urls.is_empty() {
-                            for url in &icon_urls {
-                                self.channel_icon_pending.insert(url.clone());
-                            }
-                            Self::spawn_channel_icon_prefetch(icon_urls);
+                    Ok(crate::app::PrepMsg::Batch(items)) => {
+                        self.absorb_prep_rows(ctx, items);
+                        if self.boot_phase != crate::app::BootPhase::Ready {
+                            self.set_status(format!(
+                                "Stage 2/4 - Loading guide… {} airings so far.",
+                                self.rows.len()
+                            ));
                         }
+                        processed += 1;
+                        seen_any = true;
+                    }
+                    Ok(crate::app::PrepMsg::Repeats(repeats)) => {
+                        self.other_airings = repeats;
+                        processed += 1;
+                    }
+                    Ok(crate::app::PrepMsg::Done(list)) => {
+                        self.absorb_prep_rows(ctx, list);
+                        self.last_hotset = None;
 
-                        // Warm-start: upload last hotset first (bounded)
-                        if let Some(hs) = self.last_hotset.take() {
-                            for row in &mut self.rows {
-                                if let Some(p) = hs.get(&row.key) {
-                                    if p.exists() {
-                                        row.path = Some(p.clone());
-                                        row.state = crate::app::PosterState::Cached;
-                                    }
-                                }
-                            }
-                            let mut uploaded = 0usize;
-                            for i in 0..self.rows.len() {
-                                if uploaded >= crate::app::PREWARM_UPLOADS {
-                                    break;
-                                }
-                                let should_upload = self
-                                    .rows
-                                    .get(i)
-                                    .is_some_and(|row| hs.contains_key(&row.key));
-                                if should_upload && self.try_lazy_upload_row(ctx, i) {
-                                    uploaded += 1;
-                                }
-                            }
-                        }
+                        self.health.guide = if self.demo_mode {
+                            crate::app::health::Health::Ok
+                        } else {
+                            crate::app::health::Health::ok_or_stale(
+                                &crate::config::local_db_path(),
+                                self.now(),
+                            )
+                        };
 
                         // Scheduled recordings (from Plex library DB)
                         self.refresh_scheduled_index();
+                        self.apply_premiere_flags();
+                        self.apply_new_listing_flags();
 
                         // Owned flags (if ready)
                         self.apply_owned_flags();
+                        self.evaluate_alerts();
                         let poster_done_status =
                             format!("Poster prep complete. {} items ready.", self.rows.len());
                         if self.owned_keys.is_some() {
                             self.boot_phase = crate::app::BootPhase::Ready;
                             self.set_status(poster_done_status);
                         } else {
-                            self.boot_phase = crate::app::BootPhase::Caching;
+                            if self.boot_phase != crate::app::BootPhase::Ready {
+                                self.boot_phase = crate::app::BootPhase::Caching;
+                            }
                             self.set_status("Poster prep complete. Scanning owned library...");
                         }
-
-                        self.start_prefetch(ctx);
                         self.prewarm_first_screen(ctx);
 
                         keep = None;
@@ -742,11 +901,17 @@ impl crate::app::PexApp {
                     }
                     Ok(crate::app::PrepMsg::Error(e)) => {
                         self.set_status(format!("Poster prep error: {e}"));
+                        self.health.guide = crate::app::health::Health::Failed(e);
                         keep = None;
                         seen_any = true;
                     }
                     Err(TryRecvError::Empty) => break,
                     Err(TryRecvError::Disconnected) => {
+                        if self.health.guide == crate::app::health::Health::Loading {
+                            self.health.guide = crate::app::health::Health::Failed(
+                                "guide prep stopped before finishing".into(),
+                            );
+                        }
                         keep = None;
                         break;
                     }
@@ -763,3 +928,23 @@ impl crate::app::PexApp {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn airing_window_spans_whole_days_from_today() {
+        // 2024-03-10 15:30 UTC
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_710_084_600);
+        let midnight = 1_710_028_800;
+        assert_eq!(
+            airing_window(DayRange::Two, now),
+            (midnight, midnight + 2 * 86_400)
+        );
+        assert_eq!(
+            airing_window(DayRange::Fourteen, now),
+            (midnight, midnight + 14 * 86_400)
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// src/app/profiles.rs
+use std::process::Command;
+
+use eframe::egui as eg;
+use tracing::{info, warn};
+
+/// This run's arguments without `--profile`, so a relaunch can pass its own.
+pub(crate) fn args_without_profile(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            args.next();
+        } else if !arg.starts_with("--profile=") {
+            out.push(arg);
+        }
+    }
+    out
+}
+
+/// Start a new Pex on `profile` (`None` = top-level settings) with the same arguments.
+fn relaunch(profile: Option<&str>) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|err| format!("Cannot find Pex itself: {err}"))?;
+    Command::new(exe)
+        .args(args_without_profile(std::env::args().skip(1)))
+        .arg(format!("--profile={}", profile.unwrap_or("")))
+        .spawn()
+        .map(|_| ())
+        .map_err(|err| format!("Could not restart Pex: {err}"))
+}
+
+impl crate::app::PexApp {
+    /// Remember `profile` and reopen Pex on it. The cache folder and database copies belong to
+    /// the profile, so switching restarts rather than swapping them under running workers.
+    fn switch_profile(&mut self, ctx: &eg::Context, profile: Option<String>) {
+        if let Err(err) = crate::config::remember_profile(profile.as_deref()) {
+            warn!("Failed to remember profile: {err}");
+        }
+        match relaunch(profile.as_deref()) {
+            Ok(()) => {
+                info!(
+                    "Switching to profile {}",
+                    profile.as_deref().unwrap_or("(default)")
+                );
+                // Really exit, even with "Keep running when closed" on: two instances
+                // must not share the cache and database copies.
+                self.quit(ctx);
+            }
+            Err(err) => {
+                warn!("{err}");
+                self.set_status(err);
+            }
+        }
+    }
+
+    /// Profile picker in the top bar; hidden until config.json names at least one profile.
+    pub(crate) fn ui_render_profile_switcher(&mut self, ui: &mut eg::Ui) {
+        let Some(profiles) = self
+            .config_watch
+            .applied()
+            .map(|cfg| cfg.profiles.clone())
+            .filter(|profiles| !profiles.is_empty())
+        else {
+            return;
+        };
+        let active = crate::config::active_profile();
+        let mut pick: Option<Option<String>> = None;
+        eg::ComboBox::from_id_source("profile_combo")
+            .selected_text(format!("Server: {}", active.unwrap_or("Default")))
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(active.is_none(), "Default").clicked() && active.is_some() {
+                    pick = Some(None);
+                }
+                for name in &profiles {
+                    let current = active == Some(name.as_str());
+                    if ui.selectable_label(current, name).clicked() && !current {
+                        pick = Some(Some(name.clone()));
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Profiles from config.json. Switching restarts Pex on that server.");
+        if let Some(profile) = pick {
+            let ctx = ui.ctx().clone();
+            self.switch_profile(&ctx, profile);
+        }
+        ui.separator();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relaunch_drops_the_old_profile_flag() {
+        let args = [
+            "--safe-mode",
+            "--profile",
+            "Parents",
+            "--profile=Cabin",
+            "--x",
+        ]
+        .map(String::from);
+        assert_eq!(args_without_profile(args), vec!["--safe-mode", "--x"]);
+    }
+}
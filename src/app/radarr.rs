@@ -0,0 +1,473 @@
+// src/app/radarr.rs
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+
+use eframe::egui as eg;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::app::owned::nfo;
+use crate::app::PosterRow;
+use crate::config::AppConfig;
+
+/// Radarr's accent, used for the "In Radarr" chip and the grid dot.
+pub(crate) const RADARR_COLOR: eg::Color32 = eg::Color32::from_rgb(255, 194, 48);
+
+/// Radarr instance (`radarr_url` + `radarr_api_key`).
+#[derive(Clone, Debug)]
+pub(crate) struct RadarrServer {
+    pub url: String,
+    pub api_key: String,
+    /// Where added films go; None uses Radarr's first root folder.
+    pub root_folder: Option<String>,
+}
+
+impl RadarrServer {
+    pub fn from_config(cfg: &AppConfig) -> Option<Self> {
+        Some(Self {
+            url: cfg.radarr_url.clone()?,
+            api_key: cfg.radarr_api_key.clone()?,
+            root_folder: cfg.radarr_root_folder.clone(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RadarrMovie {
+    #[serde(default)]
+    title: String,
+    year: Option<i32>,
+    tmdb_id: Option<u64>,
+    imdb_id: Option<String>,
+    #[serde(default)]
+    monitored: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct QualityProfile {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+struct RootFolder {
+    path: String,
+}
+
+enum RadarrMsg {
+    Library(Result<(HashSet<String>, Vec<QualityProfile>), String>),
+    /// A "Send to Radarr" finished for the row with this poster key.
+    Added {
+        key: String,
+        result: Result<String, String>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum AddState {
+    Pending,
+    Added(String),
+    Failed(String),
+}
+
+/// Films Radarr monitors, its quality profiles and the adds made from the detail panel.
+#[derive(Default)]
+pub(crate) struct RadarrState {
+    server: Option<RadarrServer>,
+    /// Id keys (`imdb:` / `tmdb:`) and title keys of every monitored film.
+    monitored: HashSet<String>,
+    profiles: Vec<QualityProfile>,
+    /// Quality profile for new films (the `radarr_profile` UI pref).
+    pub profile_id: Option<u32>,
+    adds: HashMap<String, AddState>,
+    syncing: bool,
+    error: Option<String>,
+    tx: Option<Sender<RadarrMsg>>,
+    rx: Option<Receiver<RadarrMsg>>,
+}
+
+impl RadarrState {
+    pub fn configure(&mut self, cfg: &AppConfig) {
+        self.server = RadarrServer::from_config(cfg);
+        if self.server.is_none() {
+            self.monitored.clear();
+            self.profiles.clear();
+            self.error = None;
+        }
+    }
+
+    pub const fn is_configured(&self) -> bool {
+        self.server.is_some()
+    }
+
+    fn sender(&mut self) -> Sender<RadarrMsg> {
+        if let Some(tx) = &self.tx {
+            return tx.clone();
+        }
+        let (tx, rx) = mpsc::channel();
+        self.tx = Some(tx.clone());
+        self.rx = Some(rx);
+        tx
+    }
+}
+
+/// Keys a row can match a Radarr film on: the ids in its guid, then its title variants.
+pub(crate) fn row_keys(row: &PosterRow) -> Vec<String> {
+    let guid = row.guid.as_deref();
+    let mut keys = nfo::id_keys(
+        guid.and_then(crate::app::imdb_id_from_guid).as_deref(),
+        guid.and_then(nfo::tmdb_id_from_guid).as_deref(),
+    );
+    keys.extend(crate::app::PexApp::owned_key_variants(&row.title, row.year));
+    keys
+}
+
+fn monitored_keys(movies: &[RadarrMovie]) -> HashSet<String> {
+    let mut keys = HashSet::new();
+    for movie in movies.iter().filter(|movie| movie.monitored) {
+        let tmdb = movie.tmdb_id.filter(|&id| id > 0).map(|id| id.to_string());
+        keys.extend(nfo::id_keys(
+            movie.imdb_id.as_deref().filter(|id| !id.is_empty()),
+            tmdb.as_deref(),
+        ));
+        if !movie.title.trim().is_empty() {
+            keys.extend(crate::app::PexApp::owned_key_variants(
+                &movie.title,
+                movie.year,
+            ));
+        }
+    }
+    keys
+}
+
+/// The lookup result to add: the first one from `year` when known, else Radarr's best match.
+fn pick_lookup(results: Vec<Value>, year: Option<i32>) -> Option<Value> {
+    let same_year =
+        |movie: &Value| movie.get("year").and_then(Value::as_i64) == year.map(i64::from);
+    let idx = results
+        .iter()
+        .position(same_year)
+        .or((!results.is_empty()).then_some(0))?;
+    results.into_iter().nth(idx)
+}
+
+/// `POST /api/v3/movie` body: the lookup result plus where and how to add it.
+fn add_body(mut movie: Value, profile_id: u32, root_folder: &str) -> Value {
+    if let Some(obj) = movie.as_object_mut() {
+        obj.insert("qualityProfileId".into(), Value::from(profile_id));
+        obj.insert("rootFolderPath".into(), Value::from(root_folder));
+        obj.insert("monitored".into(), Value::Bool(true));
+        obj.insert(
+            "addOptions".into(),
+            serde_json::json!({ "searchForMovie": true }),
+        );
+    }
+    movie
+}
+
+struct Radarr {
+    client: reqwest::blocking::Client,
+    server: RadarrServer,
+}
+
+impl Radarr {
+    fn new(server: RadarrServer) -> Result<Self, String> {
+        let client = crate::app::net::client_builder()
+            .user_agent("pex/radarr")
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|err| format!("client: {err}"))?;
+        Ok(Self { client, server })
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        let resp = crate::app::net::track(
+            self.client
+                .get(format!("{}/api/v3/{path}", self.server.url))
+                .header("X-Api-Key", &self.server.api_key)
+                .send(),
+        )
+        .map_err(|err| format!("network: {err}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("{path}: HTTP {}", resp.status()));
+        }
+        let body = resp.text().map_err(|err| format!("read: {err}"))?;
+        serde_json::from_str(&body).map_err(|err| format!("parse {path}: {err}"))
+    }
+
+    fn library(&self) -> Result<(HashSet<String>, Vec<QualityProfile>), String> {
+        let movies: Vec<RadarrMovie> = self.get("movie")?;
+        let profiles: Vec<QualityProfile> = self.get("qualityprofile")?;
+        Ok((monitored_keys(&movies), profiles))
+    }
+
+    /// Look the film up and add it; returns the title Radarr added it under.
+    fn add(&self, job: &AddJob) -> Result<String, String> {
+        let term = match (&job.imdb, &job.tmdb) {
+            (Some(imdb), _) => format!("imdb:{imdb}"),
+            (None, Some(tmdb)) => format!("tmdb:{tmdb}"),
+            (None, None) => match job.year {
+                Some(year) => format!("{} {year}", job.title),
+                None => job.title.clone(),
+            },
+        };
+        let results: Vec<Value> =
+            self.get(&format!("movie/lookup?term={}", urlencoding::encode(&term)))?;
+        let movie = pick_lookup(results, job.year)
+            .ok_or_else(|| format!("Radarr found nothing for \"{term}\""))?;
+        let root_folder = match &self.server.root_folder {
+            Some(path) => path.clone(),
+            None => {
+                let folders: Vec<RootFolder> = self.get("rootfolder")?;
+                folders
+                    .into_iter()
+                    .next()
+                    .map(|folder| folder.path)
+                    .ok_or("Radarr has no root folder; set radarr_root_folder")?
+            }
+        };
+        let title = movie
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or(&job.title)
+            .to_string();
+        let resp = crate::app::net::track(
+            self.client
+                .post(format!("{}/api/v3/movie", self.server.url))
+                .header("X-Api-Key", &self.server.api_key)
+                .header("Content-Type", "application/json")
+                .body(add_body(movie, job.profile_id, &root_folder).to_string())
+                .send(),
+        )
+        .map_err(|err| format!("network: {err}"))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().unwrap_or_default();
+            return Err(format!("add: HTTP {status} {}", body.trim()));
+        }
+        Ok(title)
+    }
+}
+
+struct AddJob {
+    key: String,
+    title: String,
+    year: Option<i32>,
+    imdb: Option<String>,
+    tmdb: Option<String>,
+    profile_id: u32,
+}
+
+impl crate::app::PexApp {
+    /// Fetch the films Radarr monitors and its quality profiles in the background.
+    pub(crate) fn sync_radarr(&mut self) {
+        let Some(server) = self.radarr.server.clone() else {
+            return;
+        };
+        if self.radarr.syncing || self.is_offline() || self.demo_mode {
+            return;
+        }
+        self.radarr.syncing = true;
+        let tx = self.radarr.sender();
+        std::thread::spawn(move || {
+            let result = Radarr::new(server).and_then(|radarr| radarr.library());
+            let _ = tx.send(RadarrMsg::Library(result));
+        });
+    }
+
+    pub(crate) fn poll_radarr(&mut self) {
+        let mut flags_changed = false;
+        while let Some(rx) = self.radarr.rx.as_ref() {
+            match rx.try_recv() {
+                Ok(RadarrMsg::Library(result)) => {
+                    self.radarr.syncing = false;
+                    match result {
+                        Ok((monitored, profiles)) => {
+                            self.radarr.monitored = monitored;
+                            if !profiles
+                                .iter()
+                                .any(|p| Some(p.id) == self.radarr.profile_id)
+                            {
+                                self.radarr.profile_id = profiles.first().map(|p| p.id);
+                            }
+                            self.radarr.profiles = profiles;
+                            self.radarr.error = None;
+                            flags_changed = true;
+                        }
+                        Err(err) => {
+                            tracing::warn!("Radarr sync failed: {err}");
+                            self.radarr.error = Some(err);
+                        }
+                    }
+                }
+                Ok(RadarrMsg::Added { key, result }) => {
+                    let state = match result {
+                        Ok(title) => {
+                            if let Some(row) = self.rows.iter().find(|row| row.key == key) {
+                                self.radarr.monitored.extend(row_keys(row));
+                            }
+                            self.set_status(format!("Added \"{title}\" to Radarr."));
+                            flags_changed = true;
+                            AddState::Added(title)
+                        }
+                        Err(err) => {
+                            self.set_status(format!("Send to Radarr failed: {err}"));
+                            AddState::Failed(err)
+                        }
+                    };
+                    self.radarr.adds.insert(key, state);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.radarr.tx = None;
+                    self.radarr.rx = None;
+                    break;
+                }
+            }
+        }
+        if flags_changed {
+            self.apply_radarr_flags_from(0);
+        }
+    }
+
+    /// Mark rows (from `start` on) whose film Radarr already monitors.
+    pub(crate) fn apply_radarr_flags_from(&mut self, start: usize) {
+        let monitored = &self.radarr.monitored;
+        for row in self.rows.iter_mut().skip(start) {
+            row.radarr =
+                !monitored.is_empty() && row_keys(row).iter().any(|key| monitored.contains(key));
+        }
+    }
+
+    fn send_to_radarr(&mut self, idx: usize) {
+        let (Some(server), Some(profile_id)) = (self.radarr.server.clone(), self.radarr.profile_id)
+        else {
+            return;
+        };
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let guid = row.guid.as_deref();
+        let job = AddJob {
+            key: row.key.clone(),
+            title: row.title.clone(),
+            year: row.year,
+            imdb: guid.and_then(crate::app::imdb_id_from_guid),
+            tmdb: guid.and_then(nfo::tmdb_id_from_guid),
+            profile_id,
+        };
+        self.radarr.adds.insert(job.key.clone(), AddState::Pending);
+        let tx = self.radarr.sender();
+        std::thread::spawn(move || {
+            let result = Radarr::new(server).and_then(|radarr| radarr.add(&job));
+            let _ = tx.send(RadarrMsg::Added {
+                key: job.key,
+                result,
+            });
+        });
+    }
+
+    /// Radarr line for the detail panel: "In Radarr", or a profile picker and Send button.
+    pub(crate) fn ui_render_radarr_actions(&mut self, ui: &mut eg::Ui, idx: usize) {
+        if !self.radarr.is_configured() {
+            return;
+        }
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let (key, monitored) = (row.key.clone(), row.radarr);
+        ui.add_space(6.0);
+        if monitored {
+            ui.label(eg::RichText::new("In Radarr").color(RADARR_COLOR))
+                .on_hover_text("Radarr already monitors this film");
+            return;
+        }
+        let add_state = self.radarr.adds.get(&key).cloned();
+        let mut send = false;
+        ui.horizontal_wrapped(|ui| {
+            let current = self
+                .radarr
+                .profiles
+                .iter()
+                .find(|p| Some(p.id) == self.radarr.profile_id)
+                .map_or("Quality profile", |p| p.name.as_str())
+                .to_string();
+            let mut picked = self.radarr.profile_id;
+            eg::ComboBox::from_id_source("radarr_profile")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for profile in &self.radarr.profiles {
+                        ui.selectable_value(&mut picked, Some(profile.id), &profile.name);
+                    }
+                });
+            if picked != self.radarr.profile_id {
+                self.radarr.profile_id = picked;
+                self.mark_dirty();
+            }
+            let busy = matches!(add_state, Some(AddState::Pending));
+            let can_send = !busy && !self.is_offline() && self.radarr.profile_id.is_some();
+            send = ui
+                .add_enabled(can_send, eg::Button::new("Send to Radarr"))
+                .on_hover_text("Add this film to Radarr, monitored, and search for it")
+                .clicked();
+            match &add_state {
+                Some(AddState::Pending) => {
+                    ui.add(eg::Spinner::new().size(12.0));
+                }
+                Some(AddState::Added(title)) => {
+                    ui.label(eg::RichText::new(format!("Added as \"{title}\"")).weak());
+                }
+                Some(AddState::Failed(err)) => {
+                    ui.label(eg::RichText::new(err).color(eg::Color32::LIGHT_RED));
+                }
+                None => {}
+            }
+        });
+        if let Some(err) = &self.radarr.error {
+            ui.label(
+                eg::RichText::new(format!("Radarr unavailable: {err}"))
+                    .color(eg::Color32::LIGHT_RED),
+            );
+        }
+        if send {
+            self.send_to_radarr(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monitored_films_become_keys_and_lookups_pick_the_year() {
+        let movies: Vec<RadarrMovie> = serde_json::from_str(
+            r#"[
+                {"title": "Se7en", "year": 1995, "tmdbId": 807, "imdbId": "tt0114369",
+                 "monitored": true},
+                {"title": "Heat", "year": 1995, "tmdbId": 949, "monitored": false}
+            ]"#,
+        )
+        .unwrap();
+        let keys = monitored_keys(&movies);
+        assert!(keys.contains("imdb:tt0114369"));
+        assert!(keys.contains("tmdb:807"));
+        assert!(!keys.contains("tmdb:949"));
+
+        let results: Vec<Value> = serde_json::from_str(
+            r#"[{"title": "Alien", "year": 2019}, {"title": "Alien", "year": 1979}]"#,
+        )
+        .unwrap();
+        let movie = pick_lookup(results.clone(), Some(1979)).unwrap();
+        assert_eq!(movie["year"], 1979);
+        assert_eq!(pick_lookup(results, None).unwrap()["year"], 2019);
+        assert!(pick_lookup(Vec::new(), Some(1979)).is_none());
+
+        let body = add_body(movie, 4, "/movies");
+        assert_eq!(body["qualityProfileId"], 4);
+        assert_eq!(body["rootFolderPath"], "/movies");
+        assert_eq!(body["addOptions"]["searchForMovie"], true);
+    }
+}
@@ -0,0 +1,818 @@
+// src/app/ratings.rs
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+use reqwest::blocking::Client;
+use serde::{de::DeserializeOwned, Deserialize};
+use tracing::warn;
+use urlencoding::encode;
+
+use crate::app::filters::day_window_bucket;
+use crate::app::net;
+use crate::app::types::{RatingMsg, RatingState};
+use crate::app::utils::day_bucket;
+use crate::config::AppConfig;
+
+const RATINGS_FILE: &str = "ratings.txt";
+/// Gap between background requests; keeps well under TMDb's ~40 requests per 10 seconds.
+const BULK_REQUEST_INTERVAL: Duration = Duration::from_millis(300);
+/// Pause after a provider answers HTTP 429 (too many requests).
+const BULK_RATE_LIMIT_PAUSE: Duration = Duration::from_secs(10);
+/// Cached ratings older than this are fetched again by the background job.
+const RATING_MAX_AGE_SECS: u64 = 14 * 24 * 60 * 60;
+
+/// Online services that can rate a film for the detail panel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RatingSource {
+    Tmdb,
+    Omdb,
+}
+
+impl RatingSource {
+    /// Default lookup order when `rating_providers` is not set.
+    pub const ALL: [Self; 2] = [Self::Tmdb, Self::Omdb];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tmdb => "tmdb",
+            Self::Omdb => "omdb",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Tmdb => "TMDb",
+            Self::Omdb => "OMDb",
+        }
+    }
+
+    const fn key_name(self) -> &'static str {
+        match self {
+            Self::Tmdb => "tmdb_api_key",
+            Self::Omdb => "omdb_api_key",
+        }
+    }
+
+    fn api_key(self, cfg: &AppConfig) -> Option<String> {
+        let key = match self {
+            Self::Tmdb => cfg.tmdb_api_key.as_deref(),
+            Self::Omdb => cfg.omdb_api_key.as_deref(),
+        };
+        key.map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(str::to_string)
+    }
+}
+
+impl FromStr for RatingSource {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|source| source.as_str().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+/// What a provider gets to identify the film.
+pub(crate) struct RatingQuery {
+    pub imdb_id: Option<String>,
+    pub title: String,
+    pub year: Option<i32>,
+}
+
+/// A provider's answer: display text plus a 0–10 score for badges and sorting.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Rating {
+    pub text: String,
+    pub score: Option<f32>,
+}
+
+/// One rating service. `Ok(None)` means the service has no rating for the film.
+pub(crate) trait RatingProvider: Send {
+    fn source(&self) -> RatingSource;
+    fn fetch(&self, client: &Client, query: &RatingQuery) -> Result<Option<Rating>, String>;
+}
+
+/// `rating_providers` from config.json in order (unknown names and repeats dropped), or the
+/// default order when it is unset.
+fn provider_order(cfg: &AppConfig) -> Vec<RatingSource> {
+    if cfg.rating_providers.is_empty() {
+        return RatingSource::ALL.to_vec();
+    }
+    let mut order = Vec::new();
+    for source in cfg.rating_providers.iter().filter_map(|n| n.parse().ok()) {
+        if !order.contains(&source) {
+            order.push(source);
+        }
+    }
+    order
+}
+
+/// Providers to ask, in order; those without an API key are left out.
+pub(crate) fn configured_providers(cfg: &AppConfig) -> Vec<Box<dyn RatingProvider>> {
+    provider_order(cfg)
+        .into_iter()
+        .filter_map(|source| {
+            let api_key = source.api_key(cfg)?;
+            Some(match source {
+                RatingSource::Tmdb => Box::new(Tmdb { api_key }) as Box<dyn RatingProvider>,
+                RatingSource::Omdb => Box::new(Omdb { api_key }),
+            })
+        })
+        .collect()
+}
+
+/// Setup warnings about the rating configuration.
+pub(crate) fn config_warnings(cfg: &AppConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for name in &cfg.rating_providers {
+        if name.parse::<RatingSource>().is_err() {
+            warnings.push(format!(
+                "rating_providers: unknown provider \"{name}\" (use \"tmdb\" and/or \"omdb\")."
+            ));
+        }
+    }
+    let order = provider_order(cfg);
+    for source in &order {
+        match source.api_key(cfg) {
+            Some(key) if key.contains("REPLACE_ME") || key.contains("YOUR") => {
+                warnings.push(format!(
+                    "{} still uses the placeholder value; replace it with your {} API key.",
+                    source.key_name(),
+                    source.label()
+                ));
+            }
+            None if !cfg.rating_providers.is_empty() => warnings.push(format!(
+                "rating_providers lists {} but {} is not set.",
+                source.as_str(),
+                source.key_name()
+            )),
+            _ => {}
+        }
+    }
+    if order.iter().all(|source| source.api_key(cfg).is_none()) {
+        warnings.push(
+            "No rating provider has an API key (tmdb_api_key / omdb_api_key); ratings button will be disabled."
+                .into(),
+        );
+    }
+    // Before OMDb support, `omdb_api_key` was read as the TMDb key (those are 32 characters).
+    if cfg.tmdb_api_key.is_none() && cfg.omdb_api_key.as_ref().is_some_and(|k| k.len() == 32) {
+        warnings.push(
+            "omdb_api_key looks like a TMDb V3 key; rename it to tmdb_api_key in config.json."
+                .into(),
+        );
+    }
+    warnings
+}
+
+/// Ask each provider in turn; the first rating found wins. An error is only reported when no
+/// provider had a rating.
+pub(crate) fn fetch_rating(
+    providers: &[Box<dyn RatingProvider>],
+    query: &RatingQuery,
+) -> RatingState {
+    if providers.is_empty() {
+        return RatingState::MissingApiKey;
+    }
+    match rating_client() {
+        Ok(client) => ask_providers(&client, providers, query),
+        Err(err) => RatingState::Error(err),
+    }
+}
+
+fn rating_client() -> Result<Client, String> {
+    net::client_builder()
+        .user_agent("pex/rating-fetch")
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|err| format!("client: {err}"))
+}
+
+fn ask_providers(
+    client: &Client,
+    providers: &[Box<dyn RatingProvider>],
+    query: &RatingQuery,
+) -> RatingState {
+    if query.imdb_id.is_none() && query.title.trim().is_empty() {
+        return RatingState::NotFound;
+    }
+    let mut first_error = None;
+    for provider in providers {
+        let source = provider.source().label();
+        match provider.fetch(client, query) {
+            Ok(Some(Rating { text, score })) => {
+                return RatingState::Success {
+                    source,
+                    text,
+                    score,
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                first_error.get_or_insert_with(|| format!("{source}: {err}"));
+            }
+        }
+    }
+    first_error.map_or(RatingState::NotFound, RatingState::Error)
+}
+
+fn http_get(client: &Client, url: &str) -> Result<String, String> {
+    let resp = client
+        .get(url)
+        .send()
+        .map_err(|err| format!("network: {err}"))?;
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    resp.text().map_err(|err| format!("read: {err}"))
+}
+
+// ---------- Ratings cache + background prefetch ----------
+
+/// Opt-in background fetch of ratings for the rows in the current day range.
+#[derive(Default)]
+pub(crate) struct BulkRatings {
+    pub enabled: bool,
+    rx: Option<Receiver<RatingMsg>>,
+    cancel: Option<Arc<AtomicBool>>,
+    /// Day range + first day bucket the last job covered; a new job starts when either moves.
+    scope: Option<(&'static str, i64)>,
+    pub total: usize,
+    pub done: usize,
+    /// Unix time each cached rating was fetched.
+    fetched_at: HashMap<String, u64>,
+    dirty: bool,
+}
+
+impl BulkRatings {
+    pub fn running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    fn stop(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        self.rx = None;
+        self.scope = None;
+    }
+}
+
+fn ratings_cache_path() -> std::path::PathBuf {
+    crate::app::cache::cache_dir().join(RATINGS_FILE)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// `key \t fetched_unix \t source \t score \t text`; `-` as the source records "no rating
+/// found" and an empty score means the provider gave no number.
+fn format_cache_line(key: &str, fetched: u64, state: &RatingState) -> Option<String> {
+    let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+    match state {
+        RatingState::Success {
+            source,
+            text,
+            score,
+        } => {
+            let score = score.map(|s| format!("{s:.1}")).unwrap_or_default();
+            Some(format!(
+                "{key}\t{fetched}\t{source}\t{score}\t{}",
+                clean(text)
+            ))
+        }
+        RatingState::NotFound => Some(format!("{key}\t{fetched}\t-\t\t")),
+        _ => None,
+    }
+}
+
+fn parse_cache_line(line: &str) -> Option<(String, u64, RatingState)> {
+    let mut parts = line.splitn(5, '\t');
+    let key = parts.next()?.trim();
+    let fetched = parts.next()?.trim().parse().ok()?;
+    let source = parts.next()?.trim();
+    let score = parts.next()?.trim();
+    let text = parts.next()?.trim();
+    let state = if source == "-" {
+        RatingState::NotFound
+    } else {
+        let source = RatingSource::ALL
+            .into_iter()
+            .find(|s| s.label() == source)?
+            .label();
+        RatingState::Success {
+            source,
+            text: text.to_string(),
+            score: if score.is_empty() {
+                None
+            } else {
+                Some(score.parse().ok()?)
+            },
+        }
+    };
+    (!key.is_empty()).then(|| (key.to_string(), fetched, state))
+}
+
+impl crate::app::PexApp {
+    /// Fill `rating_states` from the ratings cache (fetched or "not found" results only).
+    pub(crate) fn load_rating_cache(&mut self) {
+        let Ok(text) = fs::read_to_string(ratings_cache_path()) else {
+            return;
+        };
+        for (key, fetched, state) in text.lines().filter_map(parse_cache_line) {
+            self.bulk_ratings.fetched_at.insert(key.clone(), fetched);
+            self.rating_states.entry(key).or_insert(state);
+        }
+    }
+
+    pub(crate) fn save_rating_cache(&mut self) -> io::Result<()> {
+        if !self.bulk_ratings.dirty {
+            return Ok(());
+        }
+        let mut lines: Vec<String> = self
+            .rating_states
+            .iter()
+            .filter_map(|(key, state)| {
+                let fetched = self.bulk_ratings.fetched_at.get(key).copied()?;
+                format_cache_line(key, fetched, state)
+            })
+            .collect();
+        lines.sort_unstable();
+        fs::write(ratings_cache_path(), lines.join("\n"))?;
+        self.bulk_ratings.dirty = false;
+        Ok(())
+    }
+
+    /// Store a finished lookup; fetched and "not found" results go to the ratings cache.
+    pub(crate) fn record_rating(&mut self, msg: RatingMsg) {
+        if matches!(
+            msg.state,
+            RatingState::Success { .. } | RatingState::NotFound
+        ) {
+            self.bulk_ratings
+                .fetched_at
+                .insert(msg.key.clone(), unix_now());
+            self.bulk_ratings.dirty = true;
+        }
+        self.rating_states.insert(msg.key, msg.state);
+    }
+
+    pub(crate) fn set_bulk_ratings(&mut self, enabled: bool) {
+        self.bulk_ratings.enabled = enabled;
+        if !enabled {
+            self.bulk_ratings.stop();
+        }
+        self.mark_dirty();
+    }
+
+    /// Forget the running job (pipeline restarts); the next frame starts a fresh one.
+    pub(crate) fn reset_bulk_ratings(&mut self) {
+        self.bulk_ratings.stop();
+    }
+
+    /// Drain background results and start a job when the day range moves on.
+    pub(crate) fn poll_bulk_ratings(&mut self) {
+        while let Some(rx) = self.bulk_ratings.rx.as_ref() {
+            match rx.try_recv() {
+                Ok(msg) => {
+                    self.bulk_ratings.done += 1;
+                    self.record_rating(msg);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.bulk_ratings.rx = None;
+                    self.bulk_ratings.cancel = None;
+                    if self.is_offline() {
+                        // Stopped early; pick up where it left off once back online.
+                        self.bulk_ratings.scope = None;
+                    }
+                    if let Err(err) = self.save_rating_cache() {
+                        warn!("failed to save ratings cache: {err}");
+                    }
+                    break;
+                }
+            }
+        }
+
+        if !self.bulk_ratings.enabled
+            || self.bulk_ratings.running()
+            || self.is_offline()
+            || self.rows.is_empty()
+        {
+            return;
+        }
+        let scope = (self.current_range.as_str(), day_bucket(self.now()));
+        if self.bulk_ratings.scope != Some(scope) {
+            self.bulk_ratings.scope = Some(scope);
+            self.start_bulk_ratings();
+        }
+    }
+
+    fn start_bulk_ratings(&mut self) {
+        let providers = configured_providers(&crate::config::load_config());
+        if providers.is_empty() {
+            return;
+        }
+        let now = self.now();
+        let stale_before = unix_now().saturating_sub(RATING_MAX_AGE_SECS);
+        let mut jobs: Vec<(String, RatingQuery)> = Vec::new();
+        for row in &self.rows {
+            let in_range = row
+                .airing
+                .and_then(|airing| day_window_bucket(airing, now, self.current_range))
+                .is_some();
+            if !in_range || jobs.iter().any(|(key, _)| *key == row.key) {
+                continue;
+            }
+            let fresh = self
+                .bulk_ratings
+                .fetched_at
+                .get(&row.key)
+                .is_some_and(|fetched| *fetched >= stale_before);
+            let skip = match self.rating_states.get(&row.key) {
+                Some(RatingState::Pending) => true,
+                Some(RatingState::Success { .. } | RatingState::NotFound) => fresh,
+                _ => false,
+            };
+            if skip {
+                continue;
+            }
+            jobs.push((
+                row.key.clone(),
+                RatingQuery {
+                    imdb_id: row.guid.as_deref().and_then(super::imdb_id_from_guid),
+                    title: row.title.clone(),
+                    year: row.year,
+                },
+            ));
+        }
+        self.bulk_ratings.total = jobs.len();
+        self.bulk_ratings.done = 0;
+        if jobs.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.bulk_ratings.rx = Some(rx);
+        self.bulk_ratings.cancel = Some(Arc::clone(&cancel));
+        std::thread::spawn(move || {
+            let client = match rating_client() {
+                Ok(client) => client,
+                Err(err) => {
+                    warn!("background ratings: {err}");
+                    return;
+                }
+            };
+            for (key, query) in jobs {
+                if cancel.load(Ordering::Relaxed) || net::is_offline() {
+                    return;
+                }
+                let state = ask_providers(&client, &providers, &query);
+                let throttled = matches!(&state, RatingState::Error(err) if err.contains("429"));
+                if tx.send(RatingMsg { key, state }).is_err() {
+                    return;
+                }
+                std::thread::sleep(if throttled {
+                    BULK_RATE_LIMIT_PAUSE
+                } else {
+                    BULK_REQUEST_INTERVAL
+                });
+            }
+        });
+    }
+}
+
+// ---------- TMDb ----------
+
+struct Tmdb {
+    api_key: String,
+}
+
+#[derive(Deserialize)]
+struct TmdbFindResponse {
+    #[serde(default)]
+    movie_results: Vec<TmdbMovie>,
+}
+
+#[derive(Deserialize)]
+struct TmdbSearchResponse {
+    #[serde(default)]
+    results: Vec<TmdbMovie>,
+}
+
+#[derive(Deserialize)]
+struct TmdbMovie {
+    #[serde(default)]
+    vote_average: f32,
+    #[serde(default)]
+    vote_count: u32,
+    #[serde(default)]
+    release_date: Option<String>,
+}
+
+impl RatingProvider for Tmdb {
+    fn source(&self) -> RatingSource {
+        RatingSource::Tmdb
+    }
+
+    fn fetch(&self, client: &Client, query: &RatingQuery) -> Result<Option<Rating>, String> {
+        let api_key = &self.api_key;
+        if let Some(imdb_id) = query.imdb_id.as_deref() {
+            let url = format!(
+                "https://api.themoviedb.org/3/find/{imdb_id}?api_key={api_key}&language=en-US&external_source=imdb_id"
+            );
+            let parsed: TmdbFindResponse = parse_tmdb_body(&http_get(client, &url)?)?;
+            if let Some(rating) = extract_tmdb_rating(parsed.movie_results, query.year) {
+                return Ok(Some(rating));
+            }
+        }
+
+        let title = query.title.trim();
+        if title.is_empty() {
+            return Ok(None);
+        }
+        let mut url = format!(
+            "https://api.themoviedb.org/3/search/movie?api_key={api_key}&language=en-US&include_adult=false&query={}",
+            encode(title)
+        );
+        if let Some(y) = query.year {
+            url.push_str(&format!("&year={y}"));
+        }
+        let parsed: TmdbSearchResponse = parse_tmdb_body(&http_get(client, &url)?)?;
+        Ok(extract_tmdb_rating(parsed.results, query.year))
+    }
+}
+
+fn parse_tmdb_body<T: DeserializeOwned>(body: &str) -> Result<T, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|err| format!("parse: {err}"))?;
+    if let Some(status) = value.get("status_code") {
+        let code = status.as_i64().unwrap_or_default();
+        let message = value
+            .get("status_message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("TMDb request failed");
+        return Err(format!("TMDb error {code}: {message}"));
+    }
+    serde_json::from_value(value).map_err(|err| format!("parse: {err}"))
+}
+
+fn extract_tmdb_rating(movies: Vec<TmdbMovie>, target_year: Option<i32>) -> Option<Rating> {
+    let mut fallback: Option<(f32, u32)> = None;
+
+    for movie in movies {
+        if movie.vote_average <= 0.0 || movie.vote_count == 0 {
+            continue;
+        }
+
+        if let Some(target) = target_year {
+            if tmdb_release_year(&movie.release_date) == Some(target) {
+                return Some(format_tmdb_rating(movie.vote_average, movie.vote_count));
+            }
+        }
+
+        if fallback.is_none() {
+            fallback = Some((movie.vote_average, movie.vote_count));
+        }
+    }
+
+    fallback.map(|(avg, count)| format_tmdb_rating(avg, count))
+}
+
+fn tmdb_release_year(date: &Option<String>) -> Option<i32> {
+    let value = date.as_ref()?;
+    let year = value.split('-').next()?;
+    year.parse().ok()
+}
+
+fn format_tmdb_rating(avg: f32, count: u32) -> Rating {
+    let votes = match count {
+        0 => "0 votes".to_string(),
+        1 => "1 vote".to_string(),
+        _ => format!("{count} votes"),
+    };
+    Rating {
+        text: format!("{avg:.1}/10 ({votes})"),
+        score: Some(avg),
+    }
+}
+
+// ---------- OMDb ----------
+
+struct Omdb {
+    api_key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OmdbResponse {
+    response: String,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(rename = "imdbRating", default)]
+    imdb_rating: Option<String>,
+    #[serde(rename = "imdbVotes", default)]
+    imdb_votes: Option<String>,
+    #[serde(default)]
+    ratings: Vec<OmdbRating>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OmdbRating {
+    source: String,
+    value: String,
+}
+
+impl Omdb {
+    /// One OMDb lookup; a "not found" answer is `Ok(None)`.
+    fn lookup(&self, client: &Client, params: &str) -> Result<Option<Rating>, String> {
+        let url = format!(
+            "https://www.omdbapi.com/?apikey={}&{params}",
+            encode(&self.api_key)
+        );
+        let resp = client
+            .get(&url)
+            .send()
+            .map_err(|err| format!("network: {err}"))?;
+        let status = resp.status();
+        let body = resp.text().map_err(|err| format!("read: {err}"))?;
+        // Bad keys come back as 401 with an `Error` body, which is the more useful message.
+        let parsed: OmdbResponse = serde_json::from_str(&body).map_err(|err| {
+            if status.is_success() {
+                format!("parse: {err}")
+            } else {
+                format!("HTTP {status}")
+            }
+        })?;
+        if parsed.response.eq_ignore_ascii_case("true") {
+            return Ok(format_omdb_ratings(&parsed));
+        }
+        match parsed.error {
+            Some(err) if err.to_ascii_lowercase().contains("not found") => Ok(None),
+            Some(err) => Err(err),
+            None => Err(format!("HTTP {status}")),
+        }
+    }
+}
+
+impl RatingProvider for Omdb {
+    fn source(&self) -> RatingSource {
+        RatingSource::Omdb
+    }
+
+    fn fetch(&self, client: &Client, query: &RatingQuery) -> Result<Option<Rating>, String> {
+        if let Some(imdb_id) = query.imdb_id.as_deref() {
+            if let Some(rating) = self.lookup(client, &format!("i={}", encode(imdb_id)))? {
+                return Ok(Some(rating));
+            }
+        }
+
+        let title = query.title.trim();
+        if title.is_empty() {
+            return Ok(None);
+        }
+        let mut params = format!("t={}&type=movie", encode(title));
+        if let Some(y) = query.year {
+            params.push_str(&format!("&y={y}"));
+        }
+        self.lookup(client, &params)
+    }
+}
+
+/// "7.8/10", "94%", "88/100" or a bare "7.8" on a 0–10 scale.
+fn score_out_of_ten(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let score = if let Some(pct) = value.strip_suffix('%') {
+        pct.trim().parse::<f32>().ok()? / 10.0
+    } else if let Some((num, den)) = value.split_once('/') {
+        let den: f32 = den.trim().parse().ok()?;
+        (den > 0.0).then_some(())?;
+        num.trim().parse::<f32>().ok()? * 10.0 / den
+    } else {
+        value.parse().ok()?
+    };
+    (0.0..=10.0).contains(&score).then_some(score)
+}
+
+/// "IMDb 7.8/10 (1,234 votes), Rotten Tomatoes 94%, Metacritic 88/100", skipping N/A values.
+/// The score is IMDb's, else the first other rating that parses.
+fn format_omdb_ratings(resp: &OmdbResponse) -> Option<Rating> {
+    let known = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty() && *v != "N/A")
+            .map(str::to_string)
+    };
+    let mut parts = Vec::new();
+    let mut score = known(&resp.imdb_rating).and_then(|r| score_out_of_ten(&r));
+    if let Some(rating) = known(&resp.imdb_rating) {
+        match known(&resp.imdb_votes) {
+            Some(votes) => parts.push(format!("IMDb {rating}/10 ({votes} votes)")),
+            None => parts.push(format!("IMDb {rating}/10")),
+        }
+    }
+    for rating in &resp.ratings {
+        // IMDb is already covered by imdbRating above.
+        if rating.source != "Internet Movie Database" && rating.value != "N/A" {
+            parts.push(format!("{} {}", rating.source, rating.value));
+            score = score.or_else(|| score_out_of_ten(&rating.value));
+        }
+    }
+    (!parts.is_empty()).then(|| Rating {
+        text: parts.join(", "),
+        score,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_lines_round_trip() {
+        let success = RatingState::Success {
+            source: "OMDb",
+            text: "IMDb 7.8/10\t(12 votes)".into(),
+            score: Some(7.8),
+        };
+        let line = format_cache_line("abc", 1_700_000_000, &success).unwrap();
+        let (key, fetched, state) = parse_cache_line(&line).unwrap();
+        assert_eq!((key.as_str(), fetched), ("abc", 1_700_000_000));
+        assert!(matches!(
+            state,
+            RatingState::Success { source: "OMDb", ref text, score: Some(s) }
+                if text == "IMDb 7.8/10 (12 votes)" && (s - 7.8).abs() < 1e-6
+        ));
+        let line = format_cache_line("def", 5, &RatingState::NotFound).unwrap();
+        assert!(matches!(
+            parse_cache_line(&line),
+            Some((_, 5, RatingState::NotFound))
+        ));
+        assert!(format_cache_line("x", 1, &RatingState::Pending).is_none());
+        assert!(parse_cache_line("ghi\t1\tIMDb\t8.0\t8.0/10").is_none());
+        assert!(parse_cache_line("ghi\t1\tTMDb\tabc\t8.0/10").is_none());
+    }
+
+    #[test]
+    fn provider_order_and_omdb_formatting() {
+        let mut cfg = AppConfig {
+            omdb_api_key: Some(" abcd1234 ".into()),
+            ..AppConfig::default()
+        };
+        // Default order, keyless providers dropped.
+        let sources: Vec<_> = configured_providers(&cfg)
+            .iter()
+            .map(|p| p.source())
+            .collect();
+        assert_eq!(sources, [RatingSource::Omdb]);
+
+        cfg.rating_providers = vec!["OMDb".into(), "bogus".into(), "tmdb".into(), "omdb".into()];
+        assert_eq!(
+            provider_order(&cfg),
+            [RatingSource::Omdb, RatingSource::Tmdb]
+        );
+        let warnings = config_warnings(&cfg);
+        assert!(warnings.iter().any(|w| w.contains("\"bogus\"")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("tmdb_api_key is not set")));
+
+        let body = r#"{"Response":"True","imdbRating":"7.8","imdbVotes":"1,234",
+            "Ratings":[{"Source":"Internet Movie Database","Value":"7.8/10"},
+                       {"Source":"Rotten Tomatoes","Value":"94%"}]}"#;
+        let parsed: OmdbResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            format_omdb_ratings(&parsed),
+            Some(Rating {
+                text: "IMDb 7.8/10 (1,234 votes), Rotten Tomatoes 94%".into(),
+                score: Some(7.8),
+            })
+        );
+        let parsed: OmdbResponse = serde_json::from_str(
+            r#"{"Response":"True","imdbRating":"N/A","Ratings":[{"Source":"Metacritic","Value":"88/100"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            format_omdb_ratings(&parsed).and_then(|r| r.score),
+            Some(8.8)
+        );
+        let parsed: OmdbResponse =
+            serde_json::from_str(r#"{"Response":"True","imdbRating":"N/A"}"#).unwrap();
+        assert_eq!(format_omdb_ratings(&parsed), None);
+    }
+}
@@ -0,0 +1,76 @@
+// src/app/resume.rs
+use eframe::egui as eg;
+
+/// Where the user was when pex last closed: the selected card and the grid's scroll offset,
+/// saved in the UI prefs and put back once the guide has rows again on the next launch.
+#[derive(Default)]
+pub(crate) struct Resume {
+    /// Selected card's key from the prefs, until the guide has loaded and it is applied.
+    pending_key: Option<String>,
+    pending: bool,
+    /// Grid scroll offset, tracked every frame (and loaded from the prefs).
+    pub scroll_y: f32,
+    /// Offset for the grid to jump to next frame.
+    pub restore_scroll: Option<f32>,
+}
+
+impl Resume {
+    pub fn load_key(&mut self, key: &str) {
+        self.pending_key = (!key.is_empty()).then(|| key.to_string());
+        self.pending = true;
+    }
+
+    pub fn load_scroll(&mut self, scroll_y: f32) {
+        self.scroll_y = scroll_y.max(0.0);
+        self.pending = true;
+    }
+
+    /// The grid's scroll area, jumping to the saved offset on the frame it is put back.
+    pub fn grid_scroll_area(&mut self) -> eg::ScrollArea {
+        let area = eg::ScrollArea::vertical().auto_shrink([false; 2]);
+        match self.restore_scroll.take() {
+            Some(y) => area.vertical_scroll_offset(y),
+            None => area,
+        }
+    }
+}
+
+impl crate::app::PexApp {
+    /// Key of the selected card for the prefs; the saved one while it has not been applied yet.
+    pub(crate) fn resume_key(&self) -> String {
+        if self.resume.pending {
+            return self.resume.pending_key.clone().unwrap_or_default();
+        }
+        self.selected_idx
+            .and_then(|idx| self.rows.get(idx))
+            .map(|row| row.key.clone())
+            .unwrap_or_default()
+    }
+
+    /// First frame with a guide: reselect the saved card and bring it into view if it is still
+    /// listed and passes the filters, else restore the grid's old scroll offset (grid view only).
+    pub(crate) fn apply_resume(&mut self) {
+        if !self.resume.pending || self.rows.is_empty() {
+            return;
+        }
+        self.resume.pending = false;
+        let visible = self.build_grouped_indices();
+        let idx = self.resume.pending_key.take().and_then(|key| {
+            visible
+                .iter()
+                .flat_map(|(_, idxs)| idxs)
+                .copied()
+                .find(|&idx| self.rows[idx].key == key)
+        });
+        match idx {
+            Some(idx) => {
+                self.selected_idx = Some(idx);
+                self.scroll_to_idx = Some(idx);
+            }
+            None if self.view_mode == crate::app::ViewMode::Grid => {
+                self.resume.restore_scroll = Some(self.resume.scroll_y);
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// src/app/safe_mode.rs
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui as eg;
+use tracing::warn;
+
+// Lives next to config.json rather than in the cache dir, which a bad config can move.
+const STARTUP_MARKER: &str = "pex_startup.txt";
+// Launches in a row that never reached the guide before safe mode kicks in by itself.
+const UNFINISHED_STARTUPS_LIMIT: u32 = 2;
+
+/// Why this session runs in safe mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SafeModeReason {
+    /// Started with `--safe-mode`.
+    Flag,
+    /// The previous launches crashed (or were killed) before the guide came up.
+    UnfinishedStartups(u32),
+}
+
+impl SafeModeReason {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Flag => "Started with --safe-mode.".into(),
+            Self::UnfinishedStartups(n) => {
+                format!("The last {n} launches did not finish starting up.")
+            }
+        }
+    }
+}
+
+fn marker_path() -> PathBuf {
+    crate::config::base_dir().join(STARTUP_MARKER)
+}
+
+/// Record this launch as in progress; returns how many launches before it never finished.
+pub fn begin_startup() -> u32 {
+    let path = marker_path();
+    let unfinished = fs::read_to_string(&path)
+        .ok()
+        .and_then(|txt| txt.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+    if let Err(err) = fs::write(&path, format!("{}\n", unfinished.saturating_add(1))) {
+        warn!("Failed to write startup marker {}: {err}", path.display());
+    }
+    unfinished
+}
+
+/// The guide came up (or the app closed cleanly): forget the pending launch.
+pub fn finish_startup() {
+    let _ = fs::remove_file(marker_path());
+}
+
+/// Safe mode for this launch, from the command-line flag or the startup marker count.
+pub fn decide(flag: bool, unfinished_startups: u32) -> Option<SafeModeReason> {
+    if flag {
+        Some(SafeModeReason::Flag)
+    } else if unfinished_startups >= UNFINISHED_STARTUPS_LIMIT {
+        Some(SafeModeReason::UnfinishedStartups(unfinished_startups))
+    } else {
+        None
+    }
+}
+
+impl crate::app::PexApp {
+    /// App for a launch that may be in safe mode: default prefs, no prefetch, no background
+    /// scans. Leaving safe mode is just a normal restart.
+    pub fn with_safe_mode(reason: Option<SafeModeReason>) -> Self {
+        if let Some(reason) = &reason {
+            tracing::info!("safe mode: {}", reason.describe());
+        }
+        Self {
+            safe_mode: reason,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) const fn in_safe_mode(&self) -> bool {
+        self.safe_mode.is_some()
+    }
+
+    /// Clear the startup marker the first time the guide is usable.
+    pub(crate) fn confirm_startup(&mut self) {
+        if !self.startup_confirmed {
+            self.startup_confirmed = true;
+            finish_startup();
+        }
+    }
+
+    /// Banner explaining what safe mode switched off.
+    pub(crate) fn ui_render_safe_mode_banner(&self, ctx: &eg::Context) {
+        let Some(reason) = self.safe_mode.as_ref() else {
+            return;
+        };
+        let text = format!(
+            "{} Saved preferences were not loaded; poster prefetch and library scans are paused. \
+             Changing a setting replaces the saved preferences. Restart Pex to leave safe mode.",
+            reason.describe()
+        );
+        eg::TopBottomPanel::top("safe_mode_banner")
+            .frame(
+                eg::Frame::default()
+                    .inner_margin(eg::Margin::symmetric(10.0, 6.0))
+                    .fill(eg::Color32::from_rgb(120, 90, 20)),
+            )
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        eg::RichText::new("⚠ Safe mode")
+                            .strong()
+                            .color(eg::Color32::WHITE),
+                    );
+                    ui.add(
+                        eg::Label::new(eg::RichText::new(text).color(eg::Color32::WHITE)).wrap(),
+                    );
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_unfinished_startups_switch_safe_mode_on() {
+        assert_eq!(decide(false, 0), None);
+        assert_eq!(decide(false, 1), None);
+        assert_eq!(
+            decide(false, 2),
+            Some(SafeModeReason::UnfinishedStartups(2))
+        );
+        assert_eq!(decide(true, 0), Some(SafeModeReason::Flag));
+    }
+}
@@ -6,6 +6,7 @@ use serde_json::Value;
 use tracing::warn;
 use urlencoding::decode;
 
+use crate::app::clock::{unix_now, Clock};
 use crate::app::utils;
 use crate::config::local_library_db_path;
 
@@ -17,6 +18,14 @@ pub(crate) struct ScheduledIndex {
 }
 
 impl ScheduledIndex {
+    /// Index that only matches the given GUIDs (demo data).
+    pub fn from_guids(guids: HashSet<String>) -> Self {
+        Self {
+            guids,
+            title_slots: HashMap::new(),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.guids.is_empty() && self.title_slots.is_empty()
     }
@@ -43,6 +52,14 @@ impl ScheduledIndex {
             .get(&key)
             .is_some_and(|set| set.contains(&airing_ts))
     }
+
+    /// Set the REC flag on exactly the rows this index has scheduled.
+    pub fn flag_rows(&self, rows: &mut [crate::app::PosterRow]) {
+        for row in rows {
+            row.scheduled =
+                self.is_scheduled(row.guid.as_deref(), &row.title, row.year, row.airing);
+        }
+    }
 }
 
 fn system_time_to_unix(ts: SystemTime) -> Option<i64> {
@@ -61,7 +78,7 @@ fn make_title_key(title: &str, year: Option<i32>) -> Option<String> {
     Some(format!("{lowered}:{year}"))
 }
 
-pub(crate) fn load_scheduled_index() -> Result<ScheduledIndex, String> {
+pub(crate) fn load_scheduled_index(clock: &dyn Clock) -> Result<ScheduledIndex, String> {
     let path = local_library_db_path();
     if !path.exists() {
         return Ok(ScheduledIndex::default());
@@ -76,6 +93,11 @@ pub(crate) fn load_scheduled_index() -> Result<ScheduledIndex, String> {
     let conn = Connection::open_with_flags(&path, flags)
         .map_err(|err| format!("open library db failed: {err}"))?;
 
+    build_scheduled_index(&conn, unix_now(clock))
+}
+
+/// Build the index from an open library DB, ignoring airings that ended more than a day before `now`.
+fn build_scheduled_index(conn: &Connection, now: i64) -> Result<ScheduledIndex, String> {
     let mut stmt = conn
         .prepare(
             r#"
@@ -95,10 +117,6 @@ pub(crate) fn load_scheduled_index() -> Result<ScheduledIndex, String> {
         .map_err(|err| format!("prepare scheduled query failed: {err}"))?;
 
     let mut index = ScheduledIndex::default();
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
 
     let mut rows = stmt
         .query([])
@@ -148,8 +166,8 @@ pub(crate) fn load_scheduled_index() -> Result<ScheduledIndex, String> {
         }
     }
 
-    load_from_media_subscriptions(&conn, &mut index)?;
-    load_from_subscription_desired(&conn, &mut index)?;
+    load_from_media_subscriptions(conn, &mut index, now)?;
+    load_from_subscription_desired(conn, &mut index)?;
 
     Ok(index)
 }
@@ -157,6 +175,7 @@ pub(crate) fn load_scheduled_index() -> Result<ScheduledIndex, String> {
 fn load_from_media_subscriptions(
     conn: &Connection,
     index: &mut ScheduledIndex,
+    now: i64,
 ) -> Result<(), String> {
     let mut stmt = conn
         .prepare("SELECT extra_data FROM media_subscriptions WHERE extra_data IS NOT NULL")
@@ -166,11 +185,6 @@ fn load_from_media_subscriptions(
         .query([])
         .map_err(|err| format!("query media_subscriptions failed: {err}"))?;
 
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
-
     while let Some(row) = rows.next().map_err(|err| err.to_string())? {
         let blob: Option<String> = row.get(0).map_err(|err| err.to_string())?;
         let Some(blob) = blob else {
@@ -276,3 +290,84 @@ fn decode_mt_key(mt_key: &str) -> Option<String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::clock::FixedClock;
+    use std::time::Duration;
+
+    const NOW: i64 = 1_710_077_400;
+
+    fn library_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE media_grabs (status INTEGER, extra_data TEXT);
+             CREATE TABLE media_subscriptions (extra_data TEXT);
+             CREATE TABLE metadata_subscription_desired_items (remote_id TEXT);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn insert_grab(conn: &Connection, title: &str, begins_at: i64) {
+        let extra = serde_json::json!({
+            "mt:title": title,
+            "mt:year": "1982",
+            "me:beginsAt": begins_at.to_string(),
+        });
+        conn.execute(
+            "INSERT INTO media_grabs (status, extra_data) VALUES (1, ?1)",
+            [extra.to_string()],
+        )
+        .unwrap();
+    }
+
+    fn airing(ts: i64) -> Option<SystemTime> {
+        Some(UNIX_EPOCH + Duration::from_secs(ts as u64))
+    }
+
+    #[test]
+    fn grabs_older_than_a_day_are_pruned() {
+        let conn = library_db();
+        let stale = NOW - 2 * 86_400;
+        let recent = NOW - 3_600;
+        let upcoming = NOW + 86_400;
+        insert_grab(&conn, "Stale", stale);
+        insert_grab(&conn, "Recent", recent);
+        insert_grab(&conn, "Upcoming", upcoming);
+
+        let index = build_scheduled_index(&conn, NOW).unwrap();
+        assert!(!index.is_scheduled(None, "Stale", Some(1982), airing(stale)));
+        assert!(index.is_scheduled(None, "Recent", Some(1982), airing(recent)));
+        assert!(index.is_scheduled(None, "Upcoming", Some(1982), airing(upcoming)));
+    }
+
+    #[test]
+    fn subscription_airings_follow_the_clock() {
+        let conn = library_db();
+        let first = NOW + 3_600;
+        let second = NOW + 3 * 86_400;
+        let extra = serde_json::json!({
+            "hi:title": "Blade Runner",
+            "hi:year": "1982",
+            "pv:airingTimes": format!("{first}%2C{second}"),
+        });
+        conn.execute(
+            "INSERT INTO media_subscriptions (extra_data) VALUES (?1)",
+            [extra.to_string()],
+        )
+        .unwrap();
+
+        let clock = FixedClock::at_unix(NOW as u64);
+        let index = build_scheduled_index(&conn, unix_now(&clock)).unwrap();
+        assert!(index.is_scheduled(None, "Blade Runner", Some(1982), airing(first)));
+        assert!(index.is_scheduled(None, "Blade Runner", Some(1982), airing(second)));
+
+        // Two days later the first airing has aged out; the second is still pending.
+        clock.advance(Duration::from_secs(2 * 86_400 + 3_600));
+        let index = build_scheduled_index(&conn, unix_now(&clock)).unwrap();
+        assert!(!index.is_scheduled(None, "Blade Runner", Some(1982), airing(first)));
+        assert!(index.is_scheduled(None, "Blade Runner", Some(1982), airing(second)));
+    }
+}
@@ -0,0 +1,466 @@
+// src/app/search.rs
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::app::PosterRow;
+
+// Queries shorter than this only match as substrings; two letters match nearly everything.
+const FUZZY_MIN_CHARS: usize = 3;
+// Scores by match kind; within a kind, tighter matches score higher.
+const SUBSTRING_BASE: u32 = 3000;
+const SUBSEQUENCE_BASE: u32 = 2000;
+const TYPO_BASE: u32 = 1000;
+
+/// Row field a `prefix:value` search term is limited to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SearchField {
+    Title,
+    Genre,
+    Channel,
+    Summary,
+}
+
+impl SearchField {
+    pub const ALL: [Self; 4] = [Self::Title, Self::Genre, Self::Channel, Self::Summary];
+
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Genre => "genre",
+            Self::Channel => "channel",
+            Self::Summary => "summary",
+        }
+    }
+
+    pub(crate) fn from_prefix(prefix: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.prefix().eq_ignore_ascii_case(prefix))
+    }
+
+    /// The row's text for this field (channel covers display name, call sign and title).
+    pub(crate) fn values(self, row: &PosterRow) -> Vec<&str> {
+        match self {
+            Self::Title => vec![row.title.as_str()],
+            Self::Genre => row.genres.iter().map(String::as_str).collect(),
+            Self::Channel => [&row.channel, &row.channel_raw, &row.channel_title]
+                .into_iter()
+                .filter_map(|c| c.as_deref())
+                .collect(),
+            Self::Summary => row.summary.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+/// Search box contents: free text plus `genre:western`-style terms (all must match).
+/// Values with spaces can be quoted: `channel:"bbc one"`. Unknown prefixes stay free text,
+/// so titles like "Mission: Impossible" still work.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SearchQuery {
+    /// Lowercased free text, matched against the title (or every field).
+    pub free: String,
+    /// Lowercased field-limited terms.
+    pub fields: Vec<(SearchField, String)>,
+}
+
+impl SearchQuery {
+    pub(crate) fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut free: Vec<String> = Vec::new();
+        for token in split_tokens(input) {
+            let field_term = token.split_once(':').and_then(|(prefix, value)| {
+                let field = SearchField::from_prefix(prefix)?;
+                let value = value.trim_matches('"').trim().to_lowercase();
+                Some((field, value))
+            });
+            match field_term {
+                Some((_, value)) if value.is_empty() => {}
+                Some(term) => query.fields.push(term),
+                None => free.push(token.replace('"', "").to_lowercase()),
+            }
+        }
+        query.free = free.join(" ");
+        query
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.free.is_empty() && self.fields.is_empty()
+    }
+}
+
+/// Whitespace-separated tokens; double quotes keep spaces inside one token.
+pub(crate) fn split_tokens(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut cur = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                cur.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !cur.is_empty() {
+                    tokens.push(std::mem::take(&mut cur));
+                }
+            }
+            c => cur.push(c),
+        }
+    }
+    if !cur.is_empty() {
+        tokens.push(cur);
+    }
+    tokens
+}
+
+/// Regex search: on with the toggle, or for `/pattern/` input. The pattern is compiled once
+/// per change of the search text, case-insensitively.
+#[derive(Default)]
+pub(crate) struct RegexSearch {
+    pub enabled: bool,
+    input: String,
+    compiled: Option<Result<Regex, String>>,
+}
+
+impl RegexSearch {
+    /// Recompile when the search box or the toggle changed since the last frame.
+    pub(crate) fn sync(&mut self, input: &str) {
+        let pattern = regex_pattern(input, self.enabled);
+        let key = format!("{}{input}", u8::from(self.enabled));
+        if key == self.input {
+            return;
+        }
+        self.input = key;
+        self.compiled = pattern.map(|p| {
+            RegexBuilder::new(p)
+                .case_insensitive(true)
+                .size_limit(1 << 20)
+                .build()
+                .map_err(|err| match err {
+                    regex::Error::Syntax(msg) => msg,
+                    other => other.to_string(),
+                })
+        });
+    }
+
+    /// `None` outside regex mode; otherwise the compiled pattern or why it failed.
+    pub(crate) const fn active(&self) -> Option<&Result<Regex, String>> {
+        self.compiled.as_ref()
+    }
+}
+
+/// The pattern when the input is a regex search: `/…/` always, anything non-empty with the
+/// toggle on.
+fn regex_pattern(input: &str, enabled: bool) -> Option<&str> {
+    let trimmed = input.trim();
+    if let Some(inner) = trimmed
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_suffix('/'))
+    {
+        return (!inner.is_empty()).then_some(inner);
+    }
+    (enabled && !trimmed.is_empty()).then_some(trimmed)
+}
+
+/// Substring match for plain mode. `query` is already lowercased.
+pub(crate) fn plain_match(query: &str, text: &str) -> bool {
+    text.to_lowercase().contains(query)
+}
+
+/// Fuzzy match score (higher is better), or `None` when `text` does not match.
+/// `query` is already lowercased.
+pub(crate) fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if let Some(pos) = text.find(query) {
+        // Prefer matches near the start and titles that are mostly the query.
+        let extra = text.len().saturating_sub(query.len()) + pos;
+        return Some(SUBSTRING_BASE.saturating_sub(extra.min(999) as u32));
+    }
+    if query.chars().filter(|c| !c.is_whitespace()).count() < FUZZY_MIN_CHARS {
+        return None;
+    }
+    subsequence_score(query, &text)
+        .map(|s| SUBSEQUENCE_BASE + s.min(999))
+        .or_else(|| typo_score(query, &text).map(|s| TYPO_BASE + s))
+}
+
+/// Byte ranges of `text` to highlight for `query` (already lowercased): every substring
+/// occurrence, else in fuzzy mode the letters of the subsequence match or the words within
+/// typo distance, mirroring how `fuzzy_score` accepted the text.
+pub(crate) fn match_ranges(query: &str, text: &str, fuzzy: bool) -> Vec<Range<usize>> {
+    let needle: Vec<char> = query.chars().collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    // (byte offset, byte length, lowercased char) so matches map back onto `text`.
+    let chars: Vec<(usize, usize, char)> = text
+        .char_indices()
+        .map(|(i, c)| (i, c.len_utf8(), c.to_lowercase().next().unwrap_or(c)))
+        .collect();
+    let bytes = |from: usize, to: usize| chars[from].0..chars[to - 1].0 + chars[to - 1].1;
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= chars.len() {
+        if chars[i..i + needle.len()]
+            .iter()
+            .map(|c| c.2)
+            .eq(needle.iter().copied())
+        {
+            ranges.push(bytes(i, i + needle.len()));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    if !ranges.is_empty() || !fuzzy {
+        return ranges;
+    }
+
+    let lower = text.to_lowercase();
+    let letters: Vec<char> = needle.into_iter().filter(|c| !c.is_whitespace()).collect();
+    if letters.len() < FUZZY_MIN_CHARS {
+        return ranges;
+    }
+    if subsequence_score(query, &lower).is_some() {
+        // Same greedy walk as subsequence_score; neighbouring letters join into one range.
+        let mut qi = 0;
+        for (ci, c) in chars.iter().enumerate() {
+            if qi == letters.len() {
+                break;
+            }
+            if c.2 != letters[qi] {
+                continue;
+            }
+            qi += 1;
+            let range = bytes(ci, ci + 1);
+            match ranges.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => ranges.push(range),
+            }
+        }
+    } else if typo_score(query, &lower).is_some() {
+        let mut start = None;
+        for ci in 0..=chars.len() {
+            let alnum = chars.get(ci).is_some_and(|c| c.2.is_alphanumeric());
+            match (alnum, start) {
+                (true, None) => start = Some(ci),
+                (false, Some(from)) => {
+                    let word: String = chars[from..ci].iter().map(|c| c.2).collect();
+                    let near = query.split_whitespace().any(|q| {
+                        let allowed = match q.chars().count() {
+                            0..=3 => 0,
+                            4..=7 => 1,
+                            _ => 2,
+                        };
+                        edit_distance(q, &word) <= allowed
+                    });
+                    if near {
+                        ranges.push(bytes(from, ci));
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+    ranges
+}
+
+/// Every query letter appears in order. Rewards consecutive letters and word starts;
+/// matches spread over more than three times the query length are rejected.
+fn subsequence_score(query: &str, text: &str) -> Option<u32> {
+    let needle: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    let hay: Vec<char> = text.chars().collect();
+    let mut qi = 0;
+    let mut score: i64 = 0;
+    let mut first = None;
+    let mut prev: Option<usize> = None;
+    for (i, &c) in hay.iter().enumerate() {
+        if qi == needle.len() {
+            break;
+        }
+        if c != needle[qi] {
+            continue;
+        }
+        score += 10;
+        if prev.is_some_and(|p| p + 1 == i) {
+            score += 15;
+        }
+        if i == 0 || !hay[i - 1].is_alphanumeric() {
+            score += 20;
+        }
+        first.get_or_insert(i);
+        prev = Some(i);
+        qi += 1;
+    }
+    if qi < needle.len() {
+        return None;
+    }
+    let span = prev? + 1 - first?;
+    if span > needle.len() * 3 {
+        return None;
+    }
+    score -= (span - needle.len()) as i64;
+    Some(score.max(0) as u32)
+}
+
+/// Each query word of four or more letters is within a small edit distance of some word in
+/// `text` (one edit, two from eight letters); shorter words must appear as they are.
+fn typo_score(query: &str, text: &str) -> Option<u32> {
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut total = 0usize;
+    for q in query.split_whitespace() {
+        let len = q.chars().count();
+        let allowed = match len {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        let best = words.iter().map(|w| edit_distance(q, w)).min()?;
+        if best > allowed {
+            return None;
+        }
+        total += best;
+    }
+    Some(999usize.saturating_sub(total * 100) as u32)
+}
+
+/// Optimal string alignment distance (Levenshtein plus adjacent transpositions).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev2: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut cur = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        prev2 = std::mem::replace(&mut prev, cur);
+    }
+    prev[b.len()]
+}
+
+impl crate::app::PexApp {
+    /// Parts of `title` the current search matched: regex matches in regex mode, otherwise the
+    /// free text and any `title:` terms. Sorted by start; empty without a search.
+    pub(crate) fn title_highlights(&self, title: &str) -> Vec<Range<usize>> {
+        if let Some(compiled) = self.regex_search.active() {
+            return compiled.as_ref().map_or_else(
+                |_| Vec::new(),
+                |re| {
+                    re.find_iter(title)
+                        .filter(|m| !m.is_empty())
+                        .map(|m| m.range())
+                        .collect()
+                },
+            );
+        }
+        if self.search_query.trim().is_empty() {
+            return Vec::new();
+        }
+        let query = SearchQuery::parse(&self.search_query);
+        let title_terms = query
+            .fields
+            .iter()
+            .filter(|(field, _)| *field == SearchField::Title)
+            .map(|(_, term)| term.as_str());
+        let mut ranges: Vec<Range<usize>> = std::iter::once(query.free.as_str())
+            .chain(title_terms)
+            .flat_map(|term| match_ranges(term, title, self.fuzzy_search))
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_prefixes_split_out_of_the_query() {
+        let q = SearchQuery::parse(r#"genre:Western  channel:"BBC One" the good"#);
+        assert_eq!(q.free, "the good");
+        assert_eq!(
+            q.fields,
+            vec![
+                (SearchField::Genre, "western".to_string()),
+                (SearchField::Channel, "bbc one".to_string()),
+            ]
+        );
+        // Unknown prefixes are part of the title.
+        let q = SearchQuery::parse("Mission: Impossible");
+        assert_eq!(q.free, "mission: impossible");
+        assert!(q.fields.is_empty());
+        assert!(SearchQuery::parse("genre:").is_empty());
+    }
+
+    #[test]
+    fn slashes_or_toggle_turn_on_regex_search() {
+        assert_eq!(
+            regex_pattern("/^(The )?Matrix/", false),
+            Some("^(The )?Matrix")
+        );
+        assert_eq!(regex_pattern("Matrix", false), None);
+        assert_eq!(regex_pattern(" Matrix ", true), Some("Matrix"));
+        assert_eq!(regex_pattern("//", false), None);
+
+        let mut search = RegexSearch::default();
+        search.sync("/^(the )?matrix/");
+        let Some(Ok(re)) = search.active() else {
+            panic!("pattern should compile");
+        };
+        assert!(re.is_match("The Matrix Reloaded"));
+        assert!(!re.is_match("Enter the Matrix"));
+        search.sync("/(unclosed/");
+        assert!(matches!(search.active(), Some(Err(_))));
+        search.sync("plain");
+        assert!(search.active().is_none());
+    }
+
+    #[test]
+    fn fuzzy_tolerates_missing_letters_and_typos() {
+        let title = "The Godfather";
+        assert!(fuzzy_score("godfater", title).is_some());
+        assert!(fuzzy_score("godfahter", title).is_some());
+        assert!(fuzzy_score("gdfthr", title).is_some());
+        assert!(fuzzy_score("casablanca", title).is_none());
+        // Short queries stay strict.
+        assert!(fuzzy_score("gx", title).is_none());
+
+        let exact = fuzzy_score("godfather", title).unwrap();
+        let scattered = fuzzy_score("gdfthr", title).unwrap();
+        let typo = fuzzy_score("godfahter", title).unwrap();
+        assert!(exact > scattered && scattered > typo);
+        assert!(
+            fuzzy_score("godfather", "The Godfather").unwrap()
+                > fuzzy_score("godfather", "The Godfather Part II").unwrap()
+        );
+    }
+
+    #[test]
+    fn match_ranges_mark_substrings_then_fuzzy_letters() {
+        let hit = |query: &str, text: &str, fuzzy| {
+            match_ranges(query, text, fuzzy)
+                .into_iter()
+                .map(|r| text[r].to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hit("the", "The Way of the Gun", false), ["The", "the"]);
+        assert_eq!(hit("amelie", "Amélie", false), Vec::<String>::new());
+        assert_eq!(hit("amé", "AMÉLIE", false), ["AMÉ"]);
+        assert_eq!(hit("gdfthr", "The Godfather", true), ["G", "df", "th", "r"]);
+        assert_eq!(hit("godfahter", "The Godfather", true), ["Godfather"]);
+        assert!(hit("gdfthr", "The Godfather", false).is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+// src/app/server_identity.rs
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+
+use crate::config::{local_db_path, local_library_db_path, AppConfig};
+
+// Preference keys Plex has used for the server's machine id.
+const IDENTIFIER_KEYS: [&str; 2] = ["ProcessedMachineIdentifier", "MachineIdentifier"];
+
+/// Read the Plex server machine identifier stored in a database's `preferences` table.
+/// Returns None when the DB can't be opened or carries no identifier.
+pub(crate) fn read_server_identifier(path: &Path) -> Option<String> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .ok()?;
+    server_identifier_from_conn(&conn)
+}
+
+fn server_identifier_from_conn(conn: &Connection) -> Option<String> {
+    let has_prefs: bool = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'preferences'",
+            [],
+            |_| Ok(true),
+        )
+        .optional()
+        .ok()
+        .flatten()
+        .unwrap_or(false);
+    if !has_prefs {
+        return None;
+    }
+    IDENTIFIER_KEYS.iter().find_map(|key| {
+        conn.query_row(
+            "SELECT value FROM preferences WHERE name = ?1 LIMIT 1",
+            [key],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .ok()
+        .flatten()
+        .flatten()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+    })
+}
+
+/// Compare the EPG and library databases and describe a mismatch, if any.
+///
+/// The stored machine identifiers are authoritative when both DBs have one. Otherwise fall
+/// back to the configured source paths: Plex keeps both files in the same `Databases` folder,
+/// so sources in different folders almost certainly belong to different servers.
+pub(crate) fn server_mismatch_warning(cfg: &AppConfig) -> Option<String> {
+    let epg_path = existing(cfg.plex_epg_db_source.as_ref(), local_db_path());
+    let library_path = existing(cfg.plex_library_db_source.as_ref(), local_library_db_path());
+
+    let epg_id = epg_path.as_deref().and_then(read_server_identifier);
+    let library_id = library_path.as_deref().and_then(read_server_identifier);
+    if let (Some(epg_id), Some(library_id)) = (&epg_id, &library_id) {
+        return (epg_id != library_id).then(|| {
+            format!(
+                "The EPG database (server {epg_id}) and library database (server {library_id}) come from different Plex servers. Owned and scheduled markers will be wrong; point plex_epg_db_source and plex_library_db_source at the same server's Databases folder."
+            )
+        });
+    }
+
+    let (Some(epg_src), Some(library_src)) = (
+        cfg.plex_epg_db_source.as_deref(),
+        cfg.plex_library_db_source.as_deref(),
+    ) else {
+        return None;
+    };
+    (!same_parent(epg_src, library_src)).then(|| {
+        format!(
+            "plex_epg_db_source ({}) and plex_library_db_source ({}) are in different folders, so they may come from different Plex servers. Owned and scheduled markers are only reliable when both come from the same server's Databases folder.",
+            epg_src.display(),
+            library_src.display()
+        )
+    })
+}
+
+fn existing(source: Option<&PathBuf>, local: PathBuf) -> Option<PathBuf> {
+    source
+        .filter(|p| p.exists())
+        .cloned()
+        .or_else(|| local.exists().then_some(local))
+}
+
+fn same_parent(a: &Path, b: &Path) -> bool {
+    let parent = |p: &Path| {
+        p.parent()
+            .map(|dir| dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()))
+    };
+    parent(a) == parent(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db_with_prefs(rows: &[(&str, &str)]) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE preferences (id INTEGER PRIMARY KEY, name TEXT, value TEXT);",
+        )
+        .unwrap();
+        for (name, value) in rows {
+            conn.execute(
+                "INSERT INTO preferences (name, value) VALUES (?1, ?2)",
+                [name, value],
+            )
+            .unwrap();
+        }
+        conn
+    }
+
+    #[test]
+    fn identifier_prefers_processed_machine_id() {
+        let conn = db_with_prefs(&[
+            ("MachineIdentifier", "raw"),
+            ("ProcessedMachineIdentifier", "abc123"),
+        ]);
+        assert_eq!(
+            server_identifier_from_conn(&conn).as_deref(),
+            Some("abc123")
+        );
+
+        let conn = db_with_prefs(&[("MachineIdentifier", " raw ")]);
+        assert_eq!(server_identifier_from_conn(&conn).as_deref(), Some("raw"));
+    }
+
+    #[test]
+    fn identifier_missing_without_preferences() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(server_identifier_from_conn(&conn), None);
+
+        let conn = db_with_prefs(&[("FriendlyName", "tower")]);
+        assert_eq!(server_identifier_from_conn(&conn), None);
+    }
+
+    #[test]
+    fn sources_in_different_folders_differ() {
+        assert!(same_parent(
+            Path::new("/plex/Databases/epg.db"),
+            Path::new("/plex/Databases/library.db")
+        ));
+        assert!(!same_parent(
+            Path::new("/plex-a/Databases/epg.db"),
+            Path::new("/plex-b/Databases/library.db")
+        ));
+    }
+}
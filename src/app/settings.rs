@@ -0,0 +1,812 @@
+// src/app/settings.rs
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eframe::egui as eg;
+use serde_json::{Map, Value};
+
+use crate::app::file_picker::{PendingPick, PickKind};
+use crate::app::RatingState;
+use crate::config::{resolve_relative_path, AppConfig, OwnedSource, DEFAULT_OWNED_EXCLUDE_DIRS};
+
+/// Settings window state: an editable copy of config.json. Keys the form doesn't cover
+/// (`genre_aliases`, `log_level`, …) are written back unchanged.
+#[derive(Default)]
+pub(crate) struct SettingsEditor {
+    pub open: bool,
+    path: PathBuf,
+    raw: Map<String, Value>,
+    form: SettingsForm,
+    /// Why the loaded file can't be saved over (it isn't a JSON object).
+    load_error: Option<String>,
+    feedback: Option<String>,
+    /// Browse… dialog that is currently open.
+    pick: Option<PendingPick<PathField>>,
+}
+
+/// Form fields that have a Browse… button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PathField {
+    EpgSource,
+    LibrarySource,
+    KodiDb,
+    CacheDir,
+    /// Adds a folder to `library_roots` rather than replacing the list.
+    LibraryRoot,
+}
+
+impl PathField {
+    const fn kind(self) -> PickKind {
+        match self {
+            Self::EpgSource | Self::LibrarySource | Self::KodiDb => PickKind::File,
+            Self::CacheDir | Self::LibraryRoot => PickKind::Folder,
+        }
+    }
+
+    const fn dialog_title(self) -> &'static str {
+        match self {
+            Self::EpgSource => "Choose the Plex EPG database",
+            Self::LibrarySource => "Choose the Plex library database",
+            Self::KodiDb => "Choose Kodi's MyVideos database",
+            Self::CacheDir => "Choose the Pex cache folder",
+            Self::LibraryRoot => "Add a library folder to watch",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct SettingsForm {
+    epg_source: String,
+    library_source: String,
+    cache_dir: String,
+    tmdb_api_key: String,
+    omdb_api_key: String,
+    rating_providers: String,
+    plex_server_url: String,
+    plex_token: String,
+    plex_refresh_after_recording: bool,
+    offline: bool,
+    http_proxy: String,
+    https_proxy: String,
+    owned_exclude_dirs: String,
+    owned_source: String,
+    kodi_db_path: String,
+    jellyfin_url: String,
+    jellyfin_api_key: String,
+    radarr_url: String,
+    radarr_api_key: String,
+    radarr_root_folder: String,
+    trakt_client_id: String,
+    trakt_client_secret: String,
+    texture_budget_mb: String,
+    guide_refresh_minutes: String,
+    owned_rescan_hours: String,
+    tuner_count: String,
+    library_roots: String,
+}
+
+fn raw_str(raw: &Map<String, Value>, keys: &[&str]) -> String {
+    keys.iter()
+        .find_map(|key| raw.get(*key).and_then(Value::as_str))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn raw_list(raw: &Map<String, Value>, key: &str) -> Option<String> {
+    let items = raw.get(key)?.as_array()?;
+    Some(
+        items
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+fn split_list(text: &str) -> Vec<Value> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| Value::String(item.to_string()))
+        .collect()
+}
+
+impl SettingsForm {
+    fn path_mut(&mut self, field: PathField) -> &mut String {
+        match field {
+            PathField::EpgSource => &mut self.epg_source,
+            PathField::LibrarySource => &mut self.library_source,
+            PathField::KodiDb => &mut self.kodi_db_path,
+            PathField::CacheDir => &mut self.cache_dir,
+            PathField::LibraryRoot => &mut self.library_roots,
+        }
+    }
+
+    fn from_raw(raw: &Map<String, Value>) -> Self {
+        Self {
+            epg_source: raw_str(raw, &["plex_epg_db_source", "plex_db_source"]),
+            library_source: raw_str(raw, &["plex_library_db_source"]),
+            cache_dir: raw_str(raw, &["cache_dir"]),
+            tmdb_api_key: raw_str(raw, &["tmdb_api_key", "the_movie_db_api_key"]),
+            omdb_api_key: raw_str(raw, &["omdb_api_key"]),
+            rating_providers: raw_list(raw, "rating_providers").unwrap_or_default(),
+            plex_server_url: raw_str(raw, &["plex_server_url"]),
+            plex_token: raw_str(raw, &["plex_token"]),
+            plex_refresh_after_recording: raw
+                .get("plex_refresh_after_recording")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            offline: raw.get("offline").and_then(Value::as_bool).unwrap_or(false),
+            http_proxy: raw_str(raw, &["http_proxy"]),
+            https_proxy: raw_str(raw, &["https_proxy"]),
+            owned_exclude_dirs: raw_list(raw, "owned_exclude_dirs")
+                .unwrap_or_else(|| DEFAULT_OWNED_EXCLUDE_DIRS.join(", ")),
+            owned_source: raw_str(raw, &["owned_source"]),
+            kodi_db_path: raw_str(raw, &["kodi_db_path"]),
+            jellyfin_url: raw_str(raw, &["jellyfin_url", "emby_url"]),
+            jellyfin_api_key: raw_str(raw, &["jellyfin_api_key", "emby_api_key"]),
+            radarr_url: raw_str(raw, &["radarr_url"]),
+            radarr_api_key: raw_str(raw, &["radarr_api_key"]),
+            radarr_root_folder: raw_str(raw, &["radarr_root_folder"]),
+            trakt_client_id: raw_str(raw, &["trakt_client_id"]),
+            trakt_client_secret: raw_str(raw, &["trakt_client_secret"]),
+            texture_budget_mb: raw
+                .get("texture_budget_mb")
+                .and_then(Value::as_u64)
+                .map(|mb| mb.to_string())
+                .unwrap_or_default(),
+            guide_refresh_minutes: raw
+                .get("guide_refresh_minutes")
+                .and_then(Value::as_u64)
+                .map(|minutes| minutes.to_string())
+                .unwrap_or_default(),
+            owned_rescan_hours: raw
+                .get("owned_rescan_hours")
+                .and_then(Value::as_u64)
+                .map(|hours| hours.to_string())
+                .unwrap_or_default(),
+            library_roots: raw_list(raw, "library_roots").unwrap_or_default(),
+            tuner_count: raw
+                .get("tuner_count")
+                .and_then(Value::as_u64)
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Problems that block saving.
+    fn errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let budget = self.texture_budget_mb.trim();
+        if !budget.is_empty() && !budget.parse::<u32>().is_ok_and(|mb| mb > 0) {
+            errors.push("Texture budget must be a whole number of MB (or empty).".into());
+        }
+        let refresh = self.guide_refresh_minutes.trim();
+        if !refresh.is_empty() && refresh.parse::<u32>().is_err() {
+            errors.push(
+                "Guide refresh must be a whole number of minutes (0 = off, or empty).".into(),
+            );
+        }
+        let rescan = self.owned_rescan_hours.trim();
+        if !rescan.is_empty() && rescan.parse::<u32>().is_err() {
+            errors.push("Owned rescan must be a whole number of hours (0 = off, or empty).".into());
+        }
+        let tuners = self.tuner_count.trim();
+        if !tuners.is_empty() && !tuners.parse::<u32>().is_ok_and(|count| count > 0) {
+            errors.push("Tuners must be a whole number of at least 1 (or empty).".into());
+        }
+        let source = self.owned_source.trim();
+        if !source.is_empty() && source.parse::<OwnedSource>().is_err() {
+            errors.push("Owned source must be plex, kodi or jellyfin (or empty).".into());
+        }
+        let jellyfin = self.jellyfin_url.trim();
+        if !jellyfin.is_empty()
+            && !jellyfin.starts_with("http://")
+            && !jellyfin.starts_with("https://")
+        {
+            errors.push("Jellyfin/Emby URL must start with http:// or https://.".into());
+        }
+        let radarr = self.radarr_url.trim();
+        if !radarr.is_empty() && !radarr.starts_with("http://") && !radarr.starts_with("https://") {
+            errors.push("Radarr URL must start with http:// or https://.".into());
+        }
+        let url = self.plex_server_url.trim();
+        if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+            errors.push("Plex server URL must start with http:// or https://.".into());
+        }
+        errors
+    }
+
+    /// Write the form into `raw`, leaving unrelated keys alone. Empty fields remove their key
+    /// so the built-in default applies again; deprecated key names are replaced.
+    fn apply_to(&self, raw: &mut Map<String, Value>) {
+        let mut set_str = |key: &str, value: &str| {
+            let value = value.trim();
+            if value.is_empty() {
+                raw.remove(key);
+            } else {
+                raw.insert(key.to_string(), Value::String(value.to_string()));
+            }
+        };
+        set_str("plex_epg_db_source", &self.epg_source);
+        set_str("plex_library_db_source", &self.library_source);
+        set_str("cache_dir", &self.cache_dir);
+        set_str("tmdb_api_key", &self.tmdb_api_key);
+        set_str("omdb_api_key", &self.omdb_api_key);
+        set_str("plex_server_url", &self.plex_server_url);
+        set_str("plex_token", &self.plex_token);
+        set_str("http_proxy", &self.http_proxy);
+        set_str("https_proxy", &self.https_proxy);
+        set_str("owned_source", &self.owned_source.to_ascii_lowercase());
+        set_str("kodi_db_path", &self.kodi_db_path);
+        set_str("jellyfin_url", &self.jellyfin_url);
+        set_str("jellyfin_api_key", &self.jellyfin_api_key);
+        set_str("radarr_url", &self.radarr_url);
+        set_str("radarr_api_key", &self.radarr_api_key);
+        set_str("radarr_root_folder", &self.radarr_root_folder);
+        set_str("trakt_client_id", &self.trakt_client_id);
+        set_str("trakt_client_secret", &self.trakt_client_secret);
+        raw.remove("emby_url");
+        raw.remove("emby_api_key");
+        raw.remove("plex_db_source");
+        raw.remove("the_movie_db_api_key");
+
+        let providers = split_list(&self.rating_providers);
+        if providers.is_empty() {
+            raw.remove("rating_providers");
+        } else {
+            raw.insert("rating_providers".into(), Value::Array(providers));
+        }
+        raw.insert(
+            "owned_exclude_dirs".into(),
+            Value::Array(split_list(&self.owned_exclude_dirs)),
+        );
+        match self.texture_budget_mb.trim().parse::<u32>() {
+            Ok(mb) => raw.insert("texture_budget_mb".into(), Value::from(mb)),
+            Err(_) => raw.remove("texture_budget_mb"),
+        };
+        match self.guide_refresh_minutes.trim().parse::<u32>() {
+            Ok(minutes) => raw.insert("guide_refresh_minutes".into(), Value::from(minutes)),
+            Err(_) => raw.remove("guide_refresh_minutes"),
+        };
+        match self.owned_rescan_hours.trim().parse::<u32>() {
+            Ok(hours) => raw.insert("owned_rescan_hours".into(), Value::from(hours)),
+            Err(_) => raw.remove("owned_rescan_hours"),
+        };
+        match self.tuner_count.trim().parse::<u32>() {
+            Ok(count) => raw.insert("tuner_count".into(), Value::from(count)),
+            Err(_) => raw.remove("tuner_count"),
+        };
+        let roots = split_list(&self.library_roots);
+        if roots.is_empty() {
+            raw.remove("library_roots");
+        } else {
+            raw.insert("library_roots".into(), Value::Array(roots));
+        }
+        raw.insert(
+            "plex_refresh_after_recording".into(),
+            Value::Bool(self.plex_refresh_after_recording),
+        );
+        raw.insert("offline".into(), Value::Bool(self.offline));
+    }
+}
+
+/// Live hint for a path field: whether it is usable, and a short description.
+fn path_hint(input: &str, want_dir: bool) -> (bool, String) {
+    let input = input.trim();
+    if input.is_empty() {
+        return (true, "not set".into());
+    }
+    let path = resolve_relative_path(input);
+    if want_dir {
+        if path.is_dir() {
+            (true, "folder exists".into())
+        } else if path.exists() {
+            (false, "not a folder".into())
+        } else if path.parent().is_some_and(Path::is_dir) {
+            (true, "will be created".into())
+        } else {
+            (false, "parent folder not found".into())
+        }
+    } else if path.is_file() {
+        (true, "found".into())
+    } else if path.is_dir() {
+        (false, "this is a folder; pick the .db file".into())
+    } else {
+        (false, "file not found".into())
+    }
+}
+
+/// Write to a temporary file next to `path`, then rename over it, so a crash mid-save never
+/// leaves a truncated config.json.
+fn write_atomically(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, text).map_err(|err| format!("Failed to write {}: {err}", tmp.display()))?;
+    fs::rename(&tmp, path).map_err(|err| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to replace {}: {err}", path.display())
+    })
+}
+
+impl SettingsEditor {
+    /// (Re)load the form from disk; unsaved edits are dropped.
+    fn load(&mut self) {
+        self.path = crate::config::config_path();
+        self.load_error = None;
+        self.raw = match fs::read_to_string(&self.path) {
+            Ok(text) => crate::config::parse_config_text(&self.path, &text).unwrap_or_else(|err| {
+                self.load_error = Some(err);
+                Map::new()
+            }),
+            Err(_) => Map::new(),
+        };
+        self.form = SettingsForm::from_raw(&self.raw);
+    }
+
+    fn save(&mut self) -> Result<(), String> {
+        let mut raw = self.raw.clone();
+        self.form.apply_to(&mut raw);
+        let text = crate::config::render_config_text(&self.path, &raw)
+            .map_err(|err| format!("Failed to serialise settings: {err}"))?;
+        write_atomically(&self.path, &text)?;
+        self.raw = raw;
+        Ok(())
+    }
+
+    fn browse(&mut self, field: PathField) {
+        let mut current = self.form.path_mut(field).clone();
+        if field == PathField::LibraryRoot {
+            // Start next to the last folder in the list.
+            current = current.rsplit(',').next().unwrap_or("").trim().to_string();
+        }
+        self.pick = Some(PendingPick::spawn(
+            field,
+            field.kind(),
+            field.dialog_title(),
+            &current,
+        ));
+    }
+
+    /// Copy a finished Browse… pick into its field.
+    fn poll_pick(&mut self) {
+        let Some((field, result)) = self.pick.as_ref().and_then(PendingPick::poll) else {
+            return;
+        };
+        self.pick = None;
+        match result {
+            Ok(Some(path)) if field == PathField::LibraryRoot => {
+                let roots = &mut self.form.library_roots;
+                if !roots.trim().is_empty() {
+                    roots.push_str(", ");
+                }
+                roots.push_str(&path.display().to_string());
+            }
+            Ok(Some(path)) => *self.form.path_mut(field) = path.display().to_string(),
+            Ok(None) => {}
+            Err(err) => self.feedback = Some(err),
+        }
+    }
+}
+
+/// Path field with a Browse… button and a live hint; returns true when Browse… was clicked.
+fn path_row(
+    ui: &mut eg::Ui,
+    label: &str,
+    value: &mut String,
+    kind: PickKind,
+    hint: &str,
+    can_browse: bool,
+) -> bool {
+    ui.label(label);
+    ui.add(
+        eg::TextEdit::singleline(value)
+            .hint_text(hint)
+            .desired_width(320.0),
+    );
+    let (ok, text) = path_hint(value, kind == PickKind::Folder);
+    let color = if ok {
+        eg::Color32::LIGHT_GREEN
+    } else {
+        eg::Color32::LIGHT_RED
+    };
+    let clicked = ui
+        .horizontal(|ui| {
+            let clicked = ui
+                .add_enabled(can_browse, eg::Button::new("Browse…"))
+                .clicked();
+            ui.label(eg::RichText::new(text).color(color).small());
+            clicked
+        })
+        .inner;
+    ui.end_row();
+    clicked
+}
+
+fn text_row(ui: &mut eg::Ui, label: &str, value: &mut String, secret: bool, hint: &str) {
+    ui.label(label);
+    ui.add(
+        eg::TextEdit::singleline(value)
+            .password(secret)
+            .hint_text(hint)
+            .desired_width(320.0),
+    );
+    ui.label("");
+    ui.end_row();
+}
+
+impl crate::app::PexApp {
+    pub(crate) fn open_settings(&mut self) {
+        self.settings.load();
+        self.settings.feedback = None;
+        self.settings.open = true;
+    }
+
+    /// Settings window: edit config.json fields, see path problems as you type, save.
+    pub(crate) fn ui_render_settings_window(&mut self, ctx: &eg::Context) {
+        if !self.settings.open {
+            return;
+        }
+        self.settings.poll_pick();
+        let mut open = true;
+        let mut save_clicked = false;
+        let mut reload_clicked = false;
+        let mut shortcuts_clicked = false;
+        let mut browse: Option<PathField> = None;
+        eg::Window::new("Settings")
+            .collapsible(false)
+            .default_width(560.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let editor = &mut self.settings;
+                ui.label(eg::RichText::new(format!("Editing {}", editor.path.display())).weak());
+                if let Some(err) = &editor.load_error {
+                    ui.label(eg::RichText::new(err).color(eg::Color32::LIGHT_RED));
+                }
+                if let Some(profile) = crate::config::active_profile() {
+                    ui.label(
+                        eg::RichText::new(format!(
+                            "Profile \"{profile}\" is active: its entry under \"profiles\" \
+                             overrides the matching fields below."
+                        ))
+                        .weak(),
+                    );
+                }
+                ui.add_space(4.0);
+                let can_browse = editor.pick.is_none();
+                let form = &mut editor.form;
+                eg::Grid::new("settings_grid")
+                    .num_columns(3)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        let paths = [
+                            (
+                                PathField::EpgSource,
+                                "EPG database source",
+                                "…/Plug-in Support/Databases/tv.plex.providers.epg.cloud-….db",
+                            ),
+                            (
+                                PathField::LibrarySource,
+                                "Library database source",
+                                "…/com.plexapp.plugins.library.db",
+                            ),
+                            (
+                                PathField::KodiDb,
+                                "Kodi video database",
+                                "…/userdata/Database/MyVideos131.db",
+                            ),
+                            (PathField::CacheDir, "Cache folder", ".pex_cache"),
+                        ];
+                        for (field, label, hint) in paths {
+                            let value = form.path_mut(field);
+                            if path_row(ui, label, value, field.kind(), hint, can_browse) {
+                                browse = Some(field);
+                            }
+                        }
+                        text_row(ui, "TMDb API key", &mut form.tmdb_api_key, true, "");
+                        text_row(ui, "OMDb API key", &mut form.omdb_api_key, true, "");
+                        text_row(
+                            ui,
+                            "Rating providers",
+                            &mut form.rating_providers,
+                            false,
+                            "tmdb, omdb",
+                        );
+                        text_row(
+                            ui,
+                            "Plex server URL",
+                            &mut form.plex_server_url,
+                            false,
+                            "http://127.0.0.1:32400",
+                        );
+                        text_row(ui, "Plex token", &mut form.plex_token, true, "");
+                        text_row(ui, "HTTP proxy", &mut form.http_proxy, false, "");
+                        text_row(ui, "HTTPS proxy", &mut form.https_proxy, false, "");
+                        text_row(
+                            ui,
+                            "Owned scan skips folders",
+                            &mut form.owned_exclude_dirs,
+                            false,
+                            ".grab, Transcode",
+                        );
+                        text_row(
+                            ui,
+                            "Owned source",
+                            &mut form.owned_source,
+                            false,
+                            "plex, kodi or jellyfin",
+                        );
+                        text_row(
+                            ui,
+                            "Jellyfin/Emby URL",
+                            &mut form.jellyfin_url,
+                            false,
+                            "http://127.0.0.1:8096",
+                        );
+                        text_row(
+                            ui,
+                            "Jellyfin/Emby API key",
+                            &mut form.jellyfin_api_key,
+                            true,
+                            "",
+                        );
+                        text_row(
+                            ui,
+                            "Radarr URL",
+                            &mut form.radarr_url,
+                            false,
+                            "http://127.0.0.1:7878",
+                        );
+                        text_row(ui, "Radarr API key", &mut form.radarr_api_key, true, "");
+                        text_row(
+                            ui,
+                            "Radarr root folder",
+                            &mut form.radarr_root_folder,
+                            false,
+                            "Radarr's first root folder",
+                        );
+                        text_row(ui, "Trakt client ID", &mut form.trakt_client_id, true, "");
+                        text_row(
+                            ui,
+                            "Trakt client secret",
+                            &mut form.trakt_client_secret,
+                            true,
+                            "",
+                        );
+                        text_row(
+                            ui,
+                            "Texture budget (MB)",
+                            &mut form.texture_budget_mb,
+                            false,
+                            "512",
+                        );
+                        text_row(
+                            ui,
+                            "Guide refresh (minutes)",
+                            &mut form.guide_refresh_minutes,
+                            false,
+                            "60",
+                        );
+                        text_row(
+                            ui,
+                            "Owned rescan (hours)",
+                            &mut form.owned_rescan_hours,
+                            false,
+                            "off",
+                        );
+                        ui.label("Watch library folders");
+                        ui.add(
+                            eg::TextEdit::singleline(&mut form.library_roots)
+                                .hint_text("D:\\Movies, \\\\nas\\films")
+                                .desired_width(320.0),
+                        );
+                        if ui
+                            .add_enabled(can_browse, eg::Button::new("Add folder…"))
+                            .on_hover_text("Append a folder to the list")
+                            .clicked()
+                        {
+                            browse = Some(PathField::LibraryRoot);
+                        }
+                        ui.end_row();
+                        text_row(ui, "Tuners", &mut form.tuner_count, false, "2");
+                    });
+                ui.checkbox(
+                    &mut form.plex_refresh_after_recording,
+                    "Ask Plex to rescan after a recording finishes",
+                );
+                ui.checkbox(&mut form.offline, "Start offline (no network requests)");
+
+                let errors = form.errors();
+                for err in &errors {
+                    ui.label(eg::RichText::new(err).color(eg::Color32::LIGHT_RED));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let can_save = errors.is_empty() && editor.load_error.is_none();
+                    save_clicked = ui
+                        .add_enabled(can_save, eg::Button::new("Save"))
+                        .on_hover_text("Write config.json and apply the changes")
+                        .clicked();
+                    reload_clicked = ui
+                        .button("Reload from disk")
+                        .on_hover_text("Discard unsaved edits")
+                        .clicked();
+                    ui.with_layout(eg::Layout::right_to_left(eg::Align::Center), |ui| {
+                        shortcuts_clicked = ui
+                            .button("Keyboard shortcuts…")
+                            .on_hover_text("Rebind navigation and action keys (saved in prefs)")
+                            .clicked();
+                    });
+                });
+                if let Some(msg) = &editor.feedback {
+                    ui.label(eg::RichText::new(msg).italics());
+                }
+            });
+        if let Some(field) = browse {
+            self.settings.browse(field);
+        }
+        if shortcuts_clicked {
+            self.show_keymap_popup = true;
+        }
+        if reload_clicked {
+            self.settings.load();
+            self.settings.feedback = Some("Reloaded from disk.".into());
+        }
+        if save_clicked {
+            let before = crate::config::load_config();
+            match self.settings.save() {
+                Ok(()) => {
+                    let after = crate::config::load_config();
+                    let notes = self.apply_config_change(ctx, &before, &after);
+                    self.config_watch.remember(after);
+                    let msg = std::iter::once("Saved.".to_string()).chain(notes);
+                    self.settings.feedback = Some(msg.collect::<Vec<_>>().join(" "));
+                }
+                Err(err) => self.settings.feedback = Some(err),
+            }
+        }
+        self.settings.open = open;
+    }
+
+    /// Restart whatever depends on the settings that changed; returns notes for the user.
+    pub(crate) fn apply_config_change(
+        &mut self,
+        ctx: &eg::Context,
+        before: &AppConfig,
+        after: &AppConfig,
+    ) -> Vec<String> {
+        let mut notes = Vec::new();
+        if !self.did_init {
+            // Still on the setup gate: just run the checks again.
+            self.setup_checked = false;
+            return vec!["Re-running setup checks.".into()];
+        }
+
+        if before.texture_budget_mb != after.texture_budget_mb {
+            self.apply_texture_budget(after);
+        }
+        if before.guide_refresh_minutes != after.guide_refresh_minutes {
+            self.guide_refresh.configure(after);
+        }
+        if before.owned_rescan_hours != after.owned_rescan_hours
+            || before.library_roots != after.library_roots
+            || before.owned_exclude_dirs != after.owned_exclude_dirs
+        {
+            self.owned_rescan.configure(after);
+        }
+        if before.tuner_count != after.tuner_count {
+            self.recording_conflicts.configure(after);
+            self.apply_conflict_flags();
+        }
+        if before.radarr_url != after.radarr_url
+            || before.radarr_api_key != after.radarr_api_key
+            || before.radarr_root_folder != after.radarr_root_folder
+        {
+            self.radarr.configure(after);
+            self.sync_radarr();
+            self.apply_radarr_flags_from(0);
+        }
+        if before.trakt_client_id != after.trakt_client_id
+            || before.trakt_client_secret != after.trakt_client_secret
+        {
+            self.trakt.configure(after);
+            self.sync_trakt();
+            self.apply_trakt_flags_from(0);
+        }
+        if after.offline && !before.offline {
+            self.go_offline(crate::app::net::OfflineReason::Configured);
+        }
+        if before.plex_server_url != after.plex_server_url
+            || before.plex_token != after.plex_token
+            || before.plex_refresh_after_recording != after.plex_refresh_after_recording
+        {
+            let now = self.now();
+            self.library_refresh.configure(after, now);
+            self.external_links.configure(after);
+        }
+        if before.tmdb_api_key != after.tmdb_api_key
+            || before.omdb_api_key != after.omdb_api_key
+            || before.rating_providers != after.rating_providers
+        {
+            // Keep fetched ratings; let failed and key-less lookups run again.
+            self.rating_states.retain(|_, state| {
+                matches!(state, RatingState::Success { .. } | RatingState::NotFound)
+            });
+            self.reset_bulk_ratings();
+            notes.push("Rating providers updated.".into());
+        }
+        if before.plex_epg_db_source != after.plex_epg_db_source {
+            crate::app::prep::forget_epg_sync();
+            self.restart_poster_pipeline(ctx);
+            notes.push("Reloading the guide from the new EPG source.".into());
+        }
+        if before.plex_library_db_source != after.plex_library_db_source
+            || before.owned_exclude_dirs != after.owned_exclude_dirs
+            || before.owned_source != after.owned_source
+            || before.kodi_db_path != after.kodi_db_path
+            || before.jellyfin_url != after.jellyfin_url
+            || before.jellyfin_api_key != after.jellyfin_api_key
+        {
+            self.refresh_owned_scan();
+            notes.push("Rescanning the owned library.".into());
+        }
+        if before.cache_dir != after.cache_dir {
+            notes.push("The new cache folder is used after restarting Pex.".into());
+        }
+        notes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_keeps_unknown_keys_and_renames_deprecated_ones() {
+        let mut raw: Map<String, Value> = serde_json::from_str(
+            r#"{"plex_db_source": "C:\\plex\\epg.db", "log_level": "debug",
+                "genre_aliases": {"Kids": "Family"}, "rating_providers": ["omdb"]}"#,
+        )
+        .unwrap();
+        let mut form = SettingsForm::from_raw(&raw);
+        assert_eq!(form.epg_source, r"C:\plex\epg.db");
+        assert_eq!(form.rating_providers, "omdb");
+        assert_eq!(form.owned_exclude_dirs, ".grab, Transcode");
+
+        form.tmdb_api_key = " abc ".into();
+        form.rating_providers = "tmdb, omdb".into();
+        form.texture_budget_mb = "256".into();
+        assert!(form.errors().is_empty());
+        form.apply_to(&mut raw);
+
+        assert_eq!(raw["plex_epg_db_source"], r"C:\plex\epg.db");
+        assert!(!raw.contains_key("plex_db_source"));
+        assert_eq!(raw["tmdb_api_key"], "abc");
+        assert_eq!(raw["log_level"], "debug");
+        assert_eq!(raw["genre_aliases"]["Kids"], "Family");
+        assert_eq!(raw["rating_providers"], serde_json::json!(["tmdb", "omdb"]));
+        assert_eq!(raw["texture_budget_mb"], 256);
+        assert!(!raw.contains_key("cache_dir"));
+
+        form.texture_budget_mb = "lots".into();
+        form.plex_server_url = "plex.local:32400".into();
+        assert_eq!(form.errors().len(), 2);
+    }
+
+    #[test]
+    fn path_hints_flag_missing_and_wrong_kind() {
+        let dir = std::env::temp_dir();
+        let dir_str = dir.display().to_string();
+        assert_eq!(path_hint("", false), (true, "not set".into()));
+        assert!(path_hint(&dir_str, true).0);
+        assert!(!path_hint(&dir_str, false).0);
+        let missing = dir.join("pex_settings_test_missing");
+        assert!(path_hint(&missing.display().to_string(), true).0);
+        assert!(!path_hint(&missing.join("epg.db").display().to_string(), false).0);
+    }
+}
@@ -0,0 +1,135 @@
+// src/app/snapshot.rs
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use eframe::egui as eg;
+use tracing::{info, warn};
+
+use crate::app::cache::url_to_cache_key;
+use crate::app::{PosterRow, PrepItem};
+
+const SNAPSHOT_FILE: &str = "guide_snapshot.json";
+
+fn snapshot_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(SNAPSHOT_FILE)
+}
+
+fn unix_secs(ts: SystemTime) -> Option<i64> {
+    ts.duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs() as i64)
+}
+
+/// The guide listing behind a row, as prep would have produced it (None once it left the guide).
+fn snapshot_item(row: &PosterRow) -> Option<PrepItem> {
+    Some(PrepItem {
+        title: row.title.clone(),
+        thumb_url: row.url.clone(),
+        key: url_to_cache_key(&row.url),
+        begins_at: Some(unix_secs(row.airing?)?),
+        ends_at: row.ends.and_then(unix_secs),
+        year: row.year,
+        tags_genre: (!row.genres.is_empty()).then(|| row.genres.join("|")),
+        channel_call_sign: row.channel_raw.clone(),
+        channel_title: row.channel_title.clone(),
+        channel_thumb: row.channel_thumb.clone(),
+        guid: row.guid.clone(),
+        summary: row.summary.clone(),
+        audience_rating: row.audience_rating,
+        critic_rating: row.critic_rating,
+        premiere: row.premiere,
+    })
+}
+
+/// Snapshot items still inside `window` (see `prep::airing_window`).
+fn parse_snapshot(text: &str, window: (i64, i64)) -> Result<Vec<PrepItem>, String> {
+    let mut items: Vec<PrepItem> = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    items.retain(|item| {
+        item.begins_at
+            .is_some_and(|ts| ts >= window.0 && ts < window.1)
+    });
+    Ok(items)
+}
+
+fn read_snapshot(window: (i64, i64)) -> Option<Vec<PrepItem>> {
+    let text = fs::read_to_string(snapshot_path()).ok()?;
+    parse_snapshot(&text, window)
+        .map_err(|err| warn!("ignoring unreadable {SNAPSHOT_FILE}: {err}"))
+        .ok()
+}
+
+impl crate::app::PexApp {
+    /// Write the current guide (metadata only, no artwork) for the next launch.
+    pub(crate) fn save_guide_snapshot(&self) -> io::Result<()> {
+        if self.demo_mode || self.rows.is_empty() {
+            return Ok(());
+        }
+        let items: Vec<PrepItem> = self.rows.iter().filter_map(snapshot_item).collect();
+        let json = serde_json::to_string(&items).map_err(io::Error::other)?;
+        fs::write(snapshot_path(), json)
+    }
+
+    /// Show last session's guide straight away and re-read the EPG in the background, merging
+    /// any changes in place. Returns false when there is no usable snapshot.
+    pub(crate) fn start_from_snapshot(&mut self, ctx: &eg::Context) -> bool {
+        if self.demo_mode || self.in_safe_mode() {
+            return false;
+        }
+        let window = crate::app::prep::airing_window(self.current_range, self.now());
+        let Some(items) = read_snapshot(window).filter(|items| !items.is_empty()) else {
+            return false;
+        };
+        info!("snapshot: showing {} saved airings", items.len());
+        self.prep_started = true;
+        self.health.guide = crate::app::health::Health::Loading;
+        self.absorb_prep_rows(ctx, items);
+        self.last_hotset = None;
+        self.refresh_scheduled_index();
+        self.apply_premiere_flags();
+        self.apply_owned_flags();
+        // Reported like a manual refresh, so "no changes" replaces the status below too.
+        self.spawn_guide_refresh(true);
+        self.set_status("Showing the guide from last time; checking the EPG for changes…");
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_keeps_only_airings_in_the_window() {
+        let item = |title: &str, begins_at: Option<i64>| PrepItem {
+            title: title.into(),
+            thumb_url: "https://example.com/a.jpg".into(),
+            key: url_to_cache_key("https://example.com/a.jpg"),
+            begins_at,
+            ends_at: None,
+            year: Some(1979),
+            tags_genre: Some("Sci-Fi|Horror".into()),
+            channel_call_sign: Some("ITV2".into()),
+            channel_title: None,
+            channel_thumb: None,
+            guid: None,
+            summary: None,
+            audience_rating: None,
+            critic_rating: Some(9.0),
+            premiere: false,
+        };
+        let json = serde_json::to_string(&vec![
+            item("Yesterday", Some(50)),
+            item("Alien", Some(150)),
+            item("Undated", None),
+            item("Next week", Some(900)),
+        ])
+        .unwrap();
+        let items = parse_snapshot(&json, (100, 500)).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Alien");
+        assert_eq!(items[0].critic_rating, Some(9.0));
+        assert!(parse_snapshot("not json", (100, 500)).is_err());
+    }
+}
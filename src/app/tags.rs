@@ -0,0 +1,306 @@
+// src/app/tags.rs
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use eframe::egui as eg;
+use tracing::warn;
+
+const TAGS_FILE: &str = "title_tags.txt";
+
+fn tags_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(TAGS_FILE)
+}
+
+/// Tidy a typed tag: trimmed, inner whitespace collapsed, no tabs or commas.
+fn clean_tag(tag: &str) -> Option<String> {
+    let cleaned = tag
+        .replace([',', '\t'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// User tags on titles, kept in `<cache>/title_tags.txt` as `<title key>\t<tag>\t<tag>…` lines.
+/// The title key is `make_owned_key` of the guide title and year, so tags follow a film across
+/// airings and sessions.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct TitleTags {
+    by_title: BTreeMap<String, BTreeSet<String>>,
+    /// Tag manager window state.
+    pub manager_open: bool,
+    rename_from: Option<String>,
+    rename_to: String,
+    /// Tag typed in the detail panel.
+    pub new_tag: String,
+}
+
+impl TitleTags {
+    fn parse(text: &str) -> Self {
+        let mut tags = Self::default();
+        for line in text.lines() {
+            let mut parts = line.trim_end_matches('\r').split('\t');
+            let Some(key) = parts.next().filter(|k| !k.is_empty()) else {
+                continue;
+            };
+            let set: BTreeSet<String> = parts.filter_map(clean_tag).collect();
+            if !set.is_empty() {
+                tags.by_title.insert(key.to_string(), set);
+            }
+        }
+        tags
+    }
+
+    fn to_text(&self) -> String {
+        self.by_title
+            .iter()
+            .map(|(key, set)| {
+                let tags: Vec<&str> = set.iter().map(String::as_str).collect();
+                format!("{key}\t{}\n", tags.join("\t"))
+            })
+            .collect()
+    }
+
+    pub fn load() -> Self {
+        fs::read_to_string(tags_path())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Err(err) = fs::write(tags_path(), self.to_text()) {
+            warn!("Failed to write {}: {err}", tags_path().display());
+        }
+    }
+
+    pub fn tags_for(&self, title_key: &str) -> Option<&BTreeSet<String>> {
+        self.by_title.get(title_key)
+    }
+
+    /// Every tag in use with the number of titles carrying it.
+    pub fn counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for tag in self.by_title.values().flatten() {
+            *counts.entry(tag.clone()).or_default() += 1;
+        }
+        counts
+    }
+
+    fn add(&mut self, title_key: &str, tag: &str) {
+        if let Some(tag) = clean_tag(tag) {
+            self.by_title
+                .entry(title_key.to_string())
+                .or_default()
+                .insert(tag);
+        }
+    }
+
+    fn remove(&mut self, title_key: &str, tag: &str) {
+        if let Some(set) = self.by_title.get_mut(title_key) {
+            set.remove(tag);
+            if set.is_empty() {
+                self.by_title.remove(title_key);
+            }
+        }
+    }
+
+    /// Rename `from` on every title (merging into `to` where both exist); None deletes it.
+    fn retag(&mut self, from: &str, to: Option<&str>) {
+        let to = to.and_then(clean_tag);
+        for set in self.by_title.values_mut() {
+            if set.remove(from) {
+                if let Some(to) = &to {
+                    set.insert(to.clone());
+                }
+            }
+        }
+        self.by_title.retain(|_, set| !set.is_empty());
+    }
+}
+
+impl crate::app::PexApp {
+    /// Whether the row carries any of `wanted`.
+    pub(crate) fn row_has_any_tag(
+        &self,
+        row: &crate::app::PosterRow,
+        wanted: &BTreeSet<String>,
+    ) -> bool {
+        self.title_tags
+            .tags_for(&Self::make_owned_key(&row.title, row.year))
+            .is_some_and(|set| !set.is_disjoint(wanted))
+    }
+
+    /// Detail-panel tag chips (click ✕ to remove) and a box to add one.
+    pub(crate) fn ui_render_title_tags(&mut self, ui: &mut eg::Ui, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let key = Self::make_owned_key(&row.title, row.year);
+        let current: Vec<String> = self
+            .title_tags
+            .tags_for(&key)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default();
+        let known: Vec<String> = self
+            .title_tags
+            .counts()
+            .into_keys()
+            .filter(|tag| !current.contains(tag))
+            .collect();
+        let mut remove: Option<String> = None;
+        let mut add: Option<String> = None;
+        ui.add_space(6.0);
+        ui.horizontal_wrapped(|ui| {
+            ui.label(eg::RichText::new("Tags").weak());
+            for tag in &current {
+                if ui
+                    .small_button(format!("{tag} ✕"))
+                    .on_hover_text("Remove this tag")
+                    .clicked()
+                {
+                    remove = Some(tag.clone());
+                }
+            }
+            let edit = ui.add(
+                eg::TextEdit::singleline(&mut self.title_tags.new_tag)
+                    .hint_text("Add tag")
+                    .desired_width(90.0),
+            );
+            if edit.lost_focus() && ui.input(|i| i.key_pressed(eg::Key::Enter)) {
+                add = Some(self.title_tags.new_tag.clone());
+            }
+            if !known.is_empty() {
+                ui.menu_button("▾", |ui| {
+                    for tag in &known {
+                        if ui.button(tag).clicked() {
+                            add = Some(tag.clone());
+                            ui.close_menu();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Add a tag you already use");
+            }
+        });
+        if let Some(tag) = remove {
+            self.title_tags.remove(&key, &tag);
+            self.title_tags.save();
+        }
+        if let Some(tag) = add {
+            self.title_tags.add(&key, &tag);
+            self.title_tags.new_tag.clear();
+            self.title_tags.save();
+        }
+    }
+
+    /// Filters ▸ Manage tags…: every tag with its title count, to rename or delete.
+    pub(crate) fn ui_render_tag_manager(&mut self, ctx: &eg::Context) {
+        if !self.title_tags.manager_open {
+            return;
+        }
+        let counts = self.title_tags.counts();
+        let mut open = true;
+        let mut rename: Option<(String, String)> = None;
+        let mut delete: Option<String> = None;
+        eg::Window::new("Tags")
+            .collapsible(false)
+            .default_width(320.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if counts.is_empty() {
+                    ui.label("No tags yet. Add them to a film in the detail panel.");
+                    return;
+                }
+                eg::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    eg::Grid::new("tag_manager_grid")
+                        .num_columns(3)
+                        .spacing([10.0, 4.0])
+                        .show(ui, |ui| {
+                            for (tag, count) in &counts {
+                                let tags = &mut self.title_tags;
+                                if tags.rename_from.as_deref() == Some(tag.as_str()) {
+                                    let edit = ui.add(
+                                        eg::TextEdit::singleline(&mut tags.rename_to)
+                                            .desired_width(140.0),
+                                    );
+                                    if edit.lost_focus()
+                                        && ui.input(|i| i.key_pressed(eg::Key::Enter))
+                                    {
+                                        rename = Some((tag.clone(), tags.rename_to.clone()));
+                                    }
+                                } else {
+                                    ui.label(tag);
+                                }
+                                ui.label(eg::RichText::new(format!("{count} titles")).weak());
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("Rename").clicked() {
+                                        tags.rename_from = Some(tag.clone());
+                                        tags.rename_to = tag.clone();
+                                    }
+                                    if ui
+                                        .small_button("Delete")
+                                        .on_hover_text("Remove this tag from every title")
+                                        .clicked()
+                                    {
+                                        delete = Some(tag.clone());
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        if let Some((from, to)) = rename {
+            self.title_tags.retag(&from, Some(&to));
+            self.title_tags.rename_from = None;
+            self.title_tags.save();
+            if self.selected_tags.remove(&from) {
+                if let Some(to) = clean_tag(&to) {
+                    self.selected_tags.insert(to);
+                }
+                self.mark_dirty();
+            }
+        }
+        if let Some(tag) = delete {
+            self.title_tags.retag(&tag, None);
+            self.title_tags.save();
+            if self.selected_tags.remove(&tag) {
+                self.mark_dirty();
+            }
+        }
+        self.title_tags.manager_open = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_round_trip_and_rename_merges() {
+        let mut tags = TitleTags::parse(
+            "heat:1995\tnoir  marathon\tcrime\nelf:2003\tChristmas\tcrime\n\tjunk\n",
+        );
+        assert!(tags
+            .tags_for("heat:1995")
+            .unwrap()
+            .contains("noir marathon"));
+        assert_eq!(tags.counts().get("crime"), Some(&2));
+        assert_eq!(TitleTags::parse(&tags.to_text()).by_title, tags.by_title);
+
+        tags.remove("elf:2003", "crime");
+        tags.add("elf:2003", " kids ");
+        tags.retag("crime", Some("noir marathon"));
+        assert_eq!(tags.tags_for("heat:1995").map(|s| s.len()), Some(1));
+        assert_eq!(tags.counts().get("kids"), Some(&1));
+        tags.retag("kids", None);
+        tags.retag("Christmas", None);
+        assert_eq!(tags.tags_for("elf:2003"), None);
+        assert_eq!(clean_tag(" , \t"), None);
+        assert_eq!(
+            clean_tag(" Noir,  Marathon ").as_deref(),
+            Some("Noir Marathon")
+        );
+    }
+}
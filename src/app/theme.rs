@@ -0,0 +1,436 @@
+// src/app/theme.rs
+use std::str::FromStr;
+
+use eframe::egui as eg;
+use eg::Color32;
+
+/// Zoom limits for Advanced ▸ Appearance and the zoom shortcuts.
+pub(crate) const UI_SCALE_MIN: f32 = 0.5;
+pub(crate) const UI_SCALE_MAX: f32 = 3.0;
+pub(crate) const UI_SCALE_STEP: f32 = 0.1;
+
+/// Base egui visuals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+        }
+    }
+}
+
+impl FromStr for ThemeMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Colours the views paint with, so cards, chips and outlines follow the theme.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Palette {
+    /// Outline of the selected card.
+    pub selection: Color32,
+    /// Outline of the airing pinned for compare.
+    pub compare: Color32,
+    /// REC chips, "Scheduled to record" text and the timeline's REC outline.
+    pub rec: Color32,
+    pub rec_stroke: Color32,
+    /// NEW pill and Premiere chip.
+    pub new_badge: Color32,
+    /// Text on filled badges.
+    pub on_badge: Color32,
+    pub on_accent: Color32,
+    pub rating_good: Color32,
+    pub rating_ok: Color32,
+    pub rating_bad: Color32,
+    /// Poster placeholder when no swatch is known.
+    pub placeholder: Color32,
+    /// Timeline airing blocks.
+    pub block: Color32,
+    pub block_hover: Color32,
+    pub new_in_guide: Color32,
+    /// Good news: better side of a compare, owned HD, audience score.
+    pub positive: Color32,
+    /// Caution: low bitrate, critics score.
+    pub warning: Color32,
+    /// Errors and the timeline's "now" line.
+    pub error: Color32,
+    /// Neutral highlight: airing HD, owned 4K.
+    pub info: Color32,
+    /// Width of the selection / compare outlines.
+    pub outline_width: f32,
+    /// Rim around filled badges; transparent outside high contrast.
+    pub badge_outline: Color32,
+    /// Hatch owned / watched cards instead of darkening them.
+    pub hatch_dimmed: bool,
+    /// Marker behind the parts of a title the search matched.
+    pub search_hit: Color32,
+}
+
+/// Readable text colour on `fill`.
+fn contrast_text(fill: Color32) -> Color32 {
+    let [r, g, b, _] = fill.to_array();
+    let luma = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+    if luma > 150.0 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
+fn darken(color: Color32) -> Color32 {
+    let [r, g, b, _] = color.to_array();
+    let f = |c: u8| (f32::from(c) * 0.65) as u8;
+    Color32::from_rgb(f(r), f(g), f(b))
+}
+
+/// `#rrggbb` (the `#` is optional).
+pub(crate) fn parse_hex_color(text: &str) -> Option<Color32> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+pub(crate) fn hex_color(color: Color32) -> String {
+    let [r, g, b, _] = color.to_array();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Dark or light visuals, optionally with one accent colour for selection outlines,
+/// NEW / REC badges and egui's own selection highlight. Saved with the UI prefs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Theme {
+    pub mode: ThemeMode,
+    /// None keeps the classic colours (yellow selection, red REC, blue NEW).
+    pub accent: Option<Color32>,
+    /// Hatching instead of dimming, darker badge fills with a rim, thicker outlines.
+    pub high_contrast: bool,
+}
+
+impl Theme {
+    pub fn palette(self) -> Palette {
+        let mut palette = match self.mode {
+            ThemeMode::Dark => Palette {
+                selection: Color32::YELLOW,
+                compare: Color32::from_rgb(90, 200, 230),
+                rec: Color32::from_rgb(200, 40, 40),
+                rec_stroke: Color32::from_rgb(140, 16, 16),
+                new_badge: Color32::from_rgb(30, 110, 200),
+                on_badge: Color32::WHITE,
+                on_accent: Color32::WHITE,
+                rating_good: Color32::from_rgb(40, 140, 60),
+                rating_ok: Color32::from_rgb(200, 140, 30),
+                rating_bad: Color32::from_rgb(170, 50, 50),
+                placeholder: Color32::from_gray(40),
+                block: Color32::from_gray(52),
+                block_hover: Color32::from_gray(70),
+                new_in_guide: Color32::from_rgb(110, 210, 120),
+                positive: Color32::from_rgb(130, 200, 130),
+                warning: Color32::from_rgb(230, 170, 40),
+                error: Color32::from_rgb(220, 80, 80),
+                info: Color32::from_rgb(120, 180, 255),
+                outline_width: 2.0,
+                badge_outline: Color32::TRANSPARENT,
+                hatch_dimmed: false,
+                search_hit: Color32::from_rgb(250, 210, 40),
+            },
+            ThemeMode::Light => Palette {
+                selection: Color32::from_rgb(230, 150, 0),
+                compare: Color32::from_rgb(0, 140, 180),
+                rec: Color32::from_rgb(200, 40, 40),
+                rec_stroke: Color32::from_rgb(140, 16, 16),
+                new_badge: Color32::from_rgb(30, 110, 200),
+                on_badge: Color32::WHITE,
+                on_accent: Color32::WHITE,
+                rating_good: Color32::from_rgb(40, 140, 60),
+                rating_ok: Color32::from_rgb(200, 140, 30),
+                rating_bad: Color32::from_rgb(170, 50, 50),
+                placeholder: Color32::from_gray(200),
+                block: Color32::from_gray(222),
+                block_hover: Color32::from_gray(200),
+                new_in_guide: Color32::from_rgb(40, 160, 60),
+                positive: Color32::from_rgb(30, 130, 50),
+                warning: Color32::from_rgb(170, 100, 0),
+                error: Color32::from_rgb(190, 40, 40),
+                info: Color32::from_rgb(30, 100, 200),
+                outline_width: 2.0,
+                badge_outline: Color32::TRANSPARENT,
+                hatch_dimmed: false,
+                search_hit: Color32::from_rgb(255, 225, 90),
+            },
+        };
+        if let Some(accent) = self.accent {
+            palette.selection = accent;
+            palette.rec = accent;
+            palette.rec_stroke = darken(accent);
+            palette.new_badge = accent;
+            palette.on_accent = contrast_text(accent);
+        }
+        if self.high_contrast {
+            // White text on every fill clears 4.5:1, and the rim separates badges from posters.
+            palette.rating_good = Color32::from_rgb(0, 100, 30);
+            palette.rating_ok = Color32::from_rgb(150, 80, 0);
+            palette.rating_bad = Color32::from_rgb(150, 20, 20);
+            palette.new_badge = darken(palette.new_badge);
+            palette.rec = darken(palette.rec);
+            palette.on_accent = contrast_text(palette.new_badge);
+            palette.badge_outline = match self.mode {
+                ThemeMode::Dark => Color32::WHITE,
+                ThemeMode::Light => Color32::BLACK,
+            };
+            palette.rec_stroke = palette.badge_outline;
+            palette.outline_width = 4.0;
+            palette.hatch_dimmed = true;
+        }
+        palette
+    }
+
+    pub fn visuals(self) -> eg::Visuals {
+        let mut visuals = match self.mode {
+            ThemeMode::Dark => eg::Visuals::dark(),
+            ThemeMode::Light => eg::Visuals::light(),
+        };
+        if let Some(accent) = self.accent {
+            visuals.selection.bg_fill = accent;
+            visuals.selection.stroke.color = contrast_text(accent);
+            visuals.hyperlink_color = accent;
+        }
+        if self.high_contrast {
+            let strong = match self.mode {
+                ThemeMode::Dark => Color32::WHITE,
+                ThemeMode::Light => Color32::BLACK,
+            };
+            visuals.widgets.noninteractive.fg_stroke.color = strong;
+            visuals.widgets.inactive.fg_stroke.color = strong;
+            for widget in [
+                &mut visuals.widgets.inactive,
+                &mut visuals.widgets.hovered,
+                &mut visuals.widgets.active,
+                &mut visuals.widgets.open,
+            ] {
+                widget.bg_stroke = eg::Stroke::new(widget.bg_stroke.width.max(1.5), strong);
+            }
+            visuals.selection.stroke.width = 2.0;
+            visuals.window_stroke = eg::Stroke::new(1.5, strong);
+        }
+        visuals
+    }
+}
+
+/// `text` in `font`, with the byte `ranges` a search matched marked in `palette.search_hit`.
+/// Ranges must be sorted by start; overlaps are fine.
+pub(crate) fn highlight_job(
+    text: &str,
+    ranges: &[std::ops::Range<usize>],
+    font: eg::FontId,
+    color: Color32,
+    palette: &Palette,
+) -> eg::text::LayoutJob {
+    let plain = eg::TextFormat::simple(font, color);
+    let marked = eg::TextFormat {
+        background: palette.search_hit,
+        color: contrast_text(palette.search_hit),
+        ..plain.clone()
+    };
+    let mut job = eg::text::LayoutJob::default();
+    let mut at = 0;
+    for range in ranges {
+        let start = range.start.max(at);
+        let end = range.end.min(text.len());
+        if start >= end {
+            continue;
+        }
+        job.append(&text[at..start], 0.0, plain.clone());
+        job.append(&text[start..end], 0.0, marked.clone());
+        at = end;
+    }
+    job.append(&text[at..], 0.0, plain);
+    job
+}
+
+/// Mark an owned / watched poster or timeline block: a black overlay of `strength` alpha, or
+/// in high contrast diagonal black-and-white hatching plus a rim, which can't be mistaken
+/// for a poster that failed to load.
+pub(crate) fn paint_dimmed(
+    painter: &eg::Painter,
+    rect: eg::Rect,
+    rounding: f32,
+    strength: f32,
+    palette: &Palette,
+) {
+    if !palette.hatch_dimmed {
+        let alpha = (strength.clamp(0.10, 0.90) * 255.0) as u8;
+        painter.rect_filled(rect, rounding, Color32::from_black_alpha(alpha));
+        return;
+    }
+    let hatch = painter.with_clip_rect(rect.intersect(painter.clip_rect()));
+    let step = 12.0;
+    let mut x = rect.left() - rect.height();
+    while x < rect.right() {
+        let a = eg::pos2(x, rect.bottom());
+        let b = eg::pos2(x + rect.height(), rect.top());
+        hatch.line_segment([a, b], eg::Stroke::new(4.0, Color32::BLACK));
+        hatch.line_segment([a, b], eg::Stroke::new(1.5, Color32::WHITE));
+        x += step;
+    }
+    painter.rect_stroke(rect, rounding, eg::Stroke::new(2.0, palette.badge_outline));
+}
+
+impl crate::app::PexApp {
+    pub(crate) fn palette(&self) -> Palette {
+        self.theme.palette()
+    }
+
+    /// Push the theme into egui when it changed since the last frame.
+    pub(crate) fn apply_theme(&mut self, ctx: &eg::Context) {
+        if self.applied_theme != Some(self.theme) {
+            ctx.set_visuals(self.theme.visuals());
+            self.applied_theme = Some(self.theme);
+        }
+    }
+
+    /// Clamp to the zoom range and round to whole steps so repeated Ctrl+= doesn't drift.
+    pub(crate) fn set_ui_scale(&mut self, scale: f32) {
+        let scale = (scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX) * 10.0).round() / 10.0;
+        if (scale - self.ui_scale).abs() > f32::EPSILON {
+            self.ui_scale = scale;
+            self.mark_dirty();
+        }
+    }
+
+    /// Zoom egui (and with it `pixels_per_point`) to the UI scale. Card sizes are in points, so
+    /// they grow with it, and poster variant upgrades follow the new physical width.
+    pub(crate) fn apply_ui_scale(&self, ctx: &eg::Context) {
+        if (ctx.zoom_factor() - self.ui_scale).abs() > f32::EPSILON {
+            ctx.set_zoom_factor(self.ui_scale);
+        }
+    }
+
+    /// Advanced ▸ Appearance: dark/light and the accent colour.
+    pub(crate) fn advanced_theme_controls(&mut self, ui: &mut eg::Ui) {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Theme:");
+            for mode in [ThemeMode::Dark, ThemeMode::Light] {
+                changed |= ui
+                    .selectable_value(&mut self.theme.mode, mode, mode.label())
+                    .changed();
+            }
+        });
+        changed |= ui
+            .checkbox(&mut self.theme.high_contrast, "High contrast")
+            .on_hover_text(
+                "Hatch owned and watched cards instead of darkening them, darker badges with a \
+                 rim, thicker selection outlines and bordered buttons",
+            )
+            .changed();
+        ui.horizontal(|ui| {
+            let mut custom = self.theme.accent.is_some();
+            if ui
+                .checkbox(&mut custom, "Accent colour")
+                .on_hover_text(
+                    "One colour for the selection outline, NEW and REC badges and highlighted \
+                     text; off keeps the classic yellow, blue and red",
+                )
+                .changed()
+            {
+                self.theme.accent = custom.then_some(Color32::from_rgb(0, 170, 190));
+                changed = true;
+            }
+            if let Some(accent) = &mut self.theme.accent {
+                changed |= ui.color_edit_button_srgba(accent).changed();
+                ui.label(eg::RichText::new(hex_color(*accent)).monospace().weak());
+            }
+        });
+        ui.horizontal(|ui| {
+            // Buttons rather than a slider: the slider would move under the pointer as it zooms.
+            ui.label("UI scale:");
+            let zoom_out = ui.add_enabled(self.ui_scale > UI_SCALE_MIN, eg::Button::new("−"));
+            zoom_out.widget_info(|| {
+                eg::WidgetInfo::labeled(eg::WidgetType::Button, zoom_out.enabled(), "Zoom out")
+            });
+            if zoom_out.clicked() {
+                self.set_ui_scale(self.ui_scale - UI_SCALE_STEP);
+            }
+            ui.label(eg::RichText::new(format!("{:.0}%", self.ui_scale * 100.0)).monospace());
+            let zoom_in = ui.add_enabled(self.ui_scale < UI_SCALE_MAX, eg::Button::new("+"));
+            zoom_in.widget_info(|| {
+                eg::WidgetInfo::labeled(eg::WidgetType::Button, zoom_in.enabled(), "Zoom in")
+            });
+            if zoom_in.clicked() {
+                self.set_ui_scale(self.ui_scale + UI_SCALE_STEP);
+            }
+            if ui
+                .add_enabled(
+                    (self.ui_scale - 1.0).abs() > f32::EPSILON,
+                    eg::Button::new("Reset"),
+                )
+                .on_hover_text("Also Ctrl+= / Ctrl+- / Ctrl+0 (see Keyboard shortcuts)")
+                .clicked()
+            {
+                self.set_ui_scale(1.0);
+            }
+        });
+        if changed {
+            self.mark_dirty();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accent_round_trips_and_recolours_the_palette() {
+        let accent = parse_hex_color("#00aabb").unwrap();
+        assert_eq!(hex_color(accent), "#00aabb");
+        assert_eq!(parse_hex_color("00AABB"), Some(accent));
+        assert_eq!(parse_hex_color("#abc"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+
+        let classic = Theme::default().palette();
+        let themed = Theme {
+            mode: ThemeMode::Dark,
+            accent: Some(Color32::from_rgb(250, 220, 60)),
+            high_contrast: false,
+        }
+        .palette();
+        assert_eq!(themed.selection, themed.rec);
+        assert_eq!(themed.on_accent, Color32::BLACK);
+        assert_ne!(classic.rec, classic.new_badge);
+        assert_eq!(ThemeMode::from_str("light"), Ok(ThemeMode::Light));
+
+        let contrast = Theme {
+            high_contrast: true,
+            ..Theme::default()
+        }
+        .palette();
+        assert!(contrast.hatch_dimmed && !classic.hatch_dimmed);
+        assert!(contrast.outline_width > classic.outline_width);
+        assert_ne!(contrast.badge_outline, Color32::TRANSPARENT);
+    }
+}
@@ -0,0 +1,812 @@
+// src/app/trakt.rs
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use eframe::egui as eg;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::app::owned::nfo;
+use crate::config::AppConfig;
+
+const TRAKT_API: &str = "https://api.trakt.tv";
+const TOKEN_FILE: &str = "trakt_token.json";
+/// Trakt's red, used for the watched tick and the detail-panel chips.
+pub(crate) const TRAKT_COLOR: eg::Color32 = eg::Color32::from_rgb(237, 34, 36);
+/// Refresh the access token when it has less than this left.
+const REFRESH_MARGIN_SECS: i64 = 24 * 3600;
+/// Gap between watchlist writes sent in one batch.
+const WRITE_INTERVAL: Duration = Duration::from_millis(1100);
+
+fn token_path() -> PathBuf {
+    crate::app::cache::cache_dir().join(TOKEN_FILE)
+}
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |dur| dur.as_secs() as i64)
+}
+
+/// Trakt API app (`trakt_client_id` + `trakt_client_secret`).
+#[derive(Clone, Debug)]
+struct TraktApp {
+    client_id: String,
+    client_secret: String,
+}
+
+impl TraktApp {
+    fn from_config(cfg: &AppConfig) -> Option<Self> {
+        Some(Self {
+            client_id: cfg.trakt_client_id.clone()?,
+            client_secret: cfg.trakt_client_secret.clone()?,
+        })
+    }
+}
+
+/// The linked account's tokens, kept in `<cache>/trakt_token.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct TraktToken {
+    access_token: String,
+    refresh_token: String,
+    expires_at: i64,
+}
+
+impl TraktToken {
+    fn load() -> Option<Self> {
+        let text = fs::read_to_string(token_path()).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    fn save(&self) {
+        let text = serde_json::to_string_pretty(self).unwrap_or_default();
+        if let Err(err) = fs::write(token_path(), text) {
+            warn!("Failed to write {}: {err}", token_path().display());
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    created_at: i64,
+    expires_in: i64,
+}
+
+impl From<TokenResponse> for TraktToken {
+    fn from(resp: TokenResponse) -> Self {
+        Self {
+            access_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            expires_at: resp.created_at + resp.expires_in,
+        }
+    }
+}
+
+/// What the user types at `verification_url` to link the account.
+#[derive(Clone, Debug, Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_url: String,
+    expires_in: u64,
+    interval: u64,
+}
+
+#[derive(Default, Deserialize)]
+struct TraktIds {
+    imdb: Option<String>,
+    tmdb: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TraktMovie {
+    #[serde(default)]
+    title: String,
+    year: Option<i32>,
+    #[serde(default)]
+    ids: TraktIds,
+}
+
+/// One entry of `/sync/watched/movies` or `/sync/watchlist/movies`.
+#[derive(Deserialize)]
+struct MovieItem {
+    movie: TraktMovie,
+}
+
+/// Id keys (`imdb:` / `tmdb:`) and title keys of every listed film, matched like Radarr's.
+fn movie_keys(items: &[MovieItem]) -> HashSet<String> {
+    let mut keys = HashSet::new();
+    for movie in items.iter().map(|item| &item.movie) {
+        let tmdb = movie.ids.tmdb.filter(|&id| id > 0).map(|id| id.to_string());
+        keys.extend(nfo::id_keys(
+            movie.ids.imdb.as_deref().filter(|id| !id.is_empty()),
+            tmdb.as_deref(),
+        ));
+        if !movie.title.trim().is_empty() {
+            keys.extend(crate::app::PexApp::owned_key_variants(
+                &movie.title,
+                movie.year,
+            ));
+        }
+    }
+    keys
+}
+
+/// `POST /sync/watchlist` body for one film.
+fn watchlist_body(job: &WatchlistJob) -> Value {
+    let mut ids = serde_json::Map::new();
+    if let Some(imdb) = &job.imdb {
+        ids.insert("imdb".into(), Value::from(imdb.as_str()));
+    }
+    if let Some(tmdb) = job.tmdb.as_deref().and_then(|id| id.parse::<u64>().ok()) {
+        ids.insert("tmdb".into(), Value::from(tmdb));
+    }
+    serde_json::json!({
+        "movies": [{ "title": job.title, "year": job.year, "ids": ids }]
+    })
+}
+
+/// What the films Trakt knows about look like after a sync.
+struct TraktLibrary {
+    /// The token used, refreshed if it was about to expire.
+    token: TraktToken,
+    watched: HashSet<String>,
+    watchlist: HashSet<String>,
+}
+
+enum TraktMsg {
+    /// Device code to show while the user approves Pex on trakt.tv.
+    DeviceCode(DeviceCode),
+    Linked(Result<TraktToken, String>),
+    Library(Result<TraktLibrary, String>),
+    /// An "Add to Trakt watchlist" (or a removal) finished for the row with this poster key.
+    Listed {
+        key: String,
+        remove: bool,
+        result: Result<(), String>,
+    },
+}
+
+#[derive(Clone, Debug)]
+enum ListState {
+    Pending,
+    Added,
+    Failed(String),
+}
+
+/// The linked Trakt account: watched films, the watchlist and adds from the detail panel.
+#[derive(Default)]
+pub(crate) struct TraktState {
+    app: Option<TraktApp>,
+    token: Option<TraktToken>,
+    watched: HashSet<String>,
+    watchlist: HashSet<String>,
+    /// Shown in Advanced while a link is waiting for approval.
+    device: Option<(DeviceCode, Instant)>,
+    linking: bool,
+    syncing: bool,
+    adds: HashMap<String, ListState>,
+    error: Option<String>,
+    tx: Option<Sender<TraktMsg>>,
+    rx: Option<Receiver<TraktMsg>>,
+}
+
+impl TraktState {
+    pub fn configure(&mut self, cfg: &AppConfig) {
+        self.app = TraktApp::from_config(cfg);
+        if self.app.is_some() {
+            if self.token.is_none() {
+                self.token = TraktToken::load();
+            }
+        } else {
+            self.token = None;
+            self.watched.clear();
+            self.watchlist.clear();
+            self.device = None;
+            self.error = None;
+        }
+    }
+
+    const fn is_linked(&self) -> bool {
+        self.app.is_some() && self.token.is_some()
+    }
+
+    fn sender(&mut self) -> Sender<TraktMsg> {
+        if let Some(tx) = &self.tx {
+            return tx.clone();
+        }
+        let (tx, rx) = mpsc::channel();
+        self.tx = Some(tx.clone());
+        self.rx = Some(rx);
+        tx
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(
+    resp: reqwest::blocking::Response,
+    what: &str,
+) -> Result<T, String> {
+    let body = resp.text().map_err(|err| format!("read: {err}"))?;
+    serde_json::from_str(&body).map_err(|err| format!("parse {what}: {err}"))
+}
+
+struct Trakt {
+    client: reqwest::blocking::Client,
+    app: TraktApp,
+}
+
+impl Trakt {
+    fn new(app: TraktApp) -> Result<Self, String> {
+        let client = crate::app::net::client_builder()
+            .user_agent("pex/trakt")
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|err| format!("client: {err}"))?;
+        Ok(Self { client, app })
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        token: Option<&TraktToken>,
+    ) -> reqwest::blocking::RequestBuilder {
+        let mut req = self
+            .client
+            .request(method, format!("{TRAKT_API}/{path}"))
+            .header("Content-Type", "application/json")
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", &self.app.client_id);
+        if let Some(token) = token {
+            req = req.bearer_auth(&token.access_token);
+        }
+        req
+    }
+
+    fn post(
+        &self,
+        path: &str,
+        body: &Value,
+        token: Option<&TraktToken>,
+    ) -> Result<reqwest::blocking::Response, String> {
+        crate::app::net::track(
+            self.request(reqwest::Method::POST, path, token)
+                .body(body.to_string())
+                .send(),
+        )
+        .map_err(|err| format!("network: {err}"))
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        token: &TraktToken,
+    ) -> Result<T, String> {
+        let resp =
+            crate::app::net::track(self.request(reqwest::Method::GET, path, Some(token)).send())
+                .map_err(|err| format!("network: {err}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("{path}: HTTP {}", resp.status()));
+        }
+        read_json(resp, path)
+    }
+
+    fn device_code(&self) -> Result<DeviceCode, String> {
+        let resp = self.post(
+            "oauth/device/code",
+            &serde_json::json!({ "client_id": self.app.client_id }),
+            None,
+        )?;
+        if !resp.status().is_success() {
+            return Err(format!("device code: HTTP {}", resp.status()));
+        }
+        read_json(resp, "device code")
+    }
+
+    /// Poll until the user approves (or refuses) the code on trakt.tv.
+    fn await_approval(&self, code: &DeviceCode) -> Result<TraktToken, String> {
+        let body = serde_json::json!({
+            "code": code.device_code,
+            "client_id": self.app.client_id,
+            "client_secret": self.app.client_secret,
+        });
+        let deadline = Instant::now() + Duration::from_secs(code.expires_in);
+        let mut interval = Duration::from_secs(code.interval.max(1));
+        while Instant::now() < deadline {
+            std::thread::sleep(interval);
+            let resp = self.post("oauth/device/token", &body, None)?;
+            match resp.status().as_u16() {
+                200 => {
+                    return read_json::<TokenResponse>(resp, "token").map(TraktToken::from);
+                }
+                400 => {}
+                429 => interval += Duration::from_secs(1),
+                404 => return Err("Trakt did not recognise the code".into()),
+                409 => return Err("the code was already used".into()),
+                410 => return Err("the code expired; link again".into()),
+                418 => return Err("access was denied on trakt.tv".into()),
+                status => return Err(format!("device token: HTTP {status}")),
+            }
+        }
+        Err("the code expired; link again".into())
+    }
+
+    fn refresh(&self, token: &TraktToken) -> Result<TraktToken, String> {
+        let resp = self.post(
+            "oauth/token",
+            &serde_json::json!({
+                "refresh_token": token.refresh_token,
+                "client_id": self.app.client_id,
+                "client_secret": self.app.client_secret,
+                "redirect_uri": "urn:ietf:wg:oauth:2.0:oob",
+                "grant_type": "refresh_token",
+            }),
+            None,
+        )?;
+        if !resp.status().is_success() {
+            return Err(format!(
+                "token refresh: HTTP {} (link the account again)",
+                resp.status()
+            ));
+        }
+        read_json::<TokenResponse>(resp, "token").map(TraktToken::from)
+    }
+
+    fn library(&self, token: TraktToken) -> Result<TraktLibrary, String> {
+        let token = if token.expires_at - REFRESH_MARGIN_SECS < now_unix() {
+            self.refresh(&token)?
+        } else {
+            token
+        };
+        let watched: Vec<MovieItem> = self.get("sync/watched/movies", &token)?;
+        let watchlist: Vec<MovieItem> = self.get("sync/watchlist/movies", &token)?;
+        Ok(TraktLibrary {
+            watched: movie_keys(&watched),
+            watchlist: movie_keys(&watchlist),
+            token,
+        })
+    }
+
+    fn update_watchlist(&self, job: &WatchlistJob) -> Result<(), String> {
+        let path = if job.remove {
+            "sync/watchlist/remove"
+        } else {
+            "sync/watchlist"
+        };
+        let resp = self.post(path, &watchlist_body(job), Some(&job.token))?;
+        if !resp.status().is_success() {
+            return Err(format!("watchlist: HTTP {}", resp.status()));
+        }
+        Ok(())
+    }
+}
+
+struct WatchlistJob {
+    key: String,
+    title: String,
+    year: Option<i32>,
+    imdb: Option<String>,
+    tmdb: Option<String>,
+    token: TraktToken,
+    remove: bool,
+}
+
+impl crate::app::PexApp {
+    /// Start linking a Trakt account with the device code flow.
+    fn link_trakt(&mut self) {
+        let Some(app) = self.trakt.app.clone() else {
+            return;
+        };
+        if self.trakt.linking || self.is_offline() {
+            return;
+        }
+        self.trakt.linking = true;
+        self.trakt.error = None;
+        let tx = self.trakt.sender();
+        std::thread::spawn(move || {
+            let result = Trakt::new(app).and_then(|trakt| {
+                let code = trakt.device_code()?;
+                let _ = tx.send(TraktMsg::DeviceCode(code.clone()));
+                trakt.await_approval(&code)
+            });
+            let _ = tx.send(TraktMsg::Linked(result));
+        });
+    }
+
+    fn unlink_trakt(&mut self) {
+        if let Err(err) = fs::remove_file(token_path()) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove {}: {err}", token_path().display());
+            }
+        }
+        self.trakt.token = None;
+        self.trakt.watched.clear();
+        self.trakt.watchlist.clear();
+        self.trakt.adds.clear();
+        self.apply_trakt_flags_from(0);
+        self.set_status("Unlinked the Trakt account.");
+    }
+
+    /// Fetch the linked account's watched films and watchlist in the background.
+    pub(crate) fn sync_trakt(&mut self) {
+        let (Some(app), Some(token)) = (self.trakt.app.clone(), self.trakt.token.clone()) else {
+            return;
+        };
+        if self.trakt.syncing || self.is_offline() || self.demo_mode {
+            return;
+        }
+        self.trakt.syncing = true;
+        let tx = self.trakt.sender();
+        std::thread::spawn(move || {
+            let result = Trakt::new(app).and_then(|trakt| trakt.library(token));
+            let _ = tx.send(TraktMsg::Library(result));
+        });
+    }
+
+    pub(crate) fn poll_trakt(&mut self) {
+        let mut flags_changed = false;
+        let mut linked = false;
+        while let Some(rx) = self.trakt.rx.as_ref() {
+            match rx.try_recv() {
+                Ok(TraktMsg::DeviceCode(code)) => self.trakt.device = Some((code, Instant::now())),
+                Ok(TraktMsg::Linked(result)) => {
+                    self.trakt.linking = false;
+                    self.trakt.device = None;
+                    match result {
+                        Ok(token) => {
+                            token.save();
+                            self.trakt.token = Some(token);
+                            self.set_status("Linked the Trakt account.");
+                            linked = true;
+                        }
+                        Err(err) => {
+                            warn!("Trakt link failed: {err}");
+                            self.trakt.error = Some(err);
+                        }
+                    }
+                }
+                Ok(TraktMsg::Library(result)) => {
+                    self.trakt.syncing = false;
+                    match result {
+                        Ok(library) => {
+                            if self.trakt.token.as_ref() != Some(&library.token) {
+                                library.token.save();
+                                self.trakt.token = Some(library.token);
+                            }
+                            self.trakt.watched = library.watched;
+                            self.trakt.watchlist = library.watchlist;
+                            self.trakt.error = None;
+                            flags_changed = true;
+                        }
+                        Err(err) => {
+                            warn!("Trakt sync failed: {err}");
+                            self.trakt.error = Some(err);
+                        }
+                    }
+                }
+                Ok(TraktMsg::Listed {
+                    key,
+                    remove: true,
+                    result,
+                }) => match result {
+                    Ok(()) => {
+                        if let Some(row) = self.rows.iter().find(|row| row.key == key) {
+                            for row_key in crate::app::radarr::row_keys(row) {
+                                self.trakt.watchlist.remove(&row_key);
+                            }
+                        }
+                        self.trakt.adds.remove(&key);
+                        self.set_status("Removed from the Trakt watchlist.");
+                        flags_changed = true;
+                    }
+                    Err(err) => {
+                        self.set_status(format!("Remove from Trakt watchlist failed: {err}"));
+                        self.trakt.adds.insert(key, ListState::Failed(err));
+                    }
+                },
+                Ok(TraktMsg::Listed { key, result, .. }) => {
+                    let state = match result {
+                        Ok(()) => {
+                            if let Some(row) = self.rows.iter().find(|row| row.key == key) {
+                                self.trakt
+                                    .watchlist
+                                    .extend(crate::app::radarr::row_keys(row));
+                            }
+                            self.set_status("Added to the Trakt watchlist.");
+                            flags_changed = true;
+                            ListState::Added
+                        }
+                        Err(err) => {
+                            self.set_status(format!("Add to Trakt watchlist failed: {err}"));
+                            ListState::Failed(err)
+                        }
+                    };
+                    self.trakt.adds.insert(key, state);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.trakt.tx = None;
+                    self.trakt.rx = None;
+                    break;
+                }
+            }
+        }
+        if linked {
+            self.sync_trakt();
+        }
+        if flags_changed {
+            self.apply_trakt_flags_from(0);
+            self.evaluate_alerts();
+        }
+    }
+
+    /// Mark rows (from `start` on) the Trakt account has watched or wants to watch.
+    pub(crate) fn apply_trakt_flags_from(&mut self, start: usize) {
+        let (watched, watchlist) = (&self.trakt.watched, &self.trakt.watchlist);
+        for row in self.rows.iter_mut().skip(start) {
+            if watched.is_empty() && watchlist.is_empty() {
+                row.watched = false;
+                row.trakt_watchlist = false;
+                continue;
+            }
+            let keys = crate::app::radarr::row_keys(row);
+            row.watched = keys.iter().any(|key| watched.contains(key));
+            row.trakt_watchlist = keys.iter().any(|key| watchlist.contains(key));
+        }
+    }
+
+    /// Add the rows' films to the Trakt watchlist. Rows already on it, or already being added,
+    /// are skipped. False when nothing could be sent (no linked account, or offline).
+    pub(crate) fn add_to_trakt_watchlist(&mut self, idxs: &[usize]) -> bool {
+        self.update_trakt_watchlist(idxs, false)
+    }
+
+    /// W on the selected card: add it to the Trakt watchlist, or take it off again.
+    pub(crate) fn toggle_trakt_watchlist(&mut self, idx: usize) {
+        if !self.trakt.is_linked() {
+            self.set_status("Link a Trakt account in Advanced to use the watchlist.");
+            return;
+        }
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let remove =
+            row.trakt_watchlist || matches!(self.trakt.adds.get(&row.key), Some(ListState::Added));
+        self.update_trakt_watchlist(&[idx], remove);
+    }
+
+    /// Send watchlist adds or removals one request at a time (Trakt allows about one write a
+    /// second). False when the requests couldn't be queued.
+    fn update_trakt_watchlist(&mut self, idxs: &[usize], remove: bool) -> bool {
+        let (Some(app), Some(token)) = (self.trakt.app.clone(), self.trakt.token.clone()) else {
+            return false;
+        };
+        let jobs: Vec<WatchlistJob> = idxs
+            .iter()
+            .filter_map(|&idx| self.rows.get(idx))
+            .filter(|row| match self.trakt.adds.get(&row.key) {
+                Some(ListState::Pending) => false,
+                Some(ListState::Added) => remove,
+                _ => row.trakt_watchlist == remove,
+            })
+            .map(|row| {
+                let guid = row.guid.as_deref();
+                WatchlistJob {
+                    key: row.key.clone(),
+                    title: row.title.clone(),
+                    year: row.year,
+                    imdb: guid.and_then(crate::app::imdb_id_from_guid),
+                    tmdb: guid.and_then(nfo::tmdb_id_from_guid),
+                    token: token.clone(),
+                    remove,
+                }
+            })
+            .collect();
+        if jobs.is_empty() {
+            return true;
+        }
+        if self.is_offline() {
+            return false;
+        }
+        for job in &jobs {
+            self.trakt.adds.insert(job.key.clone(), ListState::Pending);
+        }
+        let tx = self.trakt.sender();
+        std::thread::spawn(move || {
+            for (i, job) in jobs.into_iter().enumerate() {
+                if i > 0 {
+                    std::thread::sleep(WRITE_INTERVAL);
+                }
+                let result = Trakt::new(app.clone()).and_then(|trakt| trakt.update_watchlist(&job));
+                let _ = tx.send(TraktMsg::Listed {
+                    key: job.key,
+                    remove: job.remove,
+                    result,
+                });
+            }
+        });
+        true
+    }
+
+    /// Whether watchlist adds can be sent (an account is linked).
+    pub(crate) const fn trakt_linked(&self) -> bool {
+        self.trakt.is_linked()
+    }
+
+    /// Trakt line for the detail panel: watched / on the watchlist, or an add button.
+    pub(crate) fn ui_render_trakt_actions(&mut self, ui: &mut eg::Ui, idx: usize) {
+        if !self.trakt.is_linked() {
+            return;
+        }
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        let (key, watched, listed) = (row.key.clone(), row.watched, row.trakt_watchlist);
+        let add_state = self.trakt.adds.get(&key).cloned();
+        let mut add = false;
+        let mut remove = false;
+        ui.add_space(6.0);
+        ui.horizontal_wrapped(|ui| {
+            if watched {
+                ui.label(eg::RichText::new("✓ Watched").color(TRAKT_COLOR))
+                    .on_hover_text("In your Trakt watch history");
+            }
+            let busy = matches!(add_state, Some(ListState::Pending));
+            if listed {
+                ui.label(eg::RichText::new("On your Trakt watchlist").color(TRAKT_COLOR));
+                remove = ui
+                    .add_enabled(
+                        !busy && !self.is_offline(),
+                        eg::Button::new("Remove").small(),
+                    )
+                    .on_hover_text("Take it off the Trakt watchlist (W)")
+                    .clicked();
+                if busy {
+                    ui.add(eg::Spinner::new().size(12.0));
+                }
+                return;
+            }
+            add = ui
+                .add_enabled(
+                    !busy && !self.is_offline(),
+                    eg::Button::new("Add to Trakt watchlist"),
+                )
+                .clicked();
+            match &add_state {
+                Some(ListState::Pending) => {
+                    ui.add(eg::Spinner::new().size(12.0));
+                }
+                Some(ListState::Failed(err)) => {
+                    ui.label(eg::RichText::new(err).color(eg::Color32::LIGHT_RED));
+                }
+                Some(ListState::Added) | None => {}
+            }
+        });
+        if add {
+            self.add_to_trakt_watchlist(&[idx]);
+        } else if remove {
+            self.update_trakt_watchlist(&[idx], true);
+        }
+    }
+
+    /// Advanced ▸ Trakt: link or unlink the account and show the last sync.
+    pub(crate) fn advanced_trakt_controls(&mut self, ui: &mut eg::Ui) {
+        ui.label(eg::RichText::new("Trakt").strong());
+        if self.trakt.app.is_none() {
+            ui.label(
+                eg::RichText::new(
+                    "Set trakt_client_id and trakt_client_secret in Settings to link an account.",
+                )
+                .weak(),
+            );
+            return;
+        }
+        if let Some((code, shown)) = &self.trakt.device {
+            let left = code.expires_in.saturating_sub(shown.elapsed().as_secs());
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Open");
+                ui.hyperlink(&code.verification_url);
+                ui.label("and enter");
+                ui.label(eg::RichText::new(&code.user_code).monospace().strong());
+            });
+            ui.label(
+                eg::RichText::new(format!(
+                    "Waiting for approval ({}:{:02} left)…",
+                    left / 60,
+                    left % 60
+                ))
+                .weak(),
+            );
+        } else if self.trakt.token.is_some() {
+            ui.label(
+                eg::RichText::new(format!(
+                    "Linked: {} watched films, {} on the watchlist.",
+                    self.trakt.watched.len(),
+                    self.trakt.watchlist.len()
+                ))
+                .weak(),
+            );
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!self.trakt.syncing, eg::Button::new("Sync now"))
+                    .clicked()
+                {
+                    self.sync_trakt();
+                }
+                if self.trakt.syncing {
+                    ui.add(eg::Spinner::new().size(12.0));
+                }
+                if ui
+                    .button("Unlink")
+                    .on_hover_text("Forget the Trakt token stored in the cache folder")
+                    .clicked()
+                {
+                    self.unlink_trakt();
+                }
+            });
+        } else if ui
+            .add_enabled(
+                !self.trakt.linking && !self.is_offline(),
+                eg::Button::new("Link Trakt account…"),
+            )
+            .on_hover_text("Shows a code to enter on trakt.tv")
+            .clicked()
+        {
+            self.link_trakt();
+        }
+        if let Some(err) = &self.trakt.error {
+            ui.label(
+                eg::RichText::new(format!("Trakt unavailable: {err}"))
+                    .color(eg::Color32::LIGHT_RED),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synced_films_become_keys_and_adds_carry_ids() {
+        let items: Vec<MovieItem> = serde_json::from_str(
+            r#"[
+                {"plays": 2, "movie": {"title": "Heat", "year": 1995,
+                 "ids": {"trakt": 1, "imdb": "tt0113277", "tmdb": 949}}},
+                {"movie": {"title": "Alien", "year": 1979, "ids": {"imdb": null}}}
+            ]"#,
+        )
+        .unwrap();
+        let keys = movie_keys(&items);
+        assert!(keys.contains("imdb:tt0113277"));
+        assert!(keys.contains("tmdb:949"));
+        assert!(keys.contains("alien:1979"));
+
+        let token: TraktToken = serde_json::from_str::<TokenResponse>(
+            r#"{"access_token": "a", "refresh_token": "r", "created_at": 1000,
+                "expires_in": 7776000, "token_type": "bearer", "scope": "public"}"#,
+        )
+        .unwrap()
+        .into();
+        assert_eq!(token.expires_at, 7_777_000);
+
+        let body = watchlist_body(&WatchlistJob {
+            key: "k".into(),
+            title: "Heat".into(),
+            year: Some(1995),
+            imdb: Some("tt0113277".into()),
+            tmdb: Some("949".into()),
+            token,
+            remove: false,
+        });
+        assert_eq!(body["movies"][0]["ids"]["tmdb"], 949);
+        assert_eq!(body["movies"][0]["ids"]["imdb"], "tt0113277");
+        assert_eq!(body["movies"][0]["year"], 1995);
+    }
+}
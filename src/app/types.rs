@@ -15,12 +15,13 @@ pub enum OwnedMsg {
     Error(String),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct PrepItem {
     pub title: String,
     pub thumb_url: String,
     pub key: String,
     pub begins_at: Option<i64>,
+    pub ends_at: Option<i64>,
     pub year: Option<i32>,
     pub tags_genre: Option<String>,
     pub channel_call_sign: Option<String>,
@@ -30,10 +31,31 @@ pub struct PrepItem {
     pub summary: Option<String>,
     pub audience_rating: Option<f32>,
     pub critic_rating: Option<f32>,
+    /// The EPG marks this airing as a premiere / first showing.
+    pub premiere: bool,
+}
+
+/// A later airing of a title the grid already has a row for (rows keep the soonest airing).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Airing {
+    pub begins_at: i64,
+    pub ends_at: Option<i64>,
+    /// Display name, as on the card.
+    pub channel: Option<String>,
+    pub broadcast_hd: bool,
 }
 
+/// Lowercased title and year: a later airing repeats the guide row with the same pair.
+pub type RepeatKey = (String, Option<i32>);
+
 pub enum PrepMsg {
     Info(String),
+    /// Rows parsed so far (soonest airings first), sent while the rest is still being read.
+    Batch(Vec<PrepItem>),
+    /// Later airings of the titles sent, keyed by lowercased title and year; sent just before
+    /// `Done`.
+    Repeats(HashMap<RepeatKey, Vec<Airing>>),
+    /// The remaining rows; the guide is complete.
     Done(Vec<PrepItem>),
     Error(String),
 }
@@ -41,6 +63,10 @@ pub enum PrepMsg {
 pub struct PrefetchDone {
     pub row_idx: usize,
     pub result: Result<PathBuf, String>,
+    /// Set when this was a larger-variant upgrade rather than the initial prefetch.
+    pub variant_w: Option<u32>,
+    /// A stale cached poster was replaced by newer artwork from the server.
+    pub refreshed: bool,
 }
 
 // ---- app phases / states ----
@@ -119,6 +145,10 @@ pub enum SortKey {
     Title,
     Channel,
     Genre,
+    /// Best rated first; unrated airings always sort last.
+    Rating,
+    /// Oldest owned recording first (`owned_modified`); films not owned sort last.
+    OwnedDate,
 }
 
 impl SortKey {
@@ -128,6 +158,8 @@ impl SortKey {
             Self::Title => "title",
             Self::Channel => "channel",
             Self::Genre => "genre",
+            Self::Rating => "rating",
+            Self::OwnedDate => "owned_date",
         }
     }
 }
@@ -141,6 +173,120 @@ impl FromStr for SortKey {
             "title" => Ok(Self::Title),
             "channel" => Ok(Self::Channel),
             "genre" => Ok(Self::Genre),
+            "rating" => Ok(Self::Rating),
+            "owned_date" => Ok(Self::OwnedDate),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What the grid's sections are: airing day (the default), channel, primary genre, or one
+/// unheaded run of cards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    Day,
+    Channel,
+    Genre,
+    None,
+}
+
+impl GroupBy {
+    pub const ALL: [Self; 4] = [Self::Day, Self::Channel, Self::Genre, Self::None];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Channel => "channel",
+            Self::Genre => "genre",
+            Self::None => "none",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Day => "Group: Day",
+            Self::Channel => "Group: Channel",
+            Self::Genre => "Group: Genre",
+            Self::None => "Group: None",
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "channel" => Ok(Self::Channel),
+            "genre" => Ok(Self::Genre),
+            "none" => Ok(Self::None),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One section of `build_grouped_indices`. Channel and genre keys are display names; an
+/// empty one collects the airings without a channel / genre.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GroupKey {
+    Day(i64),
+    Channel(String),
+    Genre(String),
+    All,
+}
+
+impl GroupKey {
+    /// Section heading; `None` for the single headerless section.
+    pub fn label(&self) -> Option<String> {
+        match self {
+            Self::Day(bucket) => Some(crate::app::utils::format_day_label(*bucket)),
+            Self::Channel(name) if name.is_empty() => Some("Unknown channel".into()),
+            Self::Genre(name) if name.is_empty() => Some("No genre".into()),
+            Self::Channel(name) | Self::Genre(name) => Some(name.clone()),
+            Self::All => None,
+        }
+    }
+
+    /// Sort key for sections: days by date, names alphabetically with the unnamed section last.
+    pub fn order_key(&self) -> (bool, String, i64) {
+        match self {
+            Self::Day(bucket) => (false, String::new(), *bucket),
+            Self::Channel(name) | Self::Genre(name) => (name.is_empty(), name.to_lowercase(), 0),
+            Self::All => (false, String::new(), 0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewMode {
+    Grid,
+    Timeline,
+    List,
+    /// Per channel: the film airing now and the next one.
+    NowNext,
+}
+
+impl ViewMode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Grid => "grid",
+            Self::Timeline => "timeline",
+            Self::List => "list",
+            Self::NowNext => "now_next",
+        }
+    }
+}
+
+impl FromStr for ViewMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "grid" => Ok(Self::Grid),
+            "timeline" => Ok(Self::Timeline),
+            "list" => Ok(Self::List),
+            "now_next" => Ok(Self::NowNext),
             _ => Err(()),
         }
     }
@@ -152,6 +298,7 @@ pub struct PosterRow {
     pub url: String,
     pub key: String,
     pub airing: Option<SystemTime>,
+    pub ends: Option<SystemTime>,
     pub year: Option<i32>,
     pub channel: Option<String>,
     pub channel_raw: Option<String>,
@@ -163,6 +310,7 @@ pub struct PosterRow {
     pub audience_rating: Option<f32>,
     pub critic_rating: Option<f32>,
     pub path: Option<PathBuf>,
+    pub variant_w: u32,             // max width of the cached variant at `path`
     pub tex: Option<TextureHandle>, // UI thread only
     pub state: PosterState,
     pub owned: bool,
@@ -170,18 +318,144 @@ pub struct PosterRow {
     pub owned_key: String,
     pub broadcast_hd: bool,
     pub scheduled: bool,
+    /// This airing, or a later one of the title, is scheduled and clashes with other
+    /// recordings for the available tuners.
+    pub conflict: bool,
+    /// First showing: flagged by the EPG or new to the guide per the airing history.
+    pub premiere: bool,
+    /// Not in the guide the last time Pex ran.
+    pub new_in_guide: bool,
+    /// Radarr already monitors this film.
+    pub radarr: bool,
+    /// In the linked Trakt account's watch history.
+    pub watched: bool,
+    /// On the linked Trakt account's watchlist.
+    pub trakt_watchlist: bool,
+    /// On the imported Letterboxd watchlist.
+    pub letterboxd: bool,
+    /// Your rating (1-10) from an imported IMDb ratings export.
+    pub my_rating: Option<u8>,
+    /// On an imported IMDb watchlist export.
+    pub imdb_watchlist: bool,
 }
 
 #[derive(Clone, Debug)]
 pub enum RatingState {
     Idle,
     Pending,
-    Success(String),
+    /// `source` is the provider label ("TMDb", "OMDb").
+    Success {
+        source: &'static str,
+        text: String,
+        /// 0–10, when the provider gave a number.
+        score: Option<f32>,
+    },
     NotFound,
     Error(String),
     MissingApiKey,
 }
 
+impl RatingState {
+    /// "TMDb: 7.8/10 (1234 votes)" for a fetched rating.
+    pub fn summary(&self) -> Option<String> {
+        match self {
+            Self::Success { source, text, .. } => Some(format!("{source}: {text}")),
+            _ => None,
+        }
+    }
+
+    pub const fn score(&self) -> Option<f32> {
+        match self {
+            Self::Success { score, .. } => *score,
+            _ => None,
+        }
+    }
+}
+
+/// Resolution tier of the best owned file for a title (`owned_hd.txt` / `owned_uhd.txt`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OwnedTier {
+    Sd,
+    Hd,
+    Uhd,
+}
+
+impl OwnedTier {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Sd => "SD",
+            Self::Hd => "HD",
+            Self::Uhd => "4K",
+        }
+    }
+}
+
+/// Encoding of the best owned file for a title, as the library recorded it (`owned_media.txt`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OwnedMedia {
+    pub bitrate_kbps: Option<u32>,
+    pub video_codec: Option<String>,
+    pub container: Option<String>,
+    pub audio_codec: Option<String>,
+    pub audio_channels: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub file_path: Option<String>,
+    /// The item's id (`ratingKey`) in the Plex library, for the Plex Web link.
+    pub plex_rating_key: Option<u64>,
+}
+
+impl OwnedMedia {
+    /// "H264 · 3.2 Mbps", or whichever half is known.
+    pub fn describe(&self) -> Option<String> {
+        let codec = self.video_codec.as_deref().map(str::to_ascii_uppercase);
+        let rate = self
+            .bitrate_kbps
+            .map(|kbps| format!("{:.1} Mbps", kbps as f32 / 1000.0));
+        match (codec, rate) {
+            (Some(c), Some(r)) => Some(format!("{c} · {r}")),
+            (c, r) => c.or(r),
+        }
+    }
+
+    /// Label/value lines for the detail panel's "File" section, skipping unknown values.
+    pub fn file_details(&self) -> Vec<(&'static str, String)> {
+        let upper = |v: &Option<String>| v.as_deref().map(str::to_ascii_uppercase);
+        let resolution = match (self.width, self.height) {
+            (Some(w), Some(h)) => Some(format!("{w}×{h}")),
+            _ => None,
+        };
+        let video = match (upper(&self.video_codec), resolution) {
+            (Some(c), Some(r)) => Some(format!("{c} · {r}")),
+            (c, r) => c.or(r),
+        };
+        let channels = self.audio_channels.map(|n| match n {
+            1 => "mono".to_string(),
+            2 => "stereo".to_string(),
+            6 => "5.1".to_string(),
+            8 => "7.1".to_string(),
+            n => format!("{n} ch"),
+        });
+        let audio = match (upper(&self.audio_codec), channels) {
+            (Some(c), Some(ch)) => Some(format!("{c} {ch}")),
+            (c, ch) => c.or(ch),
+        };
+        let bitrate = self
+            .bitrate_kbps
+            .map(|kbps| format!("{:.1} Mbps", kbps as f32 / 1000.0));
+        [
+            ("Path", self.file_path.clone()),
+            ("Container", upper(&self.container)),
+            ("Video", video),
+            ("Audio", audio),
+            ("Bitrate", bitrate),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value?)))
+        .collect()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RatingMsg {
     pub key: String,
@@ -0,0 +1,71 @@
+// src/app/ui/empty_state.rs
+use eframe::egui as eg;
+
+use crate::app::facets::Facet;
+
+fn airings(n: usize) -> String {
+    format!("{n} airing{}", if n == 1 { "" } else { "s" })
+}
+
+impl crate::app::PexApp {
+    /// Shown instead of an empty grid / list / timeline: which active filters hide the guide,
+    /// how much each one hides, and a button to turn it off. Filters that would bring airings
+    /// back on their own come first.
+    pub(crate) fn ui_render_empty_state(&mut self, ui: &mut eg::Ui) {
+        let counts = self.facet_counts();
+        // Only active filters hide anything.
+        let mut culprits: Vec<(Facet, usize, usize)> = Facet::ALL
+            .into_iter()
+            .map(|facet| (facet, counts.hidden_by(facet), counts.shown_without(facet)))
+            .filter(|&(_, hidden, _)| hidden > 0)
+            .collect();
+        culprits.sort_by_key(|&(_, hidden, shown)| std::cmp::Reverse((shown, hidden)));
+
+        ui.add_space(12.0);
+        ui.heading("No airings match the current filters");
+        if counts.in_window == 0 {
+            ui.label("The guide has nothing in the selected day range. Try a longer range.");
+            return;
+        }
+        ui.label(format!(
+            "{} in the selected days are hidden by:",
+            airings(counts.in_window)
+        ));
+        ui.add_space(6.0);
+
+        let mut relax = None;
+        eg::Grid::new("empty_state_filters")
+            .num_columns(3)
+            .spacing([12.0, 6.0])
+            .show(ui, |ui| {
+                for &(facet, hidden, shown) in &culprits {
+                    ui.label(eg::RichText::new(facet.label()).strong());
+                    let effect = if shown > 0 {
+                        format!("hides {} · off shows {shown}", airings(hidden))
+                    } else {
+                        format!("hides {}", airings(hidden))
+                    };
+                    ui.label(effect);
+                    if ui
+                        .button(facet.relax_label())
+                        .on_hover_text(format!("Relax the {} filter", facet.label()))
+                        .clicked()
+                    {
+                        relax = Some(facet);
+                    }
+                    ui.end_row();
+                }
+            });
+        if culprits.len() > 1 {
+            ui.add_space(6.0);
+            if ui.button("Clear all of these").clicked() {
+                for &(facet, _, _) in &culprits {
+                    self.relax_facet(facet);
+                }
+            }
+        }
+        if let Some(facet) = relax {
+            self.relax_facet(facet);
+        }
+    }
+}
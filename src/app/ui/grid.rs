@@ -1,15 +1,24 @@
 // src/app/ui/grid.rs
 use eframe::egui as eg;
 
+use crate::app::imdb_lists::IMDB_COLOR;
+use crate::app::radarr::RADARR_COLOR;
+use crate::app::theme::{highlight_job, paint_dimmed, Palette};
+use crate::app::trakt::TRAKT_COLOR;
+use crate::app::GroupKey;
+
 pub const H_SPACING: f32 = 4.0;
 pub const V_SPACING: f32 = 10.0;
+// Heading block above each group (spacing + separator + heading); none for "Group: None".
+const GROUP_HEADER_H: f32 = 44.0;
 
-fn draw_corner_badge(p: &eframe::egui::Painter, rect: eg::Rect, label: &str) {
+/// Badge sizes below are for the Comfortable density; `s` is the preset's badge scale.
+fn draw_corner_badge(p: &eframe::egui::Painter, rect: eg::Rect, label: &str, s: f32) {
     if label.is_empty() {
         return;
     }
     let pad = 6.0;
-    let size = eg::vec2(48.0, 20.0);
+    let size = eg::vec2(48.0, 20.0) * s;
     let r = eg::Rect::from_min_max(
         eg::pos2(rect.right() - pad - size.x, rect.top() + pad),
         eg::pos2(rect.right() - pad, rect.top() + pad + size.y),
@@ -25,233 +34,556 @@ fn draw_corner_badge(p: &eframe::egui::Painter, rect: eg::Rect, label: &str) {
         r.center(),
         eg::Align2::CENTER_CENTER,
         label,
-        eg::FontId::monospace(12.0),
+        eg::FontId::monospace(12.0 * s),
         fg,
     );
 }
 
+/// Score pill in the poster's bottom-left corner: green from 7.5, amber from 6, red below.
+fn draw_rating_badge(
+    p: &eframe::egui::Painter,
+    rect: eg::Rect,
+    score: f32,
+    palette: &Palette,
+    s: f32,
+) {
+    let pad = 6.0;
+    let size = eg::vec2(36.0, 20.0) * s;
+    let r = eg::Rect::from_min_size(
+        eg::pos2(rect.left() + pad, rect.bottom() - pad - size.y),
+        size,
+    );
+    let fill = if score >= 7.5 {
+        palette.rating_good
+    } else if score >= 6.0 {
+        palette.rating_ok
+    } else {
+        palette.rating_bad
+    };
+    p.rect_filled(r, eg::Rounding::same(6.0), fill);
+    p.rect_stroke(
+        r,
+        eg::Rounding::same(6.0),
+        eg::Stroke::new(1.5, palette.badge_outline),
+    );
+    p.text(
+        r.center(),
+        eg::Align2::CENTER_CENTER,
+        format!("{score:.1}"),
+        eg::FontId::monospace(12.0 * s),
+        palette.on_badge,
+    );
+}
+
+/// "NEW" pill for premieres / first showings, anchored at its top-left corner.
+fn draw_new_badge(p: &eframe::egui::Painter, min: eg::Pos2, palette: &Palette, s: f32) {
+    let r = eg::Rect::from_min_size(min, eg::vec2(44.0, 20.0) * s);
+    p.rect_filled(r, eg::Rounding::same(6.0), palette.new_badge);
+    p.rect_stroke(
+        r,
+        eg::Rounding::same(6.0),
+        eg::Stroke::new(1.5, palette.badge_outline),
+    );
+    p.text(
+        r.center(),
+        eg::Align2::CENTER_CENTER,
+        "NEW",
+        eg::FontId::monospace(12.0 * s),
+        palette.on_accent,
+    );
+}
+
+/// Small dot in the poster's bottom-right corner for airings new since the last run.
+fn draw_new_in_guide_dot(p: &eframe::egui::Painter, rect: eg::Rect, color: eg::Color32, s: f32) {
+    let center = eg::pos2(rect.right() - 11.0 * s, rect.bottom() - 11.0 * s);
+    p.circle_filled(center, 5.0 * s, color);
+    p.circle_stroke(center, 5.0 * s, eg::Stroke::new(1.0, eg::Color32::BLACK));
+}
+
+/// Radarr-yellow dot left of the new-in-guide dot for films Radarr already monitors.
+fn draw_radarr_dot(p: &eframe::egui::Painter, rect: eg::Rect, s: f32) {
+    let center = eg::pos2(rect.right() - 25.0 * s, rect.bottom() - 11.0 * s);
+    p.circle_filled(center, 5.0 * s, RADARR_COLOR);
+    p.circle_stroke(center, 5.0 * s, eg::Stroke::new(1.0, eg::Color32::BLACK));
+}
+
+/// Trakt-red tick left of the Radarr dot for films in the Trakt watch history.
+fn draw_watched_tick(p: &eframe::egui::Painter, rect: eg::Rect, s: f32) {
+    let center = eg::pos2(rect.right() - 41.0 * s, rect.bottom() - 11.0 * s);
+    p.circle_filled(center, 7.0 * s, TRAKT_COLOR);
+    p.circle_stroke(center, 7.0 * s, eg::Stroke::new(1.0, eg::Color32::BLACK));
+    p.text(
+        center,
+        eg::Align2::CENTER_CENTER,
+        "✓",
+        eg::FontId::proportional(11.0 * s),
+        eg::Color32::WHITE,
+    );
+}
+
+/// Letterboxd's three dots under the top-right corner badge for watchlist films.
+fn draw_letterboxd_dots(p: &eframe::egui::Painter, rect: eg::Rect, s: f32) {
+    let y = rect.top() + 6.0 + (20.0 + 10.0) * s;
+    for (i, color) in crate::app::letterboxd::LETTERBOXD_DOTS.iter().enumerate() {
+        let center = eg::pos2(rect.right() - (34.0 - i as f32 * 11.0) * s, y);
+        p.circle_filled(center, 5.0 * s, *color);
+        p.circle_stroke(center, 5.0 * s, eg::Stroke::new(1.0, eg::Color32::BLACK));
+    }
+}
+
+/// Your IMDb rating in IMDb yellow, stacked above the score pill in the bottom-left corner.
+fn draw_my_rating_badge(p: &eframe::egui::Painter, rect: eg::Rect, rating: u8, s: f32) {
+    let pad = 6.0;
+    let size = eg::vec2(36.0, 20.0) * s;
+    let r = eg::Rect::from_min_size(
+        eg::pos2(rect.left() + pad, rect.bottom() - pad - 2.0 * size.y - 4.0),
+        size,
+    );
+    p.rect_filled(r, eg::Rounding::same(6.0), IMDB_COLOR);
+    p.text(
+        r.center(),
+        eg::Align2::CENTER_CENTER,
+        format!("★{rating}"),
+        eg::FontId::monospace(12.0 * s),
+        eg::Color32::BLACK,
+    );
+}
+
+/// Channel logo on a dark chip in the poster's bottom-left corner. Returns the height taken,
+/// so the score pills can stack above it.
+fn draw_channel_logo(
+    p: &eframe::egui::Painter,
+    rect: eg::Rect,
+    tex: &eg::TextureHandle,
+    s: f32,
+) -> f32 {
+    let pad = 6.0;
+    let size = eg::vec2(44.0, 24.0) * s;
+    let r = eg::Rect::from_min_size(
+        eg::pos2(rect.left() + pad, rect.bottom() - pad - size.y),
+        size,
+    );
+    let visuals = p.ctx().style().visuals.clone();
+    p.rect_filled(
+        r,
+        eg::Rounding::same(6.0),
+        visuals.extreme_bg_color.gamma_multiply(0.85),
+    );
+    let inner = r.shrink(3.0 * s);
+    let [w, h] = tex.size_vec2().into();
+    let fit = (inner.width() / w.max(1.0)).min(inner.height() / h.max(1.0));
+    let logo = eg::Rect::from_center_size(inner.center(), eg::vec2(w, h) * fit);
+    p.image(
+        tex.id(),
+        logo,
+        eg::Rect::from_min_max(eg::pos2(0.0, 0.0), eg::pos2(1.0, 1.0)),
+        eg::Color32::WHITE,
+    );
+    size.y + 4.0
+}
+
+/// IMDb-yellow bookmark ribbon left of the top-right corner badge for IMDb watchlist titles.
+fn draw_imdb_ribbon(p: &eframe::egui::Painter, rect: eg::Rect, s: f32) {
+    let (left, top) = (rect.right() - 6.0 - (48.0 + 18.0) * s, rect.top());
+    let (w, h) = (12.0 * s, 22.0 * s);
+    p.add(eg::Shape::convex_polygon(
+        vec![
+            eg::pos2(left, top),
+            eg::pos2(left + w, top),
+            eg::pos2(left + w, top + h),
+            eg::pos2(left + w / 2.0, 5.0f32.mul_add(-s, top + h)),
+            eg::pos2(left, top + h),
+        ],
+        IMDB_COLOR,
+        eg::Stroke::new(1.0, eg::Color32::BLACK),
+    ));
+}
+
+/// Vertical placement of one group inside the scroll content.
+struct GridSection {
+    day: Option<i64>,
+    label: Option<String>,
+    header_h: f32,
+    top: f32,
+    first_row: usize,
+    n_rows: usize,
+}
+
 impl crate::app::PexApp {
+    /// Poster grid grouped by day (or channel / genre, see `GroupBy`). Only card rows intersecting the viewport (plus one row of
+    /// margin) build widgets; everything else is just reserved height, so large guides stay cheap.
     pub(crate) fn ui_render_grouped_grid(&mut self, ui: &mut eg::Ui, ctx: &eg::Context) {
         self.handle_keyboard_navigation(ctx);
 
         let groups = self.build_grouped_indices();
         self.sync_selection_with_groups(&groups);
         self.grid_rows.clear();
+        self.visible_idxs.clear();
+        if groups.is_empty() {
+            self.ui_render_empty_state(ui);
+            return;
+        }
 
         let card_w: f32 = self.poster_width_ui;
-        let text_h: f32 = 56.0;
-        let card_h: f32 = card_w.mul_add(1.5, text_h);
+        let card_h: f32 = card_w.mul_add(1.5, self.density.preset.text_h());
+        let row_h = card_h + V_SPACING;
 
         let mut uploads_left = super::super::MAX_UPLOADS_PER_FRAME;
 
-        eg::ScrollArea::vertical()
-            .auto_shrink([false; 2])
-            .show(ui, |ui| {
-                for (bucket, idxs) in groups {
-                    ui.add_space(8.0);
-                    ui.separator();
-                    ui.heading(crate::app::utils::format_day_label(bucket));
-                    ui.add_space(4.0);
-
-                    // Columns + centering (use local module constants directly)
-                    let avail = ui.available_width();
-                    let cols = ((avail + H_SPACING) / (card_w + H_SPACING))
-                        .floor()
-                        .max(1.0) as usize;
-
-                    let used =
-                        (cols as f32).mul_add(card_w, (cols.saturating_sub(1)) as f32 * H_SPACING);
-                    let left_pad = ((avail - used) * 0.5).max(0.0);
-                    if left_pad > 0.0 {
-                        ui.add_space(left_pad);
-                    }
+        let days: Vec<(i64, usize)> = groups
+            .iter()
+            .filter_map(|(key, idxs)| match key {
+                GroupKey::Day(bucket) => Some((*bucket, idxs.len())),
+                _ => None,
+            })
+            .collect();
+        self.ui_render_day_strip(ui, &days);
 
-                    let mut row_buffer: Vec<usize> = Vec::new();
-                    ui.horizontal_wrapped(|ui| {
-                        ui.spacing_mut().item_spacing = eg::vec2(H_SPACING, V_SPACING);
-
-                        for (col, &idx) in idxs.iter().enumerate() {
-                            if col > 0 && col % cols == 0 {
-                                ui.end_row();
-                                if !row_buffer.is_empty() {
-                                    self.grid_rows.push(std::mem::take(&mut row_buffer));
-                                }
-                            }
-
-                            if row_buffer.is_empty() {
-                                row_buffer.reserve(cols);
-                            }
-                            row_buffer.push(idx);
-
-                            ui.allocate_ui_with_layout(
-                                eg::vec2(card_w, card_h),
-                                eg::Layout::top_down(eg::Align::Min),
-                                |ui| {
-                                    ui.set_min_size(eg::vec2(card_w, card_h));
-                                    let rect = ui.max_rect();
-
-                                    // selection
-                                    let id = eg::Id::new(("card_sel", idx));
-                                    if ui.interact(rect, id, eg::Sense::click()).clicked() {
-                                        self.selected_idx = Some(idx);
-                                    }
-
-                                    // opportunistic upload
-                                    if uploads_left > 0 && self.try_lazy_upload_row(ctx, idx) {
-                                        uploads_left -= 1;
-                                    }
-
-                                    // rects
-                                    let poster_rect = eg::Rect::from_min_max(
-                                        rect.min,
-                                        eg::pos2(
-                                            rect.min.x + card_w,
-                                            card_w.mul_add(1.5, rect.min.y),
-                                        ),
-                                    );
-                                    let text_rect = eg::Rect::from_min_max(
-                                        eg::pos2(rect.min.x, poster_rect.max.y),
-                                        rect.max,
-                                    );
-
-                                    if self.scroll_to_idx == Some(idx) {
-                                        ui.scroll_to_rect(rect, Some(eg::Align::Center));
-                                        self.scroll_to_idx = None;
-                                    }
-
-                                    if let Some(row) = self.rows.get(idx) {
-                                        // Poster
-                                        if let Some(tex) = &row.tex {
-                                            ui.painter().image(
-                                                tex.id(),
-                                                poster_rect,
-                                                eg::Rect::from_min_max(
-                                                    eg::pos2(0.0, 0.0),
-                                                    eg::pos2(1.0, 1.0),
-                                                ),
-                                                eg::Color32::WHITE,
-                                            );
-                                        } else {
-                                            ui.painter().rect_filled(
-                                                poster_rect,
-                                                6.0,
-                                                eg::Color32::from_gray(40),
-                                            );
-                                        }
-
-                                        if row.scheduled {
-                                            let pad = 6.0;
-                                            let size = eg::vec2(56.0, 22.0);
-                                            let rec_rect = eg::Rect::from_min_size(
-                                                eg::pos2(
-                                                    poster_rect.left() + pad,
-                                                    poster_rect.top() + pad,
-                                                ),
-                                                size,
-                                            );
-                                            let fill = eg::Color32::from_rgb(200, 40, 40);
-                                            let stroke = eg::Color32::from_rgb(140, 16, 16);
-                                            ui.painter().rect_filled(
-                                                rec_rect,
-                                                eg::Rounding::same(6.0),
-                                                fill,
-                                            );
-                                            ui.painter().rect_stroke(
-                                                rec_rect,
-                                                eg::Rounding::same(6.0),
-                                                eg::Stroke::new(1.0, stroke),
-                                            );
-                                            ui.painter().text(
-                                                rec_rect.center(),
-                                                eg::Align2::CENTER_CENTER,
-                                                "REC",
-                                                eg::FontId::monospace(13.0),
-                                                eg::Color32::WHITE,
-                                            );
-                                        }
-
-                                        // --- Compute statuses (needed for badges & dimming) ---
-                                        let broadcast_hd = Self::row_broadcast_hd(row);
-                                        let owned_is_hd = self.row_owned_is_hd(row);
-                                        let better_hd_available =
-                                            row.owned && !owned_is_hd && broadcast_hd;
-
-                                        // Corner badge: show only for HD airings; SD gets no symbol
-                                        if better_hd_available {
-                                            draw_corner_badge(ui.painter(), poster_rect, "HD ↑");
-                                        } else if broadcast_hd {
-                                            draw_corner_badge(ui.painter(), poster_rect, "HD");
-                                        }
-
-                                        // Dim overlay: do NOT dim if there's an HD upgrade airing
-                                        let should_dim =
-                                            row.owned && self.dim_owned && !better_hd_available;
-                                        if should_dim {
-                                            let a = (self.dim_strength_ui.clamp(0.10, 0.90) * 255.0)
-                                                as u8;
-                                            let overlay_rect = poster_rect.expand(0.5);
-                                            ui.painter().rect_filled(
-                                                overlay_rect,
-                                                eg::Rounding::ZERO,
-                                                eg::Color32::from_black_alpha(a),
-                                            );
-                                        }
-
-                                        // Label
-                                        let title_line = row.year.map_or_else(
-                                            || row.title.clone(),
-                                            |y| format!("{} ({})", row.title, y),
-                                        );
-                                        let ch = row
-                                            .channel
-                                            .as_deref()
-                                            .map(crate::app::utils::humanize_channel)
-                                            .unwrap_or_else(|| "—".into());
-                                        let line2 = if broadcast_hd {
-                                            format!("{ch} • HD")
-                                        } else {
-                                            ch
-                                        };
-                                        let tm = row
-                                            .airing
-                                            .map(crate::app::utils::hhmm_utc)
-                                            .unwrap_or_else(|| "—".into());
-                                        let line3 = tm + " UTC";
-
-                                        let label_text = format!(
-                                            "{title}\n{line2}\n{line3}",
-                                            title = title_line
-                                        );
-
-                                        ui.allocate_ui_at_rect(text_rect, |ui| {
-                                            ui.add(
-                                                eg::Label::new(
-                                                    eg::RichText::new(label_text).size(14.0),
-                                                )
-                                                .wrap(),
-                                            );
-                                        });
-
-                                        // Selection stroke
-                                        if self.selected_idx == Some(idx) {
-                                            let highlight = poster_rect.expand(2.0);
-                                            ui.painter().rect_stroke(
-                                                highlight,
-                                                6.0,
-                                                eg::Stroke::new(2.0, eg::Color32::YELLOW),
-                                            );
-                                        }
-                                    }
-                                },
-                            );
-                        }
+        self.resume
+            .grid_scroll_area()
+            .show_viewport(ui, |ui, viewport| {
+                // Columns + centering (use local module constants directly)
+                let avail = ui.available_width();
+                let cols = ((avail + H_SPACING) / (card_w + H_SPACING))
+                    .floor()
+                    .max(1.0) as usize;
+                let used =
+                    (cols as f32).mul_add(card_w, (cols.saturating_sub(1)) as f32 * H_SPACING);
+                let left_pad = ((avail - used) * 0.5).max(0.0);
+
+                // Layout pass: section offsets + the full grid_rows map for keyboard nav.
+                let mut sections: Vec<GridSection> = Vec::with_capacity(groups.len());
+                let mut total_h = 0.0f32;
+                for (key, idxs) in &groups {
+                    let first_row = self.grid_rows.len();
+                    self.grid_rows
+                        .extend(idxs.chunks(cols).map(<[usize]>::to_vec));
+                    let n_rows = self.grid_rows.len() - first_row;
+                    let label = key.label();
+                    let header_h = if label.is_some() { GROUP_HEADER_H } else { 0.0 };
+                    sections.push(GridSection {
+                        day: match key {
+                            GroupKey::Day(bucket) => Some(*bucket),
+                            _ => None,
+                        },
+                        label,
+                        header_h,
+                        top: total_h,
+                        first_row,
+                        n_rows,
+                    });
+                    total_h += (n_rows as f32).mul_add(row_h, header_h);
+                }
+
+                let (content, _) =
+                    ui.allocate_exact_size(eg::vec2(avail, total_h), eg::Sense::hover());
+                let origin = content.min;
+                let card_rect = |section: &GridSection, row_i: usize, col: usize| {
+                    eg::Rect::from_min_size(
+                        eg::pos2(
+                            (col as f32).mul_add(card_w + H_SPACING, origin.x + left_pad),
+                            (row_i as f32)
+                                .mul_add(row_h, origin.y + section.top + section.header_h),
+                        ),
+                        eg::vec2(card_w, card_h),
+                    )
+                };
 
-                        ui.end_row();
+                // Keyboard selection may target a card that has no widgets yet.
+                if let Some(target) = self.scroll_to_idx.take() {
+                    let hit = sections.iter().find_map(|section| {
+                        (0..section.n_rows).find_map(|row_i| {
+                            self.grid_rows[section.first_row + row_i]
+                                .iter()
+                                .position(|&idx| idx == target)
+                                .map(|col| card_rect(section, row_i, col))
+                        })
                     });
-                    if !row_buffer.is_empty() {
-                        self.grid_rows.push(std::mem::take(&mut row_buffer));
+                    if let Some(rect) = hit {
+                        ui.scroll_to_rect(rect, Some(eg::Align::Center));
+                    }
+                }
+
+                if let Some(day) = self.day_strip.scroll_to.take() {
+                    if let Some(section) = sections.iter().find(|s| s.day == Some(day)) {
+                        let header = eg::Rect::from_min_size(
+                            origin + eg::vec2(0.0, section.top),
+                            eg::vec2(avail, row_h),
+                        );
+                        ui.scroll_to_rect(header, Some(eg::Align::Min));
+                    }
+                }
+                self.resume.scroll_y = viewport.min.y;
+                self.day_strip.current = sections
+                    .iter()
+                    .find(|s| (s.n_rows as f32).mul_add(row_h, s.top + s.header_h) > viewport.min.y)
+                    .and_then(|s| s.day);
+
+                // `viewport` is relative to the content origin.
+                let win_top = viewport.min.y - row_h;
+                let win_bottom = viewport.max.y + row_h;
+                for section in &sections {
+                    let rows_top = section.top + section.header_h;
+                    let bottom = (section.n_rows as f32).mul_add(row_h, rows_top);
+                    if bottom < win_top || section.top > win_bottom {
+                        continue;
+                    }
+
+                    if let Some(label) = section.label.as_deref().filter(|_| rows_top >= win_top) {
+                        let header_rect = eg::Rect::from_min_size(
+                            origin + eg::vec2(0.0, section.top),
+                            eg::vec2(avail, section.header_h),
+                        );
+                        ui.allocate_ui_at_rect(header_rect, |ui| {
+                            ui.add_space(8.0);
+                            ui.separator();
+                            ui.heading(label);
+                        });
+                    }
+
+                    let first = ((win_top - rows_top) / row_h).floor().max(0.0) as usize;
+                    let last = (((win_bottom - rows_top) / row_h).ceil().max(0.0) as usize)
+                        .min(section.n_rows);
+                    for row_i in first..last {
+                        let row_idxs = self.grid_rows[section.first_row + row_i].clone();
+                        for (col, idx) in row_idxs.into_iter().enumerate() {
+                            let rect = card_rect(section, row_i, col);
+                            self.draw_grid_card(ui, ctx, idx, rect, &mut uploads_left);
+                        }
                     }
                 }
             });
     }
+
+    fn draw_grid_card(
+        &mut self,
+        ui: &mut eg::Ui,
+        ctx: &eg::Context,
+        idx: usize,
+        rect: eg::Rect,
+        uploads_left: &mut usize,
+    ) {
+        let card_w = rect.width();
+        let palette = self.palette();
+        let density = self.density.preset;
+        let s = density.badge_scale();
+        // selection
+        let id = eg::Id::new(("card_sel", idx));
+        let resp = ui.interact(rect, id, eg::Sense::click());
+        if resp.clicked() {
+            self.click_row(idx, &resp);
+        }
+        self.describe_card(&resp, idx);
+        if self.hover_preview.enabled {
+            resp.on_hover_ui(|ui| self.ui_render_hover_preview(ui, idx));
+        }
+
+        if ui.is_rect_visible(rect) {
+            self.visible_idxs.insert(idx);
+        }
+
+        // opportunistic upload
+        if *uploads_left > 0 && self.try_lazy_upload_row(ctx, idx) {
+            *uploads_left -= 1;
+        }
+
+        // rects
+        let poster_rect = eg::Rect::from_min_max(
+            rect.min,
+            eg::pos2(rect.min.x + card_w, card_w.mul_add(1.5, rect.min.y)),
+        );
+        let text_rect = eg::Rect::from_min_max(eg::pos2(rect.min.x, poster_rect.max.y), rect.max);
+
+        let logo = self
+            .channel_logos
+            .then(|| self.rows.get(idx).and_then(|row| row.channel_thumb.clone()))
+            .flatten()
+            .and_then(|thumb| self.channel_icon_texture(ctx, &thumb));
+
+        if let Some(row) = self.rows.get(idx) {
+            // Poster
+            if let Some(tex) = &row.tex {
+                ui.painter().image(
+                    tex.id(),
+                    poster_rect,
+                    eg::Rect::from_min_max(eg::pos2(0.0, 0.0), eg::pos2(1.0, 1.0)),
+                    eg::Color32::WHITE,
+                );
+            } else {
+                // Dominant poster colour while the texture loads; grey if never cached.
+                let fill = crate::app::cache::poster_swatch(&row.key)
+                    .map_or(palette.placeholder, |[r, g, b]| {
+                        eg::Color32::from_rgb(r, g, b)
+                    });
+                ui.painter().rect_filled(poster_rect, 6.0, fill);
+            }
+
+            if row.scheduled || row.conflict {
+                let pad = 6.0;
+                let size = eg::vec2(56.0, 22.0) * s;
+                let rec_rect = eg::Rect::from_min_size(
+                    eg::pos2(poster_rect.left() + pad, poster_rect.top() + pad),
+                    size,
+                );
+                let (label, fill, stroke) = if row.conflict {
+                    (
+                        "CLASH",
+                        crate::app::conflicts::CLASH_COLOR,
+                        eg::Color32::from_rgb(150, 90, 16),
+                    )
+                } else {
+                    ("REC", palette.rec, palette.rec_stroke)
+                };
+                ui.painter()
+                    .rect_filled(rec_rect, eg::Rounding::same(6.0), fill);
+                ui.painter().rect_stroke(
+                    rec_rect,
+                    eg::Rounding::same(6.0),
+                    eg::Stroke::new(1.0, stroke),
+                );
+                ui.painter().text(
+                    rec_rect.center(),
+                    eg::Align2::CENTER_CENTER,
+                    label,
+                    eg::FontId::monospace(13.0 * s),
+                    if row.conflict {
+                        eg::Color32::WHITE
+                    } else {
+                        palette.on_accent
+                    },
+                );
+            }
+
+            if row.premiere {
+                // Below the REC badge when both are shown.
+                let pad = 6.0;
+                let top = if row.scheduled || row.conflict {
+                    22.0f32.mul_add(s, pad + 4.0)
+                } else {
+                    pad
+                };
+                draw_new_badge(
+                    ui.painter(),
+                    eg::pos2(poster_rect.left() + pad, poster_rect.top() + top),
+                    &palette,
+                    s,
+                );
+            }
+
+            // --- Compute statuses (needed for badges & dimming) ---
+            let broadcast_hd = Self::row_broadcast_hd(row);
+            let better_hd_available = self.row_hd_upgrade_available(row);
+
+            // Corner badge: show only for HD airings; SD gets no symbol
+            if better_hd_available {
+                draw_corner_badge(ui.painter(), poster_rect, "HD ↑", s);
+            } else if broadcast_hd {
+                draw_corner_badge(ui.painter(), poster_rect, "HD", s);
+            }
+
+            // Score pills sit above the channel logo when there is one.
+            let mut pill_rect = poster_rect;
+            if let Some(logo) = logo.as_ref() {
+                let lift = draw_channel_logo(ui.painter(), poster_rect, logo, s);
+                pill_rect.max.y -= lift;
+            }
+            if self.rating_badges {
+                if let Some(score) = self.rating_states.get(&row.key).and_then(|s| s.score()) {
+                    draw_rating_badge(ui.painter(), pill_rect, score, &palette, s);
+                }
+            }
+            if row.new_in_guide {
+                draw_new_in_guide_dot(ui.painter(), poster_rect, palette.new_in_guide, s);
+            }
+            if row.radarr {
+                draw_radarr_dot(ui.painter(), poster_rect, s);
+            }
+            if row.watched {
+                draw_watched_tick(ui.painter(), poster_rect, s);
+            }
+            if row.letterboxd {
+                draw_letterboxd_dots(ui.painter(), poster_rect, s);
+            }
+            if let Some(rating) = row.my_rating {
+                draw_my_rating_badge(ui.painter(), pill_rect, rating, s);
+            }
+            if row.imdb_watchlist {
+                draw_imdb_ribbon(ui.painter(), poster_rect, s);
+            }
+
+            // Dim overlay: do NOT dim if there's an HD upgrade airing
+            let should_dim = (row.owned && self.dim_owned && !better_hd_available)
+                || (row.watched && self.dim_watched);
+            if should_dim {
+                paint_dimmed(
+                    ui.painter(),
+                    poster_rect.expand(0.5),
+                    0.0,
+                    self.dim_strength_ui,
+                    &palette,
+                );
+            }
+
+            // Label; the poster wall preset has none (the hover preview and detail panel do)
+            if density.text_h() > 0.0 {
+                let title_line = row
+                    .year
+                    .map_or_else(|| row.title.clone(), |y| format!("{} ({})", row.title, y));
+                let ch = row
+                    .channel
+                    .as_deref()
+                    .map(crate::app::utils::humanize_channel)
+                    .unwrap_or_else(|| "—".into());
+                let line2 = if broadcast_hd {
+                    format!("{ch} • HD")
+                } else {
+                    ch
+                };
+                let tm = row
+                    .airing
+                    .map(crate::app::utils::hhmm_utc)
+                    .unwrap_or_else(|| "—".into());
+                let line3 = tm + " UTC";
+
+                let label_text = format!("{title}\n{line2}\n{line3}", title = title_line);
+                // Search matches are marked in the title, which starts the label.
+                let hits = self.title_highlights(&row.title);
+
+                ui.allocate_ui_at_rect(text_rect, |ui| {
+                    if hits.is_empty() {
+                        ui.add(
+                            eg::Label::new(eg::RichText::new(label_text).size(density.text_size()))
+                                .wrap(),
+                        );
+                    } else {
+                        let job = highlight_job(
+                            &label_text,
+                            &hits,
+                            eg::FontId::proportional(density.text_size()),
+                            ui.visuals().text_color(),
+                            &palette,
+                        );
+                        ui.add(eg::Label::new(job).wrap());
+                    }
+                });
+            }
+
+            // Selection stroke
+            if self.selected_idx == Some(idx) {
+                let highlight = poster_rect.expand(2.0);
+                ui.painter().rect_stroke(
+                    highlight,
+                    6.0,
+                    eg::Stroke::new(palette.outline_width, palette.selection),
+                );
+            } else if self.compare_idx == Some(idx) {
+                let highlight = poster_rect.expand(2.0);
+                ui.painter().rect_stroke(
+                    highlight,
+                    6.0,
+                    eg::Stroke::new(palette.outline_width, palette.compare),
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,195 @@
+// src/app/ui/list.rs
+use eframe::egui as eg;
+use egui_extras::{Column, TableBuilder};
+
+use crate::app::utils::{day_bucket, format_day_label, hhmm_utc};
+use crate::app::{OwnedTier, RatingState, SortKey};
+
+const ROW_H: f32 = 22.0;
+const HEADER_H: f32 = 24.0;
+
+impl crate::app::PexApp {
+    /// Visible rows as one flat list, sorted across all days by the current SortKey.
+    fn list_view_indices(&self) -> Vec<usize> {
+        let mut idxs = self.visible_row_indices();
+        self.sort_intra_day(&mut idxs);
+        self.rank_by_search_score(&mut idxs);
+        idxs
+    }
+
+    /// Sortable header cell: clicking selects the key, clicking again flips the direction.
+    fn list_sort_header(&mut self, ui: &mut eg::Ui, label: &str, key: SortKey) {
+        let active = self.sort_key == key;
+        let text = if active {
+            format!("{label} {}", if self.sort_desc { "⏷" } else { "⏶" })
+        } else {
+            label.to_string()
+        };
+        let resp = ui
+            .add(eg::Label::new(eg::RichText::new(text).strong()).sense(eg::Sense::click()))
+            .on_hover_text("Click to sort");
+        if resp.clicked() {
+            if active {
+                self.sort_desc = !self.sort_desc;
+            } else {
+                self.sort_key = key;
+                self.sort_desc = false;
+            }
+            self.mark_dirty();
+        }
+    }
+
+    fn list_rating_text(&self, idx: usize) -> String {
+        let Some(row) = self.rows.get(idx) else {
+            return String::new();
+        };
+        if let Some(summary) = self
+            .rating_states
+            .get(&row.key)
+            .and_then(RatingState::summary)
+        {
+            return summary;
+        }
+        match (row.critic_rating, row.audience_rating) {
+            (Some(c), Some(a)) => format!("{c:.1} / {a:.1}"),
+            (Some(c), None) => format!("{c:.1}"),
+            (None, Some(a)) => format!("{a:.1}"),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Compact text-only table of the filtered airings (no poster textures).
+    pub(crate) fn ui_render_list(&mut self, ui: &mut eg::Ui, ctx: &eg::Context) {
+        let groups = self.build_grouped_indices();
+        self.sync_selection_with_groups(&groups);
+        drop(groups);
+        let idxs = self.list_view_indices();
+
+        // One entry per table row so Up/Down keyboard navigation walks the list.
+        self.grid_rows = idxs.iter().map(|&idx| vec![idx]).collect();
+        self.visible_idxs.clear();
+        self.handle_keyboard_navigation(ctx);
+
+        if idxs.is_empty() {
+            self.ui_render_empty_state(ui);
+            return;
+        }
+
+        let scroll_row = self
+            .scroll_to_idx
+            .take()
+            .and_then(|target| idxs.iter().position(|&idx| idx == target));
+
+        ui.push_id("list_view_table", |ui| {
+            self.ui_render_list_table(ui, &idxs, scroll_row)
+        });
+    }
+
+    fn ui_render_list_table(&mut self, ui: &mut eg::Ui, idxs: &[usize], scroll_row: Option<usize>) {
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(eg::Sense::click())
+            .cell_layout(eg::Layout::left_to_right(eg::Align::Center))
+            .column(Column::remainder().at_least(180.0).clip(true))
+            .column(Column::auto().at_least(44.0))
+            .column(Column::initial(150.0).at_least(80.0).clip(true))
+            .column(Column::initial(170.0).at_least(110.0))
+            .column(Column::initial(160.0).at_least(80.0).clip(true))
+            .column(Column::auto().at_least(60.0))
+            .column(Column::auto().at_least(50.0))
+            .column(Column::auto().at_least(36.0));
+        if let Some(row_i) = scroll_row {
+            table = table.scroll_to_row(row_i, Some(eg::Align::Center));
+        }
+
+        table
+            .header(HEADER_H, |mut header| {
+                header.col(|ui| self.list_sort_header(ui, "Title", SortKey::Title));
+                header.col(|ui| {
+                    ui.strong("Year");
+                });
+                header.col(|ui| self.list_sort_header(ui, "Channel", SortKey::Channel));
+                header.col(|ui| self.list_sort_header(ui, "Time (UTC)", SortKey::Time));
+                header.col(|ui| self.list_sort_header(ui, "Genres", SortKey::Genre));
+                header.col(|ui| self.list_sort_header(ui, "Rating", SortKey::Rating));
+                header.col(|ui| self.list_sort_header(ui, "Owned", SortKey::OwnedDate));
+                header.col(|ui| {
+                    ui.strong("HD");
+                });
+            })
+            .body(|body| {
+                body.rows(ROW_H, idxs.len(), |mut table_row| {
+                    let idx = idxs[table_row.index()];
+                    let Some(row) = self.rows.get(idx) else {
+                        return;
+                    };
+                    table_row.set_selected(self.selected_idx == Some(idx));
+
+                    let broadcast_hd = Self::row_broadcast_hd(row);
+                    let owned_tier = self.row_owned_tier(row);
+                    let title = if row.conflict {
+                        format!("⚠ {}", row.title)
+                    } else if row.scheduled {
+                        format!("⏺ {}", row.title)
+                    } else if row.watched {
+                        format!("✓ {}", row.title)
+                    } else {
+                        row.title.clone()
+                    };
+                    let time = row
+                        .airing
+                        .map(|ts| format!("{} {}", format_day_label(day_bucket(ts)), hhmm_utc(ts)))
+                        .unwrap_or_default();
+                    let owned = match owned_tier {
+                        None => "",
+                        Some(OwnedTier::Uhd) => "✔ 4K",
+                        Some(OwnedTier::Hd) => "✔ HD",
+                        Some(OwnedTier::Sd) => "✔",
+                    };
+                    let hd = if self.row_hd_upgrade_available(row) {
+                        "HD ↑"
+                    } else if broadcast_hd {
+                        "HD"
+                    } else {
+                        ""
+                    };
+                    let year = row.year.map(|y| y.to_string()).unwrap_or_default();
+                    let channel = row.channel.clone().unwrap_or_default();
+                    let genres = row.genres.join(", ");
+                    let rating = self.list_rating_text(idx);
+
+                    table_row.col(|ui| {
+                        ui.add(eg::Label::new(title).truncate());
+                    });
+                    table_row.col(|ui| {
+                        ui.label(year);
+                    });
+                    table_row.col(|ui| {
+                        ui.add(eg::Label::new(channel).truncate());
+                    });
+                    table_row.col(|ui| {
+                        ui.label(time);
+                    });
+                    table_row.col(|ui| {
+                        ui.add(eg::Label::new(genres).truncate());
+                    });
+                    table_row.col(|ui| {
+                        ui.label(rating);
+                    });
+                    table_row.col(|ui| {
+                        ui.label(owned);
+                    });
+                    table_row.col(|ui| {
+                        ui.label(hd);
+                    });
+
+                    let resp = table_row.response();
+                    if resp.clicked() {
+                        self.click_row(idx, &resp);
+                    }
+                    self.visible_idxs.insert(idx);
+                });
+            });
+    }
+}
@@ -0,0 +1,182 @@
+// src/app/ui/now_next.rs
+use std::time::{Duration, UNIX_EPOCH};
+
+use eframe::egui as eg;
+
+use crate::app::utils::hhmm_utc;
+
+const LABEL_W: f32 = 150.0;
+const CELL_W: f32 = 300.0;
+/// The view only changes as programmes start and end, so a minute's granularity is enough.
+const REFRESH: Duration = Duration::from_secs(60);
+
+/// "45 min" or "1h 10m".
+fn format_minutes(secs: i64) -> String {
+    let mins = (secs.max(0) + 59) / 60;
+    if mins < 60 {
+        format!("{mins} min")
+    } else {
+        format!("{}h {:02}m", mins / 60, mins % 60)
+    }
+}
+
+/// One channel's row: the airing on now (if any) and the one after it.
+struct NowNextRow {
+    channel: String,
+    now: Option<usize>,
+    next: Option<usize>,
+}
+
+impl crate::app::PexApp {
+    /// Per channel (filters applied), what is airing right now and what starts next.
+    fn build_now_next(&self, now: i64) -> Vec<NowNextRow> {
+        let groups = self.build_grouped_indices();
+        let lanes = self.build_timeline_lanes(groups.into_iter().flat_map(|(_, idxs)| idxs));
+        lanes
+            .into_iter()
+            .filter_map(|lane| {
+                let spans: Vec<(usize, (i64, i64))> = lane
+                    .idxs
+                    .iter()
+                    .filter_map(|&idx| Some((idx, self.timeline_span(idx)?)))
+                    .collect();
+                let on_now = spans
+                    .iter()
+                    .rev()
+                    .find(|(_, (start, end))| *start <= now && now < *end)
+                    .map(|(idx, _)| *idx);
+                let next = spans
+                    .iter()
+                    .find(|(_, (start, _))| *start > now)
+                    .map(|(idx, _)| *idx);
+                (on_now.is_some() || next.is_some()).then_some(NowNextRow {
+                    channel: lane.label,
+                    now: on_now,
+                    next,
+                })
+            })
+            .collect()
+    }
+
+    /// "On now & next": one line per channel with the current film (and how far through it
+    /// is) and the next one, rebuilt from the airing times every minute.
+    pub(crate) fn ui_render_now_next(&mut self, ui: &mut eg::Ui, ctx: &eg::Context) {
+        ctx.request_repaint_after(REFRESH);
+        let now = self
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let rows = self.build_now_next(now);
+
+        // Left/right moves between now and next, up/down between channels.
+        self.grid_rows = rows
+            .iter()
+            .map(|row| row.now.into_iter().chain(row.next).collect())
+            .collect();
+        self.visible_idxs.clear();
+        self.handle_keyboard_navigation(ctx);
+
+        if rows.is_empty() {
+            ui.add_space(12.0);
+            ui.label("Nothing on now or coming up that matches the current filters.");
+            return;
+        }
+
+        let scroll_to = self.scroll_to_idx.take();
+        eg::ScrollArea::vertical()
+            .id_source("now_next_scroll")
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                eg::Grid::new("now_next_grid")
+                    .striped(true)
+                    .num_columns(3)
+                    .min_col_width(LABEL_W)
+                    .spacing([12.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.strong("Channel");
+                        ui.strong(format!("On now ({} UTC)", hhmm_utc(self.now())));
+                        ui.strong("Next");
+                        ui.end_row();
+
+                        for row in &rows {
+                            ui.add_sized(
+                                [LABEL_W, 0.0],
+                                eg::Label::new(eg::RichText::new(&row.channel).strong()).truncate(),
+                            );
+                            for (idx, is_now) in [(row.now, true), (row.next, false)] {
+                                match idx {
+                                    Some(idx) => {
+                                        self.now_next_cell(ui, idx, is_now, now, scroll_to)
+                                    }
+                                    None => {
+                                        ui.label(eg::RichText::new("—").weak());
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn now_next_cell(
+        &mut self,
+        ui: &mut eg::Ui,
+        idx: usize,
+        is_now: bool,
+        now: i64,
+        scroll_to: Option<usize>,
+    ) {
+        let (Some(row), Some((start, end))) = (self.rows.get(idx), self.timeline_span(idx)) else {
+            return;
+        };
+        let title = row
+            .year
+            .map_or_else(|| row.title.clone(), |y| format!("{} ({y})", row.title));
+        let at = |secs: i64| hhmm_utc(UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64));
+        let when = if is_now {
+            format!(
+                "{}–{} · {} left",
+                at(start),
+                at(end),
+                format_minutes(end - now)
+            )
+        } else {
+            format!("{} · in {}", at(start), format_minutes(start - now))
+        };
+        let progress =
+            is_now.then(|| ((now - start) as f32 / (end - start).max(1) as f32).clamp(0.0, 1.0));
+        let selected = self.selected_idx == Some(idx);
+
+        let inner = ui.vertical(|ui| {
+            ui.set_width(CELL_W);
+            ui.add(eg::Label::new(eg::RichText::new(title).strong()).truncate());
+            ui.label(eg::RichText::new(when).weak());
+            if let Some(progress) = progress {
+                ui.add(eg::ProgressBar::new(progress).desired_height(4.0));
+            }
+        });
+        let resp = ui.interact(
+            inner.response.rect,
+            eg::Id::new(("now_next_cell", idx)),
+            eg::Sense::click(),
+        );
+        if selected {
+            let palette = self.palette();
+            ui.painter().rect_stroke(
+                inner.response.rect.expand(3.0),
+                eg::Rounding::same(4.0),
+                eg::Stroke::new(palette.outline_width, palette.selection),
+            );
+        }
+        if resp.clicked() {
+            self.click_row(idx, &resp);
+        }
+        if scroll_to == Some(idx) {
+            resp.scroll_to_me(Some(eg::Align::Center));
+        }
+        self.describe_card(&resp, idx);
+        self.visible_idxs.insert(idx);
+    }
+}
@@ -0,0 +1,346 @@
+// src/app/ui/timeline.rs
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use eframe::egui as eg;
+
+const LANE_H: f32 = 44.0;
+const LABEL_W: f32 = 150.0;
+const HEADER_H: f32 = 28.0;
+const PX_PER_MIN: f32 = 3.0;
+const DEFAULT_BLOCK_SECS: i64 = 2 * 60 * 60; // films without an end time
+
+/// One channel row in the timeline: humanized label + row indices sorted by start time.
+pub(crate) struct TimelineLane {
+    pub(crate) label: String,
+    pub(crate) idxs: Vec<usize>,
+}
+
+fn unix_secs(ts: SystemTime) -> i64 {
+    ts.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+impl crate::app::PexApp {
+    /// Start/end (unix secs) of a programme block; falls back to a two-hour slot.
+    pub(crate) fn timeline_span(&self, idx: usize) -> Option<(i64, i64)> {
+        let row = self.rows.get(idx)?;
+        let start = unix_secs(row.airing?);
+        let end = row
+            .ends
+            .map(unix_secs)
+            .filter(|end| *end > start)
+            .unwrap_or(start + DEFAULT_BLOCK_SECS);
+        Some((start, end))
+    }
+
+    /// Group the given rows into channel lanes, ordered by the raw channel title
+    /// (which keeps the broadcaster's numbering, e.g. "006 ITV2").
+    pub(crate) fn build_timeline_lanes(
+        &self,
+        idxs: impl IntoIterator<Item = usize>,
+    ) -> Vec<TimelineLane> {
+        let mut by_channel: BTreeMap<String, TimelineLane> = BTreeMap::new();
+        for idx in idxs {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            if row.airing.is_none() {
+                continue;
+            }
+            let label = row
+                .channel
+                .clone()
+                .or_else(|| row.channel_raw.clone())
+                .unwrap_or_else(|| "—".into());
+            let sort_key = row.channel_title.clone().unwrap_or_else(|| label.clone());
+            by_channel
+                .entry(format!("{sort_key}\u{0}{label}"))
+                .or_insert_with(|| TimelineLane {
+                    label,
+                    idxs: Vec::new(),
+                })
+                .idxs
+                .push(idx);
+        }
+
+        let mut lanes: Vec<TimelineLane> = by_channel.into_values().collect();
+        for lane in &mut lanes {
+            lane.idxs
+                .sort_by_key(|&i| self.timeline_span(i).map_or(i64::MAX, |(s, _)| s));
+        }
+        lanes
+    }
+
+    pub(crate) fn ui_render_timeline(&mut self, ui: &mut eg::Ui, ctx: &eg::Context) {
+        let groups = self.build_grouped_indices();
+        self.sync_selection_with_groups(&groups);
+        let lanes = self.build_timeline_lanes(groups.into_iter().flat_map(|(_, idxs)| idxs));
+        let palette = self.palette();
+
+        // Reuse the grid's keyboard navigation: left/right walks a channel, up/down switches lanes.
+        self.grid_rows = lanes.iter().map(|lane| lane.idxs.clone()).collect();
+        self.visible_idxs.clear();
+        self.handle_keyboard_navigation(ctx);
+
+        let mut t0 = i64::MAX;
+        let mut t1 = i64::MIN;
+        for idx in lanes.iter().flat_map(|lane| lane.idxs.iter().copied()) {
+            if let Some((start, end)) = self.timeline_span(idx) {
+                t0 = t0.min(start);
+                t1 = t1.max(end);
+            }
+        }
+        if lanes.is_empty() {
+            self.ui_render_empty_state(ui);
+            return;
+        }
+        if t0 > t1 {
+            ui.add_space(12.0);
+            ui.label("No airings match the current filters.");
+            return;
+        }
+        t0 -= t0.rem_euclid(3600); // align the axis to the hour
+
+        let total_w = (((t1 - t0) as f32) / 60.0).mul_add(PX_PER_MIN, LABEL_W + 40.0);
+        let total_h = (lanes.len() as f32).mul_add(LANE_H, HEADER_H);
+        let now_secs = unix_secs(self.now());
+        let mut uploads_left = super::super::MAX_UPLOADS_PER_FRAME;
+
+        eg::ScrollArea::both()
+            .id_source("timeline_scroll")
+            .auto_shrink([false; 2])
+            .show_viewport(ui, |ui, viewport| {
+                let (content, _) =
+                    ui.allocate_exact_size(eg::vec2(total_w, total_h), eg::Sense::hover());
+                let origin = content.min;
+                let visible = viewport.translate(origin.to_vec2());
+                let x_of = |ts: i64| origin.x + LABEL_W + ((ts - t0) as f32 / 60.0) * PX_PER_MIN;
+                let lane_top = |lane_i: usize| origin.y + HEADER_H + lane_i as f32 * LANE_H;
+                let block_rect = |lane_i: usize, start: i64, end: i64| {
+                    let y = lane_top(lane_i);
+                    eg::Rect::from_min_max(
+                        eg::pos2(x_of(start) + 1.0, y + 3.0),
+                        eg::pos2((x_of(end) - 1.0).max(x_of(start) + 4.0), y + LANE_H - 3.0),
+                    )
+                };
+
+                // Keyboard selection may target a lane that is currently off-screen.
+                if let Some(target) = self.scroll_to_idx.take() {
+                    let lane_i = lanes.iter().position(|lane| lane.idxs.contains(&target));
+                    if let (Some(lane_i), Some((s, e))) = (lane_i, self.timeline_span(target)) {
+                        let rect = block_rect(lane_i, s, e);
+                        ui.scroll_to_rect(
+                            rect.expand2(eg::vec2(LABEL_W, HEADER_H)),
+                            Some(eg::Align::Center),
+                        );
+                    }
+                }
+
+                let blocks_area =
+                    eg::Rect::from_min_max(visible.min + eg::vec2(LABEL_W, HEADER_H), visible.max);
+                let painter = ui.painter().clone();
+                let first_lane = ((viewport.min.y - HEADER_H) / LANE_H).floor().max(0.0) as usize;
+                let last_lane = (((viewport.max.y - HEADER_H) / LANE_H).ceil().max(0.0) as usize)
+                    .min(lanes.len());
+
+                // Programme blocks
+                for (lane_i, lane) in lanes.iter().enumerate().take(last_lane).skip(first_lane) {
+                    if lane_i % 2 == 1 {
+                        painter.rect_filled(
+                            eg::Rect::from_min_max(
+                                eg::pos2(visible.min.x, lane_top(lane_i)),
+                                eg::pos2(visible.max.x, lane_top(lane_i) + LANE_H),
+                            ),
+                            0.0,
+                            ui.visuals().faint_bg_color,
+                        );
+                    }
+
+                    for &idx in &lane.idxs {
+                        let Some((start, end)) = self.timeline_span(idx) else {
+                            continue;
+                        };
+                        let rect = block_rect(lane_i, start, end);
+                        if !rect.intersects(blocks_area) {
+                            continue;
+                        }
+
+                        // Only the part not covered by the sticky header/column is clickable.
+                        let resp = ui.interact(
+                            rect.intersect(blocks_area),
+                            eg::Id::new(("timeline_block", idx)),
+                            eg::Sense::click(),
+                        );
+                        if resp.clicked() {
+                            self.click_row(idx, &resp);
+                        }
+                        self.describe_card(&resp, idx);
+                        self.visible_idxs.insert(idx);
+                        // Keep the detail panel poster warm for blocks on screen.
+                        if uploads_left > 0 && self.try_lazy_upload_row(ctx, idx) {
+                            uploads_left -= 1;
+                        }
+
+                        let Some(row) = self.rows.get(idx) else {
+                            continue;
+                        };
+                        let broadcast_hd = Self::row_broadcast_hd(row);
+                        let better_hd_available = self.row_hd_upgrade_available(row);
+                        let fill = if resp.hovered() {
+                            palette.block_hover
+                        } else {
+                            palette.block
+                        };
+                        painter.rect_filled(rect, eg::Rounding::same(4.0), fill);
+                        if (row.owned && self.dim_owned && !better_hd_available)
+                            || (row.watched && self.dim_watched)
+                        {
+                            crate::app::theme::paint_dimmed(
+                                &painter,
+                                rect,
+                                4.0,
+                                self.dim_strength_ui,
+                                &palette,
+                            );
+                        }
+                        if row.scheduled || row.conflict {
+                            let color = if row.conflict {
+                                crate::app::conflicts::CLASH_COLOR
+                            } else {
+                                palette.rec
+                            };
+                            painter.rect_stroke(
+                                rect,
+                                eg::Rounding::same(4.0),
+                                eg::Stroke::new(1.5, color),
+                            );
+                        }
+                        if self.selected_idx == Some(idx) {
+                            painter.rect_stroke(
+                                rect.expand(1.0),
+                                eg::Rounding::same(4.0),
+                                eg::Stroke::new(palette.outline_width, palette.selection),
+                            );
+                        } else if self.compare_idx == Some(idx) {
+                            painter.rect_stroke(
+                                rect.expand(1.0),
+                                eg::Rounding::same(4.0),
+                                eg::Stroke::new(palette.outline_width, palette.compare),
+                            );
+                        }
+
+                        // Keep titles readable when a block starts left of the viewport.
+                        let text_left = rect.left().max(visible.left() + LABEL_W) + 6.0;
+                        let text_clip = rect.shrink(2.0).intersect(visible);
+                        let mut title = row.title.clone();
+                        if better_hd_available {
+                            title.push_str("  HD ↑");
+                        } else if broadcast_hd {
+                            title.push_str("  HD");
+                        }
+                        painter.with_clip_rect(text_clip).text(
+                            eg::pos2(text_left, rect.center().y),
+                            eg::Align2::LEFT_CENTER,
+                            title,
+                            eg::FontId::proportional(13.0),
+                            ui.visuals().strong_text_color(),
+                        );
+
+                        let tip_end = row
+                            .ends
+                            .map(crate::app::utils::hhmm_utc)
+                            .map(|t| format!("–{t}"))
+                            .unwrap_or_default();
+                        let tip_start = row
+                            .airing
+                            .map(crate::app::utils::hhmm_utc)
+                            .unwrap_or_default();
+                        resp.on_hover_text(format!(
+                            "{}\n{} {tip_start}{tip_end} UTC",
+                            row.title, lane.label
+                        ));
+                    }
+                }
+
+                // "Now" marker
+                if now_secs >= t0 && now_secs <= t1 {
+                    let x = x_of(now_secs);
+                    painter.line_segment(
+                        [
+                            eg::pos2(x, visible.top() + HEADER_H),
+                            eg::pos2(x, visible.bottom()),
+                        ],
+                        eg::Stroke::new(1.5, palette.error),
+                    );
+                }
+
+                // Sticky hour header
+                let header = eg::Rect::from_min_max(
+                    visible.min,
+                    eg::pos2(visible.max.x, visible.min.y + HEADER_H),
+                );
+                painter.rect_filled(header, 0.0, ui.visuals().panel_fill);
+                // t0 is hour-aligned, so the first visible tick is a whole number of hours in.
+                let scrolled_secs = ((viewport.min.x.max(0.0) / PX_PER_MIN) * 60.0) as i64;
+                let mut hour = t0 + (scrolled_secs / 3600) * 3600;
+                while hour <= t1 && x_of(hour) <= visible.right() {
+                    let x = x_of(hour);
+                    painter.line_segment(
+                        [
+                            eg::pos2(x, header.bottom() - 6.0),
+                            eg::pos2(x, header.bottom()),
+                        ],
+                        eg::Stroke::new(1.0, ui.visuals().weak_text_color()),
+                    );
+                    let label = if hour.rem_euclid(86_400) == 0 {
+                        crate::app::utils::format_day_label(hour / 86_400)
+                    } else {
+                        format!("{:02}:00", hour.rem_euclid(86_400) / 3600)
+                    };
+                    painter.text(
+                        eg::pos2(x + 3.0, header.center().y),
+                        eg::Align2::LEFT_CENTER,
+                        label,
+                        eg::FontId::proportional(12.0),
+                        ui.visuals().text_color(),
+                    );
+                    hour += 3600;
+                }
+
+                // Sticky channel column
+                let column = eg::Rect::from_min_max(
+                    eg::pos2(visible.min.x, visible.min.y + HEADER_H),
+                    eg::pos2(visible.min.x + LABEL_W, visible.max.y),
+                );
+                painter.rect_filled(column, 0.0, ui.visuals().panel_fill);
+                for (lane_i, lane) in lanes.iter().enumerate().take(last_lane).skip(first_lane) {
+                    let y = lane_top(lane_i);
+                    let cell = eg::Rect::from_min_max(
+                        eg::pos2(column.left(), y),
+                        eg::pos2(column.right() - 4.0, y + LANE_H),
+                    );
+                    painter.with_clip_rect(cell.intersect(column)).text(
+                        eg::pos2(cell.left() + 6.0, cell.center().y),
+                        eg::Align2::LEFT_CENTER,
+                        &lane.label,
+                        eg::FontId::proportional(13.0),
+                        ui.visuals().strong_text_color(),
+                    );
+                }
+                painter.rect_filled(
+                    eg::Rect::from_min_size(visible.min, eg::vec2(LABEL_W, HEADER_H)),
+                    0.0,
+                    ui.visuals().panel_fill,
+                );
+                painter.text(
+                    eg::pos2(visible.min.x + 6.0, visible.min.y + HEADER_H * 0.5),
+                    eg::Align2::LEFT_CENTER,
+                    "UTC",
+                    eg::FontId::proportional(12.0),
+                    ui.visuals().weak_text_color(),
+                );
+            });
+    }
+}
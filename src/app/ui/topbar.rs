@@ -1,9 +1,14 @@
 // src/app/ui/topbar.rs
-use super::super::{DayRange, SortKey};
+use super::super::{DayRange, GroupBy, SortKey, ViewMode};
+use crate::app::export::{default_guide_export_path, ExportFormat};
+use crate::app::facets::Facet;
+use crate::app::file_picker::{PendingPick, PickKind};
+use crate::app::health::{Health, Subsystem};
 use crate::config::AppConfig;
 
 use eframe::egui as eg;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::path::Path;
 
 struct DbSummary<'a> {
@@ -17,7 +22,7 @@ struct DbSummary<'a> {
     library_local_exists: bool,
     cache_dir: &'a Path,
     cache_exists: bool,
-    tmdb_key_present: bool,
+    rating_sources: Vec<&'static str>,
 }
 impl crate::app::PexApp {
     // ---------- TOP BAR ----------
@@ -52,32 +57,103 @@ impl crate::app::PexApp {
 
             ui.separator();
 
+            for (mode, label, hint) in [
+                (ViewMode::Grid, "Grid", "Poster grid grouped by day"),
+                (
+                    ViewMode::Timeline,
+                    "Timeline",
+                    "Channels as rows, time across",
+                ),
+                (ViewMode::List, "List", "Sortable text table, no posters"),
+                (
+                    ViewMode::NowNext,
+                    "On now",
+                    "What's airing right now and next on each channel",
+                ),
+            ] {
+                if ui
+                    .selectable_value(&mut self.view_mode, mode, label)
+                    .on_hover_text(hint)
+                    .changed()
+                {
+                    dirty = true;
+                }
+            }
+
+            ui.separator();
+
+            let search = ui.add(
+                eg::TextEdit::singleline(&mut self.search_query)
+                    .hint_text("Title, genre:…, channel:…")
+                    .desired_width(160.0),
+            );
+            if self.search_focus_pending {
+                search.request_focus();
+                self.search_focus_pending = false;
+            }
+            if search.changed() {
+                dirty = true;
+            }
+            if !self.search_query.is_empty()
+                && ui.small_button("X").on_hover_text("Clear search").clicked()
+            {
+                self.search_query.clear();
+                dirty = true;
+            }
+            if let Some(Err(err)) = self.regex_search.active() {
+                ui.label(eg::RichText::new("⚠ Invalid regex").color(eg::Color32::LIGHT_RED))
+                    .on_hover_text(format!("{err}\nThe search is ignored until the pattern is fixed."));
+            }
             if ui
-                .add(
-                    eg::TextEdit::singleline(&mut self.search_query)
-                        .hint_text("Title.")
-                        .desired_width(160.0),
+                .checkbox(&mut self.regex_search.enabled, "Regex")
+                .on_hover_text(
+                    "Treat the search text as a case-insensitive regular expression, e.g. \
+                     ^(The )?Matrix. Wrapping the text in slashes (/pattern/) does the same \
+                     without the toggle.",
                 )
                 .changed()
             {
                 dirty = true;
             }
-            if !self.search_query.is_empty()
-                && ui.small_button("X").on_hover_text("Clear search").clicked()
+            if ui
+                .checkbox(&mut self.search_all_fields, "All fields")
+                .on_hover_text(
+                    "Match the search text against genres, channel and summary as well as the \
+                     title. Prefixes limit a word to one field: title:, genre:western, \
+                     channel:bbc, summary:\"bank heist\"",
+                )
+                .changed()
+            {
+                dirty = true;
+            }
+            if ui
+                .checkbox(&mut self.fuzzy_search, "Fuzzy")
+                .on_hover_text(
+                    "Also match misspelt or partly typed titles (\"godfater\" finds The Godfather); \
+                     best matches are listed first",
+                )
+                .changed()
             {
-                self.search_query.clear();
                 dirty = true;
             }
 
             ui.separator();
 
             let filters_menu_active = self.filter_hd_only
+                || self.filter_premieres_only
+                || self.filter_new_only
+                || self.filter_letterboxd_only
                 || self.filter_owned_before_cutoff
                 || !self.selected_decades.is_empty()
+                || self.year_min.is_some()
+                || self.year_max.is_some()
                 || !self.selected_channels.is_empty()
                 || !self.selected_genres.is_empty()
+                || !self.selected_tags.is_empty()
                 || self.hide_owned
-                || self.dim_owned;
+                || self.dim_owned
+                || self.hide_watched
+                || self.dim_watched;
             let filters_label: eg::WidgetText = if filters_menu_active {
                 eg::RichText::new("Filters").strong().into()
             } else {
@@ -85,13 +161,59 @@ impl crate::app::PexApp {
             };
             let mut menu_dirty = false;
             ui.menu_button(filters_label, |ui| {
+                let counts = self.facet_counts();
+                let counted = |label: &str, facet: Facet| format!("{label} ({})", counts.toggle(facet));
+                ui.label(
+                    eg::RichText::new("(n) = airings shown with that option on")
+                        .small()
+                        .weak(),
+                );
                 if ui
-                    .checkbox(&mut self.filter_hd_only, "HD only")
+                    .checkbox(&mut self.filter_hd_only, counted("HD only", Facet::HdOnly))
                     .on_hover_text("Show only broadcast HD airings")
                     .changed()
                 {
                     menu_dirty = true;
                 }
+                if ui
+                    .checkbox(
+                        &mut self.filter_premieres_only,
+                        counted("Premieres only", Facet::PremieresOnly),
+                    )
+                    .on_hover_text(
+                        "Show only first showings: airings the guide marks as premieres, or \
+                         films that were not in any earlier guide",
+                    )
+                    .changed()
+                {
+                    menu_dirty = true;
+                }
+                if ui
+                    .checkbox(
+                        &mut self.filter_new_only,
+                        counted("New in guide only", Facet::NewOnly),
+                    )
+                    .on_hover_text(
+                        "Show only airings added to the guide since Pex last ran \
+                         (marked with a green dot)",
+                    )
+                    .changed()
+                {
+                    menu_dirty = true;
+                }
+                if ui
+                    .checkbox(
+                        &mut self.filter_letterboxd_only,
+                        counted("Letterboxd watchlist only", Facet::LetterboxdOnly),
+                    )
+                    .on_hover_text(
+                        "Show only films on the Letterboxd watchlist imported in Advanced \
+                         (marked with three dots)",
+                    )
+                    .changed()
+                {
+                    menu_dirty = true;
+                }
 
                 let decades = self.available_decades();
                 if !decades.is_empty() {
@@ -100,7 +222,8 @@ impl crate::app::PexApp {
                     ui.horizontal_wrapped(|ui| {
                         for decade in decades {
                             let mut selected = self.selected_decades.contains(&decade);
-                            let label = format!("{decade}s");
+                            let count = counts.decades.get(&decade).copied().unwrap_or(0);
+                            let label = format!("{decade}s ({count})");
                             if ui.checkbox(&mut selected, label).changed() {
                                 if selected {
                                     self.selected_decades.insert(decade);
@@ -119,10 +242,49 @@ impl crate::app::PexApp {
                     }
                 }
 
+                if let Some((first, last)) = self.available_year_bounds() {
+                    ui.separator();
+                    ui.label(eg::RichText::new("Year range").strong());
+                    let mut limit = self.year_min.is_some() || self.year_max.is_some();
+                    if ui
+                        .checkbox(&mut limit, "Limit release years")
+                        .on_hover_text("Show only films released between the two years (inclusive)")
+                        .changed()
+                    {
+                        if limit {
+                            self.year_min = Some(first);
+                            self.year_max = Some(last);
+                        } else {
+                            self.year_min = None;
+                            self.year_max = None;
+                        }
+                        menu_dirty = true;
+                    }
+                    if limit {
+                        let mut lo = self.year_min.unwrap_or(first);
+                        let mut hi = self.year_max.unwrap_or(last);
+                        ui.horizontal(|ui| {
+                            let lo_changed = ui
+                                .add(eg::DragValue::new(&mut lo).range(first.min(hi)..=hi))
+                                .changed();
+                            ui.label("–");
+                            let hi_changed = ui
+                                .add(eg::DragValue::new(&mut hi).range(lo..=last.max(lo)))
+                                .changed();
+                            if lo_changed || hi_changed {
+                                self.year_min = Some(lo);
+                                self.year_max = Some(hi);
+                                menu_dirty = true;
+                            }
+                        });
+                    }
+                }
+
                 ui.separator();
                 ui.label(eg::RichText::new("Channel & Genre").strong());
                 if ui.button("Select channels…").clicked() {
                     self.show_channel_filter_popup = true;
+                    self.channel_filter_focus_pending = true;
                     ui.close_menu();
                 }
                 if ui.button("Select genres…").clicked() {
@@ -139,9 +301,41 @@ impl crate::app::PexApp {
                     menu_dirty = true;
                 }
 
+                ui.separator();
+                ui.label(eg::RichText::new("Tags").strong());
+                for (tag, count) in self.title_tags.counts() {
+                    let mut checked = self.selected_tags.contains(&tag);
+                    if ui
+                        .checkbox(&mut checked, format!("{tag} ({count})"))
+                        .changed()
+                    {
+                        if checked {
+                            self.selected_tags.insert(tag);
+                        } else {
+                            self.selected_tags.remove(&tag);
+                        }
+                        menu_dirty = true;
+                    }
+                }
+                if ui
+                    .button("Manage tags…")
+                    .on_hover_text("Rename or delete tags; add them to a film in the detail panel")
+                    .clicked()
+                {
+                    self.title_tags.manager_open = true;
+                    ui.close_menu();
+                }
+                if !self.selected_tags.is_empty() && ui.small_button("Clear tags").clicked() {
+                    self.selected_tags.clear();
+                    menu_dirty = true;
+                }
+
                 ui.separator();
                 ui.label(eg::RichText::new("Owned recorded before").strong());
-                let checkbox_label = format!("Enable cutoff ({})", self.owned_before_cutoff_input);
+                let checkbox_label = counted(
+                    &format!("Enable cutoff {}", self.owned_before_cutoff_input),
+                    Facet::OwnedCutoff,
+                );
                 if ui
                     .checkbox(&mut self.filter_owned_before_cutoff, checkbox_label)
                     .on_hover_text("Only show owned titles recorded before the cutoff date")
@@ -175,7 +369,7 @@ impl crate::app::PexApp {
                 ui.separator();
                 ui.label(eg::RichText::new("Owned view").strong());
                 let hide_resp =
-                    ui.checkbox(&mut self.hide_owned, "Hide owned (except HD upgrades)");
+                    ui.checkbox(&mut self.hide_owned, counted("Hide owned, except HD upgrades", Facet::HideOwned));
                 if hide_resp.changed() {
                     menu_dirty = true;
                 }
@@ -190,24 +384,59 @@ impl crate::app::PexApp {
                 if dim_toggled || slider_changed {
                     menu_dirty = true;
                 }
+
+                ui.separator();
+                ui.label(eg::RichText::new("Watched (Trakt)").strong());
+                if ui
+                    .checkbox(&mut self.hide_watched, counted("Hide watched", Facet::HideWatched))
+                    .on_hover_text("Hide films in your Trakt watch history (Advanced ▸ Trakt)")
+                    .changed()
+                {
+                    menu_dirty = true;
+                }
+                if ui
+                    .checkbox(&mut self.dim_watched, "Dim watched")
+                    .on_hover_text("Darken watched films by the owned Darken % above")
+                    .changed()
+                {
+                    menu_dirty = true;
+                }
             });
             if menu_dirty {
                 dirty = true;
             }
+            self.ui_render_channel_group_chips(ui);
 
             ui.separator();
 
             if ui.button("Advanced.").clicked() {
                 self.show_advanced_popup = true;
             }
+            if ui
+                .button("Collections")
+                .on_hover_text("Franchises with upcoming airings of films you don't own")
+                .clicked()
+            {
+                self.collections.show_popup = true;
+            }
+            if ui
+                .button("Library")
+                .on_hover_text("Browse every file the owned scan indexed")
+                .clicked()
+            {
+                self.owned_browser.open();
+            }
+            self.ui_render_alerts_button(ui);
 
             ui.separator();
 
-            const SORT_OPTIONS: [(SortKey, &str); 4] = [
+            const SORT_OPTIONS: [(SortKey, &str); 6] = [
                 (SortKey::Time, "Sort: Time"),
                 (SortKey::Title, "Sort: Title"),
                 (SortKey::Channel, "Sort: Channel"),
                 (SortKey::Genre, "Sort: Genre"),
+                (SortKey::Rating, "Sort: Rating"),
+                (SortKey::OwnedDate, "Sort: Owned date"),
             ];
             let sort_label = SORT_OPTIONS
                 .iter()
@@ -229,15 +458,61 @@ impl crate::app::PexApp {
             if ui.checkbox(&mut self.sort_desc, "Desc").changed() {
                 dirty = true;
             }
+            let then_label = |key: Option<SortKey>| {
+                let name = SORT_OPTIONS
+                    .iter()
+                    .find(|(k, _)| Some(*k) == key)
+                    .map_or("None", |(_, label)| label.trim_start_matches("Sort: "));
+                format!("Then: {name}")
+            };
+            eg::ComboBox::from_id_source("then_by_combo")
+                .selected_text(then_label(self.sort_key2))
+                .show_ui(ui, |ui| {
+                    let keys = std::iter::once(None).chain(
+                        SORT_OPTIONS
+                            .iter()
+                            .map(|(key, _)| Some(*key))
+                            .filter(|key| *key != Some(self.sort_key)),
+                    );
+                    for key in keys {
+                        if ui
+                            .selectable_value(&mut self.sort_key2, key, then_label(key))
+                            .clicked()
+                        {
+                            dirty = true;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Secondary sort for airings the main sort ties on");
+            eg::ComboBox::from_id_source("group_by_combo")
+                .selected_text(self.group_by.label())
+                .show_ui(ui, |ui| {
+                    for group_by in GroupBy::ALL {
+                        if ui
+                            .selectable_value(&mut self.group_by, group_by, group_by.label())
+                            .clicked()
+                        {
+                            dirty = true;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Grid sections: airing day, channel, first genre, or none");
 
             ui.separator();
 
             ui.label("Poster:");
-            if ui
-                .add(eg::Slider::new(&mut self.poster_width_ui, 120.0..=220.0).suffix(" px"))
-                .changed()
-            {
-                dirty = true;
+            self.ui_render_density_controls(ui);
+
+            ui.separator();
+            self.ui_render_profile_switcher(ui);
+            self.ui_render_guide_refresh_button(ui);
+            self.ui_render_health_indicators(ui);
+            self.ui_render_offline_indicator(ui);
+            if self.prefetch_running() {
+                ui.separator();
+                self.ui_render_prefetch_controls(ui);
             }
 
             if dirty {
@@ -246,20 +521,155 @@ impl crate::app::PexApp {
         });
     }
 
+    /// One dot per data source: green OK, amber stale, red failed, grey loading.
+    /// Clicking a stale/failed entry retries that loader.
+    fn ui_render_health_indicators(&mut self, ui: &mut eg::Ui) {
+        if self.demo_mode {
+            ui.label(
+                eg::RichText::new("DEMO")
+                    .strong()
+                    .color(eg::Color32::from_rgb(230, 170, 40)),
+            )
+            .on_hover_text("Showing the built-in sample guide. Restart Pex to use your Plex data.");
+        }
+        let mut retry: Option<Subsystem> = None;
+        for subsystem in Subsystem::ALL {
+            let state = self.health.get(subsystem);
+            let (color, status) = match state {
+                Health::Pending => (eg::Color32::GRAY, "Waiting to start.".to_string()),
+                Health::Loading => (eg::Color32::LIGHT_BLUE, "Loading…".to_string()),
+                Health::Ok => (eg::Color32::from_rgb(90, 190, 90), "OK".to_string()),
+                Health::Stale(why) => {
+                    (eg::Color32::from_rgb(230, 170, 40), format!("Stale: {why}"))
+                }
+                Health::Failed(why) => {
+                    (eg::Color32::from_rgb(220, 70, 70), format!("Failed: {why}"))
+                }
+            };
+            let actionable = state.needs_attention();
+            let mut tooltip = format!("{status}\n{}", subsystem.impact());
+            if actionable {
+                tooltip.push_str("\nClick to retry.");
+            }
+            let text = eg::RichText::new(format!("● {}", subsystem.label())).color(color);
+            let resp = if actionable {
+                ui.add(eg::Button::new(text).small())
+            } else {
+                ui.label(text)
+            };
+            if resp.on_hover_text(tooltip).clicked() && actionable {
+                retry = Some(subsystem);
+            }
+        }
+        if let Some(subsystem) = retry {
+            let ctx = ui.ctx().clone();
+            self.retry_subsystem(subsystem, &ctx);
+        }
+    }
+
+    /// Pause/resume and cancel buttons for the Stage 4 poster download pass.
+    pub(crate) fn ui_render_prefetch_controls(&mut self, ui: &mut eg::Ui) {
+        if !self.prefetch_running() {
+            return;
+        }
+        let paused = self.prefetch_paused();
+        let (label, hint) = if paused {
+            ("▶ Resume posters", "Continue downloading poster artwork")
+        } else {
+            (
+                "⏸ Pause posters",
+                "Stop starting new poster downloads (e.g. on a metered connection)",
+            )
+        };
+        if ui.small_button(label).on_hover_text(hint).clicked() {
+            self.set_prefetch_paused(!paused);
+        }
+        if ui
+            .small_button("✖ Cancel")
+            .on_hover_text(
+                "Skip the remaining poster downloads; missing posters still load on demand",
+            )
+            .clicked()
+        {
+            self.cancel_prefetch();
+        }
+    }
+
     // ---------- CHANNEL FILTER POPUP ----------
     pub(crate) fn ui_render_channel_filter_popup(&mut self, ctx: &eg::Context) {
         if !self.show_channel_filter_popup {
             return;
         }
 
-        // Build channel list from current rows (raw values; UI presents humanized label)
-        let mut channels: Vec<String> = self
-            .rows
-            .iter()
-            .filter_map(|r| r.channel_raw.clone())
-            .collect();
+        // Build channel list from current rows (raw value + humanized label)
+        let mut channels: Vec<(String, String)> = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+        for r in &self.rows {
+            let Some(raw) = r.channel_raw.as_deref() else {
+                continue;
+            };
+            if seen.insert(raw) {
+                let label = r
+                    .channel
+                    .clone()
+                    .unwrap_or_else(|| crate::app::utils::humanize_channel(raw));
+                channels.push((raw.to_string(), label));
+            }
+        }
         channels.sort();
-        channels.dedup();
+        let counts = self.facet_counts().channels;
+
+        // Pinned favourites float to the top; the search box narrows both sections.
+        let needle = self.channel_filter_query.trim().to_lowercase();
+        let matches = |(raw, label): &(String, String)| {
+            needle.is_empty()
+                || label.to_lowercase().contains(&needle)
+                || raw.to_lowercase().contains(&needle)
+        };
+        let (pinned, others): (Vec<_>, Vec<_>) = channels
+            .iter()
+            .filter(|c| matches(c))
+            .partition(|(raw, _)| self.favorite_channels.contains(raw));
+        let ordered: Vec<&(String, String)> = pinned.iter().chain(others.iter()).copied().collect();
+
+        // Keyboard: Up/Down move the cursor, Enter toggles the channel, Ctrl+P pins it.
+        // Consumed here so the grid behind the window doesn't move its selection too.
+        if ordered.is_empty() {
+            self.channel_filter_cursor = 0;
+        } else {
+            self.channel_filter_cursor = self.channel_filter_cursor.min(ordered.len() - 1);
+        }
+        let mut scroll_to_cursor = false;
+        let mut toggle_cursor = false;
+        let mut pin_cursor = false;
+        ctx.input_mut(|input| {
+            if input.consume_key(eg::Modifiers::NONE, eg::Key::ArrowDown) {
+                if self.channel_filter_cursor + 1 < ordered.len() {
+                    self.channel_filter_cursor += 1;
+                }
+                scroll_to_cursor = true;
+            }
+            if input.consume_key(eg::Modifiers::NONE, eg::Key::ArrowUp) {
+                self.channel_filter_cursor = self.channel_filter_cursor.saturating_sub(1);
+                scroll_to_cursor = true;
+            }
+            toggle_cursor = input.consume_key(eg::Modifiers::NONE, eg::Key::Enter);
+            pin_cursor = input.consume_key(eg::Modifiers::COMMAND, eg::Key::P);
+        });
+        if let Some((raw, _)) = ordered.get(self.channel_filter_cursor) {
+            if toggle_cursor {
+                if !self.selected_channels.remove(raw) {
+                    self.selected_channels.insert(raw.clone());
+                }
+                self.mark_dirty();
+            }
+            if pin_cursor {
+                if !self.favorite_channels.remove(raw) {
+                    self.favorite_channels.insert(raw.clone());
+                }
+                self.mark_dirty();
+            }
+        }
 
         let mut open = self.show_channel_filter_popup;
         eg::Window::new("Channel filter")
@@ -268,10 +678,24 @@ impl crate::app::PexApp {
             .default_width(320.0)
             .open(&mut open)
             .show(ctx, |ui| {
+                let search = ui.add(
+                    eg::TextEdit::singleline(&mut self.channel_filter_query)
+                        .hint_text("Search channels…")
+                        .desired_width(f32::INFINITY),
+                );
+                if self.channel_filter_focus_pending {
+                    search.request_focus();
+                    self.channel_filter_focus_pending = false;
+                }
+                if search.changed() {
+                    self.channel_filter_cursor = 0;
+                }
+
                 ui.horizontal_wrapped(|ui| {
                     ui.label(eg::RichText::new("Include only these channels:").strong());
                     if ui.small_button("Select all").clicked() {
-                        self.selected_channels = channels.iter().cloned().collect();
+                        self.selected_channels =
+                            channels.iter().map(|(raw, _)| raw.clone()).collect();
                         self.mark_dirty();
                     }
                     if ui.small_button("Select none").clicked() {
@@ -283,27 +707,72 @@ impl crate::app::PexApp {
                         self.mark_dirty();
                     }
                 });
+                ui.label(
+                    eg::RichText::new(
+                        "↑/↓ move · Enter toggle · Ctrl+P pin · (n) = airings it adds",
+                    )
+                    .small()
+                    .weak(),
+                );
 
                 ui.separator();
                 eg::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
-                    for ch in channels.iter() {
-                        let mut checked = self.selected_channels.contains(ch);
-                        let label = self
-                            .rows
-                            .iter()
-                            .find(|r| r.channel_raw.as_deref() == Some(ch.as_str()))
-                            .and_then(|r| r.channel.clone())
-                            .unwrap_or_else(|| crate::app::utils::humanize_channel(ch));
-                        if ui.checkbox(&mut checked, label).clicked() {
-                            if checked {
-                                self.selected_channels.insert(ch.clone());
-                            } else {
-                                self.selected_channels.remove(ch);
+                    if ordered.is_empty() {
+                        ui.label(eg::RichText::new("No channels match.").weak());
+                    }
+                    for (pos, (raw, label)) in ordered.iter().enumerate() {
+                        if pos == 0 && !pinned.is_empty() {
+                            ui.label(eg::RichText::new("Pinned").small().strong());
+                        } else if pos == pinned.len() && !pinned.is_empty() {
+                            ui.separator();
+                        }
+
+                        let is_cursor = pos == self.channel_filter_cursor;
+                        let row = ui.horizontal(|ui| {
+                            let pinned_now = self.favorite_channels.contains(raw);
+                            let star = if pinned_now { "★" } else { "☆" };
+                            if ui
+                                .small_button(star)
+                                .on_hover_text(if pinned_now { "Unpin" } else { "Pin to top" })
+                                .clicked()
+                            {
+                                if pinned_now {
+                                    self.favorite_channels.remove(raw);
+                                } else {
+                                    self.favorite_channels.insert(raw.clone());
+                                }
+                                self.mark_dirty();
+                            }
+
+                            let mut checked = self.selected_channels.contains(raw);
+                            let count = counts.get(raw.as_str()).copied().unwrap_or(0);
+                            let text = eg::RichText::new(format!("{label} ({count})"));
+                            let text = if is_cursor { text.strong() } else { text };
+                            if ui.checkbox(&mut checked, text).clicked() {
+                                if checked {
+                                    self.selected_channels.insert(raw.clone());
+                                } else {
+                                    self.selected_channels.remove(raw);
+                                }
+                                self.channel_filter_cursor = pos;
+                                self.mark_dirty();
+                            }
+                        });
+                        if is_cursor {
+                            ui.painter().rect_stroke(
+                                row.response.rect.expand(1.0),
+                                3.0,
+                                eg::Stroke::new(1.0, ui.visuals().selection.stroke.color),
+                            );
+                            if scroll_to_cursor {
+                                row.response.scroll_to_me(None);
                             }
-                            self.mark_dirty();
                         }
                     }
                 });
+
+                ui.separator();
+                self.ui_render_channel_group_editor(ui);
             });
 
         // Apply result (avoid E0499 by setting after .show)
@@ -318,6 +787,7 @@ impl crate::app::PexApp {
         let mut genres: Vec<String> = self.rows.iter().flat_map(|r| r.genres.clone()).collect();
         genres.sort();
         genres.dedup();
+        let counts = self.facet_counts().genres;
 
         let mut open = self.show_genre_filter_popup;
         eg::Window::new("Genre filter")
@@ -346,7 +816,11 @@ impl crate::app::PexApp {
                 eg::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
                     for genre in genres.iter() {
                         let mut checked = self.selected_genres.contains(genre);
-                        if ui.checkbox(&mut checked, genre).clicked() {
+                        let count = counts.get(genre).copied().unwrap_or(0);
+                        if ui
+                            .checkbox(&mut checked, format!("{genre} ({count})"))
+                            .clicked()
+                        {
                             if checked {
                                 self.selected_genres.insert(genre.clone());
                             } else {
@@ -374,10 +848,10 @@ impl crate::app::PexApp {
         let library_db_exists = library_db_path.exists();
         let cache_dir = crate::app::cache::cache_dir();
         let cache_exists = cache_dir.exists();
-        let tmdb_key_present = cfg
-            .tmdb_api_key
-            .as_ref()
-            .is_some_and(|k| !k.trim().is_empty());
+        let rating_sources: Vec<&'static str> = crate::app::ratings::configured_providers(&cfg)
+            .iter()
+            .map(|provider| provider.source().label())
+            .collect();
 
         eg::Window::new("Advanced controls")
             .collapsible(false)
@@ -416,16 +890,59 @@ impl crate::app::PexApp {
                             library_local_exists: library_db_exists,
                             cache_dir: &cache_dir,
                             cache_exists,
-                            tmdb_key_present,
+                            rating_sources,
                         },
                     );
+                    if ui
+                        .button("Settings…")
+                        .on_hover_text("Edit config.json: database sources, cache folder, API keys")
+                        .clicked()
+                    {
+                        self.open_settings();
+                    }
                     ui.separator();
                     self.advanced_prefetch_controls(ui);
                     ui.separator();
                     self.advanced_poster_controls(ui, ctx);
                     ui.separator();
+                    self.advanced_rating_controls(ui);
+                    ui.separator();
                     self.advanced_owned_controls(ui);
                     ui.separator();
+                    self.advanced_hidden_titles(ui);
+                    ui.separator();
+                    self.advanced_trakt_controls(ui);
+                    ui.separator();
+                    self.advanced_letterboxd_controls(ui);
+                    ui.separator();
+                    self.advanced_imdb_controls(ui);
+                    ui.separator();
+                    self.advanced_export_controls(ui);
+                    ui.separator();
+                    self.advanced_digest_controls(ui);
+                    ui.separator();
+                    ui.label(eg::RichText::new("Appearance").strong());
+                    self.advanced_theme_controls(ui);
+                    if ui
+                        .checkbox(&mut self.hover_preview.enabled, "Poster preview on hover")
+                        .on_hover_text(
+                            "Hovering a grid card shows the full-size poster with its time and \
+                             summary (fetched on first hover)",
+                        )
+                        .changed()
+                    {
+                        self.mark_dirty();
+                    }
+                    if ui
+                        .checkbox(&mut self.channel_logos, "Channel logos on posters")
+                        .on_hover_text(
+                            "Show the channel's logo in the bottom-left corner of grid posters",
+                        )
+                        .changed()
+                    {
+                        self.mark_dirty();
+                    }
+                    ui.separator();
                     self.advanced_prefs_controls(ui);
                     self.advanced_feedback_section(ui);
                 });
@@ -480,9 +997,59 @@ impl crate::app::PexApp {
                 .color(if summary.cache_exists { good } else { warn }),
         );
 
-        if !summary.tmdb_key_present {
+        if summary.rating_sources.is_empty() {
             ui.label(
-                eg::RichText::new("TMDb ratings disabled (config tmdb_api_key not set).").weak(),
+                eg::RichText::new(
+                    "Ratings disabled (set tmdb_api_key or omdb_api_key in config.json).",
+                )
+                .weak(),
+            );
+        } else {
+            ui.label(
+                eg::RichText::new(format!(
+                    "Rating providers: {}",
+                    summary.rating_sources.join(" → ")
+                ))
+                .weak(),
+            );
+        }
+    }
+
+    fn advanced_rating_controls(&mut self, ui: &mut eg::Ui) {
+        ui.label(eg::RichText::new("Ratings").strong());
+        let mut enabled = self.bulk_ratings.enabled;
+        if ui
+            .checkbox(&mut enabled, "Fetch ratings in the background")
+            .on_hover_text(
+                "Look up ratings for every airing in the current day range (a few per second) \
+                 and keep them in the ratings cache for the next start",
+            )
+            .changed()
+        {
+            self.set_bulk_ratings(enabled);
+        }
+        if ui
+            .checkbox(&mut self.rating_badges, "Rating badges on posters")
+            .on_hover_text("Show fetched scores in the corner of grid posters, coloured by score")
+            .changed()
+        {
+            self.mark_dirty();
+        }
+        if self.bulk_ratings.running() {
+            ui.horizontal(|ui| {
+                ui.add(eg::Spinner::new().size(12.0));
+                ui.label(format!(
+                    "Fetching ratings: {} of {}",
+                    self.bulk_ratings.done, self.bulk_ratings.total
+                ));
+            });
+        } else if self.bulk_ratings.enabled && self.bulk_ratings.total > 0 {
+            ui.label(
+                eg::RichText::new(format!(
+                    "Looked up {} airings in this range.",
+                    self.bulk_ratings.done
+                ))
+                .weak(),
             );
         }
     }
@@ -492,18 +1059,76 @@ impl crate::app::PexApp {
         let workers_resp =
             ui.add(eg::Slider::new(&mut self.worker_count_ui, 1..=32).text("Threads"));
         if workers_resp.changed() {
+            self.apply_worker_count();
             self.mark_dirty();
         }
-        workers_resp
-            .on_hover_text("Parallel downloads. Typical 8-16. New value applies to next prefetch.");
+        workers_resp.on_hover_text(
+            "Parallel downloads. Typical 8-16. Applies to the running prefetch immediately.",
+        );
+        if let Some(queue) = self.work_queue.as_ref() {
+            ui.label(
+                eg::RichText::new(format!("{} worker threads running.", queue.live_workers()))
+                    .weak(),
+            );
+        }
     }
 
     fn advanced_poster_controls(&mut self, ui: &mut eg::Ui, ctx: &eg::Context) {
         ui.label(eg::RichText::new("Poster cache").strong());
         ui.label(
-            eg::RichText::new("Posters older than 14 days are pruned automatically on startup.")
+            eg::RichText::new("Posters older than 14 days are pruned automatically on startup; those older than 3 days are revalidated during prefetch.")
                 .weak(),
         );
+        let (decode_now, decode_peak) = crate::app::cache::poster_decode_memory();
+        ui.label(
+            eg::RichText::new(format!(
+                "Decode buffers: {:.1} MB in flight, {:.1} MB peak this run.",
+                decode_now as f64 / 1_048_576.0,
+                decode_peak as f64 / 1_048_576.0
+            ))
+            .weak(),
+        )
+        .on_hover_text("Estimated pixel memory held by poster workers while resizing downloads");
+        if self.prefetch_running() {
+            ui.horizontal(|ui| self.ui_render_prefetch_controls(ui));
+        } else if self.prefetch_started {
+            if let Some(msg) = self.stage4_complete_message.as_ref() {
+                ui.label(eg::RichText::new(msg).weak());
+            }
+            if ui
+                .button("Restart prefetch")
+                .on_hover_text("Queue missing and failed posters again")
+                .clicked()
+            {
+                self.restart_prefetch(ctx);
+                self.advanced_feedback = Some("Poster prefetch restarted.".into());
+            }
+        }
+        let mut offline = self.is_offline();
+        if ui
+            .checkbox(&mut offline, "Work offline")
+            .on_hover_text(
+                "Skip poster, channel icon, TMDb and Plex requests; show cached art only",
+            )
+            .changed()
+        {
+            if offline {
+                self.go_offline(crate::app::net::OfflineReason::Manual);
+            } else {
+                self.go_online(ctx);
+            }
+        }
+        if ui
+            .button("Revalidate artwork")
+            .on_hover_text(
+                "Ask the server whether each cached poster changed (ETag / Last-Modified); \
+                 only changed artwork is downloaded again",
+            )
+            .clicked()
+        {
+            self.revalidate_artwork(ctx);
+            self.advanced_feedback = Some("Revalidating cached posters with the server.".into());
+        }
         let ctx_clone = ctx.clone();
         if ui.button("Clear & rebuild poster cache").clicked() {
             match self.clear_poster_cache_files() {
@@ -545,11 +1170,58 @@ impl crate::app::PexApp {
                 }
             }
         }
+        if self.library_refresh.is_configured() {
+            let busy = self.library_refresh.in_flight();
+            if ui
+                .add_enabled(!busy, eg::Button::new("Ask Plex to rescan library"))
+                .on_hover_text(
+                    "Trigger a scan of the server's movie libraries (plex_server_url + plex_token) so new recordings reach the next owned scan",
+                )
+                .clicked()
+            {
+                self.request_library_refresh("requested from Advanced");
+                self.advanced_feedback = Some("Asking Plex to rescan movie libraries…".into());
+            }
+        }
         if ui.button("Refresh owned scan").clicked() {
             self.refresh_owned_scan();
             self.advanced_feedback = Some("Owned scan refresh started (incremental).".into());
             self.set_status("Refreshing owned library.");
         }
+        if ui
+            .button("Owned duplicates…")
+            .on_hover_text(
+                "Films with more than one library file (editions, resolutions), with paths and sizes",
+            )
+            .clicked()
+        {
+            self.open_owned_duplicates();
+        }
+
+        ui.horizontal(|ui| {
+            let mut changed = ui
+                .checkbox(
+                    &mut self.low_bitrate_rule,
+                    "Suggest upgrade when owned HD is below",
+                )
+                .on_hover_text(
+                    "Treat owned HD copies encoded below this bitrate like SD ones: HD airings \
+                     get the HD ↑ badge and stay visible with Hide owned",
+                )
+                .changed();
+            changed |= ui
+                .add_enabled(
+                    self.low_bitrate_rule,
+                    eg::DragValue::new(&mut self.low_bitrate_mbps)
+                        .range(0.5..=50.0)
+                        .speed(0.1)
+                        .suffix(" Mbps"),
+                )
+                .changed();
+            if changed {
+                self.mark_dirty();
+            }
+        });
 
         let owned_running = self.owned_scan_in_progress;
         let owned_messages: Vec<String> =
@@ -574,6 +1246,114 @@ impl crate::app::PexApp {
         }
     }
 
+    fn advanced_export_controls(&mut self, ui: &mut eg::Ui) {
+        if let Some((_, result)) = self.export_pick.as_ref().and_then(PendingPick::poll) {
+            self.export_pick = None;
+            match result {
+                Ok(Some(path)) => {
+                    // A .csv / .json name picks the format too.
+                    if let Some(format) = path
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .and_then(|ext| ext.parse().ok())
+                    {
+                        self.export_format = format;
+                    }
+                    self.export_path_input = path.display().to_string();
+                }
+                Ok(None) => {}
+                Err(err) => self.advanced_feedback = Some(err),
+            }
+        }
+        ui.label(eg::RichText::new("Export").strong());
+        ui.horizontal(|ui| {
+            ui.label("Format:");
+            for (format, label) in [(ExportFormat::Csv, "CSV"), (ExportFormat::Json, "JSON")] {
+                if ui
+                    .selectable_value(&mut self.export_format, format, label)
+                    .changed()
+                {
+                    // Keep the suggested file name in step with the chosen format.
+                    let path = Path::new(self.export_path_input.trim());
+                    if path.extension().is_some() {
+                        self.export_path_input = path
+                            .with_extension(format.extension())
+                            .to_string_lossy()
+                            .into_owned();
+                    }
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Save to:");
+            ui.add(
+                eg::TextEdit::singleline(&mut self.export_path_input)
+                    .hint_text(default_guide_export_path(self.export_format))
+                    .desired_width(220.0),
+            )
+            .on_hover_text("Destination file; relative paths resolve next to the executable");
+            if ui
+                .add_enabled(self.export_pick.is_none(), eg::Button::new("Browse…"))
+                .on_hover_text("Choose where to save the export")
+                .clicked()
+            {
+                let current = if self.export_path_input.trim().is_empty() {
+                    default_guide_export_path(self.export_format)
+                } else {
+                    self.export_path_input.clone()
+                };
+                self.export_pick = Some(PendingPick::spawn(
+                    (),
+                    PickKind::Save,
+                    "Export the guide as",
+                    &current,
+                ));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui
+                .button("Export guide")
+                .on_hover_text("Dump the currently filtered rows for spreadsheets or scripts")
+                .clicked()
+            {
+                let dest = self.export_path_input.clone();
+                match self.export_visible_guide(&dest, self.export_format) {
+                    Ok((path, count)) => {
+                        self.advanced_feedback = Some(format!(
+                            "Exported {count} row{} to {}",
+                            if count == 1 { "" } else { "s" },
+                            path.display()
+                        ));
+                    }
+                    Err(err) => {
+                        self.advanced_feedback = Some(format!("Guide export failed: {err}"));
+                    }
+                }
+            }
+            if ui.small_button("Default path").clicked() {
+                self.export_path_input = default_guide_export_path(self.export_format);
+            }
+        });
+        if ui
+            .button("Export .ics")
+            .on_hover_text("Write the currently filtered airings as a calendar file")
+            .clicked()
+        {
+            match self.export_visible_ics() {
+                Ok((path, count)) => {
+                    self.advanced_feedback = Some(format!(
+                        "Exported {count} airing{} to {}",
+                        if count == 1 { "" } else { "s" },
+                        path.display()
+                    ));
+                }
+                Err(err) => {
+                    self.advanced_feedback = Some(format!("Calendar export failed: {err}"));
+                }
+            }
+        }
+    }
+
     fn advanced_prefs_controls(&mut self, ui: &mut eg::Ui) {
         ui.label(eg::RichText::new("Preferences").strong());
         if ui.button("Backup UI prefs").clicked() {
@@ -601,6 +1381,37 @@ impl crate::app::PexApp {
                 }
             }
         }
+        if ui
+            .button("Keyboard shortcuts…")
+            .on_hover_text("Reassign or clear the key bindings")
+            .clicked()
+        {
+            self.show_keymap_popup = true;
+        }
+        if ui
+            .checkbox(&mut self.gamepad.enabled, "Gamepad navigation")
+            .on_hover_text(
+                "D-pad or left stick move the selection, A opens the details, B backs out, \
+                 bumpers and triggers change day (Linux joystick devices)",
+            )
+            .changed()
+        {
+            self.mark_dirty();
+        }
+        if ui
+            .checkbox(&mut self.background.enabled, "Keep running when closed")
+            .on_hover_text(
+                "The close button minimizes Pex instead of quitting; after an hour or more away \
+                 it reloads the guide and owned library when you bring it back",
+            )
+            .changed()
+        {
+            self.mark_dirty();
+        }
+        if self.background.enabled && ui.button("Quit Pex").clicked() {
+            let ctx = ui.ctx().clone();
+            self.quit(&ctx);
+        }
     }
 
     fn advanced_feedback_section(&self, ui: &mut eg::Ui) {
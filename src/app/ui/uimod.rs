@@ -1,5 +1,9 @@
 // src/app/ui/mod.rs
+pub mod empty_state;
 pub mod grid;
+pub mod list;
+pub mod now_next;
+pub mod timeline;
 pub mod topbar;
 
 use eframe::egui as eg;
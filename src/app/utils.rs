@@ -1,5 +1,6 @@
 // src/app/util.rs
 use chrono::{Local, TimeZone};
+use std::collections::HashMap;
 use std::time::SystemTime;
 pub(crate) fn normalize_title(s: &str) -> String {
     let mut normalized = String::with_capacity(s.len());
@@ -110,6 +111,13 @@ pub(crate) fn format_day_label(bucket: i64) -> String {
     format!("{} {}{} {}", wd, d, ordinal_suffix(d), month_short_name(m))
 }
 
+/// Short form for the day strip: "Fri 21st".
+pub(crate) fn format_day_chip(bucket: i64) -> String {
+    let (_y, _m, d) = civil_from_days(bucket);
+    let wd = weekday_full_from_bucket(bucket);
+    format!("{} {}{}", &wd[..3], d, ordinal_suffix(d))
+}
+
 pub(crate) fn hhmm_utc(ts: SystemTime) -> String {
     let secs = ts
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -143,12 +151,18 @@ pub(crate) fn host_from_url(u: &str) -> Option<String> {
     Some(host.split('.').next().unwrap_or(host).to_uppercase())
 }
 
-pub(crate) fn parse_genres(tags: &str) -> Vec<String> {
+/// Split Plex's `a|b|c` genre tags, folding synonyms through `aliases` (lowercased keys).
+pub(crate) fn parse_genres(tags: &str, aliases: &HashMap<String, String>) -> Vec<String> {
     let mut v: Vec<String> = tags
         .split('|')
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
+        .map(|s| {
+            aliases
+                .get(&s.to_lowercase())
+                .cloned()
+                .unwrap_or_else(|| s.to_string())
+        })
         .collect();
     v.sort();
     v.dedup();
@@ -261,3 +275,317 @@ pub fn infer_broadcast_hd(tags_genre: Option<&str>, channel: Option<&str>) -> bo
 
     false
 }
+
+// Access-service suffixes UK/EU guides append to descriptions ("[S]", "[AD,S]", "[SL]").
+const ACCESS_TAGS: [&str; 7] = ["S", "AD", "SL", "HD", "SD", "W", "SUBTITLES"];
+
+/// Clean an EPG summary for display: decode HTML entities (including double-encoded ones),
+/// drop markup (block tags become line breaks), remove access-service tags and collapse
+/// whitespace. Returns None when nothing readable is left.
+pub(crate) fn sanitize_summary(raw: &str) -> Option<String> {
+    let mut text = raw.to_string();
+    // "&amp;#39;" style double encoding is common; two passes cover it.
+    for _ in 0..2 {
+        if !text.contains('&') {
+            break;
+        }
+        text = decode_html_entities(&text);
+    }
+    let text = strip_html_tags(&text);
+
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let line = strip_access_tags(line);
+            line.split_whitespace().collect::<Vec<_>>().join(" ")
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn decode_html_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let tail = &rest[amp..];
+        // Entities are short; anything longer is a literal ampersand.
+        let decoded = tail
+            .char_indices()
+            .take(12)
+            .find(|(_, c)| *c == ';')
+            .and_then(|(semi, _)| decode_entity(&tail[1..semi]).map(|ch| (ch, semi + 1)));
+        match decoded {
+            Some((ch, consumed)) => {
+                out.push(ch);
+                rest = &tail[consumed..];
+            }
+            None => {
+                out.push('&');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code).filter(|c| !c.is_control() || c.is_whitespace());
+    }
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "pound" => '£',
+        "euro" => '€',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "eacute" => 'é',
+        "egrave" => 'è',
+        "aacute" => 'á',
+        "agrave" => 'à',
+        "iacute" => 'í',
+        "oacute" => 'ó',
+        "uacute" => 'ú',
+        "ntilde" => 'ñ',
+        "ccedil" => 'ç',
+        "auml" => 'ä',
+        "ouml" => 'ö',
+        "uuml" => 'ü',
+        "szlig" => 'ß',
+        _ => return None,
+    })
+}
+
+/// Remove `<tag …>` markup. Block-level tags become newlines; a `<` that doesn't open a tag
+/// (e.g. "a < b") is kept.
+fn strip_html_tags(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        let tail = &rest[lt..];
+        let opens_tag = tail[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        match tail.find('>').filter(|_| opens_tag) {
+            Some(gt) => {
+                let name: String = tail[1..gt]
+                    .trim_start_matches('/')
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric())
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+                if matches!(name.as_str(), "br" | "p" | "div" | "li" | "tr") {
+                    out.push('\n');
+                }
+                rest = &tail[gt + 1..];
+            }
+            None => {
+                out.push('<');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_access_tags(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(open) = rest.find('[') {
+        let tail = &rest[open..];
+        let Some(close) = tail.find(']') else {
+            break;
+        };
+        let is_access = tail[1..close]
+            .split(',')
+            .all(|tag| ACCESS_TAGS.contains(&tag.trim().to_ascii_uppercase().as_str()));
+        out.push_str(&rest[..open]);
+        if !is_access {
+            out.push_str(&tail[..=close]);
+        }
+        rest = &tail[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split CSV text (as exported by Letterboxd, IMDb and spreadsheets) into records: quoted
+/// fields may hold commas, doubled quotes and line breaks; a leading BOM and blank lines are
+/// dropped.
+pub(crate) fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|f| !f.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    record.push(field);
+    if record.iter().any(|f| !f.is_empty()) {
+        records.push(record);
+    }
+    records
+}
+
+/// Home / End over cards in display order: forward lands on the first card after the current
+/// day's (or section's) run; backward on the start of the current run, or of the previous one
+/// when `current` already starts it. None when there is nowhere to go.
+pub(crate) fn day_jump<K: PartialEq>(
+    order: &[usize],
+    day: impl Fn(usize) -> Option<K>,
+    current: usize,
+    forward: bool,
+) -> Option<usize> {
+    let pos = order.iter().position(|&idx| idx == current)?;
+    let run_start = |end: usize| {
+        let bucket = day(order[end]);
+        let mut start = end;
+        while start > 0 && day(order[start - 1]) == bucket {
+            start -= 1;
+        }
+        start
+    };
+    if forward {
+        let bucket = day(current);
+        return order[pos..].iter().copied().find(|&idx| day(idx) != bucket);
+    }
+    let start = run_start(pos);
+    if start < pos {
+        Some(order[start])
+    } else {
+        pos.checked_sub(1).map(|prev| order[run_start(prev)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_entities_are_decoded() {
+        assert_eq!(
+            sanitize_summary("Tom &amp; Jerry&#39;s &quot;big&quot; day&nbsp;out &#x2013; again")
+                .as_deref(),
+            Some("Tom & Jerry's \"big\" day out – again")
+        );
+        // Double-encoded apostrophe, plus a bare ampersand that isn't an entity.
+        assert_eq!(
+            sanitize_summary("Rock &amp;#39;n&amp;#39; roll & more &unknown; stuff").as_deref(),
+            Some("Rock 'n' roll & more &unknown; stuff")
+        );
+    }
+
+    #[test]
+    fn summary_markup_and_access_tags_are_stripped() {
+        assert_eq!(
+            sanitize_summary("<p>A <b>thrilling</b> heist.</p><p>Starring   X.<br/>Y</p>")
+                .as_deref(),
+            Some("A thrilling heist.\nStarring X.\nY")
+        );
+        assert_eq!(
+            sanitize_summary("&lt;i&gt;Escaped&lt;/i&gt; markup. 2 < 3 [S] [AD,S]").as_deref(),
+            Some("Escaped markup. 2 < 3")
+        );
+        assert_eq!(
+            sanitize_summary("Episode 4 [Part 2] (2019) [SL]").as_deref(),
+            Some("Episode 4 [Part 2] (2019)")
+        );
+    }
+
+    #[test]
+    fn empty_summaries_become_none() {
+        assert_eq!(sanitize_summary("   "), None);
+        assert_eq!(sanitize_summary("<br/> &nbsp; [S]"), None);
+    }
+
+    #[test]
+    fn genre_aliases_fold_into_one_genre() {
+        let aliases = crate::config::build_genre_aliases(
+            crate::config::DEFAULT_GENRE_ALIASES
+                .into_iter()
+                .chain([("Kids", "Family")]),
+        );
+        assert_eq!(
+            parse_genres("Science Fiction|SciFi|sci-fi|Drama|kids|Family", &aliases),
+            vec!["Drama", "Family", "Sci-Fi"]
+        );
+        assert_eq!(
+            parse_genres(" Western | ", &HashMap::new()),
+            vec!["Western"]
+        );
+    }
+
+    #[test]
+    fn csv_handles_quotes_commas_and_line_breaks() {
+        let text =
+            "\u{feff}Date,Name,Year\r\n2024-01-02,\"Crouching Tiger, Hidden Dragon\",2000\n\n\
+                    2024-01-03,\"The \"\"Burbs\"\"\nCut\",1989\n,,\n2024-01-04,Heat,";
+        let records = parse_csv(text);
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0], vec!["Date", "Name", "Year"]);
+        assert_eq!(records[1][1], "Crouching Tiger, Hidden Dragon");
+        assert_eq!(records[2][1], "The \"Burbs\"\nCut");
+        assert_eq!(records[3], vec!["2024-01-04", "Heat", ""]);
+    }
+
+    #[test]
+    fn day_jump_walks_day_runs() {
+        // Cards 10..=12 air on day 1, 20..=21 on day 2, 30 on day 3.
+        let order = [10, 11, 12, 20, 21, 30];
+        let day = |idx: usize| Some(idx as i64 / 10);
+        assert_eq!(day_jump(&order, day, 11, true), Some(20));
+        assert_eq!(day_jump(&order, day, 30, true), None);
+        assert_eq!(day_jump(&order, day, 21, false), Some(20));
+        assert_eq!(day_jump(&order, day, 20, false), Some(10));
+        assert_eq!(day_jump(&order, day, 10, false), None);
+        assert_eq!(day_jump(&order, day, 99, true), None);
+    }
+
+    #[test]
+    fn day_chip_is_short_weekday_and_ordinal() {
+        // Day 0 is Thursday 1st Jan 1970.
+        assert_eq!(format_day_chip(0), "Thu 1st");
+        assert_eq!(format_day_chip(21), "Thu 22nd");
+    }
+}
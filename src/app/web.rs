@@ -0,0 +1,364 @@
+// src/app/web.rs
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::Local;
+use tracing::{info, warn};
+
+use crate::app::guide::Guide;
+use crate::app::types::DayRange;
+use crate::app::utils::hhmm_utc;
+
+// The guide changes a few times a day; the DB copies themselves refresh at most daily.
+const REFRESH_EVERY: Duration = Duration::from_secs(30 * 60);
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One poster card on the page.
+struct WebCard {
+    title: String,
+    year: Option<i32>,
+    channel: Option<String>,
+    time: String,
+    genres: Vec<String>,
+    owned: bool,
+    scheduled: bool,
+    premiere: bool,
+    key: String,
+}
+
+/// A poster the page may ask for: its source URL and the cached file, if any.
+struct WebPoster {
+    url: String,
+    path: Option<PathBuf>,
+}
+
+/// Everything the server answers with, rebuilt after each guide refresh.
+struct WebGuide {
+    page: String,
+    posters: HashMap<String, WebPoster>,
+}
+
+impl WebGuide {
+    fn from_guide(guide: &Guide) -> Self {
+        let mut days: Vec<(String, Vec<WebCard>)> = Vec::new();
+        let mut posters = HashMap::new();
+        for (key, rows) in guide.days() {
+            let cards = rows
+                .into_iter()
+                .map(|row| {
+                    posters.insert(
+                        row.key.clone(),
+                        WebPoster {
+                            url: row.url.clone(),
+                            path: row.path.clone(),
+                        },
+                    );
+                    WebCard {
+                        title: row.title.clone(),
+                        year: row.year,
+                        channel: row.channel.clone(),
+                        time: row.airing.map(hhmm_utc).unwrap_or_default(),
+                        genres: row.genres.clone(),
+                        owned: row.owned,
+                        scheduled: row.scheduled,
+                        premiere: row.premiere,
+                        key: row.key.clone(),
+                    }
+                })
+                .collect();
+            days.push((key.label().unwrap_or_default(), cards));
+        }
+        let stamp = Local::now().format("%a %H:%M").to_string();
+        Self {
+            page: render_page(&days, &stamp),
+            posters,
+        }
+    }
+}
+
+pub(crate) fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+const PAGE_STYLE: &str = "\
+body{margin:0;padding:12px;background:#1b1b1f;color:#ddd;font:14px system-ui,sans-serif}\
+h1{font-size:18px;margin:0 0 4px}h2{font-size:16px;margin:18px 0 8px}\
+.note{color:#888;font-size:12px}\
+.grid{display:grid;grid-template-columns:repeat(auto-fill,minmax(130px,1fr));gap:10px}\
+.card{background:#26262b;border-radius:6px;overflow:hidden}\
+.card img{width:100%;aspect-ratio:2/3;object-fit:cover;display:block;background:#333}\
+.meta{padding:6px}.title{font-weight:600}.sub{color:#999;font-size:12px}\
+.badge{display:inline-block;font-size:11px;font-weight:700;padding:1px 4px;border-radius:3px;margin:4px 4px 0 0}\
+.owned{background:#3a7d44}.rec{background:#b33}.new{background:#2a6fd6}";
+
+fn render_page(days: &[(String, Vec<WebCard>)], stamp: &str) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>Pex – what's airing</title><style>{PAGE_STYLE}</style></head><body>\
+         <h1>What's airing</h1><div class=\"note\">Read-only view, updated {}. Times are UTC.</div>",
+        html_escape(stamp)
+    );
+    if days.is_empty() {
+        html.push_str("<p>No airings in the next week.</p>");
+    }
+    for (label, cards) in days {
+        let _ = write!(html, "<h2>{}</h2><div class=\"grid\">", html_escape(label));
+        for card in cards {
+            let title = match card.year {
+                Some(year) => format!("{} ({year})", card.title),
+                None => card.title.clone(),
+            };
+            let sub = [Some(card.time.clone()), card.channel.clone()]
+                .into_iter()
+                .flatten()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(" · ");
+            let _ = write!(
+                html,
+                "<div class=\"card\"><img loading=\"lazy\" alt=\"\" src=\"/poster/{}\">\
+                 <div class=\"meta\"><div class=\"title\">{}</div><div class=\"sub\">{}</div>\
+                 <div class=\"sub\">{}</div>",
+                urlencoding::encode(&card.key),
+                html_escape(&title),
+                html_escape(&sub),
+                html_escape(&card.genres.join(", ")),
+            );
+            for (on, class, text) in [
+                (card.owned, "owned", "OWNED"),
+                (card.scheduled, "rec", "REC"),
+                (card.premiere, "new", "NEW"),
+            ] {
+                if on {
+                    let _ = write!(html, "<span class=\"badge {class}\">{text}</span>");
+                }
+            }
+            html.push_str("</div></div>");
+        }
+        html.push_str("</div>");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// Method and path of an HTTP request head, e.g. `("GET", "/")`.
+fn parse_request_line(head: &str) -> Option<(&str, &str)> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    let path = target.split('?').next().unwrap_or(target);
+    Some((method, path))
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &[u8]) {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(body);
+}
+
+fn read_head(stream: &mut TcpStream) -> Option<String> {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > MAX_REQUEST_BYTES {
+            return None;
+        }
+        let n = stream.read(&mut chunk).ok()?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// Content type of a cached poster file; None for the raw `.rgba` copies, which browsers can't
+/// show.
+fn image_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Cached poster bytes for `key` and their content type, downloading a JPEG first when no
+/// image file is cached yet.
+fn poster_bytes(poster: &WebPoster, key: &str, offline: bool) -> Option<(Vec<u8>, &'static str)> {
+    let servable = |p: &PathBuf| p.exists() && image_type(p).is_some();
+    let cached = poster
+        .path
+        .clone()
+        .filter(servable)
+        .or_else(|| crate::app::cache::find_any_by_key(key).filter(servable));
+    let path = match cached {
+        Some(path) => path,
+        None if offline => return None,
+        None => crate::app::cache::download_and_store_resized(
+            &poster.url,
+            key,
+            crate::app::RESIZE_MAX_W,
+            crate::app::RESIZE_QUALITY,
+        )
+        .map_err(|err| warn!("web: poster {key} failed: {err}"))
+        .ok()?,
+    };
+    let content_type = image_type(&path)?;
+    Some((std::fs::read(path).ok()?, content_type))
+}
+
+fn handle(mut stream: TcpStream, guide: &RwLock<Arc<WebGuide>>, offline: bool) {
+    let Some(head) = read_head(&mut stream) else {
+        respond(&mut stream, "400 Bad Request", "text/plain", b"Bad request");
+        return;
+    };
+    let Some((method, path)) = parse_request_line(&head) else {
+        respond(&mut stream, "400 Bad Request", "text/plain", b"Bad request");
+        return;
+    };
+    if method != "GET" {
+        respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"Read-only",
+        );
+        return;
+    }
+    let guide = guide.read().map(|g| Arc::clone(&g)).ok();
+    let Some(guide) = guide else {
+        respond(
+            &mut stream,
+            "500 Internal Server Error",
+            "text/plain",
+            b"Unavailable",
+        );
+        return;
+    };
+    if path == "/" {
+        respond(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            guide.page.as_bytes(),
+        );
+        return;
+    }
+    // Only keys of rows on the page are served, so the URL never names a file directly.
+    let poster = path
+        .strip_prefix("/poster/")
+        .and_then(|key| urlencoding::decode(key).ok())
+        .and_then(|key| {
+            let poster = guide.posters.get(key.as_ref())?;
+            poster_bytes(poster, &key, offline)
+        });
+    match poster {
+        Some((bytes, content_type)) => respond(&mut stream, "200 OK", content_type, &bytes),
+        None => respond(&mut stream, "404 Not Found", "text/plain", b"Not found"),
+    }
+}
+
+/// Load the guide for the coming week and lay it out for the page.
+fn build_guide() -> Result<WebGuide, String> {
+    let guide = Guide::load(DayRange::Seven)?;
+    Ok(WebGuide::from_guide(&guide))
+}
+
+/// Run `pex --serve <addr>`: keep the guide and owned data fresh in the background and serve a
+/// read-only HTML grid until the process is stopped. Returns the exit code on failure.
+pub fn serve(addr: &str) -> i32 {
+    let listener = match TcpListener::bind(addr) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("pex: cannot listen on {addr}: {err}");
+            return 1;
+        }
+    };
+    let cfg = crate::config::load_config();
+    if !cfg.errors.is_empty() {
+        for err in &cfg.errors {
+            eprintln!("pex: {err}");
+        }
+        return 1;
+    }
+    let guide = match build_guide() {
+        Ok(guide) => Arc::new(RwLock::new(Arc::new(guide))),
+        Err(err) => {
+            eprintln!("pex: {err}");
+            return 1;
+        }
+    };
+    info!("web: serving the guide on http://{addr}/");
+    eprintln!("Serving the guide on http://{addr}/ (Ctrl+C to stop).");
+
+    let refresher = Arc::clone(&guide);
+    std::thread::spawn(move || loop {
+        std::thread::sleep(REFRESH_EVERY);
+        match build_guide() {
+            Ok(fresh) => {
+                if let Ok(mut current) = refresher.write() {
+                    *current = Arc::new(fresh);
+                }
+            }
+            Err(err) => warn!("web: guide refresh failed, keeping the last one: {err}"),
+        }
+    });
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let guide = Arc::clone(&guide);
+        let offline = cfg.offline;
+        std::thread::spawn(move || handle(stream, &guide, offline));
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_line_and_escaping() {
+        assert_eq!(
+            parse_request_line("GET /poster/abc__s?x=1 HTTP/1.1\r\nHost: pex\r\n\r\n"),
+            Some(("GET", "/poster/abc__s"))
+        );
+        assert_eq!(parse_request_line(""), None);
+        assert_eq!(
+            html_escape("<Tom & Jerry's \"Movie\">"),
+            "&lt;Tom &amp; Jerry&#39;s &quot;Movie&quot;&gt;"
+        );
+        assert_eq!(image_type(Path::new("posters/abc.PNG")), Some("image/png"));
+        assert_eq!(image_type(Path::new("posters/abc.jpg")), Some("image/jpeg"));
+        assert_eq!(image_type(Path::new("posters/rgba_abc.rgba")), None);
+    }
+}
@@ -1,4 +1,6 @@
 use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
 use std::io::ErrorKind;
@@ -10,26 +12,174 @@ pub const LOCAL_DB_DIR: &str = "db";
 pub const LOCAL_EPG_DB_FILE: &str = "plex_epg.db";
 pub const LOCAL_LIBRARY_DB_FILE: &str = "plex_library.db";
 const CONFIG_FILENAME: &str = "config.json";
+const CONFIG_TOML_FILENAME: &str = "config.toml";
+/// Remembers the profile picked in the top bar for the next launch.
+const PROFILE_FILENAME: &str = "pex_profile.txt";
+/// Plex DVR writes in-progress recordings under `.grab/` and transcodes under `Transcode/`.
+pub const DEFAULT_OWNED_EXCLUDE_DIRS: [&str; 2] = [".grab", "Transcode"];
+/// Spellings Plex guide data uses for the same genre; `genre_aliases` in config.json adds to these.
+pub const DEFAULT_GENRE_ALIASES: [(&str, &str); 4] = [
+    ("Science Fiction", "Sci-Fi"),
+    ("Science-Fiction", "Sci-Fi"),
+    ("SciFi", "Sci-Fi"),
+    ("Sci Fi", "Sci-Fi"),
+];
+
+/// Where the owned scan reads the movie library from (`owned_source`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OwnedSource {
+    /// The mirrored Plex library database (`db/plex_library.db`).
+    #[default]
+    Plex,
+    /// Kodi's `MyVideos<N>.db` at `kodi_db_path`.
+    Kodi,
+    /// A Jellyfin or Emby server's movie libraries (`jellyfin_url` + `jellyfin_api_key`).
+    Jellyfin,
+}
+
+impl OwnedSource {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Plex => "plex",
+            Self::Kodi => "kodi",
+            Self::Jellyfin => "jellyfin",
+        }
+    }
+
+    /// How the owned scan names the source in its status messages.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Plex => "Plex library database",
+            Self::Kodi => "Kodi video database",
+            Self::Jellyfin => "Jellyfin/Emby server",
+        }
+    }
+}
+
+impl std::str::FromStr for OwnedSource {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "plex" => Ok(Self::Plex),
+            "kodi" => Ok(Self::Kodi),
+            "jellyfin" | "emby" => Ok(Self::Jellyfin),
+            _ => Err(()),
+        }
+    }
+}
 
 static BASE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static PROFILE_FLAG: OnceLock<String> = OnceLock::new();
+static ACTIVE_PROFILE: OnceLock<Option<String>> = OnceLock::new();
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct AppConfig {
     pub cache_dir: Option<PathBuf>,
     pub plex_epg_db_source: Option<PathBuf>,
     pub plex_library_db_source: Option<PathBuf>,
     pub tmdb_api_key: Option<String>,
+    pub omdb_api_key: Option<String>,
+    /// Rating provider names in lookup order; empty means the default order.
+    pub rating_providers: Vec<String>,
+    pub texture_budget_mb: Option<u32>,
+    /// Minutes between automatic guide re-reads; None means the default, 0 turns it off.
+    pub guide_refresh_minutes: Option<u32>,
+    pub plex_server_url: Option<String>,
+    pub plex_token: Option<String>,
+    pub plex_refresh_after_recording: bool,
+    /// Recordings the DVR can make at once; more overlapping ones are flagged as clashing.
+    pub tuner_count: Option<u32>,
+    pub offline: bool,
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    /// Folder names whose files never count as owned (matched case-insensitively).
+    pub owned_exclude_dirs: Vec<String>,
+    pub owned_source: OwnedSource,
+    /// Hours between automatic owned rescans while Pex runs; None or 0 turns them off.
+    pub owned_rescan_hours: Option<u32>,
+    /// Library folders watched for new or removed files, which trigger an owned rescan.
+    pub library_roots: Vec<PathBuf>,
+    /// Kodi's `MyVideos<N>.db`, or the `Database` folder holding it (newest version wins).
+    pub kodi_db_path: Option<PathBuf>,
+    /// Jellyfin/Emby server root, e.g. `http://nas:8096` (no trailing slash).
+    pub jellyfin_url: Option<String>,
+    pub jellyfin_api_key: Option<String>,
+    /// Radarr root, e.g. `http://nas:7878` (no trailing slash).
+    pub radarr_url: Option<String>,
+    pub radarr_api_key: Option<String>,
+    /// Root folder for films sent to Radarr; None uses Radarr's first one.
+    pub radarr_root_folder: Option<String>,
+    /// Trakt API app credentials; the account itself is linked from Advanced (device code).
+    pub trakt_client_id: Option<String>,
+    pub trakt_client_secret: Option<String>,
+    /// Lowercased genre spelling -> canonical genre (canonical names map to themselves).
+    pub genre_aliases: HashMap<String, String>,
+    /// Profile whose values were merged over the top-level keys, if any.
+    pub profile: Option<String>,
+    /// Every profile named in `profiles`, sorted.
+    pub profiles: Vec<String>,
+    /// Why the config file could not be used (the rest of the config is then the defaults).
+    pub errors: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct RawConfig {
     cache_dir: Option<String>,
     #[serde(alias = "plex_db_source")]
     plex_epg_db_source: Option<String>,
     plex_library_db_source: Option<String>,
-    #[serde(alias = "omdb_api_key")]
     #[serde(alias = "the_movie_db_api_key")]
     tmdb_api_key: Option<String>,
+    omdb_api_key: Option<String>,
+    #[serde(default)]
+    rating_providers: Vec<String>,
+    texture_budget_mb: Option<u32>,
+    guide_refresh_minutes: Option<u32>,
+    plex_server_url: Option<String>,
+    plex_token: Option<String>,
+    #[serde(default)]
+    plex_refresh_after_recording: bool,
+    tuner_count: Option<u32>,
+    #[serde(default)]
+    offline: bool,
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+    owned_exclude_dirs: Option<Vec<String>>,
+    owned_source: Option<String>,
+    owned_rescan_hours: Option<u32>,
+    #[serde(default)]
+    library_roots: Vec<String>,
+    kodi_db_path: Option<String>,
+    #[serde(alias = "emby_url")]
+    jellyfin_url: Option<String>,
+    #[serde(alias = "emby_api_key")]
+    jellyfin_api_key: Option<String>,
+    radarr_url: Option<String>,
+    radarr_api_key: Option<String>,
+    radarr_root_folder: Option<String>,
+    trakt_client_id: Option<String>,
+    trakt_client_secret: Option<String>,
+    #[serde(default)]
+    genre_aliases: HashMap<String, String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, RawProfile>,
+    #[serde(skip)]
+    active_profile: Option<String>,
+}
+
+/// One entry of `profiles`: a second Plex server browsed from the same install. Keys left out
+/// fall back to the top-level value; the cache defaults to `<cache_dir>/profiles/<name>`.
+#[derive(Clone, Debug, Default, Deserialize)]
+struct RawProfile {
+    cache_dir: Option<String>,
+    #[serde(alias = "plex_db_source")]
+    plex_epg_db_source: Option<String>,
+    plex_library_db_source: Option<String>,
+    plex_server_url: Option<String>,
+    plex_token: Option<String>,
+    owned_exclude_dirs: Option<Vec<String>>,
+    library_roots: Option<Vec<String>>,
 }
 
 pub fn base_dir() -> &'static Path {
@@ -65,7 +215,7 @@ pub fn resolve_relative_path<P: AsRef<Path>>(input: P) -> PathBuf {
     }
 }
 
-fn read_config_source() -> Option<(PathBuf, String)> {
+fn config_candidates() -> Vec<PathBuf> {
     let mut candidates: Vec<PathBuf> = Vec::new();
 
     if let Ok(custom) = env::var("PEX_CONFIG") {
@@ -78,16 +228,250 @@ fn read_config_source() -> Option<(PathBuf, String)> {
         candidates.push(candidate);
     }
 
-    candidates.push(base_dir().join(CONFIG_FILENAME));
-
+    let mut dirs = vec![base_dir().to_path_buf()];
     if let Ok(cwd) = env::current_dir() {
-        let candidate = cwd.join(CONFIG_FILENAME);
-        if !candidates.contains(&candidate) {
-            candidates.push(candidate);
+        dirs.push(cwd);
+    }
+    for dir in dirs {
+        // config.json wins when a folder has both.
+        for name in [CONFIG_FILENAME, CONFIG_TOML_FILENAME] {
+            let candidate = dir.join(name);
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    candidates
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| CONFIG_FILENAME.to_string())
+}
+
+/// Parse a config file (TOML for `.toml`, JSON otherwise) into a JSON object.
+pub fn parse_config_text(
+    path: &Path,
+    text: &str,
+) -> Result<serde_json::Map<String, Value>, String> {
+    let label = file_label(path);
+    let value = if is_toml(path) {
+        toml::from_str::<Value>(text)
+            .map_err(|err| format!("{label} could not be parsed: {}", err.to_string().trim()))?
+    } else {
+        serde_json::from_str::<Value>(text)
+            .map_err(|err| format!("{label} could not be parsed: {err}"))?
+    };
+    match value {
+        Value::Object(map) => Ok(map),
+        _ => Err(format!("{label} must hold a table of settings.")),
+    }
+}
+
+/// Format settings for `path` in its own format. TOML has no null, so null keys are dropped.
+pub fn render_config_text(
+    path: &Path,
+    map: &serde_json::Map<String, Value>,
+) -> Result<String, String> {
+    let text = if is_toml(path) {
+        toml::to_string_pretty(&without_nulls(map)).map_err(|err| err.to_string())?
+    } else {
+        serde_json::to_string_pretty(map).map_err(|err| err.to_string())? + "\n"
+    };
+    Ok(text)
+}
+
+fn without_nulls(map: &serde_json::Map<String, Value>) -> serde_json::Map<String, Value> {
+    map.iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| match value {
+            Value::Object(inner) => (key.clone(), Value::Object(without_nulls(inner))),
+            other => (key.clone(), other.clone()),
+        })
+        .collect()
+}
+
+/// Shape a config value must have. `null` is accepted for every key and means "not set".
+#[derive(Clone, Copy)]
+enum Expect {
+    Text,
+    Flag,
+    /// Whole number that fits a u32.
+    Count,
+    TextList,
+    /// Table of string values.
+    TextMap,
+    /// Table of profile tables.
+    Profiles,
+}
+
+const SCHEMA: [(&str, Expect); 34] = [
+    ("cache_dir", Expect::Text),
+    ("plex_epg_db_source", Expect::Text),
+    ("plex_db_source", Expect::Text),
+    ("plex_library_db_source", Expect::Text),
+    ("tmdb_api_key", Expect::Text),
+    ("the_movie_db_api_key", Expect::Text),
+    ("omdb_api_key", Expect::Text),
+    ("rating_providers", Expect::TextList),
+    ("texture_budget_mb", Expect::Count),
+    ("guide_refresh_minutes", Expect::Count),
+    ("plex_server_url", Expect::Text),
+    ("plex_token", Expect::Text),
+    ("plex_refresh_after_recording", Expect::Flag),
+    ("tuner_count", Expect::Count),
+    ("offline", Expect::Flag),
+    ("http_proxy", Expect::Text),
+    ("https_proxy", Expect::Text),
+    ("owned_exclude_dirs", Expect::TextList),
+    ("owned_source", Expect::Text),
+    ("owned_rescan_hours", Expect::Count),
+    ("library_roots", Expect::TextList),
+    ("kodi_db_path", Expect::Text),
+    ("jellyfin_url", Expect::Text),
+    ("emby_url", Expect::Text),
+    ("jellyfin_api_key", Expect::Text),
+    ("emby_api_key", Expect::Text),
+    ("radarr_url", Expect::Text),
+    ("radarr_api_key", Expect::Text),
+    ("radarr_root_folder", Expect::Text),
+    ("trakt_client_id", Expect::Text),
+    ("trakt_client_secret", Expect::Text),
+    ("genre_aliases", Expect::TextMap),
+    ("profiles", Expect::Profiles),
+    ("log_level", Expect::Text),
+];
+
+const PROFILE_SCHEMA: [(&str, Expect); 8] = [
+    ("cache_dir", Expect::Text),
+    ("plex_epg_db_source", Expect::Text),
+    ("plex_db_source", Expect::Text),
+    ("plex_library_db_source", Expect::Text),
+    ("plex_server_url", Expect::Text),
+    ("plex_token", Expect::Text),
+    ("owned_exclude_dirs", Expect::TextList),
+    ("library_roots", Expect::TextList),
+];
+
+/// Profile names end up in folder names (`profiles/<name>`), so only `A-Z a-z 0-9 _ -` are
+/// allowed.
+fn valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Check every key against `schema`, e.g. "rating_providers[1] is not a string", and drop the
+/// ones that fail (profiles one by one), so a single bad value doesn't take the rest of the
+/// settings with it. Unknown keys are only logged, so notes and keys from newer versions don't
+/// stop Pex from starting.
+fn prune_invalid(
+    prefix: &str,
+    map: &mut serde_json::Map<String, Value>,
+    schema: &[(&str, Expect)],
+    errors: &mut Vec<String>,
+) {
+    map.retain(|key, value| {
+        let path = format!("{prefix}{key}");
+        let Some(&(_, expect)) = schema.iter().find(|(name, _)| name == key) else {
+            warn!("Unknown config key `{path}` ignored.");
+            return true;
+        };
+        if let (Expect::Profiles, Value::Object(profiles)) = (expect, &mut *value) {
+            profiles.retain(|name, profile| match profile {
+                _ if !valid_profile_name(name) => {
+                    errors.push(format!(
+                        "{path}.{name} is not a valid profile name (use letters, digits, _ and -)"
+                    ));
+                    false
+                }
+                Value::Object(table) => {
+                    prune_invalid(&format!("{path}.{name}."), table, &PROFILE_SCHEMA, errors);
+                    true
+                }
+                _ => {
+                    errors.push(format!("{path}.{name} is not a table"));
+                    false
+                }
+            });
+            return true;
+        }
+        let before = errors.len();
+        validate_value(&path, value, expect, errors);
+        errors.len() == before
+    });
+}
+
+fn validate_value(path: &str, value: &Value, expect: Expect, errors: &mut Vec<String>) {
+    match (expect, value) {
+        (_, Value::Null) | (Expect::Text, Value::String(_)) | (Expect::Flag, Value::Bool(_)) => {}
+        (Expect::Text, _) => errors.push(format!("{path} is not a string")),
+        (Expect::Flag, _) => errors.push(format!("{path} is not true or false")),
+        (Expect::Count, _) => {
+            if value.as_u64().and_then(|n| u32::try_from(n).ok()).is_none() {
+                errors.push(format!("{path} is not a whole number"));
+            }
         }
+        (Expect::TextList, Value::Array(items)) => {
+            for (i, item) in items.iter().enumerate() {
+                if !item.is_string() {
+                    errors.push(format!("{path}[{i}] is not a string"));
+                }
+            }
+        }
+        (Expect::TextList, _) => errors.push(format!("{path} is not a list of strings")),
+        (Expect::TextMap, Value::Object(map)) => {
+            for (key, item) in map {
+                if !item.is_string() {
+                    errors.push(format!("{path}.{key} is not a string"));
+                }
+            }
+        }
+        (Expect::TextMap, _) => errors.push(format!("{path} is not a table of strings")),
+        // Tables of profiles are checked entry by entry in `prune_invalid`.
+        (Expect::Profiles, Value::Object(_)) => {}
+        (Expect::Profiles, _) => errors.push(format!("{path} is not a table of profiles")),
     }
+}
 
-    for path in candidates {
+/// Validate `map` and convert it. Keys with a bad value are left at their defaults and
+/// returned as errors; `null` counts as not set.
+fn raw_from_map(label: &str, mut map: serde_json::Map<String, Value>) -> (RawConfig, Vec<String>) {
+    let mut errors = Vec::new();
+    prune_invalid("", &mut map, &SCHEMA, &mut errors);
+    let raw = serde_json::from_value::<RawConfig>(Value::Object(without_nulls(&map)))
+        .unwrap_or_else(|err| {
+            errors.push(err.to_string());
+            RawConfig::default()
+        });
+    let errors = errors
+        .into_iter()
+        .map(|err| format!("{label}: {err}"))
+        .collect();
+    (raw, errors)
+}
+
+/// The config file Pex reads (the first candidate that exists), or where a new one should go.
+pub fn config_path() -> PathBuf {
+    let candidates = config_candidates();
+    candidates
+        .iter()
+        .find(|path| path.is_file())
+        .or_else(|| candidates.first())
+        .cloned()
+        .unwrap_or_else(|| base_dir().join(CONFIG_FILENAME))
+}
+
+fn read_config_source() -> Option<(PathBuf, String)> {
+    for path in config_candidates() {
         match fs::read_to_string(&path) {
             Ok(raw) => return Some((path, raw)),
             Err(err) => {
@@ -103,74 +487,544 @@ fn read_config_source() -> Option<(PathBuf, String)> {
 }
 
 pub fn load_config() -> AppConfig {
-    let mut cfg = AppConfig::default();
-
-    if let Some((path, raw)) = read_config_source() {
-        match serde_json::from_str::<RawConfig>(&raw) {
-            Ok(mut parsed) => {
-                if let Some(cache_dir) = parsed.cache_dir.take() {
-                    let trimmed = cache_dir.trim();
-                    if !trimmed.is_empty() {
-                        cfg.cache_dir = Some(resolve_relative_path(trimmed));
+    let mut parsed = RawConfig::default();
+    let mut errors = Vec::new();
+    let mut label = CONFIG_FILENAME.to_string();
+    if let Some((path, text)) = read_config_source() {
+        label = file_label(&path);
+        match parse_config_text(&path, &text) {
+            Ok(map) => {
+                for (old, new) in [
+                    ("plex_db_source", "plex_epg_db_source"),
+                    ("the_movie_db_api_key", "tmdb_api_key"),
+                ] {
+                    if map.contains_key(old) {
+                        warn!("`{old}` is deprecated; rename it to `{new}` in {label}.");
                     }
                 }
-
-                if let Some(epg) = parsed.plex_epg_db_source.take() {
-                    let trimmed = epg.trim();
-                    if !trimmed.is_empty() {
-                        cfg.plex_epg_db_source = Some(resolve_relative_path(trimmed));
-                    }
-                    if raw.contains("\"plex_db_source\"") {
-                        warn!(
-                            "`plex_db_source` is deprecated; rename it to `plex_epg_db_source` in config.json."
-                        );
-                    }
+                (parsed, errors) = raw_from_map(&label, map);
+                if errors.is_empty() {
+                    info!("Loaded config from {}", path.display());
                 }
-
-                if let Some(library) = parsed.plex_library_db_source.take() {
-                    let trimmed = library.trim();
-                    if !trimmed.is_empty() {
-                        cfg.plex_library_db_source = Some(resolve_relative_path(trimmed));
-                    }
+                for err in &errors {
+                    warn!("{err} That setting is left at its default.");
                 }
-
-                if let Some(api_key) = parsed.tmdb_api_key.take() {
-                    cfg.tmdb_api_key = Some(api_key);
-                    if raw.contains("\"omdb_api_key\"") {
-                        warn!(
-                            "`omdb_api_key` is deprecated; rename it to `tmdb_api_key` in config.json."
-                        );
-                    }
-                    if raw.contains("\"the_movie_db_api_key\"") {
-                        warn!(
-                            "`the_movie_db_api_key` is deprecated; rename it to `tmdb_api_key` in config.json."
-                        );
-                    }
-                }
-
-                info!("Loaded config from {}", path.display());
             }
             Err(err) => {
-                warn!(
-                    "Failed to parse {} ({err}). Using defaults.",
-                    path.display()
-                );
+                warn!("{err} Using defaults.");
+                errors.push(err);
             }
         }
     } else {
         info!(
-            "No {CONFIG_FILENAME} found near {} (or via PEX_CONFIG); using defaults.",
+            "No {CONFIG_FILENAME} or {CONFIG_TOML_FILENAME} found near {} (or via PEX_CONFIG); using defaults.",
             base_dir().display()
         );
     }
 
-    cfg
+    if let Some(name) = requested_profile() {
+        if !apply_profile(&mut parsed, &name) {
+            warn!("Profile `{name}` is not in {label}; using the top-level settings.");
+        }
+    }
+
+    let overridden = apply_env_overrides(&mut parsed, |name| env::var(name).ok());
+    if !overridden.is_empty() {
+        info!("Config overridden by {}", overridden.join(", "));
+    }
+
+    AppConfig {
+        errors,
+        ..config_from_raw(parsed)
+    }
+}
+
+/// Use `--profile <name>` for this run instead of `PEX_PROFILE` or the remembered profile.
+/// An empty name means the top-level settings. Call before anything loads the config.
+pub fn select_profile(name: &str) {
+    let _ = PROFILE_FLAG.set(name.trim().to_string());
+}
+
+/// The profile asked for: the command line, then `PEX_PROFILE`, then the one picked last time.
+fn requested_profile() -> Option<String> {
+    let name = match PROFILE_FLAG.get() {
+        Some(flag) => Some(flag.clone()),
+        None => env::var("PEX_PROFILE")
+            .ok()
+            .or_else(|| fs::read_to_string(base_dir().join(PROFILE_FILENAME)).ok()),
+    };
+    name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+/// Open `name` (or the top-level settings for `None`) on the next launch.
+pub fn remember_profile(name: Option<&str>) -> std::io::Result<()> {
+    fs::write(base_dir().join(PROFILE_FILENAME), name.unwrap_or(""))
+}
+
+/// The profile this process runs with. Fixed at first use, because the cache folder and the
+/// local database copies it selects are.
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE
+        .get_or_init(|| load_config().profile)
+        .as_deref()
+}
+
+/// Merge profile `name` over the top-level keys; false when there is no such profile.
+fn apply_profile(raw: &mut RawConfig, name: &str) -> bool {
+    if !valid_profile_name(name) {
+        return false;
+    }
+    let Some(profile) = raw.profiles.get(name).cloned() else {
+        return false;
+    };
+    let base_cache = raw
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| ".pex_cache".to_string());
+    raw.cache_dir = Some(profile.cache_dir.unwrap_or_else(|| {
+        format!(
+            "{}/profiles/{name}",
+            base_cache.trim_end_matches(['/', '\\'])
+        )
+    }));
+    if profile.plex_epg_db_source.is_some() {
+        raw.plex_epg_db_source = profile.plex_epg_db_source;
+    }
+    if profile.plex_library_db_source.is_some() {
+        raw.plex_library_db_source = profile.plex_library_db_source;
+    }
+    if profile.plex_server_url.is_some() {
+        raw.plex_server_url = profile.plex_server_url;
+    }
+    if profile.plex_token.is_some() {
+        raw.plex_token = profile.plex_token;
+    }
+    if profile.owned_exclude_dirs.is_some() {
+        raw.owned_exclude_dirs = profile.owned_exclude_dirs;
+    }
+    if let Some(roots) = profile.library_roots {
+        raw.library_roots = roots;
+    }
+    raw.active_profile = Some(name.to_string());
+    true
+}
+
+/// Turn the parsed file (plus environment overrides) into the normalised config.
+fn config_from_raw(parsed: RawConfig) -> AppConfig {
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let path = |value: Option<String>| trimmed(value).map(resolve_relative_path);
+
+    AppConfig {
+        cache_dir: path(parsed.cache_dir),
+        plex_epg_db_source: path(parsed.plex_epg_db_source),
+        plex_library_db_source: path(parsed.plex_library_db_source),
+        tmdb_api_key: parsed.tmdb_api_key,
+        omdb_api_key: parsed.omdb_api_key,
+        rating_providers: parsed
+            .rating_providers
+            .into_iter()
+            .map(|name| name.trim().to_ascii_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect(),
+        texture_budget_mb: parsed.texture_budget_mb.filter(|mb| *mb > 0),
+        guide_refresh_minutes: parsed.guide_refresh_minutes,
+        plex_server_url: parsed
+            .plex_server_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty()),
+        plex_token: trimmed(parsed.plex_token),
+        plex_refresh_after_recording: parsed.plex_refresh_after_recording,
+        tuner_count: parsed.tuner_count,
+        offline: parsed.offline,
+        http_proxy: trimmed(parsed.http_proxy),
+        https_proxy: trimmed(parsed.https_proxy),
+        owned_exclude_dirs: match parsed.owned_exclude_dirs {
+            Some(dirs) => dirs
+                .into_iter()
+                .map(|dir| dir.trim().trim_matches(['/', '\\']).to_string())
+                .filter(|dir| !dir.is_empty())
+                .collect(),
+            None => DEFAULT_OWNED_EXCLUDE_DIRS.map(String::from).to_vec(),
+        },
+        owned_source: match trimmed(parsed.owned_source) {
+            Some(name) => name.parse().unwrap_or_else(|()| {
+                warn!("Unknown owned_source `{name}`; using the Plex library.");
+                OwnedSource::Plex
+            }),
+            None => OwnedSource::Plex,
+        },
+        owned_rescan_hours: parsed.owned_rescan_hours,
+        library_roots: parsed
+            .library_roots
+            .into_iter()
+            .filter_map(|root| trimmed(Some(root)))
+            .map(resolve_relative_path)
+            .collect(),
+        kodi_db_path: path(parsed.kodi_db_path),
+        jellyfin_url: parsed
+            .jellyfin_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty()),
+        jellyfin_api_key: trimmed(parsed.jellyfin_api_key),
+        radarr_url: parsed
+            .radarr_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty()),
+        radarr_api_key: trimmed(parsed.radarr_api_key),
+        radarr_root_folder: trimmed(parsed.radarr_root_folder),
+        trakt_client_id: trimmed(parsed.trakt_client_id),
+        trakt_client_secret: trimmed(parsed.trakt_client_secret),
+        genre_aliases: build_genre_aliases(
+            DEFAULT_GENRE_ALIASES
+                .iter()
+                .map(|&(alias, canonical)| (alias.to_string(), canonical.to_string()))
+                .chain(parsed.genre_aliases),
+        ),
+        profile: parsed.active_profile,
+        profiles: parsed.profiles.into_keys().collect(),
+        errors: Vec::new(),
+    }
+}
+
+/// `1`/`true`/`yes`/`on` or `0`/`false`/`no`/`off`; anything else counts as set.
+fn env_flag(value: &str) -> bool {
+    !matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "0" | "false" | "no" | "off"
+    )
+}
+
+fn env_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Apply `PEX_<KEY>` environment variables over the file's values (lists are
+/// comma-separated; `PEX_GENRE_ALIASES` takes `alias=canonical` pairs). Returns the names of
+/// the variables that were set.
+fn apply_env_overrides(
+    raw: &mut RawConfig,
+    var: impl Fn(&str) -> Option<String>,
+) -> Vec<&'static str> {
+    let mut applied = Vec::new();
+    let mut take = |name: &'static str| {
+        let value = var(name)?;
+        applied.push(name);
+        Some(value)
+    };
+
+    if let Some(v) = take("PEX_CACHE_DIR") {
+        raw.cache_dir = Some(v);
+    }
+    if let Some(v) = take("PEX_EPG_DB_SOURCE") {
+        raw.plex_epg_db_source = Some(v);
+    }
+    if let Some(v) = take("PEX_LIBRARY_DB_SOURCE") {
+        raw.plex_library_db_source = Some(v);
+    }
+    if let Some(v) = take("PEX_TMDB_API_KEY") {
+        raw.tmdb_api_key = Some(v);
+    }
+    if let Some(v) = take("PEX_OMDB_API_KEY") {
+        raw.omdb_api_key = Some(v);
+    }
+    if let Some(v) = take("PEX_RATING_PROVIDERS") {
+        raw.rating_providers = env_list(&v);
+    }
+    if let Some(v) = take("PEX_TEXTURE_BUDGET_MB") {
+        match v.trim().parse() {
+            Ok(mb) => raw.texture_budget_mb = Some(mb),
+            Err(_) => warn!("PEX_TEXTURE_BUDGET_MB={v:?} is not a number; ignored."),
+        }
+    }
+    if let Some(v) = take("PEX_GUIDE_REFRESH_MINUTES") {
+        match v.trim().parse() {
+            Ok(minutes) => raw.guide_refresh_minutes = Some(minutes),
+            Err(_) => warn!("PEX_GUIDE_REFRESH_MINUTES={v:?} is not a number; ignored."),
+        }
+    }
+    if let Some(v) = take("PEX_PLEX_SERVER_URL") {
+        raw.plex_server_url = Some(v);
+    }
+    if let Some(v) = take("PEX_PLEX_TOKEN") {
+        raw.plex_token = Some(v);
+    }
+    if let Some(v) = take("PEX_PLEX_REFRESH_AFTER_RECORDING") {
+        raw.plex_refresh_after_recording = env_flag(&v);
+    }
+    if let Some(v) = take("PEX_TUNER_COUNT") {
+        match v.trim().parse() {
+            Ok(count) => raw.tuner_count = Some(count),
+            Err(_) => warn!("PEX_TUNER_COUNT={v:?} is not a number; ignored."),
+        }
+    }
+    if let Some(v) = take("PEX_OFFLINE") {
+        raw.offline = env_flag(&v);
+    }
+    if let Some(v) = take("PEX_HTTP_PROXY") {
+        raw.http_proxy = Some(v);
+    }
+    if let Some(v) = take("PEX_HTTPS_PROXY") {
+        raw.https_proxy = Some(v);
+    }
+    if let Some(v) = take("PEX_OWNED_EXCLUDE_DIRS") {
+        raw.owned_exclude_dirs = Some(env_list(&v));
+    }
+    if let Some(v) = take("PEX_OWNED_SOURCE") {
+        raw.owned_source = Some(v);
+    }
+    if let Some(v) = take("PEX_OWNED_RESCAN_HOURS") {
+        match v.trim().parse() {
+            Ok(hours) => raw.owned_rescan_hours = Some(hours),
+            Err(_) => warn!("PEX_OWNED_RESCAN_HOURS={v:?} is not a number; ignored."),
+        }
+    }
+    if let Some(v) = take("PEX_LIBRARY_ROOTS") {
+        raw.library_roots = env_list(&v);
+    }
+    if let Some(v) = take("PEX_KODI_DB_PATH") {
+        raw.kodi_db_path = Some(v);
+    }
+    if let Some(v) = take("PEX_JELLYFIN_URL") {
+        raw.jellyfin_url = Some(v);
+    }
+    if let Some(v) = take("PEX_JELLYFIN_API_KEY") {
+        raw.jellyfin_api_key = Some(v);
+    }
+    if let Some(v) = take("PEX_RADARR_URL") {
+        raw.radarr_url = Some(v);
+    }
+    if let Some(v) = take("PEX_RADARR_API_KEY") {
+        raw.radarr_api_key = Some(v);
+    }
+    if let Some(v) = take("PEX_RADARR_ROOT_FOLDER") {
+        raw.radarr_root_folder = Some(v);
+    }
+    if let Some(v) = take("PEX_TRAKT_CLIENT_ID") {
+        raw.trakt_client_id = Some(v);
+    }
+    if let Some(v) = take("PEX_TRAKT_CLIENT_SECRET") {
+        raw.trakt_client_secret = Some(v);
+    }
+    if let Some(v) = take("PEX_GENRE_ALIASES") {
+        for pair in env_list(&v) {
+            if let Some((alias, canonical)) = pair.split_once('=') {
+                raw.genre_aliases
+                    .insert(alias.to_string(), canonical.to_string());
+            }
+        }
+    }
+    applied
+}
+
+/// Case-insensitive alias lookup table. Later pairs win, so config entries override the
+/// defaults; each canonical name also maps to itself to fold differently-cased copies.
+pub fn build_genre_aliases<A, C>(pairs: impl IntoIterator<Item = (A, C)>) -> HashMap<String, String>
+where
+    A: AsRef<str>,
+    C: AsRef<str>,
+{
+    let mut map = HashMap::new();
+    for (alias, canonical) in pairs {
+        let (alias, canonical) = (alias.as_ref().trim(), canonical.as_ref().trim());
+        if alias.is_empty() || canonical.is_empty() {
+            continue;
+        }
+        map.insert(canonical.to_lowercase(), canonical.to_string());
+        map.insert(alias.to_lowercase(), canonical.to_string());
+    }
+    map
+}
+
+/// `db/`, or `db/profiles/<name>/` so each profile keeps its own database copies.
+fn local_db_dir() -> PathBuf {
+    let dir = resolve_relative_path(Path::new(LOCAL_DB_DIR));
+    match active_profile() {
+        Some(name) => dir.join("profiles").join(name),
+        None => dir,
+    }
 }
 
 pub fn local_db_path() -> PathBuf {
-    resolve_relative_path(Path::new(LOCAL_DB_DIR)).join(LOCAL_EPG_DB_FILE)
+    local_db_dir().join(LOCAL_EPG_DB_FILE)
 }
 
 pub fn local_library_db_path() -> PathBuf {
-    resolve_relative_path(Path::new(LOCAL_DB_DIR)).join(LOCAL_LIBRARY_DB_FILE)
+    local_db_dir().join(LOCAL_LIBRARY_DB_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_overrides_replace_file_values() {
+        let mut raw: RawConfig = serde_json::from_str(
+            r#"{"cache_dir": "cache", "offline": true, "owned_exclude_dirs": [".grab"]}"#,
+        )
+        .unwrap();
+        let env: HashMap<&str, &str> = [
+            ("PEX_EPG_DB_SOURCE", "/mnt/plex/epg.db"),
+            ("PEX_OFFLINE", "0"),
+            ("PEX_RATING_PROVIDERS", "OMDb, tmdb"),
+            ("PEX_OWNED_EXCLUDE_DIRS", ""),
+            ("PEX_GENRE_ALIASES", "Kids=Family"),
+        ]
+        .into();
+        let applied = apply_env_overrides(&mut raw, |name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(applied.len(), 5);
+
+        let cfg = config_from_raw(raw);
+        assert_eq!(cfg.cache_dir, Some(resolve_relative_path("cache")));
+        assert_eq!(
+            cfg.plex_epg_db_source,
+            Some(PathBuf::from("/mnt/plex/epg.db"))
+        );
+        assert!(!cfg.offline);
+        assert_eq!(cfg.rating_providers, vec!["omdb", "tmdb"]);
+        assert!(cfg.owned_exclude_dirs.is_empty());
+        assert_eq!(cfg.genre_aliases["kids"], "Family");
+        assert_eq!(cfg.genre_aliases["scifi"], "Sci-Fi");
+    }
+
+    #[test]
+    fn profile_values_win_over_top_level_keys() {
+        let mut raw: RawConfig = serde_json::from_str(
+            r#"{
+                "cache_dir": "cache/",
+                "plex_epg_db_source": "/mnt/home/epg.db",
+                "plex_library_db_source": "/mnt/home/library.db",
+                "library_roots": ["/mnt/home/movies"],
+                "profiles": {
+                    "Parents": {
+                        "plex_db_source": "/mnt/parents/epg.db",
+                        "library_roots": ["/mnt/parents/movies"]
+                    },
+                    "Cabin": {"cache_dir": "/srv/cabin"},
+                    "../escape": {}
+                }
+            }"#,
+        )
+        .unwrap();
+        assert!(!apply_profile(&mut raw, "Nope"));
+        assert!(!apply_profile(&mut raw, "../escape"));
+        raw.profiles.remove("../escape");
+        assert!(apply_profile(&mut raw, "Parents"));
+
+        let cfg = config_from_raw(raw);
+        assert_eq!(cfg.profile.as_deref(), Some("Parents"));
+        assert_eq!(cfg.profiles, vec!["Cabin", "Parents"]);
+        assert_eq!(
+            cfg.plex_epg_db_source,
+            Some(PathBuf::from("/mnt/parents/epg.db"))
+        );
+        assert_eq!(
+            cfg.plex_library_db_source,
+            Some(PathBuf::from("/mnt/home/library.db"))
+        );
+        assert_eq!(
+            cfg.cache_dir,
+            Some(resolve_relative_path("cache/profiles/Parents"))
+        );
+        assert_eq!(
+            cfg.library_roots,
+            vec![PathBuf::from("/mnt/parents/movies")]
+        );
+    }
+
+    #[test]
+    fn profile_names_must_be_plain_folder_names() {
+        assert!(valid_profile_name("Parents") && valid_profile_name("cabin_2-old"));
+        for bad in ["", "..", "../x", "a/b", "a\\b", "C:", "Mum's"] {
+            assert!(!valid_profile_name(bad), "{bad}");
+        }
+        let map = parse_config_text(
+            Path::new("config.toml"),
+            "[profiles.Parents]\nplex_token = \"abc\"\n[profiles.\"../up\"]\nplex_token = \"x\"\n",
+        )
+        .unwrap();
+        let (raw, errors) = raw_from_map("config.toml", map);
+        assert_eq!(
+            errors,
+            vec!["config.toml: profiles.../up is not a valid profile name (use letters, digits, _ and -)"]
+        );
+        assert_eq!(raw.profiles.keys().collect::<Vec<_>>(), vec!["Parents"]);
+    }
+
+    #[test]
+    fn schema_errors_name_the_bad_value() {
+        let map = parse_config_text(
+            Path::new("config.json"),
+            r#"{"rating_providers": ["tmdb", 7], "offline": "yes", "texture_budget_mb": -1,
+                "profiles": {"Parents": {"plex_token": 42, "plex_server_url": "http://p:32400"}},
+                "plex_epg_db_source": "/mnt/plex/epg.db", "some_note": 1}"#,
+        )
+        .unwrap();
+        let (raw, errors) = raw_from_map("config.json", map);
+        assert_eq!(
+            errors,
+            vec![
+                "config.json: offline is not true or false",
+                "config.json: profiles.Parents.plex_token is not a string",
+                "config.json: rating_providers[1] is not a string",
+                "config.json: texture_budget_mb is not a whole number",
+            ]
+        );
+        assert!(raw.rating_providers.is_empty());
+        // Keys that validated are kept.
+        assert_eq!(raw.plex_epg_db_source.as_deref(), Some("/mnt/plex/epg.db"));
+        assert_eq!(
+            raw.profiles["Parents"].plex_server_url.as_deref(),
+            Some("http://p:32400")
+        );
+        assert!(parse_config_text(Path::new("config.json"), "[1]").is_err());
+    }
+
+    #[test]
+    fn null_means_not_set_for_every_key() {
+        let map = parse_config_text(
+            Path::new("config.json"),
+            r#"{"tuner_count": null, "owned_rescan_hours": null, "offline": null,
+                "rating_providers": null, "library_roots": null, "genre_aliases": null,
+                "profiles": {"Parents": {"owned_exclude_dirs": null}},
+                "plex_epg_db_source": "/mnt/plex/epg.db"}"#,
+        )
+        .unwrap();
+        let (raw, errors) = raw_from_map("config.json", map);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(raw.tuner_count, None);
+        assert!(!raw.offline && raw.library_roots.is_empty());
+        assert_eq!(raw.plex_epg_db_source.as_deref(), Some("/mnt/plex/epg.db"));
+        assert!(raw.profiles["Parents"].owned_exclude_dirs.is_none());
+    }
+
+    #[test]
+    fn toml_config_reads_and_writes_like_json() {
+        let path = Path::new("config.toml");
+        let map = parse_config_text(
+            path,
+            "plex_epg_db_source = '/mnt/plex/epg.db'\n\
+             rating_providers = ['omdb']\n\
+             [profiles.Parents]\n\
+             plex_token = 'abc'\n",
+        )
+        .unwrap();
+        let (raw, errors) = raw_from_map("config.toml", map.clone());
+        assert!(errors.is_empty());
+        assert_eq!(raw.plex_epg_db_source.as_deref(), Some("/mnt/plex/epg.db"));
+        assert_eq!(raw.profiles["Parents"].plex_token.as_deref(), Some("abc"));
+
+        let mut with_null = map.clone();
+        with_null.insert("http_proxy".into(), Value::Null);
+        let text = render_config_text(path, &with_null).unwrap();
+        assert_eq!(parse_config_text(path, &text).unwrap(), map);
+    }
 }
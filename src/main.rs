@@ -1,6 +1,7 @@
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 // src/main.rs
 use eframe::egui::{IconData, ViewportBuilder};
+use pex::app::safe_mode;
 
 #[cfg(not(target_os = "windows"))]
 use eframe::egui::Vec2;
@@ -30,7 +31,27 @@ fn pick_renderer() -> eframe::Renderer {
     }
 }
 
+/// The Windows build uses the GUI subsystem, so it starts without a console. The command-line
+/// modes attach to the console they were started from so their output shows up there.
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // SAFETY: takes no pointers; it fails harmlessly when there is no parent console
+    // (e.g. started from Explorer or with the output redirected).
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
 fn main() -> eframe::Result<()> {
+    #[cfg(target_os = "windows")]
+    if env::args().any(|arg| arg == "--headless" || arg == "--serve") {
+        attach_parent_console();
+    }
     let _ = tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env())
         .try_init();
@@ -44,7 +65,23 @@ fn main() -> eframe::Result<()> {
         info!("WINIT_UNIX_BACKEND={:?}", env::var_os("WINIT_UNIX_BACKEND"));
     }
 
-    let mut viewport = ViewportBuilder::default().with_maximized(true);
+    if let Some(profile) = flag_value(env::args().skip(1), "--profile") {
+        pex::config::select_profile(&profile);
+    }
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--headless") {
+        let mut rest = args[..pos].to_vec();
+        rest.extend_from_slice(&args[pos + 1..]);
+        std::process::exit(pex::app::headless::run(&rest));
+    }
+    if let Some(addr) = flag_value(args.iter().cloned(), "--serve") {
+        std::process::exit(pex::app::web::serve(&addr));
+    }
+    let safe_flag = args.iter().any(|arg| arg == "--safe-mode");
+    let safe_mode = safe_mode::decide(safe_flag, safe_mode::begin_startup());
+
+    // Safe mode avoids the GPU-heavy defaults: GL renderer, no vsync, windowed.
+    let mut viewport = ViewportBuilder::default().with_maximized(safe_mode.is_none());
 
     #[cfg(not(target_os = "windows"))]
     {
@@ -56,8 +93,15 @@ fn main() -> eframe::Result<()> {
     }
 
     let options = eframe::NativeOptions {
-        renderer: pick_renderer(),
+        renderer: if safe_mode.is_some() {
+            eframe::Renderer::Glow
+        } else {
+            pick_renderer()
+        },
+        vsync: safe_mode.is_none(),
         multisampling: 0,
+        // Dark/light comes from the Appearance prefs, not the OS.
+        follow_system_theme: false,
         viewport,
         ..Default::default()
     };
@@ -65,7 +109,11 @@ fn main() -> eframe::Result<()> {
     match eframe::run_native(
         "Plex EPG Explorer",
         options,
-        Box::new(|_cc| Ok(Box::new(pex::app::PexApp::default()))),
+        Box::new(|cc| {
+            // Zoom keys are bindable actions (see keymap.rs) and the zoom is saved in prefs.
+            cc.egui_ctx.options_mut(|o| o.zoom_with_keyboard = false);
+            Ok(Box::new(pex::app::PexApp::with_safe_mode(safe_mode)))
+        }),
     ) {
         Ok(_) => Ok(()),
         Err(e) => {
@@ -76,6 +124,22 @@ fn main() -> eframe::Result<()> {
     }
 }
 
+/// `<flag> <value>` or `<flag>=<value>`.
+fn flag_value(mut args: impl Iterator<Item = String>, flag: &str) -> Option<String> {
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
 fn load_app_icon() -> Option<IconData> {
     const ICON_BYTES: &[u8] = include_bytes!("assets/PEX.ico");
     let dyn_image = image::load_from_memory(ICON_BYTES).ok()?;